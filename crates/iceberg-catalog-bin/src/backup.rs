@@ -0,0 +1,114 @@
+use crate::serve::secrets_from_config;
+use anyhow::anyhow;
+use iceberg_catalog::api::iceberg::v1::RequestMetadata;
+use iceberg_catalog::api::management::v1::warehouse::backup::{
+    backup_warehouse, restore_warehouse, RestoreWarehouseRequest, RestoreWarehouseResponse,
+    WarehouseBackup,
+};
+use iceberg_catalog::implementations::postgres::{CatalogState, PostgresCatalog};
+use iceberg_catalog::implementations::Secrets;
+use iceberg_catalog::service::authz::implementations::{
+    get_default_authorizer_from_config, Authorizers,
+};
+use iceberg_catalog::service::authz::Authorizer;
+use iceberg_catalog::{ProjectIdent, WarehouseIdent, CONFIG, DEFAULT_PROJECT_ID};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub(crate) async fn backup(warehouse_id: String, output: PathBuf) -> anyhow::Result<()> {
+    let warehouse_id = WarehouseIdent::from_str(&warehouse_id).map_err(|e| e.error)?;
+    let catalog_state = connect().await?;
+
+    let backup = backup_warehouse::<PostgresCatalog>(warehouse_id, catalog_state)
+        .await
+        .map_err(|e| e.error)?;
+    std::fs::write(&output, serde_json::to_string_pretty(&backup)?)?;
+    println!(
+        "Backed up {} namespaces, {} tables and {} views to {}",
+        backup.namespaces.len(),
+        backup.tables.len(),
+        backup.views.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+pub(crate) struct RestoreOptions {
+    pub(crate) project_id: Option<String>,
+    pub(crate) warehouse_name: Option<String>,
+    pub(crate) storage_profile: Option<PathBuf>,
+    pub(crate) storage_credential: Option<PathBuf>,
+}
+
+pub(crate) async fn restore(input: PathBuf, options: RestoreOptions) -> anyhow::Result<()> {
+    let backup: WarehouseBackup = serde_json::from_str(&std::fs::read_to_string(input)?)?;
+    let project_id = match options.project_id {
+        Some(project_id) => ProjectIdent::from_str(&project_id).map_err(|e| e.error)?,
+        None => DEFAULT_PROJECT_ID
+            .ok_or_else(|| anyhow!("--project-id is required if no default project is set"))?,
+    };
+    let request = RestoreWarehouseRequest {
+        warehouse_name: options.warehouse_name,
+        project_id: Some(*project_id),
+        storage_profile: options
+            .storage_profile
+            .map(|path| serde_json::from_str(&std::fs::read_to_string(path)?))
+            .transpose()?,
+        storage_credential: options
+            .storage_credential
+            .map(|path| serde_json::from_str(&std::fs::read_to_string(path)?))
+            .transpose()?,
+        backup,
+    };
+
+    let catalog_state = connect().await?;
+    let secrets = secrets_from_config(&catalog_state).await?;
+    let response = match get_default_authorizer_from_config().await? {
+        Authorizers::AllowAll(authorizer) => {
+            restore_with(project_id, request, &authorizer, catalog_state, &secrets).await
+        }
+        Authorizers::OpenFGA(authorizer) => {
+            restore_with(project_id, request, &authorizer, catalog_state, &secrets).await
+        }
+        Authorizers::Opa(authorizer) => {
+            restore_with(project_id, request, &authorizer, catalog_state, &secrets).await
+        }
+    }?;
+    println!("{}", serde_json::to_string_pretty(&response)?);
+    Ok(())
+}
+
+async fn restore_with<A: Authorizer>(
+    project_id: ProjectIdent,
+    request: RestoreWarehouseRequest,
+    authorizer: &A,
+    catalog_state: CatalogState,
+    secrets: &Secrets,
+) -> anyhow::Result<RestoreWarehouseResponse> {
+    Ok(restore_warehouse::<PostgresCatalog, A, Secrets>(
+        project_id,
+        request,
+        authorizer,
+        catalog_state,
+        secrets,
+        &RequestMetadata::new_unauthenticated(),
+    )
+    .await
+    .map_err(|e| e.error)?)
+}
+
+async fn connect() -> anyhow::Result<CatalogState> {
+    let read_pool = iceberg_catalog::implementations::postgres::get_reader_pool(
+        CONFIG
+            .to_pool_opts()
+            .max_connections(CONFIG.pg_read_pool_connections),
+    )
+    .await?;
+    let write_pool = iceberg_catalog::implementations::postgres::get_writer_pool(
+        CONFIG
+            .to_pool_opts()
+            .max_connections(CONFIG.pg_write_pool_connections),
+    )
+    .await?;
+    Ok(CatalogState::from_pools(read_pool, write_pool))
+}
@@ -1,7 +1,7 @@
 use anyhow::Context;
 use iceberg_catalog::implementations::postgres::{get_reader_pool, get_writer_pool, ReadWrite};
 use iceberg_catalog::service::health::{HealthExt, HealthState, HealthStatus};
-use iceberg_catalog::CONFIG;
+use iceberg_catalog::{TlsClientAuth, TlsConfig, CONFIG};
 
 pub async fn health(check_db: bool, check_server: bool) -> anyhow::Result<()> {
     tracing::info!("Checking health...");
@@ -18,9 +18,25 @@ pub async fn health(check_db: bool, check_server: bool) -> anyhow::Result<()> {
     };
 
     if check_server {
-        let client = reqwest::Client::new();
+        // The server certificate is usually not issued for localhost
+        let mut client =
+            reqwest::Client::builder().danger_accept_invalid_certs(CONFIG.tls.is_some());
+        if let Some(tls) = &CONFIG.tls {
+            if let Some(identity) = healthcheck_identity(tls)? {
+                client = client.identity(identity);
+            }
+        }
+        let client = client.build()?;
+        let scheme = if CONFIG.tls.is_some() {
+            "https"
+        } else {
+            "http"
+        };
         let response = client
-            .get(format!("http://localhost:{}/health", CONFIG.listen_port))
+            .get(format!(
+                "{scheme}://localhost:{}/health",
+                CONFIG.listen_port
+            ))
             .send()
             .await?;
         let status = response.status();
@@ -40,6 +56,37 @@ pub async fn health(check_db: bool, check_server: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Client certificate to present if the server requests one.
+fn healthcheck_identity(tls: &TlsConfig) -> anyhow::Result<Option<reqwest::Identity>> {
+    let (cert_path, key_path) = match (&tls.healthcheck_cert_path, &tls.healthcheck_key_path) {
+        (Some(cert_path), Some(key_path)) => (cert_path, key_path),
+        (None, None) if tls.client_auth == TlsClientAuth::Required => {
+            return Err(anyhow::anyhow!(
+                "LAKEKEEPER__TLS__HEALTHCHECK_CERT_PATH and LAKEKEEPER__TLS__HEALTHCHECK_KEY_PATH \
+                 are required for the healthcheck if client certificates are required."
+            ));
+        }
+        (None, None) => return Ok(None),
+        _ => {
+            return Err(anyhow::anyhow!(
+                "LAKEKEEPER__TLS__HEALTHCHECK_CERT_PATH and LAKEKEEPER__TLS__HEALTHCHECK_KEY_PATH \
+                 must be set together."
+            ));
+        }
+    };
+
+    let mut pem = std::fs::read(cert_path)
+        .with_context(|| format!("Failed to read {}", cert_path.display()))?;
+    pem.push(b'\n');
+    pem.extend(
+        std::fs::read(key_path)
+            .with_context(|| format!("Failed to read {}", key_path.display()))?,
+    );
+    reqwest::Identity::from_pem(&pem)
+        .map(Some)
+        .context("Invalid healthcheck client certificate or key")
+}
+
 pub(crate) async fn db_health_check() -> anyhow::Result<()> {
     let reader = get_reader_pool(
         CONFIG
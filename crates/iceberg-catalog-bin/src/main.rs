@@ -2,14 +2,21 @@
 
 use clap::{Parser, Subcommand};
 use iceberg_catalog::api::management::v1::api_doc as v1_api_doc;
-use iceberg_catalog::service::authz::implementations::openfga::UnauthenticatedOpenFGAAuthorizer;
+use iceberg_catalog::service::authz::implementations::opa::OpaAuthorizer;
+use iceberg_catalog::service::authz::implementations::openfga::{
+    ImportOptions, UnauthenticatedOpenFGAAuthorizer,
+};
 use iceberg_catalog::service::authz::AllowAllAuthorizer;
 use iceberg_catalog::{AuthZBackend, CONFIG};
+use std::path::PathBuf;
 use tracing_subscriber::filter::LevelFilter;
 use tracing_subscriber::EnvFilter;
 
+mod backup;
 mod healthcheck;
+mod permissions;
 mod serve;
+mod tls;
 #[cfg(feature = "ui")]
 mod ui;
 mod wait_for_db;
@@ -93,6 +100,64 @@ enum Commands {
     Version {},
     /// Get the OpenAPI specification of the Management API as yaml
     ManagementOpenapi {},
+    /// Export the permission assignments of a project or warehouse to a JSON document
+    ExportPermissions {
+        #[clap(long, help = "Project to export, including its roles and warehouses.")]
+        project_id: Option<String>,
+        #[clap(long, help = "Warehouse to export.", conflicts_with("project_id"))]
+        warehouse_id: Option<String>,
+        #[clap(long, short, help = "File to write the document to.")]
+        output: PathBuf,
+    },
+    /// Import permission assignments from a JSON document. Only shows the changes unless --apply is set.
+    ImportPermissions {
+        #[clap(long, help = "Project to import into.")]
+        project_id: Option<String>,
+        #[clap(long, help = "Warehouse to import into.", conflicts_with("project_id"))]
+        warehouse_id: Option<String>,
+        #[clap(long, short, help = "File to read the document from.")]
+        input: PathBuf,
+        #[clap(default_value = "false", long, help = "Apply the changes.")]
+        apply: bool,
+        #[clap(
+            default_value = "false",
+            long,
+            help = "Delete assignments that are not part of the document."
+        )]
+        prune: bool,
+    },
+    /// Backup the namespaces, tables and views of a warehouse to a JSON document
+    Backup {
+        #[clap(long, help = "Warehouse to back up.")]
+        warehouse_id: String,
+        #[clap(long, short, help = "File to write the backup to.")]
+        output: PathBuf,
+    },
+    /// Restore a warehouse from a backup as a new warehouse
+    Restore {
+        #[clap(long, short, help = "File to read the backup from.")]
+        input: PathBuf,
+        #[clap(
+            long,
+            help = "Project to restore into. Defaults to the default project."
+        )]
+        project_id: Option<String>,
+        #[clap(
+            long,
+            help = "Name of the new warehouse. Defaults to the name in the backup."
+        )]
+        warehouse_name: Option<String>,
+        #[clap(
+            long,
+            help = "JSON file with the storage profile of the new warehouse. Defaults to the storage profile in the backup."
+        )]
+        storage_profile: Option<PathBuf>,
+        #[clap(
+            long,
+            help = "JSON file with the storage credential of the new warehouse."
+        )]
+        storage_credential: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
@@ -166,10 +231,59 @@ async fn main() -> anyhow::Result<()> {
         Some(Commands::ManagementOpenapi {}) => {
             let doc = match CONFIG.authz_backend {
                 AuthZBackend::AllowAll => v1_api_doc::<AllowAllAuthorizer>(),
-                AuthZBackend::OpenFGA => v1_api_doc::<UnauthenticatedOpenFGAAuthorizer>(),
+                AuthZBackend::OpenFGA | AuthZBackend::Postgres => {
+                    v1_api_doc::<UnauthenticatedOpenFGAAuthorizer>()
+                }
+                AuthZBackend::Opa => v1_api_doc::<OpaAuthorizer>(),
             };
             println!("{}", doc.to_yaml()?);
         }
+        Some(Commands::ExportPermissions {
+            project_id,
+            warehouse_id,
+            output,
+        }) => {
+            permissions::export(project_id, warehouse_id, output).await?;
+        }
+        Some(Commands::ImportPermissions {
+            project_id,
+            warehouse_id,
+            input,
+            apply,
+            prune,
+        }) => {
+            permissions::import(
+                project_id,
+                warehouse_id,
+                input,
+                ImportOptions { apply, prune },
+            )
+            .await?;
+        }
+        Some(Commands::Backup {
+            warehouse_id,
+            output,
+        }) => {
+            backup::backup(warehouse_id, output).await?;
+        }
+        Some(Commands::Restore {
+            input,
+            project_id,
+            warehouse_name,
+            storage_profile,
+            storage_credential,
+        }) => {
+            backup::restore(
+                input,
+                backup::RestoreOptions {
+                    project_id,
+                    warehouse_name,
+                    storage_profile,
+                    storage_credential,
+                },
+            )
+            .await?;
+        }
         None => {
             // Error out if no subcommand is provided.
             eprintln!("No subcommand provided. Use --help for more information.");
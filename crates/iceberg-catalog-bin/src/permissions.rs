@@ -0,0 +1,99 @@
+use anyhow::anyhow;
+use iceberg_catalog::implementations::postgres::{CatalogState, PostgresCatalog};
+use iceberg_catalog::service::authz::implementations::openfga::{
+    export_assignments, import_assignments, AuthzDocument, AuthzScope, ImportOptions,
+    OpenFGAAuthorizer,
+};
+use iceberg_catalog::service::authz::implementations::{
+    get_default_authorizer_from_config, Authorizers,
+};
+use iceberg_catalog::{ProjectIdent, WarehouseIdent, CONFIG};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub(crate) async fn export(
+    project_id: Option<String>,
+    warehouse_id: Option<String>,
+    output: PathBuf,
+) -> anyhow::Result<()> {
+    let scope = parse_scope(project_id, warehouse_id)?;
+    let (authorizer, catalog_state) = connect().await?;
+
+    let document = export_assignments::<PostgresCatalog>(&authorizer, scope, None, catalog_state)
+        .await
+        .map_err(|e| e.error)?;
+    std::fs::write(&output, serde_json::to_string_pretty(&document)?)?;
+    println!(
+        "Exported {} assignments to {}",
+        document.assignments.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+pub(crate) async fn import(
+    project_id: Option<String>,
+    warehouse_id: Option<String>,
+    input: PathBuf,
+    options: ImportOptions,
+) -> anyhow::Result<()> {
+    let scope = parse_scope(project_id, warehouse_id)?;
+    let document: AuthzDocument = serde_json::from_str(&std::fs::read_to_string(input)?)?;
+    let (authorizer, catalog_state) = connect().await?;
+
+    let report = import_assignments::<PostgresCatalog>(
+        &authorizer,
+        scope,
+        document,
+        options,
+        None,
+        catalog_state,
+    )
+    .await
+    .map_err(|e| e.error)?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if !report.applied {
+        println!("Dry run, no changes were applied. Use --apply to apply them.");
+    }
+    Ok(())
+}
+
+fn parse_scope(
+    project_id: Option<String>,
+    warehouse_id: Option<String>,
+) -> anyhow::Result<AuthzScope> {
+    match (project_id, warehouse_id) {
+        (Some(project_id), None) => Ok(AuthzScope::Project(
+            ProjectIdent::from_str(&project_id).map_err(|e| e.error)?,
+        )),
+        (None, Some(warehouse_id)) => Ok(AuthzScope::Warehouse(
+            WarehouseIdent::from_str(&warehouse_id).map_err(|e| e.error)?,
+        )),
+        _ => Err(anyhow!(
+            "Specify exactly one of --project-id or --warehouse-id"
+        )),
+    }
+}
+
+async fn connect() -> anyhow::Result<(OpenFGAAuthorizer, CatalogState)> {
+    let read_pool = iceberg_catalog::implementations::postgres::get_reader_pool(
+        CONFIG
+            .to_pool_opts()
+            .max_connections(CONFIG.pg_read_pool_connections),
+    )
+    .await?;
+    let write_pool = iceberg_catalog::implementations::postgres::get_writer_pool(
+        CONFIG
+            .to_pool_opts()
+            .max_connections(CONFIG.pg_write_pool_connections),
+    )
+    .await?;
+    let catalog_state = CatalogState::from_pools(read_pool, write_pool);
+
+    match get_default_authorizer_from_config().await? {
+        Authorizers::OpenFGA(authorizer) => Ok((authorizer, catalog_state)),
+        Authorizers::AllowAll(_) | Authorizers::Opa(_) => Err(anyhow!(
+            "Permissions can only be exported and imported with the openfga or postgres authorization backend"
+        )),
+    }
+}
@@ -5,21 +5,26 @@ use iceberg_catalog::implementations::Secrets;
 use iceberg_catalog::service::authz::implementations::{
     get_default_authorizer_from_config, Authorizers,
 };
+use iceberg_catalog::service::authz::row_filter::RowFilterVerifier;
 use iceberg_catalog::service::authz::Authorizer;
-use iceberg_catalog::service::contract_verification::ContractVerifiers;
+use iceberg_catalog::service::contract_verification::{ContractVerification, ContractVerifiers};
+use iceberg_catalog::service::contract_webhook::WebhookContractVerifier;
 use iceberg_catalog::service::event_publisher::{
     CloudEventBackend, CloudEventsPublisher, CloudEventsPublisherBackgroundTask, Message,
     NatsBackend,
 };
 use iceberg_catalog::service::health::ServiceHealthProvider;
+use iceberg_catalog::service::schema_compatibility::SchemaCompatibilityVerifier;
 use iceberg_catalog::service::{Catalog, StartupValidationData};
-use iceberg_catalog::{SecretBackend, CONFIG};
+use iceberg_catalog::{SecretBackend, TlsClientAuth, CONFIG};
 use reqwest::Url;
 
 use iceberg_catalog::implementations::postgres::task_queues::{
-    TabularExpirationQueue, TabularPurgeQueue,
+    NamespaceExpirationQueue, TabularExpirationQueue, TabularPurgeQueue,
 };
+use iceberg_catalog::service::authn::CertificateVerifier;
 use iceberg_catalog::service::authn::IdpVerifier;
+use iceberg_catalog::service::authn::IntrospectionVerifier;
 use iceberg_catalog::service::authn::K8sVerifier;
 use iceberg_catalog::service::task_queue::TaskQueues;
 use std::sync::Arc;
@@ -68,23 +73,7 @@ pub(crate) async fn serve(bind_addr: std::net::SocketAddr) -> Result<(), anyhow:
         }
     }
 
-    let secrets_state: Secrets = match CONFIG.secret_backend {
-        SecretBackend::KV2 => iceberg_catalog::implementations::kv2::SecretsState::from_config(
-            CONFIG
-                .kv2
-                .as_ref()
-                .ok_or_else(|| anyhow!("Need vault config to use vault as backend"))?,
-        )
-        .await?
-        .into(),
-        SecretBackend::Postgres => {
-            iceberg_catalog::implementations::postgres::SecretsState::from_pools(
-                read_pool.clone(),
-                write_pool.clone(),
-            )
-            .into()
-        }
-    };
+    let secrets_state = secrets_from_config(&catalog_state).await?;
     let authorizer = get_default_authorizer_from_config().await?;
 
     let health_provider = ServiceHealthProvider::new(
@@ -107,6 +96,10 @@ pub(crate) async fn serve(bind_addr: std::net::SocketAddr) -> Result<(), anyhow:
             ReadWrite::from_pools(read_pool.clone(), write_pool.clone()),
             CONFIG.queue_config.clone(),
         )?),
+        Arc::new(NamespaceExpirationQueue::from_config(
+            ReadWrite::from_pools(read_pool.clone(), write_pool.clone()),
+            CONFIG.queue_config.clone(),
+        )?),
     );
 
     let listener = tokio::net::TcpListener::bind(bind_addr).await?;
@@ -124,6 +117,18 @@ pub(crate) async fn serve(bind_addr: std::net::SocketAddr) -> Result<(), anyhow:
             .await?
         }
         Authorizers::OpenFGA(a) => {
+            a.spawn_grant_expiration_task();
+            serve_inner(
+                a,
+                catalog_state,
+                secrets_state,
+                queues,
+                health_provider,
+                listener,
+            )
+            .await?
+        }
+        Authorizers::Opa(a) => {
             serve_inner(
                 a,
                 catalog_state,
@@ -139,6 +144,28 @@ pub(crate) async fn serve(bind_addr: std::net::SocketAddr) -> Result<(), anyhow:
     Ok(())
 }
 
+/// Connect to the secret backend configured in `secret_backend`.
+pub(crate) async fn secrets_from_config(catalog_state: &CatalogState) -> anyhow::Result<Secrets> {
+    let secrets: Secrets = match CONFIG.secret_backend {
+        SecretBackend::KV2 => iceberg_catalog::implementations::kv2::SecretsState::from_config(
+            CONFIG
+                .kv2
+                .as_ref()
+                .ok_or_else(|| anyhow!("Need vault config to use vault as backend"))?,
+        )
+        .await?
+        .into(),
+        SecretBackend::Postgres => {
+            iceberg_catalog::implementations::postgres::SecretsState::from_pools(
+                catalog_state.read_pool(),
+                catalog_state.write_pool(),
+            )
+            .into()
+        }
+    };
+    Ok(secrets)
+}
+
 /// Helper function to remove redundant code from matching different implementations
 async fn serve_inner<A: Authorizer>(
     authorizer: A,
@@ -181,6 +208,18 @@ async fn serve_inner<A: Authorizer>(
     } else {
         None
     };
+    let mut contract_verifiers: Vec<Arc<dyn ContractVerification + Sync + Send>> = vec![
+        Arc::new(RowFilterVerifier::<PostgresCatalog>::new(
+            catalog_state.clone(),
+        )),
+        Arc::new(SchemaCompatibilityVerifier::<PostgresCatalog>::new(
+            catalog_state.clone(),
+        )),
+    ];
+    contract_verifiers.extend(WebhookContractVerifier::<PostgresCatalog>::from_config(
+        &catalog_state,
+    )?);
+
     let (layer, metrics_future) =
         iceberg_catalog::metrics::get_axum_layer_and_install_recorder(CONFIG.metrics_port)?;
     let router = new_full_router::<PostgresCatalog, _, Secrets>(RouterArgs {
@@ -189,7 +228,7 @@ async fn serve_inner<A: Authorizer>(
         secrets_state: secrets_state.clone(),
         queues: queues.clone(),
         publisher: CloudEventsPublisher::new(tx.clone()),
-        table_change_checkers: ContractVerifiers::new(vec![]),
+        table_change_checkers: ContractVerifiers::new(contract_verifiers),
         token_verifier: if let Some(uri) = CONFIG.openid_provider_uri.clone() {
             Some(
                 IdpVerifier::new(
@@ -202,7 +241,20 @@ async fn serve_inner<A: Authorizer>(
         } else {
             None
         },
+        introspection_token_verifier: CONFIG.openid_introspection.as_ref().map(|config| {
+            IntrospectionVerifier::new(
+                config.endpoint.clone(),
+                config.client_id.clone(),
+                config.client_secret.clone(),
+                CONFIG.openid_audience.clone(),
+            )
+        }),
         k8s_token_verifier,
+        certificate_verifier: CONFIG
+            .tls
+            .as_ref()
+            .filter(|tls| tls.client_auth != TlsClientAuth::Disabled)
+            .map(|tls| CertificateVerifier::new(tls.client_identity)),
         service_health_provider: health_provider,
         cors_origins: CONFIG.allow_origin.as_deref(),
         metrics_layer: Some(layer),
@@ -233,9 +285,17 @@ async fn serve_inner<A: Authorizer>(
         };
     });
 
+    let server = async move {
+        if let Some(tls_config) = CONFIG.tls.as_ref() {
+            crate::tls::serve_tls(listener, router, tls_config).await
+        } else {
+            service_serve(listener, router).await
+        }
+    };
+
     tokio::select!(
         _ = queues.spawn_queues::<PostgresCatalog, _, _>(catalog_state, secrets_state, authorizer) => tracing::error!("Tabular queue task failed"),
-        err = service_serve(listener, router) => tracing::error!("Service failed: {err:?}"),
+        err = server => tracing::error!("Service failed: {err:?}"),
         _ = metrics_future => tracing::error!("Metrics server failed"),
     );
 
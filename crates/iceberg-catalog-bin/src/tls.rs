@@ -0,0 +1,294 @@
+use anyhow::{anyhow, Context};
+use axum::{Extension, Router};
+use axum_server::accept::{Accept, DefaultAcceptor};
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
+use futures::future::BoxFuture;
+use iceberg_catalog::service::authn::TlsConnectionInfo;
+use iceberg_catalog::{TlsClientAuth, TlsConfig};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tower::Layer;
+
+/// Serve the given router with TLS on the given listener.
+///
+/// Certificates are reloaded from disk when they change, so that rotated
+/// certificates are picked up without a restart.
+pub(crate) async fn serve_tls(
+    listener: tokio::net::TcpListener,
+    router: Router,
+    config: &'static TlsConfig,
+) -> anyhow::Result<()> {
+    let rustls_config = RustlsConfig::from_config(Arc::new(build_server_config(config)?));
+    tracing::info!(
+        "Serving with TLS, client certificate authentication: {:?}",
+        config.client_auth
+    );
+
+    tokio::spawn(reload_certificates(rustls_config.clone(), config));
+
+    let handle = axum_server::Handle::new();
+    let shutdown_handle = handle.clone();
+    tokio::spawn(async move {
+        iceberg_catalog::api::shutdown_signal().await;
+        shutdown_handle.graceful_shutdown(Some(Duration::from_secs(30)));
+    });
+
+    axum_server::from_tcp(listener.into_std()?)
+        .acceptor(ClientCertificateAcceptor {
+            inner: RustlsAcceptor::new(rustls_config),
+        })
+        .handle(handle)
+        .serve(router.into_make_service())
+        .await
+        .map_err(|e| anyhow!(e).context("error running HTTPS server"))
+}
+
+/// Terminates TLS and makes the client certificate available to the
+/// authentication middleware via [`TlsConnectionInfo`].
+#[derive(Clone)]
+struct ClientCertificateAcceptor {
+    inner: RustlsAcceptor<DefaultAcceptor>,
+}
+
+impl<I, S> Accept<I, S> for ClientCertificateAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = TlsStream<I>;
+    type Service = <Extension<TlsConnectionInfo> as Layer<S>>::Service;
+    type Future = BoxFuture<'static, std::io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let acceptor = self.inner.clone();
+
+        Box::pin(async move {
+            let (stream, service) = acceptor.accept(stream, service).await?;
+            let client_certificate = stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(<[CertificateDer<'static>]>::first)
+                .map(|certificate| certificate.as_ref().to_vec());
+            let service = Extension(TlsConnectionInfo { client_certificate }).layer(service);
+            Ok((stream, service))
+        })
+    }
+}
+
+fn build_server_config(config: &TlsConfig) -> anyhow::Result<ServerConfig> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let certificates = load_certificates(&config.cert_path)?;
+    let key = load_private_key(&config.key_path)?;
+
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .context("Failed to configure TLS protocol versions")?;
+
+    let builder = match config.client_auth {
+        TlsClientAuth::Disabled => builder.with_no_client_auth(),
+        TlsClientAuth::Optional | TlsClientAuth::Required => {
+            let client_ca_path = config.client_ca_path.as_ref().ok_or_else(|| {
+                anyhow!("A client CA is required if client certificate authentication is enabled")
+            })?;
+            let mut roots = RootCertStore::empty();
+            for certificate in load_certificates(client_ca_path)? {
+                roots
+                    .add(certificate)
+                    .context("Failed to add client CA certificate")?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+            let verifier = if config.client_auth == TlsClientAuth::Optional {
+                verifier.allow_unauthenticated()
+            } else {
+                verifier
+            };
+            builder.with_client_cert_verifier(
+                verifier
+                    .build()
+                    .context("Failed to build client certificate verifier")?,
+            )
+        }
+    };
+
+    let mut server_config = builder
+        .with_single_cert(certificates, key)
+        .context("Invalid server certificate or key")?;
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(server_config)
+}
+
+fn load_certificates(path: &Path) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open certificate file {}", path.display()))?;
+    let certificates = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Failed to parse certificates in {}", path.display()))?;
+    if certificates.is_empty() {
+        return Err(anyhow!("No certificates found in {}", path.display()));
+    }
+    Ok(certificates)
+}
+
+fn load_private_key(path: &Path) -> anyhow::Result<PrivateKeyDer<'static>> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open private key file {}", path.display()))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .with_context(|| format!("Failed to parse private key in {}", path.display()))?
+        .ok_or_else(|| anyhow!("No private key found in {}", path.display()))
+}
+
+/// Periodically check the certificate files for changes and reload them.
+/// If the new certificates are invalid, the previous certificates stay active.
+async fn reload_certificates(rustls_config: RustlsConfig, config: &'static TlsConfig) {
+    let mut last_modified = modification_times(config);
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(config.reload_interval_seconds.max(1)));
+    // The first tick completes immediately
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+        reload_if_changed(&rustls_config, config, &mut last_modified);
+    }
+}
+
+/// Reload the certificates if any of the files changed since `last_modified`.
+/// Returns `true` if a new configuration was loaded.
+fn reload_if_changed(
+    rustls_config: &RustlsConfig,
+    config: &TlsConfig,
+    last_modified: &mut Vec<Option<SystemTime>>,
+) -> bool {
+    let modified = modification_times(config);
+    if modified == *last_modified {
+        return false;
+    }
+
+    match build_server_config(config) {
+        Ok(server_config) => {
+            rustls_config.reload_from_config(Arc::new(server_config));
+            tracing::info!("Reloaded TLS certificates");
+            *last_modified = modified;
+            true
+        }
+        Err(e) => {
+            tracing::error!("Failed to reload TLS certificates, keeping previous ones: {e:?}");
+            false
+        }
+    }
+}
+
+fn modification_times(config: &TlsConfig) -> Vec<Option<SystemTime>> {
+    [
+        Some(&config.cert_path),
+        Some(&config.key_path),
+        config.client_ca_path.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use iceberg_catalog::CertificateIdentitySource;
+
+    const CERTS_DIR: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../iceberg-catalog/tests/certs"
+    );
+
+    fn copy_fixture(name: &str, target: &Path, modified: SystemTime) {
+        std::fs::copy(Path::new(CERTS_DIR).join(name), target).unwrap();
+        set_modified(target, modified);
+    }
+
+    fn set_modified(path: &Path, modified: SystemTime) {
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    fn tls_config(dir: &Path) -> TlsConfig {
+        TlsConfig {
+            cert_path: dir.join("server.crt"),
+            key_path: dir.join("server.key"),
+            client_ca_path: Some(dir.join("ca.crt")),
+            client_auth: TlsClientAuth::Required,
+            client_identity: CertificateIdentitySource::SubjectCommonName,
+            reload_interval_seconds: 60,
+            healthcheck_cert_path: None,
+            healthcheck_key_path: None,
+        }
+    }
+
+    #[test]
+    fn test_reload_certificates() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let dir = std::env::temp_dir().join(format!(
+            "lakekeeper-tls-{}-{}",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        copy_fixture("server.crt", &dir.join("server.crt"), t0);
+        copy_fixture("server.key", &dir.join("server.key"), t0);
+        copy_fixture("ca.crt", &dir.join("ca.crt"), t0);
+
+        let config = tls_config(&dir);
+        let rustls_config =
+            RustlsConfig::from_config(Arc::new(build_server_config(&config).unwrap()));
+        let mut last_modified = modification_times(&config);
+        let initial = rustls_config.get_inner();
+
+        // Unchanged files are not reloaded
+        assert!(!reload_if_changed(
+            &rustls_config,
+            &config,
+            &mut last_modified
+        ));
+        assert!(Arc::ptr_eq(&initial, &rustls_config.get_inner()));
+
+        // Invalid files keep the previous configuration
+        let t1 = t0 + Duration::from_secs(60);
+        std::fs::write(dir.join("server.crt"), "not a certificate").unwrap();
+        set_modified(&dir.join("server.crt"), t1);
+        assert!(!reload_if_changed(
+            &rustls_config,
+            &config,
+            &mut last_modified
+        ));
+        assert!(Arc::ptr_eq(&initial, &rustls_config.get_inner()));
+
+        // Rotated certificates are picked up
+        let t2 = t1 + Duration::from_secs(60);
+        copy_fixture("server-rotated.crt", &dir.join("server.crt"), t2);
+        copy_fixture("server-rotated.key", &dir.join("server.key"), t2);
+        assert!(reload_if_changed(
+            &rustls_config,
+            &config,
+            &mut last_modified
+        ));
+        assert!(!Arc::ptr_eq(&initial, &rustls_config.get_inner()));
+        assert_eq!(last_modified, modification_times(&config));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,20 @@
+-- Relationship tuples of the built-in postgres authorizer.
+-- Objects and users are stored split into type and id, so that all lookups
+-- of the authorizer can be answered by the indexes below.
+create table authz_tuple
+(
+    tuple_id      bigint generated always as identity primary key,
+    object_type   text not null,
+    object_id     text not null,
+    relation      text not null,
+    user_type     text not null,
+    user_id       text not null,
+    -- Empty for direct users, i.e. `assignee` for `role:<id>#assignee`
+    user_relation text not null default '',
+    CONSTRAINT "unique_authz_tuple" UNIQUE (object_type, object_id, relation, user_type, user_id, user_relation)
+);
+
+create index authz_tuple_user_idx on authz_tuple (user_type, user_id, user_relation, object_type);
+
+call add_time_columns('authz_tuple');
+select trigger_updated_at('authz_tuple');
@@ -0,0 +1,34 @@
+-- Expirations of time-bound grants. Tuples of the authorizer do not expire on
+-- their own, a background job deletes them once `expires_at` has passed and
+-- records this in `revoked_at`.
+create table authz_grant_expiration
+(
+    object     text        not null,
+    relation   text        not null,
+    -- OpenFGA user, i.e. `user:<id>` or `role:<id>#assignee`
+    principal  text        not null,
+    expires_at timestamptz not null,
+    revoked_at timestamptz,
+    primary key (object, relation, principal)
+);
+
+create index authz_grant_expiration_due_idx on authz_grant_expiration (expires_at) where revoked_at is null;
+
+call add_time_columns('authz_grant_expiration');
+select trigger_updated_at('authz_grant_expiration');
+
+-- Tasks that are not scoped to a warehouse, i.e. grant expirations, have no warehouse.
+alter table task
+    alter column warehouse_id drop not null;
+
+-- Grants revoked by a `grant_expiration` task. The task is suspended until the grant expires.
+create table authz_grant_expiration_task
+(
+    object    text not null,
+    relation  text not null,
+    principal text not null,
+    task_id   uuid primary key references task (task_id)
+);
+
+call add_time_columns('authz_grant_expiration_task');
+select trigger_updated_at('authz_grant_expiration_task');
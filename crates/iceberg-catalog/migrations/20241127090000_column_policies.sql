@@ -0,0 +1,16 @@
+create type column_policy_effect as enum ('deny', 'mask');
+
+-- Column-level access policies. Policies reference columns by their field id, so that
+-- they remain valid if a column is renamed.
+create table column_policy
+(
+    table_id     uuid                 not null references "table" (table_id) on delete cascade,
+    field_id     int                  not null,
+    effect       column_policy_effect not null,
+    exempt_users text[]               not null default '{}',
+    exempt_roles uuid[]               not null default '{}',
+    primary key (table_id, field_id)
+);
+
+call add_time_columns('column_policy');
+select trigger_updated_at('column_policy');
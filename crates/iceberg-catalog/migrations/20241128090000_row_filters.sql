@@ -0,0 +1,12 @@
+-- Row-level filter policies. A filter restricts the rows that assignees of a role
+-- may read from a table. The filter is an Iceberg expression in its JSON representation.
+create table row_filter_policy
+(
+    table_id uuid  not null references "table" (table_id) on delete cascade,
+    role_id  uuid  not null references role (id) on delete cascade,
+    filter   jsonb not null,
+    primary key (table_id, role_id)
+);
+
+call add_time_columns('row_filter_policy');
+select trigger_updated_at('row_filter_policy');
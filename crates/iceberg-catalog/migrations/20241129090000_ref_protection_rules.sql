@@ -0,0 +1,19 @@
+-- Protection rules for branches and tags. A rule applies to all tables of a warehouse,
+-- of a namespace including its children, or to a single table.
+create table ref_protection_rule
+(
+    rule_id            uuid primary key,
+    warehouse_id       uuid    not null references warehouse (warehouse_id) on delete cascade,
+    namespace_id       uuid references namespace (namespace_id) on delete cascade,
+    table_id           uuid references "table" (table_id) on delete cascade,
+    ref_pattern        text    not null,
+    allowed_committers uuid[]  not null default '{}',
+    prevent_deletion   boolean not null default false,
+    fast_forward_only  boolean not null default false,
+    check (namespace_id is null or table_id is null)
+);
+
+create index ref_protection_rule_warehouse_id_idx on ref_protection_rule (warehouse_id);
+
+call add_time_columns('ref_protection_rule');
+select trigger_updated_at('ref_protection_rule');
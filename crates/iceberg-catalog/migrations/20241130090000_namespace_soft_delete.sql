@@ -0,0 +1,21 @@
+alter table namespace
+    add column deleted_at timestamptz;
+
+alter table namespace
+    drop constraint unique_namespace_per_warehouse;
+
+alter table namespace
+    add constraint unique_namespace_per_warehouse unique nulls not distinct (warehouse_id, namespace_name, deleted_at);
+
+create table namespace_expirations
+(
+    namespace_id  uuid          not null,
+    warehouse_id  uuid          not null,
+    deletion_kind deletion_kind not null,
+    task_id       uuid primary key references task (task_id)
+);
+
+create index namespace_expirations_namespace_id_idx on namespace_expirations (namespace_id);
+
+call add_time_columns('namespace_expirations');
+select trigger_updated_at('"namespace_expirations"');
@@ -0,0 +1,4 @@
+-- Properties of a warehouse. Currently only `table-default.*` and `table-override.*`
+-- properties are supported, which are inherited by tables at creation time.
+alter table warehouse
+    add column properties jsonb not null default '{}'::jsonb;
@@ -0,0 +1,4 @@
+-- Policy for the properties of tables and views in a warehouse.
+-- See `TablePropertyPolicy` for the format.
+alter table warehouse
+    add column table_property_policy jsonb not null default '{}'::jsonb;
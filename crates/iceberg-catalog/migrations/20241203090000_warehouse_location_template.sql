@@ -0,0 +1,4 @@
+-- Template for the default location of tables and views in a warehouse.
+-- If null, tables and views are placed at `{namespace-location}/{uuid}`.
+alter table warehouse
+    add column location_template text;
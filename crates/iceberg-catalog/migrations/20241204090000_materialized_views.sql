@@ -0,0 +1,22 @@
+-- Link between a materialized view and the table that stores its data.
+-- View commits replace the view row, the link is carried over by the catalog.
+create table materialized_view
+(
+    view_id          uuid primary key references view (view_id) on delete cascade,
+    storage_table_id uuid not null unique references "table" (table_id) on delete cascade
+);
+
+call add_time_columns('materialized_view');
+select trigger_updated_at('materialized_view');
+
+-- Snapshots of the source tables the storage table was last refreshed from.
+-- Source tables may be dropped, so their id and name are kept without a foreign key.
+create table materialized_view_source
+(
+    view_id          uuid   not null references materialized_view (view_id) on delete cascade,
+    source_table_id  uuid   not null,
+    namespace_name   text[] not null,
+    table_name       text   not null,
+    snapshot_id      bigint,
+    primary key (view_id, source_table_id)
+);
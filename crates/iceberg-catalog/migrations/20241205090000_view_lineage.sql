@@ -0,0 +1,13 @@
+-- Tables and views referenced by the SQL representations of a view.
+-- Dependencies may be dropped or replaced, so they are kept without a foreign key.
+create table view_dependency
+(
+    view_id       uuid not null references view (view_id) on delete cascade,
+    dependency_id uuid not null,
+    primary key (view_id, dependency_id)
+);
+
+call add_time_columns('view_dependency');
+select trigger_updated_at('view_dependency');
+
+create index view_dependency_dependency_id_idx on view_dependency (dependency_id);
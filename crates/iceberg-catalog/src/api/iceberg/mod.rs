@@ -28,8 +28,8 @@ pub mod v1 {
         CreateNamespaceResponse, CreateTableRequest, CreateViewRequest, ErrorModel,
         GetNamespaceResponse, IcebergErrorResponse, ListNamespacesResponse, ListTablesResponse,
         LoadTableResult, LoadViewResult, OAuthTokenRequest, OAuthTokenResponse,
-        RegisterTableRequest, RenameTableRequest, Result, UpdateNamespacePropertiesRequest,
-        UpdateNamespacePropertiesResponse,
+        RegisterTableRequest, RegisterViewRequest, RenameTableRequest, Result,
+        UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse,
     };
     pub use crate::request_metadata::RequestMetadata;
 
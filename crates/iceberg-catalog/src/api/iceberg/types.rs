@@ -196,6 +196,17 @@ pub struct DropParams {
     pub purge_requested: Option<bool>,
 }
 
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DropNamespaceParams {
+    /// Also drop all child namespaces, tables and views
+    #[serde(deserialize_with = "deserialize_bool", default)]
+    pub recursive: Option<bool>,
+    /// Purge the data of dropped tables and views
+    #[serde(deserialize_with = "deserialize_bool", default)]
+    pub purge: Option<bool>,
+}
+
 fn deserialize_bool<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
 where
     D: Deserializer<'de>,
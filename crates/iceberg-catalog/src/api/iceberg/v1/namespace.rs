@@ -1,6 +1,6 @@
 use std::ops::Deref;
 
-use crate::api::iceberg::types::{PageToken, Prefix};
+use crate::api::iceberg::types::{DropNamespaceParams, PageToken, Prefix};
 use crate::api::{ApiContext, Result};
 use crate::request_metadata::RequestMetadata;
 use axum::extract::{Path, Query, State};
@@ -55,9 +55,11 @@ where
         request_metadata: RequestMetadata,
     ) -> Result<()>;
 
-    /// Drop a namespace from the catalog. Namespace must be empty.
+    /// Drop a namespace from the catalog.
+    /// Namespace must be empty unless `recursive` is set.
     async fn drop_namespace(
         parameters: NamespaceParameters,
+        drop_params: DropNamespaceParams,
         state: ApiContext<S>,
         request_metadata: RequestMetadata,
     ) -> Result<()>;
@@ -196,9 +198,10 @@ pub fn router<I: Service<S>, S: crate::api::ThreadSafe>() -> Router<ApiContext<S
                     .map(|()| StatusCode::NO_CONTENT.into_response())
                 },
             )
-            // Drop a namespace from the catalog. Namespace must be empty.
+            // Drop a namespace from the catalog. Namespace must be empty unless `recursive` is set.
             .delete(
                 |Path((prefix, namespace)): Path<(Prefix, NamespaceIdentUrl)>,
+                 Query(drop_params): Query<DropNamespaceParams>,
                  State(api_context): State<ApiContext<S>>,
                  Extension(metadata): Extension<RequestMetadata>| async {
                     I::drop_namespace(
@@ -206,6 +209,7 @@ pub fn router<I: Service<S>, S: crate::api::ThreadSafe>() -> Router<ApiContext<S
                             prefix: Some(prefix),
                             namespace: namespace.into(),
                         },
+                        drop_params,
                         api_context,
                         metadata,
                     )
@@ -395,9 +399,10 @@ mod tests {
                 panic!("Should not be called");
             }
 
-            /// Drop a namespace from the catalog. Namespace must be empty.
+            /// Drop a namespace from the catalog.
             async fn drop_namespace(
                 _parameters: NamespaceParameters,
+                _drop_params: DropNamespaceParams,
                 _state: ApiContext<ThisState>,
                 _request_metadata: RequestMetadata,
             ) -> Result<()> {
@@ -497,9 +502,10 @@ mod tests {
                 panic!("Should not be called");
             }
 
-            /// Drop a namespace from the catalog. Namespace must be empty.
+            /// Drop a namespace from the catalog.
             async fn drop_namespace(
                 _parameters: NamespaceParameters,
+                _drop_params: DropNamespaceParams,
                 _state: ApiContext<ThisState>,
                 _request_metadata: RequestMetadata,
             ) -> Result<()> {
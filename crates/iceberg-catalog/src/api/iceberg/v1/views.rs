@@ -2,8 +2,9 @@ use crate::api::iceberg::types::{DropParams, Prefix};
 use crate::api::iceberg::v1::namespace::{NamespaceIdentUrl, NamespaceParameters};
 use crate::api::iceberg::v1::DataAccess;
 use crate::api::{
-    ApiContext, CommitViewRequest, CreateViewRequest, ListTablesResponse, LoadViewResult,
-    RenameTableRequest, Result,
+    ApiContext, CommitMaterializedViewRequest, CommitViewRequest, CreateMaterializedViewRequest,
+    CreateViewRequest, ListTablesResponse, LoadMaterializedViewResult, LoadViewResult,
+    RegisterViewRequest, RenameTableRequest, Result,
 };
 use crate::request_metadata::RequestMetadata;
 use axum::extract::State;
@@ -42,6 +43,15 @@ where
         request_metadata: RequestMetadata,
     ) -> Result<LoadViewResult>;
 
+    /// Register a view in the given namespace using given metadata file location
+    async fn register_view(
+        parameters: NamespaceParameters,
+        request: RegisterViewRequest,
+        state: ApiContext<S>,
+        data_access: DataAccess,
+        request_metadata: RequestMetadata,
+    ) -> Result<LoadViewResult>;
+
     /// Load a view from the catalog
     async fn load_view(
         parameters: ViewParameters,
@@ -81,6 +91,32 @@ where
         state: ApiContext<S>,
         request_metadata: RequestMetadata,
     ) -> Result<()>;
+
+    /// Create a materialized view and its storage table in the given namespace
+    async fn create_materialized_view(
+        parameters: NamespaceParameters,
+        request: CreateMaterializedViewRequest,
+        state: ApiContext<S>,
+        data_access: DataAccess,
+        request_metadata: RequestMetadata,
+    ) -> Result<LoadMaterializedViewResult>;
+
+    /// Load a materialized view with its storage table and freshness
+    async fn load_materialized_view(
+        parameters: ViewParameters,
+        state: ApiContext<S>,
+        data_access: DataAccess,
+        request_metadata: RequestMetadata,
+    ) -> Result<LoadMaterializedViewResult>;
+
+    /// Commit updates to a materialized view and its storage table
+    async fn commit_materialized_view(
+        parameters: ViewParameters,
+        request: CommitMaterializedViewRequest,
+        state: ApiContext<S>,
+        data_access: DataAccess,
+        request_metadata: RequestMetadata,
+    ) -> Result<LoadMaterializedViewResult>;
 }
 
 #[allow(clippy::too_many_lines)]
@@ -129,6 +165,29 @@ pub fn router<I: Service<S>, S: crate::api::ThreadSafe>() -> Router<ApiContext<S
                 },
             ),
         )
+        // /{prefix}/namespaces/{namespace}/register-view
+        .route(
+            "/:prefix/namespaces/:namespace/register-view",
+            // Register a view in the given namespace using given metadata file location
+            post(
+                |Path((prefix, namespace)): Path<(Prefix, NamespaceIdentUrl)>,
+                 State(api_context): State<ApiContext<S>>,
+                 headers: HeaderMap,
+                 Extension(metadata): Extension<RequestMetadata>,
+                 Json(request): Json<RegisterViewRequest>| {
+                    I::register_view(
+                        NamespaceParameters {
+                            prefix: Some(prefix),
+                            namespace: namespace.into(),
+                        },
+                        request,
+                        api_context,
+                        crate::api::iceberg::v1::tables::parse_data_access(&headers),
+                        metadata,
+                    )
+                },
+            ),
+        )
         // /{prefix}/namespaces/{namespace}/views/{view}
         .route(
             "/:prefix/namespaces/:namespace/views/:view",
@@ -222,6 +281,73 @@ pub fn router<I: Service<S>, S: crate::api::ThreadSafe>() -> Router<ApiContext<S
                 },
             ),
         )
+        // /{prefix}/namespaces/{namespace}/materialized-views
+        .route(
+            "/:prefix/namespaces/:namespace/materialized-views",
+            // Create a materialized view and its storage table in the given namespace
+            post(
+                |Path((prefix, namespace)): Path<(Prefix, NamespaceIdentUrl)>,
+                 State(api_context): State<ApiContext<S>>,
+                 headers: HeaderMap,
+                 Extension(metadata): Extension<RequestMetadata>,
+                 Json(request): Json<CreateMaterializedViewRequest>| {
+                    I::create_materialized_view(
+                        NamespaceParameters {
+                            prefix: Some(prefix),
+                            namespace: namespace.into(),
+                        },
+                        request,
+                        api_context,
+                        crate::api::iceberg::v1::tables::parse_data_access(&headers),
+                        metadata,
+                    )
+                },
+            ),
+        )
+        // /{prefix}/namespaces/{namespace}/materialized-views/{view}
+        .route(
+            "/:prefix/namespaces/:namespace/materialized-views/:view",
+            get(
+                |Path((prefix, namespace, view)): Path<(Prefix, NamespaceIdentUrl, String)>,
+                 State(api_context): State<ApiContext<S>>,
+                 headers: HeaderMap,
+                 Extension(metadata): Extension<RequestMetadata>| {
+                    I::load_materialized_view(
+                        ViewParameters {
+                            prefix: Some(prefix),
+                            view: TableIdent {
+                                namespace: namespace.into(),
+                                name: view,
+                            },
+                        },
+                        api_context,
+                        crate::api::iceberg::v1::tables::parse_data_access(&headers),
+                        metadata,
+                    )
+                },
+            )
+            .post(
+                |Path((prefix, namespace, view)): Path<(Prefix, NamespaceIdentUrl, String)>,
+                 State(api_context): State<ApiContext<S>>,
+                 headers: HeaderMap,
+                 Extension(metadata): Extension<RequestMetadata>,
+                 Json(request): Json<CommitMaterializedViewRequest>| {
+                    I::commit_materialized_view(
+                        ViewParameters {
+                            prefix: Some(prefix),
+                            view: TableIdent {
+                                namespace: namespace.into(),
+                                name: view,
+                            },
+                        },
+                        request,
+                        api_context,
+                        crate::api::iceberg::v1::tables::parse_data_access(&headers),
+                        metadata,
+                    )
+                },
+            ),
+        )
         // /{prefix}/views/rename
         .route(
             "/:prefix/views/rename",
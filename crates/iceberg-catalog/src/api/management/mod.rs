@@ -1,7 +1,11 @@
 pub mod v1 {
     pub mod bootstrap;
+    pub mod lineage;
+    pub mod namespace;
     pub mod project;
+    pub mod ref_protection;
     pub mod role;
+    pub mod table;
     pub mod user;
     pub mod warehouse;
 
@@ -17,25 +21,41 @@ pub mod v1 {
     use crate::api::management::v1::warehouse::UndropTabularsRequest;
     use crate::service::authn::UserId;
     use crate::service::{
-        authz::Authorizer, storage::S3Flavor, Actor, Catalog, CreateOrUpdateUserResponse, RoleId,
-        SecretStore, State, TabularIdentUuid,
+        authz::{
+            Authorizer, ColumnPolicy, ColumnPolicyEffect, PolicyPrincipal, RefProtectionRule,
+            RefProtectionScope, RowFilter,
+        },
+        storage::S3Flavor,
+        Actor, Catalog, CreateOrUpdateUserResponse, RoleId, SecretStore, State, TabularIdentUuid,
     };
     use crate::ProjectIdent;
     use axum::extract::{Path, Query, State as AxumState};
     use axum::response::{IntoResponse, Response};
-    use axum::routing::{get, post};
+    use axum::routing::{delete, get, post};
     use bootstrap::{AuthZBackend, BootstrapRequest, ServerInfo, Service as _};
     use http::StatusCode;
     use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+    use lineage::{GetLineageResponse, LineageEntry, Service as _};
+    use namespace::{
+        DeletedNamespaceResponse, ListDeletedNamespacesQuery, ListDeletedNamespacesResponse,
+        RenameNamespaceRequest, Service as _, UndropNamespacesRequest,
+    };
     use project::{
         CreateProjectRequest, CreateProjectResponse, GetProjectResponse, ListProjectsResponse,
         RenameProjectRequest, Service as _,
     };
+    use ref_protection::{
+        CreateRefProtectionRuleRequest, ListRefProtectionRulesResponse, Service as _,
+    };
     use role::{
         CreateRoleRequest, ListRolesQuery, ListRolesResponse, Role, SearchRoleRequest,
         SearchRoleResponse, Service as _, UpdateRoleRequest,
     };
     use serde::Serialize;
+    use table::{
+        GetColumnPoliciesResponse, GetRowFiltersResponse, Service as _, SetColumnPoliciesRequest,
+        SetRowFiltersRequest,
+    };
     use user::{
         CreateUserRequest, SearchUser, SearchUserRequest, SearchUserResponse, Service as _,
         UpdateUserRequest, User, UserLastUpdatedWith, UserType,
@@ -43,10 +63,13 @@ pub mod v1 {
     use warehouse::{
         AdlsProfile, AzCredential, CreateWarehouseRequest, CreateWarehouseResponse, GcsCredential,
         GcsProfile, GcsServiceKey, GetWarehouseResponse, ListDeletedTabularsQuery,
-        ListWarehousesRequest, ListWarehousesResponse, RenameWarehouseRequest, S3Credential,
-        S3Profile, Service as _, StorageCredential, StorageProfile, TabularDeleteProfile,
-        UpdateWarehouseCredentialRequest, UpdateWarehouseDeleteProfileRequest,
-        UpdateWarehouseStorageRequest, WarehouseStatus,
+        ListWarehousesRequest, ListWarehousesResponse, NamespaceBackup, RenameWarehouseRequest,
+        RestoreWarehouseRequest, RestoreWarehouseResponse, S3Credential, S3Profile, Service as _,
+        StorageCredential, StorageProfile, TablePropertyPolicy, TablePropertyRule, TabularBackup,
+        TabularDeleteProfile, UpdateTablePropertyPolicyRequest, UpdateWarehouseCredentialRequest,
+        UpdateWarehouseDeleteProfileRequest, UpdateWarehouseLocationTemplateRequest,
+        UpdateWarehousePropertiesRequest, UpdateWarehouseStorageRequest, WarehouseBackup,
+        WarehouseStatus,
     };
 
     pub(crate) fn default_page_size() -> i64 {
@@ -63,6 +86,9 @@ pub mod v1 {
             (name = "server", description = "Manage Server"),
             (name = "project", description = "Manage Projects"),
             (name = "warehouse", description = "Manage Warehouses"),
+            (name = "namespace", description = "Manage Namespaces"),
+            (name = "table", description = "Manage Tables"),
+            (name = "view", description = "Manage Views"),
             (name = "user", description = "Manage Users"),
             (name = "role", description = "Manage Roles")
         ),
@@ -71,39 +97,56 @@ pub mod v1 {
         ),
         paths(
             activate_warehouse,
+            backup_warehouse,
             bootstrap,
             create_project,
+            create_ref_protection_rule,
             create_role,
             create_user,
             create_warehouse,
             deactivate_warehouse,
             delete_default_project,
             delete_project_by_id,
+            delete_ref_protection_rule,
             delete_role,
             delete_user,
             delete_warehouse,
+            get_column_policies,
             get_default_project,
             get_project_by_id,
+            get_table_lineage,
+            get_view_lineage,
             get_role,
+            get_row_filters,
             get_server_info,
             get_user,
             get_warehouse,
+            list_deleted_namespaces,
             list_deleted_tabulars,
             list_projects,
+            list_ref_protection_rules,
             list_roles,
             list_user,
             list_warehouses,
             rename_default_project,
+            rename_namespace,
             rename_project_by_id,
             rename_warehouse,
+            restore_warehouse,
             search_role,
             search_user,
+            set_column_policies,
+            set_row_filters,
+            undrop_namespaces,
             undrop_tabulars,
             update_role,
             update_storage_credential,
             update_storage_profile,
+            update_table_property_policy,
             update_user,
             update_warehouse_delete_profile,
+            update_warehouse_location_template,
+            update_warehouse_properties,
             whoami,
         ),
         components(schemas(
@@ -112,32 +155,50 @@ pub mod v1 {
             AzCredential,
             AdlsProfile,
             BootstrapRequest,
+            ColumnPolicy,
+            ColumnPolicyEffect,
             CreateProjectRequest,
             CreateProjectResponse,
+            CreateRefProtectionRuleRequest,
             CreateRoleRequest,
             CreateRoleRequest,
             CreateRoleRequest,
             CreateUserRequest,
             CreateWarehouseRequest,
             CreateWarehouseResponse,
+            DeletedNamespaceResponse,
             DeletedTabularResponse,
             DeleteKind,
             GcsCredential,
             GcsProfile,
             GcsServiceKey,
+            GetColumnPoliciesResponse,
+            GetLineageResponse,
             GetProjectResponse,
+            GetRowFiltersResponse,
             GetWarehouseResponse,
             IcebergErrorResponse,
+            LineageEntry,
+            ListDeletedNamespacesResponse,
             ListDeletedTabularsResponse,
             ListProjectsResponse,
+            ListRefProtectionRulesResponse,
             ListRolesResponse,
             ListUsersResponse,
             ListWarehousesRequest,
             ListWarehousesResponse,
+            NamespaceBackup,
+            PolicyPrincipal,
             ProjectIdent,
+            RefProtectionRule,
+            RefProtectionScope,
+            RenameNamespaceRequest,
             RenameProjectRequest,
             RenameWarehouseRequest,
+            RestoreWarehouseRequest,
+            RestoreWarehouseResponse,
             Role,
+            RowFilter,
             S3Credential,
             S3Flavor,
             S3Profile,
@@ -147,20 +208,30 @@ pub mod v1 {
             SearchUserRequest,
             SearchUserResponse,
             ServerInfo,
+            SetColumnPoliciesRequest,
+            SetRowFiltersRequest,
             StorageCredential,
             StorageProfile,
+            TablePropertyPolicy,
+            TablePropertyRule,
+            TabularBackup,
             TabularDeleteProfile,
             TabularType,
             TabularIdentUuid,
+            UndropNamespacesRequest,
             UndropTabularsRequest,
             UpdateRoleRequest,
             UpdateUserRequest,
             UpdateWarehouseCredentialRequest,
+            UpdateTablePropertyPolicyRequest,
             UpdateWarehouseDeleteProfileRequest,
+            UpdateWarehouseLocationTemplateRequest,
+            UpdateWarehousePropertiesRequest,
             UpdateWarehouseStorageRequest,
             User,
             UserLastUpdatedWith,
             UserType,
+            WarehouseBackup,
             WarehouseStatus,
         )),
         modifiers(&SecurityAddon)
@@ -803,6 +874,97 @@ pub mod v1 {
         .await
     }
 
+    /// Replace the properties of a warehouse.
+    ///
+    /// Properties prefixed with `table-default.` are set on new tables unless the
+    /// create request specifies them. Properties prefixed with `table-override.` are
+    /// enforced on new tables and cannot be changed afterwards.
+    #[utoipa::path(
+            post,
+            tag = "warehouse",
+            path = "/management/v1/warehouse/{warehouse_id}/properties",
+            request_body = UpdateWarehousePropertiesRequest,
+            responses(
+                (status = 200, description = "Properties updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+            )
+        )]
+    async fn update_warehouse_properties<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehousePropertiesRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_properties(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Set the template for the default location of new tables and views in a warehouse.
+    ///
+    /// Locations of existing tables and views are not changed.
+    #[utoipa::path(
+            post,
+            tag = "warehouse",
+            path = "/management/v1/warehouse/{warehouse_id}/location-template",
+            request_body = UpdateWarehouseLocationTemplateRequest,
+            responses(
+                (status = 200, description = "Location template updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+            )
+        )]
+    async fn update_warehouse_location_template<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseLocationTemplateRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_location_template(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Replace the table property policy of a warehouse.
+    ///
+    /// The policy is enforced when tables are created and when table or view
+    /// commits set or remove properties.
+    #[utoipa::path(
+            post,
+            tag = "warehouse",
+            path = "/management/v1/warehouse/{warehouse_id}/table-property-policy",
+            request_body = UpdateTablePropertyPolicyRequest,
+            responses(
+                (status = 200, description = "Table property policy updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+            )
+        )]
+    async fn update_table_property_policy<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateTablePropertyPolicyRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_table_property_policy(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
     /// Deactivate a warehouse
     #[utoipa::path(
         post,
@@ -936,6 +1098,396 @@ pub mod v1 {
         Ok(StatusCode::NO_CONTENT)
     }
 
+    /// Rename or move a namespace
+    ///
+    /// Moves the namespace and all of its children to a new identifier within the warehouse.
+    /// Ids and locations of namespaces, tables and views are kept.
+    /// Requires the `delete` privilege on the namespace and the `create_namespace` privilege
+    /// on the new parent namespace or, for top-level namespaces, on the warehouse.
+    #[utoipa::path(
+        post,
+        tag = "namespace",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/rename",
+        request_body = RenameNamespaceRequest,
+        responses(
+            (status = 200, description = "Namespace renamed successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn rename_namespace<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RenameNamespaceRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::rename_namespace(
+            warehouse_id.into(),
+            namespace_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// List soft-deleted namespaces
+    ///
+    /// List all soft-deleted namespaces in the warehouse that are visible to you.
+    #[utoipa::path(
+        get,
+        tag = "namespace",
+        path = "/management/v1/warehouse/{warehouse_id}/deleted-namespaces",
+        params(ListDeletedNamespacesQuery),
+        responses(
+            (status = 200, description = "List of soft-deleted namespaces", body = ListDeletedNamespacesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_deleted_namespaces<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<ListDeletedNamespacesQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListDeletedNamespacesResponse> {
+        ApiServer::<C, A, S>::list_deleted_namespaces(
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Undrop soft-deleted namespaces
+    ///
+    /// Restores the namespaces together with the children, tables and views that were
+    /// dropped with them. The parent of each namespace must exist.
+    #[utoipa::path(
+        post,
+        tag = "namespace",
+        path = "/management/v1/warehouse/{warehouse_id}/deleted-namespaces/undrop",
+        request_body = UndropNamespacesRequest,
+        responses(
+            (status = 204, description = "Namespaces undropped successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn undrop_namespaces<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UndropNamespacesRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::undrop_namespaces(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Get the column policies of a table
+    #[utoipa::path(
+        get,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/column-policies",
+        responses(
+            (status = 200, description = "Column policies of the table", body = GetColumnPoliciesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_column_policies<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<GetColumnPoliciesResponse>> {
+        ApiServer::<C, A, S>::get_column_policies(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Replace the column policies of a table
+    ///
+    /// Column policies deny or mask columns for all principals that are not exempt.
+    /// Policies reference columns by field id, so they remain valid if a column is renamed.
+    #[utoipa::path(
+        put,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/column-policies",
+        request_body = SetColumnPoliciesRequest,
+        responses(
+            (status = 204, description = "Column policies replaced successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_column_policies<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetColumnPoliciesRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::set_column_policies(
+            warehouse_id.into(),
+            table_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Get the row filters of a table
+    #[utoipa::path(
+        get,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/row-filters",
+        responses(
+            (status = 200, description = "Row filters of the table", body = GetRowFiltersResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_row_filters<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<GetRowFiltersResponse>> {
+        ApiServer::<C, A, S>::get_row_filters(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Replace the row filters of a table
+    ///
+    /// Assignees of a role only see the rows matching the filter of the role.
+    /// If multiple filters apply to a principal, rows matching any of them are visible.
+    /// Filters are validated against the current schema of the table.
+    #[utoipa::path(
+        put,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/row-filters",
+        request_body = SetRowFiltersRequest,
+        responses(
+            (status = 204, description = "Row filters replaced successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_row_filters<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetRowFiltersRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::set_row_filters(
+            warehouse_id.into(),
+            table_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Get the lineage of a table
+    ///
+    /// Returns the views that reference the table in their SQL representations.
+    /// Views the caller may not see are omitted.
+    #[utoipa::path(
+        get,
+        tag = "table",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/lineage",
+        responses(
+            (status = 200, description = "Lineage of the table", body = GetLineageResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_lineage<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<GetLineageResponse>> {
+        ApiServer::<C, A, S>::get_table_lineage(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Get the lineage of a view
+    ///
+    /// Returns the tables and views referenced by the SQL representations of the view
+    /// and the views that reference the view. Tabulars the caller may not see are omitted.
+    #[utoipa::path(
+        get,
+        tag = "view",
+        path = "/management/v1/warehouse/{warehouse_id}/view/{view_id}/lineage",
+        responses(
+            (status = 200, description = "Lineage of the view", body = GetLineageResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_view_lineage<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, view_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<GetLineageResponse>> {
+        ApiServer::<C, A, S>::get_view_lineage(
+            warehouse_id.into(),
+            view_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// List the ref protection rules of a warehouse
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/ref-protection-rules",
+        responses(
+            (status = 200, description = "Ref protection rules of the warehouse", body = ListRefProtectionRulesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_ref_protection_rules<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<ListRefProtectionRulesResponse>> {
+        ApiServer::<C, A, S>::list_ref_protection_rules(warehouse_id.into(), api_context, metadata)
+            .await
+            .map(Json)
+    }
+
+    /// Create a ref protection rule
+    ///
+    /// Ref protection rules protect branches and tags of all tables in a warehouse,
+    /// in a namespace including its children, or of a single table.
+    /// They are enforced whenever changes to a table are committed.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/ref-protection-rules",
+        request_body = CreateRefProtectionRuleRequest,
+        responses(
+            (status = 201, description = "Ref protection rule created successfully", body = RefProtectionRule),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn create_ref_protection_rule<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<CreateRefProtectionRuleRequest>,
+    ) -> Response {
+        match ApiServer::<C, A, S>::create_ref_protection_rule(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        {
+            Ok(rule) => (StatusCode::CREATED, Json(rule)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Delete a ref protection rule
+    #[utoipa::path(
+        delete,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/ref-protection-rules/{rule_id}",
+        responses(
+            (status = 204, description = "Ref protection rule deleted successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn delete_ref_protection_rule<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, rule_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::delete_ref_protection_rule(
+            warehouse_id.into(),
+            rule_id,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Backup a warehouse
+    ///
+    /// Returns the namespaces, tables and views of the warehouse together with the
+    /// location of their current metadata files. Metadata and data files are not copied
+    /// and storage credentials are not included.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/backup",
+        responses(
+            (status = 200, description = "Backup of the warehouse", body = WarehouseBackup),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn backup_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<WarehouseBackup>> {
+        ApiServer::<C, A, S>::backup_warehouse(warehouse_id.into(), api_context, metadata)
+            .await
+            .map(Json)
+    }
+
+    /// Restore a warehouse from a backup
+    ///
+    /// Creates a new warehouse with the namespaces, tables and views of the backup.
+    /// All metadata files referenced by the backup must be readable with the storage
+    /// profile and credential of the new warehouse.
+    /// Namespaces get new IDs. IDs of tables and views are preserved, thus the restore
+    /// is rejected with a 409 if any of them still exists on this server.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/restore",
+        request_body = RestoreWarehouseRequest,
+        responses(
+            (status = 201, description = "Warehouse restored successfully", body = RestoreWarehouseResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn restore_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RestoreWarehouseRequest>,
+    ) -> Response {
+        match ApiServer::<C, A, S>::restore_warehouse(request, api_context, metadata).await {
+            Ok(response) => (StatusCode::CREATED, Json(response)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
     #[derive(Debug, Serialize, utoipa::ToSchema)]
     pub struct ListDeletedTabularsResponse {
         /// List of tabulars
@@ -1031,6 +1583,8 @@ pub mod v1 {
                 .route("/project/:project_id/rename", post(rename_project_by_id))
                 // Create a new warehouse
                 .route("/warehouse", post(create_warehouse))
+                // Restore a warehouse from a backup
+                .route("/warehouse/restore", post(restore_warehouse))
                 // List all projects
                 .route("/project-list", get(list_projects))
                 .route(
@@ -1077,6 +1631,55 @@ pub mod v1 {
                     "/warehouse/:warehouse_id/delete-profile",
                     post(update_warehouse_delete_profile),
                 )
+                .route(
+                    "/warehouse/:warehouse_id/properties",
+                    post(update_warehouse_properties),
+                )
+                .route(
+                    "/warehouse/:warehouse_id/location-template",
+                    post(update_warehouse_location_template),
+                )
+                .route(
+                    "/warehouse/:warehouse_id/table-property-policy",
+                    post(update_table_property_policy),
+                )
+                .route(
+                    "/warehouse/:warehouse_id/namespace/:namespace_id/rename",
+                    post(rename_namespace),
+                )
+                .route(
+                    "/warehouse/:warehouse_id/deleted-namespaces",
+                    get(list_deleted_namespaces),
+                )
+                .route(
+                    "/warehouse/:warehouse_id/deleted-namespaces/undrop",
+                    post(undrop_namespaces),
+                )
+                .route(
+                    "/warehouse/:warehouse_id/table/:table_id/column-policies",
+                    get(get_column_policies).put(set_column_policies),
+                )
+                .route(
+                    "/warehouse/:warehouse_id/table/:table_id/row-filters",
+                    get(get_row_filters).put(set_row_filters),
+                )
+                .route(
+                    "/warehouse/:warehouse_id/table/:table_id/lineage",
+                    get(get_table_lineage),
+                )
+                .route(
+                    "/warehouse/:warehouse_id/view/:view_id/lineage",
+                    get(get_view_lineage),
+                )
+                .route("/warehouse/:warehouse_id/backup", get(backup_warehouse))
+                .route(
+                    "/warehouse/:warehouse_id/ref-protection-rules",
+                    get(list_ref_protection_rules).post(create_ref_protection_rule),
+                )
+                .route(
+                    "/warehouse/:warehouse_id/ref-protection-rules/:rule_id",
+                    delete(delete_ref_protection_rule),
+                )
                 .merge(authorizer.new_router())
         }
     }
@@ -17,6 +17,8 @@ pub enum AuthZBackend {
     AllowAll,
     #[serde(rename = "openfga")]
     OpenFGA,
+    Opa,
+    Postgres,
 }
 
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
@@ -189,6 +191,8 @@ pub(super) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             authz_backend: match CONFIG.authz_backend {
                 config::AuthZBackend::AllowAll => AuthZBackend::AllowAll,
                 config::AuthZBackend::OpenFGA => AuthZBackend::OpenFGA,
+                config::AuthZBackend::Opa => AuthZBackend::Opa,
+                config::AuthZBackend::Postgres => AuthZBackend::Postgres,
             },
         })
     }
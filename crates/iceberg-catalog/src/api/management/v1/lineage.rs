@@ -0,0 +1,198 @@
+use crate::api::management::v1::{ApiServer, TabularType};
+use crate::api::{ApiContext, Result};
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{
+    Authorizer, CatalogTableAction, CatalogViewAction, CatalogWarehouseAction,
+};
+use crate::service::{
+    Catalog, LineageTabular, ListFlags, SecretStore, State, TableIdentUuid, TabularIdentUuid,
+    Transaction, ViewIdentUuid,
+};
+use crate::WarehouseIdent;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, ToSchema, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetLineageResponse {
+    /// Tables and views the tabular reads from.
+    /// Only views have upstream dependencies.
+    pub upstream: Vec<LineageEntry>,
+    /// Views that read from the tabular.
+    pub downstream: Vec<LineageEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct LineageEntry {
+    /// Unique identifier of the tabular
+    pub id: uuid::Uuid,
+    /// Type of the tabular
+    pub typ: TabularType,
+    /// List of namespace parts the tabular belongs to
+    pub namespace: Vec<String>,
+    /// Name of the tabular
+    pub name: String,
+}
+
+impl From<LineageTabular> for LineageEntry {
+    fn from(tabular: LineageTabular) -> Self {
+        Self {
+            id: *tabular.tabular_id.as_ref(),
+            typ: tabular.tabular_id.into(),
+            namespace: tabular.ident.namespace.inner(),
+            name: tabular.ident.name,
+        }
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn get_table_lineage(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetLineageResponse> {
+        // ------------------- AuthZ -------------------
+        let catalog = context.v1_state.catalog;
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                warehouse_id,
+                table,
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(catalog).await?;
+        let downstream =
+            C::list_dependent_views(TabularIdentUuid::from(table_id), t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(GetLineageResponse {
+            upstream: vec![],
+            downstream: filter_visible(&authorizer, &request_metadata, warehouse_id, downstream)
+                .await?,
+        })
+    }
+
+    async fn get_view_lineage(
+        warehouse_id: WarehouseIdent,
+        view_id: ViewIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetLineageResponse> {
+        // ------------------- AuthZ -------------------
+        let catalog = context.v1_state.catalog;
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let view = C::get_tabular_namespace(TabularIdentUuid::from(view_id), catalog.clone())
+            .await
+            .map(|namespace| {
+                namespace
+                    .filter(|namespace| namespace.warehouse_id == warehouse_id)
+                    .map(|_| view_id)
+            });
+        authorizer
+            .require_view_action(
+                &request_metadata,
+                warehouse_id,
+                view,
+                &CatalogViewAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(catalog).await?;
+        let upstream = C::list_view_dependencies(view_id, t.transaction()).await?;
+        let downstream =
+            C::list_dependent_views(TabularIdentUuid::from(view_id), t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(GetLineageResponse {
+            upstream: filter_visible(&authorizer, &request_metadata, warehouse_id, upstream)
+                .await?,
+            downstream: filter_visible(&authorizer, &request_metadata, warehouse_id, downstream)
+                .await?,
+        })
+    }
+}
+
+/// Only return tabulars the caller may see the metadata of.
+pub(crate) async fn filter_visible<A: Authorizer>(
+    authorizer: &A,
+    request_metadata: &RequestMetadata,
+    warehouse_id: WarehouseIdent,
+    tabulars: Vec<LineageTabular>,
+) -> Result<Vec<LineageEntry>> {
+    let table_ids = tabulars
+        .iter()
+        .filter_map(|tabular| match tabular.tabular_id {
+            TabularIdentUuid::Table(id) => Some(id.into()),
+            TabularIdentUuid::View(_) => None,
+        })
+        .collect::<Vec<TableIdentUuid>>();
+    let view_ids = tabulars
+        .iter()
+        .filter_map(|tabular| match tabular.tabular_id {
+            TabularIdentUuid::View(id) => Some(id.into()),
+            TabularIdentUuid::Table(_) => None,
+        })
+        .collect::<Vec<ViewIdentUuid>>();
+    let (tables_allowed, views_allowed) = futures::try_join!(
+        authorizer.are_allowed_table_actions(
+            request_metadata,
+            warehouse_id,
+            &table_ids,
+            &CatalogTableAction::CanGetMetadata,
+        ),
+        authorizer.are_allowed_view_actions(
+            request_metadata,
+            warehouse_id,
+            &view_ids,
+            &CatalogViewAction::CanGetMetadata,
+        ),
+    )?;
+
+    // Both masks are in the order of `tabulars`
+    let mut tables_allowed = tables_allowed.into_iter();
+    let mut views_allowed = views_allowed.into_iter();
+    let mask = tabulars
+        .iter()
+        .map(|tabular| match tabular.tabular_id {
+            TabularIdentUuid::Table(_) => tables_allowed.next().unwrap_or(false),
+            TabularIdentUuid::View(_) => views_allowed.next().unwrap_or(false),
+        })
+        .collect::<Vec<_>>();
+
+    Ok(tabulars
+        .into_iter()
+        .zip(mask)
+        .filter_map(|(tabular, allowed)| allowed.then(|| tabular.into()))
+        .collect())
+}
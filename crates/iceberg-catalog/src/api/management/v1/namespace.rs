@@ -0,0 +1,437 @@
+use crate::api::iceberg::types::PageToken;
+use crate::api::iceberg::v1::{NamespaceIdent, PaginationQuery};
+use crate::api::management::v1::{default_page_size, ApiServer};
+use crate::api::{ApiContext, Result};
+use crate::catalog::namespace::{validate_namespace_ident, MAX_NAMESPACE_DEPTH};
+use crate::catalog::UnfilteredPage;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{
+    Authorizer, CatalogNamespaceAction, CatalogWarehouseAction, NamespaceParent,
+};
+use crate::service::task_queue::TaskFilter;
+use crate::service::{Catalog, NamespaceIdentUuid, SecretStore, State, Transaction};
+use crate::{WarehouseIdent, CONFIG};
+use futures::FutureExt;
+use iceberg_ext::catalog::rest::ErrorModel;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RenameNamespaceRequest {
+    /// New identifier of the namespace, for example `["domain", "sales", "raw"]`.
+    /// The parent of the new identifier must exist.
+    pub destination: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UndropNamespacesRequest {
+    /// Soft-deleted namespaces to undrop
+    #[schema(value_type = Vec<uuid::Uuid>)]
+    pub targets: Vec<NamespaceIdentUuid>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDeletedNamespacesQuery {
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+impl ListDeletedNamespacesQuery {
+    #[must_use]
+    pub fn pagination_query(&self) -> PaginationQuery {
+        PaginationQuery {
+            page_token: self
+                .page_token
+                .clone()
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(self.page_size),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListDeletedNamespacesResponse {
+    /// List of namespaces
+    pub namespaces: Vec<DeletedNamespaceResponse>,
+    /// Token to fetch the next page
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeletedNamespaceResponse {
+    /// Unique identifier of the namespace
+    pub id: uuid::Uuid,
+    /// List of namespace parts
+    pub namespace: Vec<String>,
+    /// Warehouse ID where the namespace is stored
+    pub warehouse_id: uuid::Uuid,
+    /// Date when the namespace was created
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Date when the namespace was deleted
+    pub deleted_at: chrono::DateTime<chrono::Utc>,
+    /// Date when the namespace will not be recoverable anymore
+    pub expiration_date: chrono::DateTime<chrono::Utc>,
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn rename_namespace(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        request: RenameNamespaceRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let destination = NamespaceIdent::from_vec(request.destination).map_err(|e| {
+            ErrorModel::bad_request(
+                "Invalid destination namespace",
+                "InvalidNamespace",
+                Some(Box::new(e)),
+            )
+        })?;
+        validate_namespace_ident(&destination)?;
+        if is_reserved(&destination) {
+            return Err(ErrorModel::bad_request(
+                "Cannot move namespace into a namespace which is reserved for internal use.",
+                "ReservedNamespace",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AUTHZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        // Moving a namespace removes it from its current parent.
+        authorizer
+            .require_namespace_action(
+                &request_metadata,
+                warehouse_id,
+                Ok(Some(namespace_id)),
+                &CatalogNamespaceAction::CanDelete,
+            )
+            .await?;
+
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let subtree = C::get_namespace_subtree(warehouse_id, namespace_id, t.transaction()).await?;
+        let Some((_, source)) = subtree
+            .namespaces
+            .iter()
+            .find(|(id, _)| *id == namespace_id)
+        else {
+            return Err(ErrorModel::internal(
+                "Namespace is missing in its own subtree",
+                "NamespaceSubtreeInconsistent",
+                None,
+            )
+            .into());
+        };
+
+        let new_parent_id = if let Some(new_parent) = destination.parent() {
+            let new_parent_id =
+                C::namespace_to_id(warehouse_id, &new_parent, t.transaction()).await;
+            let new_parent_id = authorizer
+                .require_namespace_action(
+                    &request_metadata,
+                    warehouse_id,
+                    new_parent_id,
+                    &CatalogNamespaceAction::CanCreateNamespace,
+                )
+                .await?;
+            Some(new_parent_id)
+        } else {
+            authorizer
+                .require_warehouse_action(
+                    &request_metadata,
+                    warehouse_id,
+                    &CatalogWarehouseAction::CanCreateNamespace,
+                )
+                .await?;
+            None
+        };
+
+        // ------------------- BUSINESS LOGIC -------------------
+        if is_reserved(source) {
+            return Err(ErrorModel::bad_request(
+                "Cannot move namespace which is reserved for internal use.",
+                "ReservedNamespace",
+                None,
+            )
+            .into());
+        }
+
+        if destination.len() > source.len() && is_prefix_of(source, &destination) {
+            return Err(ErrorModel::bad_request(
+                "Cannot move a namespace into itself or one of its children",
+                "NamespaceMovedIntoItself",
+                None,
+            )
+            .into());
+        }
+
+        let depth = subtree
+            .namespaces
+            .iter()
+            .map(|(_, namespace)| namespace.len() - source.len() + destination.len())
+            .max()
+            .unwrap_or(destination.len());
+        if depth > MAX_NAMESPACE_DEPTH as usize {
+            return Err(ErrorModel::bad_request(
+                format!("Namespace exceeds maximum depth of {MAX_NAMESPACE_DEPTH}"),
+                "NamespaceDepthExceeded",
+                None,
+            )
+            .into());
+        }
+
+        // A rename that only changes the case resolves to the namespace itself.
+        if let Some(existing_id) =
+            C::namespace_to_id(warehouse_id, &destination, t.transaction()).await?
+        {
+            if existing_id != namespace_id {
+                return Err(ErrorModel::conflict(
+                    format!("Namespace {} already exists", destination.to_url_string()),
+                    "NamespaceAlreadyExists",
+                    None,
+                )
+                .into());
+            }
+        }
+
+        let old_parent_id = match source.parent() {
+            Some(old_parent) => Some(
+                C::namespace_to_id(warehouse_id, &old_parent, t.transaction())
+                    .await?
+                    .ok_or_else(|| {
+                        ErrorModel::internal(
+                            format!("Parent of namespace {namespace_id} not found"),
+                            "NamespaceParentNotFound",
+                            None,
+                        )
+                    })?,
+            ),
+            None => None,
+        };
+
+        C::rename_namespace(warehouse_id, namespace_id, &destination, t.transaction()).await?;
+
+        if old_parent_id != new_parent_id {
+            let as_parent = |parent_id: Option<NamespaceIdentUuid>| match parent_id {
+                Some(parent_id) => NamespaceParent::Namespace(parent_id),
+                None => NamespaceParent::Warehouse(warehouse_id),
+            };
+            authorizer
+                .move_namespace(
+                    &request_metadata,
+                    namespace_id,
+                    as_parent(old_parent_id),
+                    as_parent(new_parent_id),
+                )
+                .await?;
+        }
+
+        t.commit().await
+    }
+
+    async fn undrop_namespaces(
+        warehouse_id: WarehouseIdent,
+        request: UndropNamespacesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AUTHZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        for namespace_id in &request.targets {
+            authorizer
+                .require_namespace_action(
+                    &request_metadata,
+                    warehouse_id,
+                    Ok(Some(*namespace_id)),
+                    &CatalogNamespaceAction::CanUndrop,
+                )
+                .await?;
+        }
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let mut namespace_tasks = vec![];
+        let mut tabular_tasks = vec![];
+        for namespace_id in request.targets {
+            let undropped =
+                C::undrop_namespace(warehouse_id, namespace_id, t.transaction()).await?;
+            let parent = match undropped.parent_id {
+                Some(parent_id) => NamespaceParent::Namespace(parent_id),
+                None => NamespaceParent::Warehouse(warehouse_id),
+            };
+            authorizer
+                .undrop_namespace(&request_metadata, namespace_id, parent)
+                .await?;
+            namespace_tasks.extend(undropped.namespace_expiration_tasks);
+            tabular_tasks.extend(undropped.tabular_expiration_tasks);
+        }
+
+        let queues = &context.v1_state.queues;
+        if !namespace_tasks.is_empty() {
+            queues
+                .cancel_namespace_expiration(TaskFilter::TaskIds(namespace_tasks))
+                .await?;
+        }
+        if !tabular_tasks.is_empty() {
+            queues
+                .cancel_tabular_expiration(TaskFilter::TaskIds(tabular_tasks))
+                .await?;
+        }
+        t.commit().await
+    }
+
+    async fn list_deleted_namespaces(
+        warehouse_id: WarehouseIdent,
+        query: ListDeletedNamespacesQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListDeletedNamespacesResponse> {
+        // ------------------- AUTHZ -------------------
+        let catalog = context.v1_state.catalog;
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanListDeletedTabulars,
+            )
+            .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        let pagination_query = query.pagination_query();
+        let mut t = C::Transaction::begin_read(catalog).await?;
+        let (namespaces, ids, next_page_token) =
+            crate::catalog::fetch_until_full_page::<_, _, _, C>(
+                pagination_query.page_size,
+                pagination_query.page_token,
+                |page_size, page_token, t| {
+                    let authorizer = authorizer.clone();
+                    let request_metadata = request_metadata.clone();
+                    async move {
+                        let query = PaginationQuery {
+                            page_size: Some(page_size),
+                            page_token: page_token.into(),
+                        };
+                        let page = C::list_deleted_namespaces(warehouse_id, query, t.transaction())
+                            .await?;
+                        let (ids, namespaces, tokens): (Vec<_>, Vec<_>, Vec<_>) =
+                            page.into_iter_with_page_tokens().multiunzip();
+
+                        let mask = authorizer
+                            .are_allowed_namespace_actions(
+                                &request_metadata,
+                                warehouse_id,
+                                &ids,
+                                &CatalogNamespaceAction::CanGetMetadata,
+                            )
+                            .await?;
+
+                        Ok(UnfilteredPage::new(
+                            namespaces,
+                            ids,
+                            tokens,
+                            mask,
+                            page_size
+                                .clamp(0, i64::MAX)
+                                .try_into()
+                                .expect("We clamped."),
+                        ))
+                    }
+                    .boxed()
+                },
+                &mut t,
+            )
+            .await?;
+        t.commit().await?;
+
+        let namespaces = ids
+            .into_iter()
+            .zip(namespaces)
+            .map(|(id, (namespace, deleted))| DeletedNamespaceResponse {
+                id: *id,
+                namespace: namespace.inner(),
+                warehouse_id: *warehouse_id,
+                created_at: deleted.created_at,
+                deleted_at: deleted.deleted_at,
+                expiration_date: deleted.expiration_date,
+            })
+            .collect();
+
+        Ok(ListDeletedNamespacesResponse {
+            namespaces,
+            next_page_token,
+        })
+    }
+}
+
+impl axum::response::IntoResponse for ListDeletedNamespacesResponse {
+    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+        axum::Json(self).into_response()
+    }
+}
+
+fn is_reserved(namespace: &NamespaceIdent) -> bool {
+    CONFIG
+        .reserved_namespaces
+        .contains(&namespace.as_ref()[0].to_lowercase())
+}
+
+/// Namespace names are case insensitive.
+fn is_prefix_of(prefix: &NamespaceIdent, namespace: &NamespaceIdent) -> bool {
+    prefix.len() <= namespace.len()
+        && prefix
+            .iter()
+            .zip(namespace.iter())
+            .all(|(a, b)| a.to_lowercase() == b.to_lowercase())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_prefix_of() {
+        let namespace = |parts: &[&str]| {
+            NamespaceIdent::from_vec(parts.iter().map(ToString::to_string).collect()).unwrap()
+        };
+        assert!(is_prefix_of(
+            &namespace(&["team_a"]),
+            &namespace(&["Team_A", "raw"])
+        ));
+        assert!(is_prefix_of(
+            &namespace(&["a", "b"]),
+            &namespace(&["a", "b"])
+        ));
+        assert!(!is_prefix_of(&namespace(&["a", "b"]), &namespace(&["a"])));
+        assert!(!is_prefix_of(&namespace(&["a"]), &namespace(&["b", "a"])));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::api::management::v1::ApiServer;
+use crate::api::{ApiContext, Result};
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{
+    Authorizer, CatalogWarehouseAction, RefProtectionRule, RefProtectionScope,
+};
+use crate::service::{Catalog, RoleId, SecretStore, State, Transaction};
+use crate::WarehouseIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateRefProtectionRuleRequest {
+    /// Objects the rule applies to.
+    pub scope: RefProtectionScope,
+    /// Names of the protected branches and tags.
+    /// `*` matches any sequence of characters, for example `release-*`.
+    pub ref_pattern: String,
+    /// Roles whose assignees may create, move or remove matching refs.
+    /// If empty, all principals that may commit to the table are allowed.
+    #[serde(default)]
+    #[schema(value_type = Vec<uuid::Uuid>)]
+    pub allowed_committers: Vec<RoleId>,
+    /// Matching refs must not be removed.
+    #[serde(default)]
+    pub prevent_deletion: bool,
+    /// Matching refs may only be moved to descendants of their current snapshot.
+    #[serde(default)]
+    pub fast_forward_only: bool,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListRefProtectionRulesResponse {
+    /// Ref protection rules of the warehouse.
+    pub rules: Vec<RefProtectionRule>,
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn list_ref_protection_rules(
+        warehouse_id: WarehouseIdent,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListRefProtectionRulesResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanReadRefProtectionRules,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let rules = C::list_ref_protection_rules(warehouse_id, context.v1_state.catalog).await?;
+        Ok(ListRefProtectionRulesResponse { rules })
+    }
+
+    async fn create_ref_protection_rule(
+        warehouse_id: WarehouseIdent,
+        request: CreateRefProtectionRuleRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RefProtectionRule> {
+        // ------------------- Validations -------------------
+        if request.ref_pattern.is_empty() {
+            return Err(ErrorModel::bad_request(
+                "Ref pattern must not be empty",
+                "EmptyRefPattern",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanManageRefProtectionRules,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let CreateRefProtectionRuleRequest {
+            scope,
+            ref_pattern,
+            mut allowed_committers,
+            prevent_deletion,
+            fast_forward_only,
+        } = request;
+        allowed_committers.sort_unstable();
+        allowed_committers.dedup();
+        let rule = RefProtectionRule {
+            rule_id: uuid::Uuid::now_v7(),
+            scope,
+            ref_pattern,
+            allowed_committers,
+            prevent_deletion,
+            fast_forward_only,
+        };
+
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::create_ref_protection_rule(warehouse_id, &rule, t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(rule)
+    }
+
+    async fn delete_ref_protection_rule(
+        warehouse_id: WarehouseIdent,
+        rule_id: uuid::Uuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanManageRefProtectionRules,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::delete_ref_protection_rule(warehouse_id, rule_id, t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(())
+    }
+}
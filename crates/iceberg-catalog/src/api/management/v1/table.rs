@@ -0,0 +1,273 @@
+use crate::api::management::v1::ApiServer;
+use crate::api::{ApiContext, Result};
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::row_filter::validate_row_filters;
+use crate::service::authz::{
+    Authorizer, CatalogTableAction, CatalogWarehouseAction, ColumnPolicy, RowFilter,
+};
+use crate::service::{Catalog, ListFlags, SecretStore, State, TableIdentUuid, Transaction};
+use crate::WarehouseIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, ToSchema, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetColumnPoliciesResponse {
+    /// Column policies of the table, ordered by field id.
+    pub policies: Vec<ColumnPolicy>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetColumnPoliciesRequest {
+    /// Column policies of the table. Replaces all existing policies.
+    /// At most one policy per field id is allowed.
+    pub policies: Vec<ColumnPolicy>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetRowFiltersResponse {
+    /// Row filters of the table, ordered by role id.
+    pub filters: Vec<RowFilter>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetRowFiltersRequest {
+    /// Row filters of the table. Replaces all existing filters.
+    /// At most one filter per role is allowed.
+    pub filters: Vec<RowFilter>,
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn get_column_policies(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetColumnPoliciesResponse> {
+        // ------------------- AuthZ -------------------
+        let catalog = context.v1_state.catalog;
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                warehouse_id,
+                table,
+                &CatalogTableAction::CanReadColumnPolicies,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let policies = C::get_column_policies(table_id, catalog).await?;
+        Ok(GetColumnPoliciesResponse { policies })
+    }
+
+    async fn set_column_policies(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        request: SetColumnPoliciesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let catalog = context.v1_state.catalog;
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                warehouse_id,
+                table,
+                &CatalogTableAction::CanManageColumnPolicies,
+            )
+            .await?;
+
+        // ------------------- Validations -------------------
+        let mut field_ids = HashSet::new();
+        if let Some(policy) = request
+            .policies
+            .iter()
+            .find(|policy| !field_ids.insert(policy.field_id))
+        {
+            return Err(ErrorModel::bad_request(
+                format!("Multiple policies for field id {}", policy.field_id),
+                "DuplicateColumnPolicy",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(catalog).await?;
+        let mut tables = C::load_tables(warehouse_id, [table_id], false, t.transaction()).await?;
+        let Some(table) = tables.remove(&table_id) else {
+            return Err(ErrorModel::not_found(
+                format!("Table {table_id} not found"),
+                "TableNotFound",
+                None,
+            )
+            .into());
+        };
+        // Policies may reference columns of previous schemas, for example to keep
+        // protecting a dropped column when the table is time-travelled.
+        if let Some(policy) = request.policies.iter().find(|policy| {
+            !table
+                .table_metadata
+                .schemas_iter()
+                .any(|schema| schema.field_by_id(policy.field_id).is_some())
+        }) {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Field id {} does not exist in any schema of table {table_id}",
+                    policy.field_id
+                ),
+                "ColumnPolicyFieldNotFound",
+                None,
+            )
+            .into());
+        }
+        C::set_column_policies(table_id, &request.policies, t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(())
+    }
+
+    async fn get_row_filters(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetRowFiltersResponse> {
+        // ------------------- AuthZ -------------------
+        let catalog = context.v1_state.catalog;
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                warehouse_id,
+                table,
+                &CatalogTableAction::CanReadRowFilters,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let filters = C::get_row_filters(table_id, catalog).await?;
+        Ok(GetRowFiltersResponse { filters })
+    }
+
+    async fn set_row_filters(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        request: SetRowFiltersRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let catalog = context.v1_state.catalog;
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                warehouse_id,
+                table,
+                &CatalogTableAction::CanManageRowFilters,
+            )
+            .await?;
+
+        // ------------------- Validations -------------------
+        let mut role_ids = HashSet::new();
+        if let Some(filter) = request
+            .filters
+            .iter()
+            .find(|filter| !role_ids.insert(filter.role_id))
+        {
+            return Err(ErrorModel::bad_request(
+                format!("Multiple row filters for role {}", filter.role_id),
+                "DuplicateRowFilter",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(catalog).await?;
+        let mut tables = C::load_tables(warehouse_id, [table_id], false, t.transaction()).await?;
+        let Some(table) = tables.remove(&table_id) else {
+            return Err(ErrorModel::not_found(
+                format!("Table {table_id} not found"),
+                "TableNotFound",
+                None,
+            )
+            .into());
+        };
+        // Unlike column policies, filters reference columns by name and must be
+        // evaluated against the schema queries run on.
+        validate_row_filters(&request.filters, table.table_metadata.current_schema())?;
+        C::set_row_filters(table_id, &request.filters, t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,646 @@
+//! Backup and restore of the catalog state of a warehouse.
+//!
+//! A backup references the metadata files of tables and views by location. It does
+//! neither contain the files themselves nor storage credentials.
+
+use super::{
+    validate_location_template, validate_warehouse_name, TablePropertyPolicy, TabularDeleteProfile,
+};
+use crate::api::iceberg::v1::{NamespaceIdent, TableIdent};
+use crate::api::Result;
+use crate::catalog::io::read_metadata_file;
+use crate::catalog::{list_all_namespaces, list_all_tabulars};
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{Authorizer, NamespaceParent};
+use crate::service::storage::{StorageCredential, StorageProfile, TabularLocationTemplate};
+use crate::service::{
+    Catalog, CreateNamespaceRequest, NamespaceIdentUuid, SecretStore, TableCreation,
+    TableIdentUuid, TabularIdentOwned, TabularIdentUuid, Transaction, ViewIdentUuid,
+};
+use crate::{ProjectIdent, WarehouseIdent};
+use futures::{StreamExt, TryStreamExt};
+use iceberg::spec::{TableMetadata, ViewMetadata};
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+use iceberg_ext::configs::Location;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Version of the [`WarehouseBackup`] format
+pub const WAREHOUSE_BACKUP_VERSION: u32 = 1;
+/// Number of metadata files that are read concurrently during restore
+const MAX_CONCURRENT_READS: usize = 16;
+
+/// Catalog state of a warehouse.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct WarehouseBackup {
+    /// Version of the backup format
+    pub version: u32,
+    /// Time the backup was created
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Name of the backed up warehouse
+    pub warehouse_name: String,
+    /// Storage profile of the backed up warehouse
+    pub storage_profile: StorageProfile,
+    /// Delete profile of the backed up warehouse
+    pub delete_profile: TabularDeleteProfile,
+    /// Properties of the backed up warehouse
+    #[serde(default)]
+    pub properties: HashMap<String, String>,
+    /// Table property policy of the backed up warehouse
+    #[serde(default)]
+    pub table_property_policy: TablePropertyPolicy,
+    /// Location template of the backed up warehouse
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub location_template: Option<TabularLocationTemplate>,
+    /// Namespaces, parents before their children
+    pub namespaces: Vec<NamespaceBackup>,
+    pub tables: Vec<TabularBackup>,
+    pub views: Vec<TabularBackup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct NamespaceBackup {
+    pub namespace_id: uuid::Uuid,
+    pub namespace: Vec<String>,
+    #[serde(default)]
+    pub properties: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TabularBackup {
+    pub namespace: Vec<String>,
+    pub name: String,
+    /// Location of the current metadata file
+    pub metadata_location: String,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RestoreWarehouseRequest {
+    /// Name of the restored warehouse. Defaults to the name of the backed up warehouse.
+    pub warehouse_name: Option<String>,
+    /// Project ID in which to restore the warehouse.
+    /// If no default project is set for this server, this field is required.
+    pub project_id: Option<uuid::Uuid>,
+    /// Storage profile of the restored warehouse.
+    /// Defaults to the storage profile of the backed up warehouse.
+    /// All metadata files must be located within the storage profile.
+    pub storage_profile: Option<StorageProfile>,
+    /// Optional storage credential to use for the restored warehouse.
+    pub storage_credential: Option<StorageCredential>,
+    pub backup: WarehouseBackup,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RestoreWarehouseResponse {
+    /// ID of the restored warehouse.
+    pub warehouse_id: uuid::Uuid,
+    /// Number of restored namespaces
+    pub namespaces: usize,
+    /// Number of restored tables
+    pub tables: usize,
+    /// Number of restored views
+    pub views: usize,
+}
+
+/// Create a backup of all active namespaces, tables and views of a warehouse.
+pub async fn backup_warehouse<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    catalog_state: C::State,
+) -> Result<WarehouseBackup> {
+    let namespace_ids = list_all_namespaces::<C>(warehouse_id, catalog_state.clone()).await?;
+    let tabulars = list_all_tabulars::<C>(warehouse_id, catalog_state.clone()).await?;
+
+    let mut t = C::Transaction::begin_read(catalog_state).await?;
+    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+
+    let mut namespaces = Vec::with_capacity(namespace_ids.len());
+    for (namespace_id, _) in namespace_ids {
+        let namespace = C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
+        namespaces.push(NamespaceBackup {
+            namespace_id: *namespace_id,
+            namespace: namespace.namespace.inner(),
+            properties: namespace.properties.unwrap_or_default(),
+        });
+    }
+    namespaces
+        .sort_by(|a, b| (a.namespace.len(), &a.namespace).cmp(&(b.namespace.len(), &b.namespace)));
+
+    let table_ids = tabulars.iter().filter_map(|(id, _)| match id {
+        TabularIdentUuid::Table(id) => Some(TableIdentUuid::from(*id)),
+        TabularIdentUuid::View(_) => None,
+    });
+    let loaded_tables = C::load_tables(warehouse_id, table_ids, false, t.transaction()).await?;
+
+    let mut tables = vec![];
+    let mut views = vec![];
+    for (id, ident) in tabulars {
+        match (id, ident) {
+            (TabularIdentUuid::Table(id), TabularIdentOwned::Table(ident)) => {
+                // Staged tables have no metadata file yet
+                if let Some(metadata_location) = loaded_tables
+                    .get(&TableIdentUuid::from(id))
+                    .and_then(|table| table.metadata_location.as_ref())
+                {
+                    tables.push(tabular_backup(ident, metadata_location.to_string()));
+                }
+            }
+            (TabularIdentUuid::View(id), TabularIdentOwned::View(ident)) => {
+                let view = C::load_view(ViewIdentUuid::from(id), false, t.transaction()).await?;
+                views.push(tabular_backup(ident, view.metadata_location));
+            }
+            (id, ident) => {
+                return Err(ErrorModel::internal(
+                    format!("Tabular {id} has mismatching type of {ident:?}"),
+                    "TabularTypeMismatch",
+                    None,
+                )
+                .into());
+            }
+        }
+    }
+    t.commit().await?;
+
+    for tabulars in [&mut tables, &mut views] {
+        tabulars.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+    }
+
+    Ok(WarehouseBackup {
+        version: WAREHOUSE_BACKUP_VERSION,
+        created_at: chrono::Utc::now(),
+        warehouse_name: warehouse.name,
+        storage_profile: warehouse.storage_profile,
+        delete_profile: warehouse.tabular_delete_profile,
+        properties: warehouse.properties,
+        table_property_policy: warehouse.table_property_policy,
+        location_template: warehouse.location_template,
+        namespaces,
+        tables,
+        views,
+    })
+}
+
+fn tabular_backup(ident: TableIdent, metadata_location: String) -> TabularBackup {
+    TabularBackup {
+        namespace: ident.namespace.inner(),
+        name: ident.name,
+        metadata_location,
+    }
+}
+
+/// Restore a backup as a new warehouse of `project_id`.
+///
+/// All metadata files are read before anything is written. Namespaces get new ids.
+/// Tables and views keep their ids, as they are part of their metadata files, so the
+/// restore is rejected if any of them still exists on this server, for example because
+/// the backed up warehouse was not deleted.
+pub async fn restore_warehouse<C: Catalog, A: Authorizer, S: SecretStore>(
+    project_id: ProjectIdent,
+    request: RestoreWarehouseRequest,
+    authorizer: &A,
+    catalog_state: C::State,
+    secret_store: &S,
+    request_metadata: &RequestMetadata,
+) -> Result<RestoreWarehouseResponse> {
+    let RestoreWarehouseRequest {
+        warehouse_name,
+        project_id: _,
+        storage_profile,
+        storage_credential,
+        backup,
+    } = request;
+
+    // ------------------- Validations -------------------
+    if backup.version > WAREHOUSE_BACKUP_VERSION {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Backup version {} is not supported. Latest supported version is {WAREHOUSE_BACKUP_VERSION}",
+                backup.version
+            ),
+            "UnsupportedBackupVersion",
+            None,
+        )
+        .into());
+    }
+    let warehouse_name = warehouse_name.unwrap_or(backup.warehouse_name);
+    validate_warehouse_name(&warehouse_name)?;
+    let mut storage_profile = storage_profile.unwrap_or(backup.storage_profile);
+    storage_profile.normalize()?;
+    storage_profile
+        .validate_access(storage_credential.as_ref(), None)
+        .await?;
+    if let Some(location_template) = &backup.location_template {
+        validate_location_template(location_template, &storage_profile)?;
+    }
+
+    let file_io = storage_profile.file_io(storage_credential.as_ref())?;
+    let tables = futures::stream::iter(backup.tables)
+        .map(|tabular| async {
+            let location = require_metadata_location(&storage_profile, &tabular)?;
+            let metadata: TableMetadata = read_metadata_file(&file_io, &location).await?;
+            Ok::<_, IcebergErrorResponse>((tabular, location, metadata))
+        })
+        .buffered(MAX_CONCURRENT_READS)
+        .try_collect::<Vec<_>>()
+        .await?;
+    let views = futures::stream::iter(backup.views)
+        .map(|tabular| async {
+            let location = require_metadata_location(&storage_profile, &tabular)?;
+            let metadata: ViewMetadata = read_metadata_file(&file_io, &location).await?;
+            Ok::<_, IcebergErrorResponse>((tabular, location, metadata))
+        })
+        .buffered(MAX_CONCURRENT_READS)
+        .try_collect::<Vec<_>>()
+        .await?;
+    let tabular_ids = tables
+        .iter()
+        .map(|(_, _, metadata)| TabularIdentUuid::Table(metadata.uuid()))
+        .chain(
+            views
+                .iter()
+                .map(|(_, _, metadata)| TabularIdentUuid::View(metadata.view_uuid)),
+        );
+    require_tabulars_not_exist::<C>(tabular_ids, catalog_state.clone()).await?;
+
+    // ------------------- Business Logic -------------------
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let secret_id = if let Some(storage_credential) = storage_credential {
+        Some(secret_store.create_secret(storage_credential).await?)
+    } else {
+        None
+    };
+    let warehouse_id = C::create_warehouse(
+        warehouse_name,
+        project_id,
+        storage_profile,
+        backup.delete_profile,
+        secret_id,
+        t.transaction(),
+    )
+    .await?;
+    if !backup.properties.is_empty() {
+        C::set_warehouse_properties(warehouse_id, &backup.properties, t.transaction()).await?;
+    }
+    if backup.location_template.is_some() {
+        C::set_warehouse_location_template(
+            warehouse_id,
+            backup.location_template.as_ref(),
+            t.transaction(),
+        )
+        .await?;
+    }
+    if !backup.table_property_policy.rules.is_empty() {
+        C::set_warehouse_table_property_policy(
+            warehouse_id,
+            &backup.table_property_policy,
+            t.transaction(),
+        )
+        .await?;
+    }
+    authorizer
+        .create_warehouse(request_metadata, warehouse_id, project_id)
+        .await?;
+
+    let mut namespace_ids = HashMap::with_capacity(backup.namespaces.len());
+    for NamespaceBackup {
+        namespace_id: _,
+        namespace,
+        properties,
+    } in backup.namespaces
+    {
+        let namespace_id = NamespaceIdentUuid::default();
+        let parent = match namespace.split_last() {
+            Some((_, parent)) if !parent.is_empty() => {
+                NamespaceParent::Namespace(require_namespace_id(&namespace_ids, parent)?)
+            }
+            _ => NamespaceParent::Warehouse(warehouse_id),
+        };
+        C::create_namespace(
+            warehouse_id,
+            namespace_id,
+            CreateNamespaceRequest {
+                namespace: namespace_ident(namespace.clone())?,
+                properties: Some(properties),
+            },
+            t.transaction(),
+        )
+        .await?;
+        authorizer
+            .create_namespace(request_metadata, namespace_id, parent)
+            .await?;
+        namespace_ids.insert(namespace, namespace_id);
+    }
+
+    let n_tables = tables.len();
+    for (tabular, location, metadata) in tables {
+        let namespace_id = require_namespace_id(&namespace_ids, &tabular.namespace)?;
+        let table_id = TableIdentUuid::from(metadata.uuid());
+        let table_ident = TableIdent::new(namespace_ident(tabular.namespace)?, tabular.name);
+        C::create_table(
+            TableCreation {
+                namespace_id,
+                table_ident: &table_ident,
+                metadata_location: Some(&location),
+                table_metadata: metadata,
+            },
+            t.transaction(),
+        )
+        .await?;
+        authorizer
+            .create_table(request_metadata, table_id, namespace_id)
+            .await?;
+    }
+
+    let n_views = views.len();
+    for (tabular, location, metadata) in views {
+        let namespace_id = require_namespace_id(&namespace_ids, &tabular.namespace)?;
+        let view_id = ViewIdentUuid::from(metadata.view_uuid);
+        let view_location = Location::from_str(&metadata.location).map_err(|e| {
+            ErrorModel::bad_request(
+                format!("Invalid view location: '{}'", metadata.location),
+                "InvalidLocation",
+                Some(Box::new(e)),
+            )
+        })?;
+        let view_ident = TableIdent::new(namespace_ident(tabular.namespace)?, tabular.name);
+        C::create_view(
+            namespace_id,
+            &view_ident,
+            metadata,
+            &location,
+            &view_location,
+            t.transaction(),
+        )
+        .await?;
+        authorizer
+            .create_view(request_metadata, view_id, namespace_id)
+            .await?;
+    }
+
+    t.commit().await?;
+
+    Ok(RestoreWarehouseResponse {
+        warehouse_id: *warehouse_id,
+        namespaces: namespace_ids.len(),
+        tables: n_tables,
+        views: n_views,
+    })
+}
+
+/// Fail if any of `tabular_ids` exists on this server, in any warehouse.
+async fn require_tabulars_not_exist<C: Catalog>(
+    tabular_ids: impl Iterator<Item = TabularIdentUuid>,
+    catalog_state: C::State,
+) -> Result<()> {
+    let mut existing = futures::stream::iter(tabular_ids)
+        .map(|tabular_id| {
+            let catalog_state = catalog_state.clone();
+            async move {
+                C::get_tabular_namespace(tabular_id, catalog_state)
+                    .await
+                    .map(|namespace| namespace.map(|namespace| (tabular_id, namespace)))
+            }
+        })
+        .buffered(MAX_CONCURRENT_READS)
+        .try_filter_map(|existing| async move { Ok(existing) });
+
+    if let Some((tabular_id, namespace)) = existing.try_next().await? {
+        return Err(ErrorModel::conflict(
+            format!(
+                "{} {tabular_id} of the backup already exists in warehouse {}",
+                tabular_id.typ_str(),
+                namespace.warehouse_id
+            ),
+            "TabularAlreadyExists",
+            None,
+        )
+        .append_detail(
+            "Tables and views keep their ids when restored. Delete the backed up warehouse first or restore into a different server.",
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Parse the metadata location of `tabular` and check that it is within `storage_profile`.
+fn require_metadata_location(
+    storage_profile: &StorageProfile,
+    tabular: &TabularBackup,
+) -> Result<Location> {
+    let location = Location::from_str(&tabular.metadata_location).map_err(|e| {
+        ErrorModel::bad_request(
+            format!("Invalid metadata location: '{}'", tabular.metadata_location),
+            "InvalidLocation",
+            Some(Box::new(e)),
+        )
+    })?;
+    if !storage_profile.is_allowed_location(&location) {
+        return Err(ErrorModel::bad_request(
+            format!("Metadata location {location} is not within the storage profile"),
+            "InvalidMetadataLocation",
+            None,
+        )
+        .into());
+    }
+    Ok(location)
+}
+
+fn namespace_ident(namespace: Vec<String>) -> Result<NamespaceIdent> {
+    NamespaceIdent::from_vec(namespace).map_err(|e| {
+        ErrorModel::bad_request(
+            "Invalid namespace in backup",
+            "InvalidBackup",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+fn require_namespace_id(
+    namespace_ids: &HashMap<Vec<String>, NamespaceIdentUuid>,
+    namespace: &[String],
+) -> Result<NamespaceIdentUuid> {
+    namespace_ids.get(namespace).copied().ok_or_else(|| {
+        ErrorModel::bad_request(
+            format!(
+                "Namespace {} is referenced but not part of the backup",
+                namespace.join(".")
+            ),
+            "InvalidBackup",
+            None,
+        )
+        .into()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::iceberg::types::{DropParams, Prefix};
+    use crate::api::iceberg::v1::tables::Service as _;
+    use crate::api::iceberg::v1::{DataAccess, NamespaceParameters, TableParameters};
+    use crate::api::management::v1::project::{CreateProjectRequest, Service as _};
+    use crate::api::management::v1::ApiServer;
+    use crate::catalog::tables::test::create_request;
+    use crate::catalog::test::random_request_metadata;
+    use crate::catalog::CatalogServer;
+    use crate::implementations::postgres::PostgresCatalog;
+    use crate::service::authz::AllowAllAuthorizer;
+
+    #[sqlx::test]
+    async fn test_backup_restore_into_new_project(pool: sqlx::PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool,
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let table = CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: prefix.clone(),
+                namespace: ns.namespace.clone(),
+            },
+            create_request(Some("tab-1".to_string())),
+            DataAccess {
+                vended_credentials: true,
+                remote_signing: false,
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+
+        let backup = backup_warehouse::<PostgresCatalog>(
+            warehouse.warehouse_id.into(),
+            ctx.v1_state.catalog.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(backup.namespaces.len(), 1);
+        assert_eq!(backup.tables.len(), 1);
+        assert_eq!(
+            Some(backup.tables[0].metadata_location.as_str()),
+            table.metadata_location.as_deref()
+        );
+
+        let project = ApiServer::create_project(
+            CreateProjectRequest {
+                project_name: "restored".to_string(),
+                project_id: None,
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+        let restore = |backup: WarehouseBackup| {
+            restore_warehouse::<PostgresCatalog, _, _>(
+                ProjectIdent::from(project.project_id),
+                RestoreWarehouseRequest {
+                    warehouse_name: Some("restored".to_string()),
+                    project_id: Some(project.project_id),
+                    storage_profile: None,
+                    storage_credential: None,
+                    backup,
+                },
+                &ctx.v1_state.authz,
+                ctx.v1_state.catalog.clone(),
+                &ctx.v1_state.secrets,
+                &random_request_metadata(),
+            )
+        };
+
+        // The table still exists in the backed up warehouse
+        let err = restore(backup.clone()).await.unwrap_err();
+        assert_eq!(err.error.r#type, "TabularAlreadyExists");
+        assert_eq!(err.error.code, http::StatusCode::CONFLICT.as_u16());
+
+        // Dropping the table without purge keeps its metadata file
+        CatalogServer::drop_table(
+            TableParameters {
+                prefix,
+                table: TableIdent::new(ns.namespace.clone(), "tab-1".to_string()),
+            },
+            DropParams {
+                purge_requested: Some(false),
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+
+        let restored = restore(backup.clone()).await.unwrap();
+        assert_eq!(restored.namespaces, 1);
+        assert_eq!(restored.tables, 1);
+        assert_eq!(restored.views, 0);
+
+        let restored_backup = backup_warehouse::<PostgresCatalog>(
+            restored.warehouse_id.into(),
+            ctx.v1_state.catalog.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(restored_backup.warehouse_name, "restored");
+        assert_eq!(restored_backup.tables, backup.tables);
+        assert_eq!(
+            restored_backup.namespaces[0].namespace,
+            backup.namespaces[0].namespace
+        );
+        assert_ne!(
+            restored_backup.namespaces[0].namespace_id,
+            backup.namespaces[0].namespace_id
+        );
+    }
+
+    #[test]
+    fn test_backup_roundtrip() {
+        let backup = serde_json::json!({
+            "version": 1,
+            "created-at": "2024-11-30T10:00:00Z",
+            "warehouse-name": "prod",
+            "storage-profile": {
+                "type": "s3",
+                "bucket": "lakehouse",
+                "region": "eu-central-1",
+                "sts-enabled": false,
+            },
+            "delete-profile": {"type": "hard"},
+            "namespaces": [{
+                "namespace-id": "0193775f-05c5-7a11-9b5a-2ad6a39eb3a0",
+                "namespace": ["sales"],
+            }],
+            "tables": [{
+                "namespace": ["sales"],
+                "name": "orders",
+                "metadata-location": "s3://lakehouse/0193775f/00001-abc.gz.metadata.json",
+            }],
+            "views": [],
+        });
+        let parsed: WarehouseBackup = serde_json::from_value(backup).unwrap();
+        assert_eq!(parsed.namespaces[0].properties, HashMap::new());
+        assert_eq!(parsed.tables[0].name, "orders");
+
+        let reparsed: WarehouseBackup =
+            serde_json::from_value(serde_json::to_value(&parsed).unwrap()).unwrap();
+        assert_eq!(reparsed.tables, parsed.tables);
+        assert_eq!(reparsed.namespaces, parsed.namespaces);
+    }
+}
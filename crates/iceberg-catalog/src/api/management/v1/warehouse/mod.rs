@@ -1,15 +1,26 @@
+pub mod backup;
 mod undrop;
 
 use crate::api::management::v1::{ApiServer, DeletedTabularResponse, ListDeletedTabularsResponse};
 use crate::api::{ApiContext, Result};
+use crate::catalog::tables::{
+    InheritedTableProperties, TABLE_DEFAULT_PREFIX, TABLE_OVERRIDE_PREFIX,
+};
 use crate::request_metadata::RequestMetadata;
 use crate::service::authz::{CatalogProjectAction, CatalogWarehouseAction};
 pub use crate::service::storage::{
     AdlsProfile, AzCredential, GcsCredential, GcsProfile, GcsServiceKey, S3Credential, S3Profile,
     StorageCredential, StorageProfile,
 };
+use crate::service::storage::{TabularLocationContext, TabularLocationTemplate};
+pub use backup::{
+    NamespaceBackup, RestoreWarehouseRequest, RestoreWarehouseResponse, TabularBackup,
+    WarehouseBackup, WAREHOUSE_BACKUP_VERSION,
+};
 use futures::FutureExt;
+use iceberg::NamespaceIdent;
 use itertools::Itertools;
+use std::collections::HashMap;
 
 use crate::api::iceberg::v1::{PageToken, PaginationQuery};
 use crate::service::{NamespaceIdentUuid, TableIdentUuid};
@@ -17,6 +28,7 @@ use crate::service::{NamespaceIdentUuid, TableIdentUuid};
 use super::default_page_size;
 use crate::api::management::v1::role::require_project_id;
 use crate::catalog::UnfilteredPage;
+pub use crate::service::table_property_policy::{TablePropertyPolicy, TablePropertyRule};
 use crate::service::task_queue::TaskFilter;
 pub use crate::service::WarehouseStatus;
 use crate::service::{
@@ -175,6 +187,34 @@ pub struct UpdateWarehouseDeleteProfileRequest {
     pub delete_profile: TabularDeleteProfile,
 }
 
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehousePropertiesRequest {
+    /// New properties of the warehouse. Replaces all existing properties.
+    /// Only `table-default.*` and `table-override.*` properties are supported.
+    pub properties: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseLocationTemplateRequest {
+    /// Template for the default location of new tables and views.
+    /// If not specified, the default layout `{namespace-location}/{uuid}` is restored.
+    ///
+    /// The template must start with `{namespace-location}` or `{base-location}`.
+    /// Supported placeholders are `{namespace}`, `{namespace-id}`, `{name}`,
+    /// `{uuid}` and `{hash}`. The last segment must contain `{uuid}`.
+    #[schema(example = "{base-location}/{hash}/{namespace}/{name}-{uuid}")]
+    pub location_template: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateTablePropertyPolicyRequest {
+    /// New table property policy of the warehouse. Replaces the existing policy.
+    pub policy: TablePropertyPolicy,
+}
+
 #[derive(Debug, Clone, serde::Deserialize, ToSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct RenameProjectRequest {
@@ -197,6 +237,13 @@ pub struct GetWarehouseResponse {
     pub delete_profile: TabularDeleteProfile,
     /// Whether the warehouse is active.
     pub status: WarehouseStatus,
+    /// Properties of the warehouse.
+    pub properties: HashMap<String, String>,
+    /// Policy for the properties of tables and views in the warehouse.
+    pub table_property_policy: TablePropertyPolicy,
+    /// Template for the default location of tables and views.
+    /// If not set, they are placed at `{namespace-location}/{uuid}`.
+    pub location_template: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, ToSchema)]
@@ -451,6 +498,105 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         Ok(())
     }
 
+    async fn update_warehouse_properties(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehousePropertiesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateProperties,
+            )
+            .await?;
+
+        // ------------------- Validations -------------------
+        validate_warehouse_properties(&request.properties)?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_properties(warehouse_id, &request.properties, transaction.transaction())
+            .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_location_template(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseLocationTemplateRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Validations -------------------
+        let location_template = request
+            .location_template
+            .map(TabularLocationTemplate::try_new)
+            .transpose()?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        if let Some(location_template) = &location_template {
+            validate_location_template(location_template, &warehouse.storage_profile)?;
+        }
+        C::set_warehouse_location_template(
+            warehouse_id,
+            location_template.as_ref(),
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_table_property_policy(
+        warehouse_id: WarehouseIdent,
+        request: UpdateTablePropertyPolicyRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateProperties,
+            )
+            .await?;
+
+        // ------------------- Validations -------------------
+        request.policy.validate()?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_table_property_policy(
+            warehouse_id,
+            &request.policy,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
     async fn deactivate_warehouse(
         warehouse_id: WarehouseIdent,
         context: ApiContext<State<A, C, S>>,
@@ -651,6 +797,63 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         Ok(())
     }
 
+    async fn backup_warehouse(
+        warehouse_id: WarehouseIdent,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<WarehouseBackup> {
+        // ------------------- AuthZ -------------------
+        context
+            .v1_state
+            .authz
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanBackup,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        backup::backup_warehouse::<C>(warehouse_id, context.v1_state.catalog).await
+    }
+
+    async fn restore_warehouse(
+        request: RestoreWarehouseRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RestoreWarehouseResponse> {
+        let project_id = request
+            .project_id
+            .map(ProjectIdent::from)
+            .or(*DEFAULT_PROJECT_ID)
+            .ok_or(ErrorModel::bad_request(
+                "project_id must be specified",
+                "RestoreWarehouseProjectIdMissing",
+                None,
+            ))?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_project_action(
+                &request_metadata,
+                project_id,
+                &CatalogProjectAction::CanCreateWarehouse,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        backup::restore_warehouse::<C, A, S>(
+            project_id,
+            request,
+            &authorizer,
+            context.v1_state.catalog,
+            &context.v1_state.secrets,
+            &request_metadata,
+        )
+        .await
+    }
+
     async fn undrop_tabulars(
         request_metadata: RequestMetadata,
         warehouse_ident: WarehouseIdent,
@@ -830,10 +1033,57 @@ impl From<crate::service::GetWarehouseResponse> for GetWarehouseResponse {
             storage_profile: warehouse.storage_profile,
             status: warehouse.status,
             delete_profile: warehouse.tabular_delete_profile,
+            properties: warehouse.properties,
+            table_property_policy: warehouse.table_property_policy,
+            location_template: warehouse.location_template.map(String::from),
         }
     }
 }
 
+fn validate_warehouse_properties(properties: &HashMap<String, String>) -> Result<()> {
+    if let Some(key) = properties
+        .keys()
+        .find(|k| InheritedTableProperties::strip_prefix(k).is_none())
+    {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Warehouse property '{key}' is not supported. Only properties prefixed with '{TABLE_DEFAULT_PREFIX}' or '{TABLE_OVERRIDE_PREFIX}' are allowed."
+            ),
+            "InvalidWarehouseProperty",
+            None,
+        )
+        .into());
+    }
+    InheritedTableProperties::validate_keys(properties.keys())
+}
+
+/// Check that locations generated by the template are valid sub-locations of the storage profile.
+fn validate_location_template(
+    location_template: &TabularLocationTemplate,
+    storage_profile: &StorageProfile,
+) -> Result<()> {
+    let namespace_id = NamespaceIdentUuid::from(uuid::Uuid::now_v7());
+    let location = location_template.render(&TabularLocationContext {
+        base_location: &storage_profile.base_location()?,
+        namespace_location: &storage_profile.default_namespace_location(namespace_id)?,
+        namespace: &NamespaceIdent::new("namespace".to_string()),
+        namespace_id,
+        name: "table",
+        tabular_id: TabularIdentUuid::Table(uuid::Uuid::now_v7()),
+    });
+    if !storage_profile.is_allowed_location(&location) {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Location template '{location_template}' generates locations outside of the storage profile, for example '{location}'"
+            ),
+            "InvalidLocationTemplate",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
 fn validate_warehouse_name(warehouse_name: &str) -> Result<()> {
     if warehouse_name.is_empty() {
         return Err(ErrorModel::bad_request(
@@ -1215,4 +1465,135 @@ mod test {
             assert_eq!(next_page_items[idx], format!("view-{i}"));
         }
     }
+
+    #[needs_env_var::needs_env_var(TEST_OPENFGA = 1)]
+    mod openfga {
+        use crate::api::management::v1::project::{CreateProjectRequest, Service as _};
+        use crate::api::management::v1::ref_protection::{
+            CreateRefProtectionRuleRequest, Service as _,
+        };
+        use crate::api::management::v1::warehouse::{
+            CreateWarehouseRequest, Service as _, TabularDeleteProfile,
+            UpdateTablePropertyPolicyRequest, UpdateWarehousePropertiesRequest,
+        };
+        use crate::api::management::v1::ApiServer;
+        use crate::catalog::test::{get_api_context, test_io_profile};
+        use crate::request_metadata::RequestMetadata;
+        use crate::service::authn::{Principal, UserId};
+        use crate::service::authz::implementations::openfga::migration::tests::authorizer_for_empty_store;
+        use crate::service::authz::{Authorizer, RefProtectionScope};
+        use crate::service::table_property_policy::TablePropertyPolicy;
+        use crate::service::AuthDetails;
+        use http::StatusCode;
+        use std::collections::HashMap;
+        use uuid::Uuid;
+
+        fn principal_metadata() -> RequestMetadata {
+            let user_id = UserId::oidc(&Uuid::now_v7().to_string()).unwrap();
+            RequestMetadata {
+                request_id: Uuid::now_v7(),
+                auth_details: AuthDetails::Principal(Principal::new_test(user_id)),
+            }
+        }
+
+        #[sqlx::test]
+        async fn test_warehouse_actions_with_openfga(pool: sqlx::PgPool) {
+            let (_, authorizer) = authorizer_for_empty_store().await;
+            let operator = principal_metadata();
+            authorizer.bootstrap(&operator, true).await.unwrap();
+            let ctx = get_api_context(pool, authorizer);
+
+            let project = ApiServer::create_project(
+                CreateProjectRequest {
+                    project_name: format!("test-project-{}", Uuid::now_v7()),
+                    project_id: Some(Uuid::now_v7()),
+                },
+                ctx.clone(),
+                operator.clone(),
+            )
+            .await
+            .unwrap();
+            let warehouse = ApiServer::create_warehouse(
+                CreateWarehouseRequest {
+                    warehouse_name: format!("test-warehouse-{}", Uuid::now_v7()),
+                    project_id: Some(project.project_id),
+                    storage_profile: test_io_profile(),
+                    storage_credential: None,
+                    delete_profile: TabularDeleteProfile::Hard {},
+                },
+                ctx.clone(),
+                operator.clone(),
+            )
+            .await
+            .unwrap();
+            let warehouse_id = warehouse.warehouse_id.into();
+
+            let rule = ApiServer::create_ref_protection_rule(
+                warehouse_id,
+                CreateRefProtectionRuleRequest {
+                    scope: RefProtectionScope::Warehouse,
+                    ref_pattern: "main".to_string(),
+                    allowed_committers: vec![],
+                    prevent_deletion: true,
+                    fast_forward_only: false,
+                },
+                ctx.clone(),
+                operator.clone(),
+            )
+            .await
+            .unwrap();
+            let rules =
+                ApiServer::list_ref_protection_rules(warehouse_id, ctx.clone(), operator.clone())
+                    .await
+                    .unwrap();
+            assert_eq!(rules.rules, vec![rule.clone()]);
+            ApiServer::delete_ref_protection_rule(
+                warehouse_id,
+                rule.rule_id,
+                ctx.clone(),
+                operator.clone(),
+            )
+            .await
+            .unwrap();
+
+            ApiServer::update_warehouse_properties(
+                warehouse_id,
+                UpdateWarehousePropertiesRequest {
+                    properties: HashMap::from([(
+                        "table-default.write.format.default".to_string(),
+                        "parquet".to_string(),
+                    )]),
+                },
+                ctx.clone(),
+                operator.clone(),
+            )
+            .await
+            .unwrap();
+            ApiServer::update_table_property_policy(
+                warehouse_id,
+                UpdateTablePropertyPolicyRequest {
+                    policy: TablePropertyPolicy { rules: vec![] },
+                },
+                ctx.clone(),
+                operator.clone(),
+            )
+            .await
+            .unwrap();
+            ApiServer::backup_warehouse(warehouse_id, ctx.clone(), operator.clone())
+                .await
+                .unwrap();
+
+            // A principal without any grants is denied instead of failing the check.
+            let stranger = principal_metadata();
+            let error =
+                ApiServer::list_ref_protection_rules(warehouse_id, ctx.clone(), stranger.clone())
+                    .await
+                    .unwrap_err();
+            assert_eq!(error.error.code, StatusCode::FORBIDDEN);
+            let error = ApiServer::backup_warehouse(warehouse_id, ctx.clone(), stranger)
+                .await
+                .unwrap_err();
+            assert_eq!(error.error.code, StatusCode::FORBIDDEN);
+        }
+    }
 }
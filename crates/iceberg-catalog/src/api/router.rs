@@ -3,7 +3,9 @@ use crate::tracing::{MakeRequestUuid7, RestMakeSpan};
 
 use crate::api::management::v1::{api_doc as v1_api_doc, ApiServer};
 use crate::api::{iceberg::v1::new_v1_full_router, shutdown_signal, ApiContext};
+use crate::service::authn::CertificateVerifier;
 use crate::service::authn::IdpVerifier;
+use crate::service::authn::IntrospectionVerifier;
 use crate::service::authn::K8sVerifier;
 use crate::service::authn::VerifierChain;
 use crate::service::contract_verification::ContractVerifiers;
@@ -39,7 +41,9 @@ pub struct RouterArgs<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
     pub publisher: CloudEventsPublisher,
     pub table_change_checkers: ContractVerifiers,
     pub token_verifier: Option<IdpVerifier>,
+    pub introspection_token_verifier: Option<IntrospectionVerifier>,
     pub k8s_token_verifier: Option<K8sVerifier>,
+    pub certificate_verifier: Option<CertificateVerifier>,
     pub service_health_provider: ServiceHealthProvider,
     pub cors_origins: Option<&'static [HeaderValue]>,
     pub metrics_layer: Option<PrometheusMetricLayer<'static>>,
@@ -55,7 +59,12 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> std::fmt::Debug for Rout
             .field("publisher", &self.publisher)
             .field("table_change_checkers", &self.table_change_checkers)
             .field("token_verifier", &self.token_verifier)
+            .field(
+                "introspection_token_verifier",
+                &self.introspection_token_verifier,
+            )
             .field("k8s_token_verifier", &self.k8s_token_verifier)
+            .field("certificate_verifier", &self.certificate_verifier)
             .field("svhp", &self.service_health_provider)
             .field("cors_origins", &self.cors_origins)
             .field(
@@ -79,7 +88,9 @@ pub fn new_full_router<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         publisher,
         table_change_checkers,
         token_verifier,
+        introspection_token_verifier,
         k8s_token_verifier,
+        certificate_verifier,
         service_health_provider,
         cors_origins,
         metrics_layer,
@@ -114,12 +125,24 @@ pub fn new_full_router<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
                 Method::OPTIONS,
             ])
     }));
-    let maybe_auth_layer = match (token_verifier, k8s_token_verifier) {
-        (None, None) => option_layer(None),
-        (idp_verifier, k8s_verifier) => option_layer(Some(axum::middleware::from_fn_with_state(
-            VerifierChain::try_new(idp_verifier, k8s_verifier)?,
-            crate::service::authn::auth_middleware_fn,
-        ))),
+    let maybe_auth_layer = match (
+        token_verifier,
+        introspection_token_verifier,
+        k8s_token_verifier,
+        certificate_verifier,
+    ) {
+        (None, None, None, None) => option_layer(None),
+        (idp_verifier, introspection_verifier, k8s_verifier, certificate_verifier) => {
+            option_layer(Some(axum::middleware::from_fn_with_state(
+                VerifierChain::try_new(
+                    idp_verifier,
+                    introspection_verifier,
+                    k8s_verifier,
+                    certificate_verifier,
+                )?,
+                crate::service::authn::auth_middleware_fn,
+            )))
+        }
     };
 
     let router = Router::new()
@@ -2,12 +2,17 @@ use super::compression_codec::CompressionCodec;
 use crate::api::{ErrorModel, Result};
 use crate::retry::retry_fn;
 use crate::service::storage::path_utils;
+use flate2::read::GzDecoder;
 use futures::stream::BoxStream;
 use futures::StreamExt;
 use iceberg::io::FileIO;
 use iceberg_ext::catalog::rest::IcebergErrorResponse;
 use iceberg_ext::configs::Location;
+use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::io::Read;
+
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
 
 pub(crate) async fn write_metadata_file(
     metadata_location: &Location,
@@ -80,6 +85,25 @@ pub(crate) async fn read_file(file_io: &FileIO, file: &Location) -> Result<Vec<u
     .await
 }
 
+/// Read and deserialize a metadata file. Gzip compressed files are decompressed.
+pub(crate) async fn read_metadata_file<T: DeserializeOwned>(
+    file_io: &FileIO,
+    location: &Location,
+) -> Result<T, IoError> {
+    let content = read_file(file_io, location).await?;
+    let content = if content.starts_with(&GZIP_MAGIC_BYTES) {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(&content[..])
+            .read_to_end(&mut decompressed)
+            .map_err(IoError::FileDecompression)?;
+        decompressed
+    } else {
+        content
+    };
+
+    serde_json::from_slice(&content).map_err(IoError::Deserialization)
+}
+
 pub(crate) async fn remove_all(file_io: &FileIO, location: &Location) -> Result<(), IoError> {
     let location = location.as_str();
     let location = if location.starts_with("abfs") {
@@ -145,6 +169,10 @@ pub enum IoError {
     Write(#[source] iceberg::Error),
     #[error("Failed to finish compressing file.")]
     FileCompression(#[source] std::io::Error),
+    #[error("Failed to decompress file.")]
+    FileDecompression(#[source] std::io::Error),
+    #[error("Failed to deserialize file.")]
+    Deserialization(#[source] serde_json::Error),
     #[error("Failed to write file. Please check the storage credentials.")]
     FileWrite(#[source] Box<dyn std::error::Error + Sync + Send + 'static>),
     #[error("Failed to read file. Please check the storage credentials.")]
@@ -179,6 +207,8 @@ impl From<IoError> for IcebergErrorResponse {
             | IoError::FileWrite(_)
             | IoError::FileWriterCreation(_)
             | IoError::FileCreation(_)
+            | IoError::FileDecompression(_)
+            | IoError::Deserialization(_)
             | IoError::List(_) => ErrorModel::failed_dependency(message, typ, Some(boxed)).into(),
 
             IoError::FileCompression(_) | IoError::Write(_) | IoError::Serialization(_) => {
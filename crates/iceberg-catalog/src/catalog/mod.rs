@@ -14,11 +14,16 @@ use iceberg::spec::{TableMetadata, ViewMetadata};
 use iceberg_ext::catalog::rest::IcebergErrorResponse;
 pub use namespace::{MAX_NAMESPACE_DEPTH, NAMESPACE_ID_PROPERTY, UNSUPPORTED_NAMESPACE_PROPERTIES};
 
-use crate::api::iceberg::v1::{PageToken, MAX_PAGE_SIZE};
+use crate::api::iceberg::v1::{
+    ListNamespacesQuery, NamespaceIdent, PageToken, PaginationQuery, MAX_PAGE_SIZE,
+};
 use crate::api::{iceberg::v1::Prefix, ErrorModel, Result};
 use crate::service::storage::StorageCredential;
 use crate::{
-    service::{authz::Authorizer, secrets::SecretStore, Catalog},
+    service::{
+        authz::Authorizer, secrets::SecretStore, Catalog, ListFlags, NamespaceIdentUuid,
+        TabularIdentOwned, TabularIdentUuid, Transaction,
+    },
     WarehouseIdent,
 };
 use futures::future::BoxFuture;
@@ -78,6 +83,8 @@ pub(crate) async fn maybe_get_secret<S: SecretStore>(
 }
 
 pub const DEFAULT_PAGE_SIZE: i64 = 100;
+/// Page size used to list all entities of a warehouse
+const LIST_ALL_PAGE_SIZE: i64 = 1000;
 
 lazy_static::lazy_static! {
     pub static ref DEFAULT_PAGE_SIZE_USIZE: usize = DEFAULT_PAGE_SIZE.try_into().expect("1, 1000 is a valid usize");
@@ -233,6 +240,76 @@ where
     Ok((entities, entity_ids, next_page_token))
 }
 
+/// List all namespaces of a warehouse, including nested namespaces.
+pub(crate) async fn list_all_namespaces<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    catalog_state: C::State,
+) -> Result<Vec<(NamespaceIdentUuid, NamespaceIdent)>> {
+    let mut t = C::Transaction::begin_read(catalog_state).await?;
+    let mut namespaces = vec![];
+    let mut parents = vec![None];
+    while let Some(parent) = parents.pop() {
+        let mut page_token = PageToken::NotSpecified;
+        loop {
+            let page = C::list_namespaces(
+                warehouse_id,
+                &ListNamespacesQuery {
+                    page_token,
+                    page_size: Some(LIST_ALL_PAGE_SIZE),
+                    parent: parent.clone(),
+                    return_uuids: true,
+                },
+                t.transaction(),
+            )
+            .await?;
+            let next_token = page.next_token().map(ToString::to_string);
+            let is_last_page = i64::try_from(page.len()).unwrap_or(i64::MAX) < LIST_ALL_PAGE_SIZE;
+            for (namespace_id, namespace) in page {
+                parents.push(Some(namespace.clone()));
+                namespaces.push((namespace_id, namespace));
+            }
+            match next_token {
+                Some(token) if !is_last_page => page_token = PageToken::Present(token),
+                _ => break,
+            }
+        }
+    }
+    t.commit().await?;
+    Ok(namespaces)
+}
+
+/// List all active tables and views of a warehouse.
+pub(crate) async fn list_all_tabulars<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    catalog_state: C::State,
+) -> Result<Vec<(TabularIdentUuid, TabularIdentOwned)>> {
+    let mut t = C::Transaction::begin_read(catalog_state).await?;
+    let mut tabulars = vec![];
+    let mut page_token = PageToken::NotSpecified;
+    loop {
+        let page = C::list_tabulars(
+            warehouse_id,
+            None,
+            ListFlags::default(),
+            t.transaction(),
+            PaginationQuery {
+                page_token,
+                page_size: Some(LIST_ALL_PAGE_SIZE),
+            },
+        )
+        .await?;
+        let next_token = page.next_token().map(ToString::to_string);
+        let is_last_page = i64::try_from(page.len()).unwrap_or(i64::MAX) < LIST_ALL_PAGE_SIZE;
+        tabulars.extend(page.into_iter().map(|(id, (ident, _))| (id, ident)));
+        match next_token {
+            Some(token) if !is_last_page => page_token = PageToken::Present(token),
+            _ => break,
+        }
+    }
+    t.commit().await?;
+    Ok(tabulars)
+}
+
 #[cfg(test)]
 #[allow(dead_code)]
 pub(crate) mod test {
@@ -377,7 +454,10 @@ pub(crate) mod test {
                         crate::implementations::postgres::task_queues::TabularExpirationQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap(),
                     ),
                     Arc::new(
-                        crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), CONFIG.queue_config.clone()).unwrap()
+                        crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::NamespaceExpirationQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), CONFIG.queue_config.clone()).unwrap()
                     ),
                 ),
             },
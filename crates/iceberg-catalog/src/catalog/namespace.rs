@@ -1,16 +1,36 @@
 use super::{require_warehouse_id, CatalogServer, UnfilteredPage};
 use crate::api::iceberg::v1::namespace::GetNamespacePropertiesQuery;
 use crate::api::iceberg::v1::{
-    ApiContext, CreateNamespaceRequest, CreateNamespaceResponse, ErrorModel, GetNamespaceResponse,
-    ListNamespacesQuery, ListNamespacesResponse, NamespaceParameters, Prefix, Result,
-    UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse,
+    ApiContext, CreateNamespaceRequest, CreateNamespaceResponse, DropNamespaceParams, ErrorModel,
+    GetNamespaceResponse, ListNamespacesQuery, ListNamespacesResponse, NamespaceParameters, Prefix,
+    Result, UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse,
 };
+use crate::api::management::v1::warehouse::TabularDeleteProfile;
+use crate::api::management::v1::TabularType;
 use crate::api::set_not_found_status_code;
+use crate::catalog::tables::InheritedTableProperties;
 use crate::request_metadata::RequestMetadata;
-use crate::service::authz::{CatalogNamespaceAction, CatalogWarehouseAction, NamespaceParent};
+use crate::service::authz::{
+    CatalogNamespaceAction, CatalogTableAction, CatalogViewAction, CatalogWarehouseAction,
+    NamespaceParent,
+};
+use crate::service::contract_verification::ContractVerification;
+use crate::service::event_publisher::{
+    CloudEventsPublisher, EventMetadata, NamespaceEventMetadata,
+};
+use crate::service::schema_compatibility::{
+    check_schema_compatibility_change, SCHEMA_COMPATIBILITY_KEY,
+};
+use crate::service::task_queue::namespace_expiration_queue::NamespaceExpirationInput;
+use crate::service::task_queue::tabular_expiration_queue::TabularExpirationInput;
+use crate::service::task_queue::tabular_purge_queue::TabularPurgeInput;
+use crate::service::task_queue::TaskFilter;
 use crate::service::{authz::Authorizer, secrets::SecretStore, Catalog, State, Transaction as _};
-use crate::service::{GetWarehouseResponse, NamespaceIdentUuid};
-use crate::{catalog, CONFIG};
+use crate::service::{
+    GetWarehouseResponse, NamespaceIdentUuid, NamespaceSubtree, TableIdentUuid, TabularIdentUuid,
+    ViewIdentUuid,
+};
+use crate::{catalog, WarehouseIdent, CONFIG};
 use futures::FutureExt;
 use http::StatusCode;
 use iceberg::NamespaceIdent;
@@ -19,6 +39,7 @@ use iceberg_ext::configs::{ConfigProperty as _, Location};
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::ops::Deref;
+use uuid::Uuid;
 
 pub const UNSUPPORTED_NAMESPACE_PROPERTIES: &[&str] = &[];
 // If this is increased, we need to modify namespace creation and deletion
@@ -100,20 +121,21 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                         Vec<_>,
                         Vec<_>,
                         Vec<bool>,
-                    ) = futures::future::try_join_all(ids.iter().map(|n| {
-                        authorizer.is_allowed_namespace_action(
+                    ) = authorizer
+                        .are_allowed_namespace_actions(
                             &request_metadata,
                             warehouse_id,
-                            *n,
+                            &ids,
                             &CatalogNamespaceAction::CanGetMetadata,
                         )
-                    }))
-                    .await?
-                    .into_iter()
-                    .zip(idents.into_iter().zip(ids.into_iter()))
-                    .zip(tokens.into_iter())
-                    .map(|((allowed, namespace), token)| (namespace.0, namespace.1, token, allowed))
-                    .multiunzip();
+                        .await?
+                        .into_iter()
+                        .zip(idents.into_iter().zip(ids.into_iter()))
+                        .zip(tokens.into_iter())
+                        .map(|((allowed, namespace), token)| {
+                            (namespace.0, namespace.1, token, allowed)
+                        })
+                        .multiunzip();
 
                     Ok(UnfilteredPage::new(
                         next_namespaces,
@@ -320,14 +342,18 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         Ok(())
     }
 
-    /// Drop a namespace from the catalog. Namespace must be empty.
+    /// Drop a namespace from the catalog.
+    /// Namespace must be empty unless `recursive` is set.
+    /// If the warehouse uses soft deletion, the namespace is only marked as deleted
+    /// and expires through the namespace expiration queue.
     async fn drop_namespace(
         parameters: NamespaceParameters,
+        DropNamespaceParams { recursive, purge }: DropNamespaceParams,
         state: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<()> {
         //  ------------------- VALIDATIONS -------------------
-        let warehouse_id = require_warehouse_id(parameters.prefix)?;
+        let warehouse_id = require_warehouse_id(parameters.prefix.clone())?;
         validate_namespace_ident(&parameters.namespace)?;
 
         if CONFIG
@@ -343,7 +369,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         }
 
         //  ------------------- AUTHZ -------------------
-        let authorizer = state.v1_state.authz;
+        let authorizer = state.v1_state.authz.clone();
         authorizer
             .require_warehouse_action(
                 &request_metadata,
@@ -351,7 +377,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 &CatalogWarehouseAction::CanUse,
             )
             .await?;
-        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
         let namespace_id =
             C::namespace_to_id(warehouse_id, &parameters.namespace, t.transaction()).await; // Cannot fail before authz
 
@@ -365,11 +391,72 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             .await?;
 
         //  ------------------- BUSINESS LOGIC -------------------
-        C::drop_namespace(warehouse_id, namespace_id, t.transaction()).await?;
-        authorizer
-            .delete_namespace(&request_metadata, namespace_id)
-            .await?;
-        t.commit().await?;
+        if recursive.unwrap_or(false) {
+            return drop_namespace_recursive(
+                warehouse_id,
+                namespace_id,
+                purge.unwrap_or(false),
+                parameters.prefix,
+                t,
+                state,
+                request_metadata,
+            )
+            .await;
+        }
+
+        let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+        match warehouse.tabular_delete_profile {
+            TabularDeleteProfile::Hard {} => {
+                C::drop_namespace(warehouse_id, namespace_id, t.transaction()).await?;
+                authorizer
+                    .delete_namespace(&request_metadata, namespace_id)
+                    .await?;
+                t.commit().await?;
+            }
+            TabularDeleteProfile::Soft { expiration_seconds } => {
+                let subtree =
+                    C::get_namespace_subtree(warehouse_id, namespace_id, t.transaction()).await?;
+                // Soft-deleted tables and views are deleted along with the namespace.
+                if subtree.namespaces.len() > 1
+                    || subtree
+                        .tabulars
+                        .iter()
+                        .any(|tabular| tabular.expiration_task_id.is_none())
+                {
+                    return Err(ErrorModel::conflict(
+                        "Namespace is not empty",
+                        "NamespaceNotEmpty",
+                        None,
+                    )
+                    .into());
+                }
+                C::mark_namespace_as_deleted(warehouse_id, namespace_id, t.transaction()).await?;
+                t.commit().await?;
+
+                state
+                    .v1_state
+                    .queues
+                    .queue_namespace_expiration(NamespaceExpirationInput {
+                        namespace_id: *namespace_id,
+                        warehouse_ident: warehouse_id,
+                        purge: purge.unwrap_or(false),
+                        expire_at: chrono::Utc::now() + expiration_seconds,
+                    })
+                    .await?;
+            }
+        }
+
+        publish_drop_namespace_event(
+            &state.v1_state.publisher,
+            warehouse_id,
+            namespace_id,
+            &parameters.namespace,
+            parameters.prefix.as_ref(),
+            1,
+            0,
+            &request_metadata,
+        )
+        .await;
         Ok(())
     }
 
@@ -422,8 +509,19 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         //  ------------------- BUSINESS LOGIC -------------------
         let previous_properties =
             C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
+        let previous_schema_compatibility = previous_properties
+            .properties
+            .as_ref()
+            .and_then(|p| p.get(SCHEMA_COMPATIBILITY_KEY).cloned());
         let (new_properties, r) =
             update_namespace_properties(previous_properties.properties, updates, removals);
+        check_schema_compatibility_change(
+            previous_schema_compatibility.as_deref(),
+            new_properties
+                .get(SCHEMA_COMPATIBILITY_KEY)
+                .map(String::as_str),
+            &format!("namespace {namespace_id}"),
+        )?;
         C::update_namespace_properties(warehouse_id, namespace_id, new_properties, t.transaction())
             .await?;
         t.commit().await?;
@@ -461,10 +559,294 @@ where
                 .build()
                 .into());
         }
+        InheritedTableProperties::validate_keys(std::iter::once(prop))?;
     }
     Ok(())
 }
 
+/// Drop a namespace including its children, tables and views.
+///
+/// Namespaces, tables and views are dropped according to the delete profile of the warehouse.
+/// With soft deletion, all of them are marked as deleted at the same time, so that
+/// they can be restored together by undropping the namespace.
+#[allow(clippy::too_many_lines)]
+async fn drop_namespace_recursive<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    purge: bool,
+    prefix: Option<Prefix>,
+    mut t: C::Transaction,
+    state: ApiContext<State<A, C, S>>,
+    request_metadata: RequestMetadata,
+) -> Result<()> {
+    let NamespaceSubtree {
+        namespaces,
+        tabulars,
+    } = C::get_namespace_subtree(warehouse_id, namespace_id, t.transaction()).await?;
+
+    //  ------------------- AUTHZ -------------------
+    let authorizer = &state.v1_state.authz;
+    let child_ids = namespaces
+        .iter()
+        .map(|(id, _)| *id)
+        .filter(|id| *id != namespace_id)
+        .collect_vec();
+    let table_ids = tabulars
+        .iter()
+        .filter_map(|tabular| match tabular.tabular_id {
+            TabularIdentUuid::Table(id) => Some(TableIdentUuid::from(id)),
+            TabularIdentUuid::View(_) => None,
+        })
+        .collect_vec();
+    let view_ids = tabulars
+        .iter()
+        .filter_map(|tabular| match tabular.tabular_id {
+            TabularIdentUuid::View(id) => Some(ViewIdentUuid::from(id)),
+            TabularIdentUuid::Table(_) => None,
+        })
+        .collect_vec();
+    let (namespaces_allowed, tables_allowed, views_allowed) = futures::try_join!(
+        authorizer.are_allowed_namespace_actions(
+            &request_metadata,
+            warehouse_id,
+            &child_ids,
+            &CatalogNamespaceAction::CanDelete,
+        ),
+        authorizer.are_allowed_table_actions(
+            &request_metadata,
+            warehouse_id,
+            &table_ids,
+            &CatalogTableAction::CanDrop,
+        ),
+        authorizer.are_allowed_view_actions(
+            &request_metadata,
+            warehouse_id,
+            &view_ids,
+            &CatalogViewAction::CanDrop,
+        ),
+    )?;
+    if !namespaces_allowed.into_iter().all(|allowed| allowed) {
+        return Err(ErrorModel::forbidden(
+            format!(
+                "Namespace action {} forbidden",
+                CatalogNamespaceAction::CanDelete
+            ),
+            "NamespaceActionForbidden",
+            None,
+        )
+        .into());
+    }
+    if !tables_allowed.into_iter().all(|allowed| allowed) {
+        return Err(ErrorModel::forbidden(
+            format!("Table action {} forbidden", CatalogTableAction::CanDrop),
+            "TableActionForbidden",
+            None,
+        )
+        .into());
+    }
+    if !views_allowed.into_iter().all(|allowed| allowed) {
+        return Err(ErrorModel::forbidden(
+            format!("View action {} forbidden", CatalogViewAction::CanDrop),
+            "ViewActionForbidden",
+            None,
+        )
+        .into());
+    }
+
+    //  ------------------- BUSINESS LOGIC -------------------
+    for tabular in &tabulars {
+        state
+            .v1_state
+            .contract_verifiers
+            .check_drop(tabular.tabular_id)
+            .await?
+            .into_result()?;
+    }
+
+    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+    let (dropped_tabulars, delete_namespace_permissions) = match warehouse.tabular_delete_profile {
+        TabularDeleteProfile::Hard {} => {
+            let mut locations = Vec::with_capacity(tabulars.len());
+            for tabular in &tabulars {
+                let location = match tabular.tabular_id {
+                    TabularIdentUuid::Table(id) => {
+                        C::drop_table(TableIdentUuid::from(id), t.transaction()).await?
+                    }
+                    TabularIdentUuid::View(id) => {
+                        C::drop_view(ViewIdentUuid::from(id), t.transaction()).await?
+                    }
+                };
+                locations.push(location);
+            }
+            for (id, _) in &namespaces {
+                C::drop_namespace(warehouse_id, *id, t.transaction()).await?;
+            }
+            // Same as for single tables: rather leave undeleted files than tables
+            // pointing to purged locations.
+            t.commit().await?;
+
+            let expiration_tasks = tabulars
+                .iter()
+                .filter_map(|tabular| tabular.expiration_task_id)
+                .collect_vec();
+            if !expiration_tasks.is_empty() {
+                state
+                    .v1_state
+                    .queues
+                    .cancel_tabular_expiration(TaskFilter::TaskIds(expiration_tasks))
+                    .await?;
+            }
+            for (tabular, location) in tabulars.iter().zip(locations) {
+                if purge {
+                    state
+                        .v1_state
+                        .queues
+                        .queue_tabular_purge(TabularPurgeInput {
+                            tabular_id: *tabular.tabular_id,
+                            tabular_location: location,
+                            warehouse_ident: warehouse_id,
+                            tabular_type: tabular_type(tabular.tabular_id),
+                            parent_id: None,
+                        })
+                        .await?;
+                }
+                match tabular.tabular_id {
+                    TabularIdentUuid::Table(id) => {
+                        authorizer.delete_table(TableIdentUuid::from(id)).await?;
+                    }
+                    TabularIdentUuid::View(id) => {
+                        authorizer.delete_view(ViewIdentUuid::from(id)).await?;
+                    }
+                }
+            }
+            (tabulars, true)
+        }
+        TabularDeleteProfile::Soft { expiration_seconds } => {
+            let active_tabulars = tabulars
+                .iter()
+                .filter(|tabular| tabular.expiration_task_id.is_none())
+                .cloned()
+                .collect_vec();
+            C::mark_namespace_as_deleted(warehouse_id, namespace_id, t.transaction()).await?;
+            t.commit().await?;
+
+            let expire_at = chrono::Utc::now() + expiration_seconds;
+            for (id, _) in &namespaces {
+                state
+                    .v1_state
+                    .queues
+                    .queue_namespace_expiration(NamespaceExpirationInput {
+                        namespace_id: **id,
+                        warehouse_ident: warehouse_id,
+                        purge,
+                        expire_at,
+                    })
+                    .await?;
+            }
+            for tabular in &active_tabulars {
+                state
+                    .v1_state
+                    .queues
+                    .queue_tabular_expiration(TabularExpirationInput {
+                        tabular_id: *tabular.tabular_id,
+                        warehouse_ident: warehouse_id,
+                        tabular_type: tabular_type(tabular.tabular_id),
+                        purge,
+                        expire_at,
+                    })
+                    .await?;
+            }
+            // Permissions are kept, so that they are still in place after an undrop.
+            (active_tabulars, false)
+        }
+    };
+
+    if delete_namespace_permissions {
+        for (id, _) in &namespaces {
+            authorizer.delete_namespace(&request_metadata, *id).await?;
+        }
+    }
+
+    let num_events = dropped_tabulars.len() + namespaces.len();
+    for (sequence_number, tabular) in dropped_tabulars.into_iter().enumerate() {
+        let _ = state
+            .v1_state
+            .publisher
+            .publish(
+                Uuid::now_v7(),
+                match tabular.tabular_id {
+                    TabularIdentUuid::Table(_) => "dropTable",
+                    TabularIdentUuid::View(_) => "dropView",
+                },
+                serde_json::Value::Null,
+                EventMetadata {
+                    tabular_id: tabular.tabular_id,
+                    warehouse_id: *warehouse_id,
+                    name: tabular.tabular_ident.name,
+                    namespace: tabular.tabular_ident.namespace.to_url_string(),
+                    prefix: prefix.clone().map(Prefix::into_string).unwrap_or_default(),
+                    num_events,
+                    sequence_number,
+                    trace_id: request_metadata.request_id,
+                },
+            )
+            .await;
+    }
+    // Children are ordered before their parents, so the dropped namespace comes last.
+    let first_sequence_number = num_events - namespaces.len();
+    for (i, (id, namespace)) in namespaces.iter().enumerate() {
+        publish_drop_namespace_event(
+            &state.v1_state.publisher,
+            warehouse_id,
+            *id,
+            namespace,
+            prefix.as_ref(),
+            num_events,
+            first_sequence_number + i,
+            &request_metadata,
+        )
+        .await;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn publish_drop_namespace_event(
+    publisher: &CloudEventsPublisher,
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    namespace: &NamespaceIdent,
+    prefix: Option<&Prefix>,
+    num_events: usize,
+    sequence_number: usize,
+    request_metadata: &RequestMetadata,
+) {
+    let _ = publisher
+        .publish(
+            Uuid::now_v7(),
+            "dropNamespace",
+            serde_json::Value::Null,
+            NamespaceEventMetadata {
+                namespace_id: *namespace_id,
+                warehouse_id: *warehouse_id,
+                namespace: namespace.to_url_string(),
+                prefix: prefix.map(|p| p.as_str().to_string()).unwrap_or_default(),
+                num_events,
+                sequence_number,
+                trace_id: request_metadata.request_id,
+            },
+        )
+        .await;
+}
+
+fn tabular_type(tabular_id: TabularIdentUuid) -> TabularType {
+    match tabular_id {
+        TabularIdentUuid::Table(_) => TabularType::Table,
+        TabularIdentUuid::View(_) => TabularType::View,
+    }
+}
+
 pub(crate) fn validate_namespace_ident(namespace: &NamespaceIdent) -> Result<()> {
     if namespace.len() > MAX_NAMESPACE_DEPTH as usize {
         return Err(ErrorModel::bad_request(
@@ -909,4 +1291,92 @@ mod tests {
         assert!(result.missing.is_none());
         assert!(new_props.is_empty());
     }
+
+    #[sqlx::test]
+    async fn test_drop_namespace_recursive_emits_events(pool: PgPool) {
+        use crate::api::iceberg::types::DropNamespaceParams;
+        use crate::api::iceberg::v1::tables::Service as _;
+        use crate::api::iceberg::v1::{DataAccess, NamespaceParameters};
+        use crate::service::authz::AllowAllAuthorizer;
+        use crate::service::event_publisher::{
+            CloudEventsPublisher, Message, Payload, PayloadMetadata,
+        };
+
+        let (mut ctx, warehouse) = crate::catalog::test::setup(
+            pool,
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+        )
+        .await;
+        let prefix = Some(Prefix(warehouse.warehouse_id.to_string()));
+
+        let parent = NamespaceIdent::new("parent".to_string());
+        let child =
+            NamespaceIdent::from_vec(vec!["parent".to_string(), "child".to_string()]).unwrap();
+        for namespace in [&parent, &child] {
+            CatalogServer::create_namespace(
+                prefix.clone(),
+                CreateNamespaceRequest {
+                    namespace: namespace.clone(),
+                    properties: None,
+                },
+                ctx.clone(),
+                random_request_metadata(),
+            )
+            .await
+            .unwrap();
+        }
+        CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: prefix.clone(),
+                namespace: child.clone(),
+            },
+            crate::catalog::tables::test::create_request(Some("tab".to_string())),
+            DataAccess {
+                vended_credentials: true,
+                remote_signing: false,
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        ctx.v1_state.publisher = CloudEventsPublisher::new(tx);
+        CatalogServer::drop_namespace(
+            NamespaceParameters {
+                prefix,
+                namespace: parent.clone(),
+            },
+            DropNamespaceParams {
+                recursive: Some(true),
+                purge: Some(false),
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+
+        let mut events = vec![];
+        while let Ok(Message::Event(Payload { typ, metadata, .. })) = rx.try_recv() {
+            let (subject, num_events, sequence_number) = match metadata {
+                PayloadMetadata::Tabular(m) => (m.name, m.num_events, m.sequence_number),
+                PayloadMetadata::Namespace(m) => (m.namespace, m.num_events, m.sequence_number),
+            };
+            assert_eq!(num_events, 3);
+            events.push((typ, subject, sequence_number));
+        }
+        assert_eq!(
+            events,
+            vec![
+                ("dropTable".to_string(), "tab".to_string(), 0),
+                ("dropNamespace".to_string(), child.to_url_string(), 1),
+                ("dropNamespace".to_string(), parent.to_url_string(), 2),
+            ]
+        );
+    }
 }
@@ -6,6 +6,8 @@ use std::vec;
 use crate::api::iceberg::types::Prefix;
 use crate::api::{ApiContext, Result};
 use crate::api::{ErrorModel, IcebergErrorResponse, S3SignRequest, S3SignResponse};
+use crate::service::authz::column_policy::get_column_restrictions;
+use crate::service::authz::row_filter::get_row_filter;
 use crate::service::authz::{CatalogTableAction, CatalogWarehouseAction};
 use aws_sigv4::http_request::{sign as aws_sign, SignableBody, SignableRequest, SigningSettings};
 use aws_sigv4::sign::v4;
@@ -140,10 +142,46 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             &request_metadata,
             warehouse_id,
             table_id,
-            authorizer,
+            authorizer.clone(),
         )
         .await?;
 
+        // A signed request gives access to all columns and rows of a data file.
+        let column_restrictions = get_column_restrictions::<C, A>(
+            &authorizer,
+            &request_metadata,
+            warehouse_id,
+            table_id,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
+        if !column_restrictions.is_empty() {
+            return Err(ErrorModel::forbidden(
+                "Remote signing is not allowed for principals restricted by column policies",
+                "ColumnPolicyRestricted",
+                None,
+            )
+            .append_detail(format!("Table ID: {table_id}"))
+            .into());
+        }
+        let row_filter = get_row_filter::<C, A>(
+            &authorizer,
+            &request_metadata,
+            warehouse_id,
+            table_id,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
+        if row_filter.is_some() {
+            return Err(ErrorModel::forbidden(
+                "Remote signing is not allowed for principals restricted by row filters",
+                "RowFilterRestricted",
+                None,
+            )
+            .append_detail(format!("Table ID: {table_id}"))
+            .into());
+        }
+
         let extend_err = |mut e: IcebergErrorResponse| {
             e.error = e
                 .error
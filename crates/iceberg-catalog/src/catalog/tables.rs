@@ -10,17 +10,25 @@ use crate::api::iceberg::v1::{
     LoadTableResult, NamespaceParameters, PaginationQuery, Prefix, RegisterTableRequest,
     RenameTableRequest, Result, TableIdent, TableParameters,
 };
+use crate::api::management::v1::lineage::filter_visible;
 use crate::api::management::v1::warehouse::TabularDeleteProfile;
 use crate::api::management::v1::TabularType;
 use crate::api::set_not_found_status_code;
 use crate::catalog::compression_codec::CompressionCodec;
 use crate::request_metadata::RequestMetadata;
+use crate::service::authz::column_policy::get_column_restrictions;
+use crate::service::authz::ref_protection::{modified_refs, require_ref_protection};
+use crate::service::authz::row_filter::{get_row_filter, row_filter_table_config};
 use crate::service::authz::{CatalogNamespaceAction, CatalogTableAction, CatalogWarehouseAction};
-use crate::service::contract_verification::{ContractVerification, ContractVerificationOutcome};
+use crate::service::contract_verification::{
+    ContractVerification, ContractVerificationOutcome, ContractVerifiers,
+};
 use crate::service::event_publisher::{CloudEventsPublisher, EventMetadata};
 use crate::service::storage::{
-    StorageLocations as _, StoragePermissions, StorageProfile, ValidationError,
+    StorageLocations as _, StoragePermissions, StorageProfile, TabularLocationContext,
+    TabularLocationTemplate, ValidationError,
 };
+use crate::service::table_property_policy::{modified_table_properties, table_policy_properties};
 use crate::service::task_queue::tabular_expiration_queue::TabularExpirationInput;
 use crate::service::task_queue::tabular_purge_queue::TabularPurgeInput;
 use crate::service::TabularIdentUuid;
@@ -29,7 +37,8 @@ use crate::service::{
     LoadTableResponse as CatalogLoadTableResult, State, Transaction,
 };
 use crate::service::{
-    GetNamespaceResponse, TableCommit, TableCreation, TableIdentUuid, WarehouseStatus,
+    GetNamespaceResponse, GetWarehouseResponse, TableCommit, TableCreation, TableIdentUuid,
+    WarehouseStatus,
 };
 use futures::FutureExt;
 use fxhash::FxHashSet;
@@ -39,7 +48,9 @@ use std::str::FromStr as _;
 use crate::catalog;
 use crate::catalog::tabular::list_entities;
 use crate::retry::retry_fn;
+use crate::CONFIG;
 use http::StatusCode;
+use iceberg::io::FileIO;
 use iceberg::spec::{
     FormatVersion, MetadataLog, SchemaId, SortOrder, TableMetadata, TableMetadataBuildResult,
     TableMetadataBuilder, UnboundPartitionSpec, PROPERTY_FORMAT_VERSION,
@@ -47,6 +58,7 @@ use iceberg::spec::{
 };
 use iceberg::{NamespaceIdent, TableUpdate};
 use iceberg_ext::configs::namespace::NamespaceProperties;
+use iceberg_ext::configs::table::TableProperties;
 use iceberg_ext::configs::Location;
 use itertools::Itertools;
 use serde::Serialize;
@@ -55,6 +67,11 @@ use uuid::Uuid;
 const PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED: &str =
     "write.metadata.delete-after-commit.enabled";
 const PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED_DEFAULT: bool = false;
+/// Prefix of warehouse and namespace properties that tables inherit at creation
+/// unless they set the property themselves.
+pub(crate) const TABLE_DEFAULT_PREFIX: &str = "table-default.";
+/// Prefix of warehouse and namespace properties that are enforced on tables.
+pub(crate) const TABLE_OVERRIDE_PREFIX: &str = "table-override.";
 
 #[async_trait::async_trait]
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
@@ -127,8 +144,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     /// Create a table in the given namespace
     async fn create_table(
         parameters: NamespaceParameters,
-        // mut because we need to change location
-        mut request: CreateTableRequest,
+        request: CreateTableRequest,
         data_access: DataAccess,
         state: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
@@ -164,111 +180,28 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             .await?;
 
         // ------------------- BUSINESS LOGIC -------------------
-        let id = Uuid::now_v7();
-        let tabular_id = TabularIdentUuid::Table(id);
-        let table_id = TableIdentUuid::from(id);
-
         let namespace = C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
         let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
         let storage_profile = &warehouse.storage_profile;
         require_active_warehouse(warehouse.status)?;
 
-        let table_location = determine_tabular_location(
-            &namespace,
-            request.location.clone(),
-            tabular_id,
-            storage_profile,
-        )?;
-
-        // Update the request for event
-        request.location = Some(table_location.to_string());
-        let request = request; // Make it non-mutable again for our sanity
-
-        // If stage-create is true, we should not create the metadata file
-        let metadata_location = if request.stage_create.unwrap_or(false) {
-            None
-        } else {
-            let metadata_id = Uuid::now_v7();
-            Some(storage_profile.default_metadata_location(
-                &table_location,
-                &CompressionCodec::try_from_maybe_properties(request.properties.as_ref())?,
-                metadata_id,
-                0,
-            ))
-        };
-
-        // serialize body before moving it
-        let body = maybe_body_to_json(&request);
-
-        let table_metadata = create_table_request_into_table_metadata(table_id, request)?;
+        // We don't commit the transaction yet, first we need to write the metadata file.
+        let storage_secret =
+            maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+        let file_io = storage_profile.file_io(storage_secret.as_ref())?;
 
-        let CreateTableResponse {
+        let CreatedTable {
+            table_id,
+            table_location,
+            metadata_location,
             table_metadata,
             staged_table_id,
-        } = C::create_table(
-            TableCreation {
-                namespace_id: namespace.namespace_id,
-                table_ident: &table,
-                table_metadata,
-                metadata_location: metadata_location.as_ref(),
-            },
-            t.transaction(),
+            body,
+        } = create_table_in_transaction::<C>(
+            &warehouse, &namespace, &table, request, &file_io, &mut t,
         )
         .await?;
 
-        // We don't commit the transaction yet, first we need to write the metadata file.
-        let storage_secret = if let Some(secret_id) = &warehouse.storage_secret_id {
-            let secret_state = state.v1_state.secrets;
-            Some(secret_state.get_secret_by_id(secret_id).await?.secret)
-        } else {
-            None
-        };
-
-        let file_io = storage_profile.file_io(storage_secret.as_ref())?;
-        retry_fn(|| async {
-            match crate::service::storage::check_location_is_empty(
-                &file_io,
-                &table_location,
-                storage_profile,
-                || crate::service::storage::ValidationError::InvalidLocation {
-                    reason: "Unexpected files in location, tabular locations have to be empty"
-                        .to_string(),
-                    location: table_location.to_string(),
-                    source: None,
-                    storage_type: storage_profile.storage_type(),
-                },
-            )
-            .await
-            {
-                Err(e @ ValidationError::IoOperationFailed(_, _)) => {
-                    tracing::warn!(
-                        "Error while checking location is empty: {e}, retrying up to three times.."
-                    );
-                    Err(e)
-                }
-                Ok(()) => {
-                    tracing::debug!("Location is empty");
-                    Ok(Ok(()))
-                }
-                Err(other) => {
-                    tracing::error!("Unrecoverable error: {other:?}");
-                    Ok(Err(other))
-                }
-            }
-        })
-        .await??;
-
-        if let Some(metadata_location) = &metadata_location {
-            let compression_codec = CompressionCodec::try_from_metadata(&table_metadata)?;
-            write_metadata_file(
-                metadata_location,
-                &table_metadata,
-                compression_codec,
-                &file_io,
-            )
-            .await?;
-        };
-
         // This requires the storage secret
         // because the table config might contain vended-credentials based
         //
@@ -288,11 +221,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         };
 
         authorizer
-            .create_table(
-                &request_metadata,
-                TableIdentUuid::from(*tabular_id),
-                namespace_id,
-            )
+            .create_table(&request_metadata, table_id, namespace_id)
             .await?;
 
         // Metadata file written, now we can commit the transaction
@@ -304,7 +233,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         emit_change_event(
             EventMetadata {
-                tabular_id: TabularIdentUuid::Table(*tabular_id),
+                tabular_id: TabularIdentUuid::Table(*table_id),
                 warehouse_id: *warehouse_id,
                 name: table.name.clone(),
                 namespace: table.namespace.to_url_string(),
@@ -374,7 +303,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let include_deleted = false;
         let include_active = true;
 
-        let mut t = C::Transaction::begin_read(state.v1_state.catalog).await?;
+        let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
         let table_id = C::table_to_id(
             warehouse_id,
             &table,
@@ -411,7 +340,30 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             ),
         )?;
 
-        let storage_permissions = if write_access {
+        let column_restrictions = get_column_restrictions::<C, A>(
+            &authorizer,
+            &request_metadata,
+            warehouse_id,
+            table_id,
+            state.v1_state.catalog.clone(),
+        )
+        .await?;
+        let row_filter = get_row_filter::<C, A>(
+            &authorizer,
+            &request_metadata,
+            warehouse_id,
+            table_id,
+            state.v1_state.catalog,
+        )
+        .await?;
+        let restricted = !column_restrictions.is_empty() || row_filter.is_some();
+
+        // Data files contain all columns and rows, so principals restricted by column policies
+        // or row filters must not access them directly. Only engines that enforce the
+        // restrictions may read them.
+        let storage_permissions = if restricted {
+            None
+        } else if write_access {
             Some(StoragePermissions::ReadWriteDelete)
         } else if read_access {
             Some(StoragePermissions::Read)
@@ -460,10 +412,27 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             None
         };
 
+        let config = if restricted {
+            let column_config = column_restrictions.to_table_config();
+            let row_filter_config = row_filter
+                .as_ref()
+                .map(row_filter_table_config)
+                .unwrap_or_default();
+            Some(TableProperties::from_props_unchecked(
+                column_config
+                    .inner()
+                    .clone()
+                    .into_iter()
+                    .chain(row_filter_config.inner().clone()),
+            ))
+        } else {
+            storage_config
+        };
+
         let load_table_result = LoadTableResult {
             metadata_location: metadata_location.as_ref().map(ToString::to_string),
             metadata: table_metadata,
-            config: storage_config.map(Into::into),
+            config: config.map(Into::into),
         };
 
         Ok(load_table_result)
@@ -560,6 +529,54 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
 
+        if let Some(view_id) =
+            C::get_materialized_view_of_storage_table(table_id, t.transaction()).await?
+        {
+            return Err(ErrorModel::conflict(
+                format!(
+                    "Table is the storage table of materialized view '{view_id}'. Drop the materialized view first."
+                ),
+                "StorageTableOfMaterializedView",
+                None,
+            )
+            .into());
+        }
+
+        let dependent_views =
+            C::list_dependent_views(TabularIdentUuid::from(table_id), t.transaction()).await?;
+        if !dependent_views.is_empty() {
+            if CONFIG.block_drop_of_view_dependencies {
+                // Only name views the caller may see, without revealing how many others exist
+                let visible_views = filter_visible(
+                    &authorizer,
+                    &request_metadata,
+                    warehouse_id,
+                    dependent_views,
+                )
+                .await?;
+                return Err(ErrorModel::conflict(
+                    "Table is referenced by views. Drop the views first.".to_string(),
+                    "DependentViewsExist",
+                    None,
+                )
+                .append_details(visible_views.into_iter().map(|view| {
+                    format!(
+                        "Referenced by view {}.{}",
+                        view.namespace.join("."),
+                        view.name
+                    )
+                }))
+                .into());
+            }
+            tracing::warn!(
+                "Dropping table '{table_id}' that is referenced by views: {}",
+                dependent_views
+                    .iter()
+                    .map(|view| view.tabular_id.to_string())
+                    .join(", ")
+            );
+        }
+
         state
             .v1_state
             .contract_verifiers
@@ -818,7 +835,6 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     // ------------------- VALIDATIONS -------------------
     let warehouse_id = require_warehouse_id(prefix.clone())?;
     for change in &request.table_changes {
-        validate_table_updates(&change.updates)?;
         change
             .identifier
             .as_ref()
@@ -902,9 +918,6 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
         .into());
     }
 
-    let mut transaction = C::Transaction::begin_write(state.v1_state.catalog).await?;
-    let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
-
     // Store data for events before it is moved
     let mut events = vec![];
     let mut event_table_ids: Vec<(TableIdent, TableIdentUuid)> = vec![];
@@ -919,6 +932,254 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
         }
     }
 
+    let mut transaction = C::Transaction::begin_write(state.v1_state.catalog).await?;
+    let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+
+    // We don't commit the transaction yet, first we need to write the metadata files.
+    let storage_secret =
+        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+    let file_io = warehouse.storage_profile.file_io(storage_secret.as_ref())?;
+
+    let (commits, expired_metadata_logs) = commit_tables_in_transaction::<C, A>(
+        &warehouse,
+        request.table_changes,
+        &table_ids,
+        &authorizer,
+        &state.v1_state.contract_verifiers,
+        &request_metadata,
+        &file_io,
+        &mut transaction,
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    // Delete files in parallel - if one delete fails, we still want to delete the rest
+    let _ = futures::future::join_all(
+        expired_metadata_logs
+            .into_iter()
+            .map(|expired_metadata_log| file_io.delete(expired_metadata_log.metadata_file))
+            .collect::<Vec<_>>(),
+    )
+    .await
+    .into_iter()
+    .map(|r| {
+        r.map_err(|e| tracing::warn!("Failed to delete metadata file: {:?}", e))
+            .ok()
+    });
+
+    let number_of_events = events.len();
+
+    for (event_sequence_number, (body, (table_ident, table_id))) in
+        events.into_iter().zip(event_table_ids).enumerate()
+    {
+        emit_change_event(
+            EventMetadata {
+                tabular_id: TabularIdentUuid::Table(*table_id),
+                warehouse_id: *warehouse_id,
+                name: table_ident.name,
+                namespace: table_ident.namespace.to_url_string(),
+                prefix: prefix
+                    .clone()
+                    .map(|p| p.as_str().to_string())
+                    .unwrap_or_default(),
+                num_events: number_of_events,
+                sequence_number: event_sequence_number,
+                trace_id: request_metadata.request_id,
+            },
+            body,
+            "updateTable",
+            state.v1_state.publisher.clone(),
+        )
+        .await;
+    }
+    Ok(commits)
+}
+
+/// A table created by [`create_table_in_transaction`].
+pub(crate) struct CreatedTable {
+    pub(crate) table_id: TableIdentUuid,
+    pub(crate) table_location: Location,
+    pub(crate) metadata_location: Option<Location>,
+    pub(crate) table_metadata: TableMetadata,
+    pub(crate) staged_table_id: Option<TableIdentUuid>,
+    /// Request body of the creation for events
+    pub(crate) body: serde_json::Value,
+}
+
+/// Create a table and write its metadata file without committing the transaction.
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn create_table_in_transaction<C: Catalog>(
+    warehouse: &GetWarehouseResponse,
+    namespace: &GetNamespaceResponse,
+    table: &TableIdent,
+    // mut because we need to change location
+    mut request: CreateTableRequest,
+    file_io: &FileIO,
+    transaction: &mut C::Transaction,
+) -> Result<CreatedTable> {
+    let id = Uuid::now_v7();
+    let tabular_id = TabularIdentUuid::Table(id);
+    let table_id = TableIdentUuid::from(id);
+    let storage_profile = &warehouse.storage_profile;
+
+    let table_location = determine_tabular_location(
+        namespace,
+        request.location.clone(),
+        tabular_id,
+        &request.name,
+        storage_profile,
+        warehouse.location_template.as_ref(),
+    )?;
+
+    // Update the request for event
+    request.location = Some(table_location.to_string());
+    let namespace_properties = C::get_namespace_hierarchy_properties(
+        warehouse.id,
+        &table.namespace,
+        transaction.transaction(),
+    )
+    .await?;
+    request.properties = InheritedTableProperties::from_layers(
+        std::iter::once(&warehouse.properties).chain(&namespace_properties),
+    )
+    .apply(request.properties.take());
+    let request = request; // Make it non-mutable again for our sanity
+
+    // If stage-create is true, we should not create the metadata file
+    let metadata_location = if request.stage_create.unwrap_or(false) {
+        None
+    } else {
+        let metadata_id = Uuid::now_v7();
+        Some(storage_profile.default_metadata_location(
+            &table_location,
+            &CompressionCodec::try_from_maybe_properties(request.properties.as_ref())?,
+            metadata_id,
+            0,
+        ))
+    };
+
+    // serialize body before moving it
+    let body = maybe_body_to_json(&request);
+
+    let table_metadata = create_table_request_into_table_metadata(table_id, request)?;
+    warehouse
+        .table_property_policy
+        .require_compliance(&table_policy_properties(&table_metadata), None)?;
+
+    let CreateTableResponse {
+        table_metadata,
+        staged_table_id,
+    } = C::create_table(
+        TableCreation {
+            namespace_id: namespace.namespace_id,
+            table_ident: table,
+            table_metadata,
+            metadata_location: metadata_location.as_ref(),
+        },
+        transaction.transaction(),
+    )
+    .await?;
+
+    retry_fn(|| async {
+        match crate::service::storage::check_location_is_empty(
+            file_io,
+            &table_location,
+            storage_profile,
+            || crate::service::storage::ValidationError::InvalidLocation {
+                reason: "Unexpected files in location, tabular locations have to be empty"
+                    .to_string(),
+                location: table_location.to_string(),
+                source: None,
+                storage_type: storage_profile.storage_type(),
+            },
+        )
+        .await
+        {
+            Err(e @ ValidationError::IoOperationFailed(_, _)) => {
+                tracing::warn!(
+                    "Error while checking location is empty: {e}, retrying up to three times.."
+                );
+                Err(e)
+            }
+            Ok(()) => {
+                tracing::debug!("Location is empty");
+                Ok(Ok(()))
+            }
+            Err(other) => {
+                tracing::error!("Unrecoverable error: {other:?}");
+                Ok(Err(other))
+            }
+        }
+    })
+    .await??;
+
+    if let Some(metadata_location) = &metadata_location {
+        let compression_codec = CompressionCodec::try_from_metadata(&table_metadata)?;
+        write_metadata_file(
+            metadata_location,
+            &table_metadata,
+            compression_codec,
+            file_io,
+        )
+        .await?;
+    };
+
+    Ok(CreatedTable {
+        table_id,
+        table_location,
+        metadata_location,
+        table_metadata,
+        staged_table_id,
+        body,
+    })
+}
+
+/// Apply the changes of a commit transaction and write the new metadata files.
+///
+/// The catalog transaction is not committed, so the changes can be combined with
+/// other changes to the catalog. Returns the metadata logs that can be deleted
+/// once the transaction is committed.
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
+pub(crate) async fn commit_tables_in_transaction<C: Catalog, A: Authorizer>(
+    warehouse: &GetWarehouseResponse,
+    table_changes: Vec<CommitTableRequest>,
+    table_ids: &HashMap<TableIdent, TableIdentUuid>,
+    authorizer: &A,
+    contract_verifiers: &ContractVerifiers,
+    request_metadata: &RequestMetadata,
+    file_io: &FileIO,
+    transaction: &mut C::Transaction,
+) -> Result<(Vec<CommitContext>, Vec<MetadataLog>)> {
+    let warehouse_id = warehouse.id;
+    let include_deleted = false;
+
+    let mut inherited_properties: HashMap<&NamespaceIdent, InheritedTableProperties> =
+        HashMap::new();
+    for change in &table_changes {
+        let Some(identifier) = &change.identifier else {
+            continue;
+        };
+        if !inherited_properties.contains_key(&identifier.namespace) {
+            let namespace_properties = C::get_namespace_hierarchy_properties(
+                warehouse_id,
+                &identifier.namespace,
+                transaction.transaction(),
+            )
+            .await?;
+            inherited_properties.insert(
+                &identifier.namespace,
+                InheritedTableProperties::from_layers(
+                    std::iter::once(&warehouse.properties).chain(&namespace_properties),
+                ),
+            );
+        }
+        validate_table_updates(
+            &change.updates,
+            &inherited_properties[&identifier.namespace].overrides,
+        )?;
+    }
+
     // Load old metadata
     let mut previous_metadatas = C::load_tables(
         warehouse_id,
@@ -931,8 +1192,7 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     let mut expired_metadata_logs: Vec<MetadataLog> = vec![];
 
     // Apply changes
-    let commits = request
-        .table_changes
+    let commits = table_changes
         .into_iter()
         .map(|change| {
             let table_ident = change.identifier.ok_or_else(||
@@ -954,6 +1214,10 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
                 &change.requirements,
                 change.updates.clone(),
             )?;
+            warehouse.table_property_policy.require_compliance(
+                &table_policy_properties(&new_metadata),
+                Some(&modified_table_properties(&change.updates)),
+            )?;
 
             let number_expired_metadata_log_entries = this_expired.len();
 
@@ -995,6 +1259,27 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
         })
         .collect::<Result<Vec<_>>>()?;
 
+    // Check ref protection rules
+    for commit in commits
+        .iter()
+        .filter(|c| !modified_refs(&c.previous_metadata, &c.new_metadata).is_empty())
+    {
+        let table_id = TableIdentUuid::from(commit.new_metadata.uuid());
+        let rules =
+            C::get_table_ref_protection_rules(warehouse_id, table_id, transaction.transaction())
+                .await?;
+        require_ref_protection(
+            authorizer,
+            request_metadata,
+            warehouse_id,
+            table_id,
+            rules,
+            &commit.previous_metadata,
+            &commit.new_metadata,
+        )
+        .await?;
+    }
+
     // Commit changes in DB
     C::commit_table_transaction(
         warehouse_id,
@@ -1004,12 +1289,9 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     .await?;
 
     // Check contract verification
-    let futures = commits.iter().map(|c| {
-        state
-            .v1_state
-            .contract_verifiers
-            .check_table_updates(&c.updates, &c.previous_metadata)
-    });
+    let futures = commits
+        .iter()
+        .map(|c| contract_verifiers.check_table_updates(&c.updates, &c.previous_metadata));
 
     futures::future::try_join_all(futures)
         .await?
@@ -1017,13 +1299,7 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
         .map(ContractVerificationOutcome::into_result)
         .collect::<Result<Vec<()>, ErrorModel>>()?;
 
-    // We don't commit the transaction yet, first we need to write the metadata file.
-    let storage_secret =
-        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
-
     // Write metadata files
-    let file_io = warehouse.storage_profile.file_io(storage_secret.as_ref())?;
-
     let write_futures: Vec<_> = commits
         .iter()
         .map(|commit| {
@@ -1031,54 +1307,13 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
                 &commit.new_metadata_location,
                 &commit.new_metadata,
                 commit.new_compression_codec,
-                &file_io,
+                file_io,
             )
         })
         .collect();
     futures::future::try_join_all(write_futures).await?;
 
-    transaction.commit().await?;
-
-    // Delete files in parallel - if one delete fails, we still want to delete the rest
-    let _ = futures::future::join_all(
-        expired_metadata_logs
-            .into_iter()
-            .map(|expired_metadata_log| file_io.delete(expired_metadata_log.metadata_file))
-            .collect::<Vec<_>>(),
-    )
-    .await
-    .into_iter()
-    .map(|r| {
-        r.map_err(|e| tracing::warn!("Failed to delete metadata file: {:?}", e))
-            .ok()
-    });
-
-    let number_of_events = events.len();
-
-    for (event_sequence_number, (body, (table_ident, table_id))) in
-        events.into_iter().zip(event_table_ids).enumerate()
-    {
-        emit_change_event(
-            EventMetadata {
-                tabular_id: TabularIdentUuid::Table(*table_id),
-                warehouse_id: *warehouse_id,
-                name: table_ident.name,
-                namespace: table_ident.namespace.to_url_string(),
-                prefix: prefix
-                    .clone()
-                    .map(|p| p.as_str().to_string())
-                    .unwrap_or_default(),
-                num_events: number_of_events,
-                sequence_number: event_sequence_number,
-                trace_id: request_metadata.request_id,
-            },
-            body,
-            "updateTable",
-            state.v1_state.publisher.clone(),
-        )
-        .await;
-    }
-    Ok(commits)
+    Ok((commits, expired_metadata_logs))
 }
 
 pub(crate) fn extract_count_from_metadata_location(location: &Location) -> Option<usize> {
@@ -1099,7 +1334,7 @@ pub(crate) fn extract_count_from_metadata_location(location: &Location) -> Optio
     }
 }
 
-struct CommitContext {
+pub(crate) struct CommitContext {
     pub new_metadata: iceberg::spec::TableMetadata,
     pub new_metadata_location: Location,
     pub previous_metadata: iceberg::spec::TableMetadata,
@@ -1311,7 +1546,9 @@ pub(super) fn determine_tabular_location(
     namespace: &GetNamespaceResponse,
     request_table_location: Option<String>,
     table_id: TabularIdentUuid,
+    table_name: &str,
     storage_profile: &StorageProfile,
+    location_template: Option<&TabularLocationTemplate>,
 ) -> Result<Location> {
     let request_table_location = request_table_location
         .map(|l| parse_location(&l, StatusCode::BAD_REQUEST))
@@ -1345,7 +1582,34 @@ pub(super) fn determine_tabular_location(
                 })?,
         };
 
-        storage_profile.default_tabular_location(&namespace_location, table_id)
+        if let Some(location_template) = location_template {
+            let base_location = storage_profile.base_location().map_err(|e| {
+                ErrorModel::internal(
+                    "Failed to get base location of storage profile",
+                    "InvalidBaseLocation",
+                    Some(Box::new(e)),
+                )
+            })?;
+            let location = location_template.render(&TabularLocationContext {
+                base_location: &base_location,
+                namespace_location: &namespace_location,
+                namespace: &namespace.namespace,
+                namespace_id: namespace.namespace_id,
+                name: table_name,
+                tabular_id: table_id,
+            });
+            if !storage_profile.is_allowed_location(&location) {
+                return Err(ErrorModel::bad_request(
+                    format!("Location generated by the location template of the warehouse is not allowed: {location}"),
+                    "InvalidTableLocation",
+                    None,
+                )
+                .into());
+            }
+            location
+        } else {
+            storage_profile.default_tabular_location(&namespace_location, table_id)
+        }
     };
     // all locations are without a trailing slash
     location.without_trailing_slash();
@@ -1429,14 +1693,39 @@ async fn emit_change_event(
 
 // Quick validation of properties for early fails.
 // Full validation is performed when changes are applied.
-fn validate_table_updates(updates: &Vec<TableUpdate>) -> Result<()> {
+// Properties enforced via `table-override.*` can neither be removed nor changed.
+fn validate_table_updates(
+    updates: &Vec<TableUpdate>,
+    property_overrides: &HashMap<String, String>,
+) -> Result<()> {
     for update in updates {
         match update {
             TableUpdate::SetProperties { updates } => {
                 validate_table_properties(updates.keys())?;
+                if let Some((key, _)) = updates
+                    .iter()
+                    .find(|(k, v)| property_overrides.get(*k).is_some_and(|o| o != *v))
+                {
+                    return Err(property_overridden_error(key).into());
+                }
             }
             TableUpdate::RemoveProperties { removals } => {
                 validate_table_properties(removals)?;
+                if let Some(key) = removals
+                    .iter()
+                    .find(|k| property_overrides.contains_key(*k))
+                {
+                    return Err(property_overridden_error(key).into());
+                }
+            }
+            // `format-version` is not stored as a property, so it is checked against the upgrade.
+            TableUpdate::UpgradeFormatVersion { format_version } => {
+                if property_overrides
+                    .get(PROPERTY_FORMAT_VERSION)
+                    .is_some_and(|o| parse_format_version(o).ok() != Some(*format_version))
+                {
+                    return Err(property_overridden_error(PROPERTY_FORMAT_VERSION).into());
+                }
             }
             _ => {}
         }
@@ -1444,6 +1733,16 @@ fn validate_table_updates(updates: &Vec<TableUpdate>) -> Result<()> {
     Ok(())
 }
 
+fn property_overridden_error(key: &str) -> ErrorModel {
+    ErrorModel::conflict(
+        format!(
+            "Property '{key}' is enforced by a '{TABLE_OVERRIDE_PREFIX}{key}' property of the namespace or warehouse and cannot be modified"
+        ),
+        "PropertyOverridden",
+        None,
+    )
+}
+
 pub(crate) fn get_delete_after_commit_enabled(properties: &HashMap<String, String>) -> bool {
     properties
         .get(PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED)
@@ -1478,6 +1777,76 @@ where
     Ok(())
 }
 
+/// Table properties inherited from the warehouse and the namespace hierarchy of a table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct InheritedTableProperties {
+    /// Properties set on new tables unless specified in the create request.
+    pub(crate) defaults: HashMap<String, String>,
+    /// Properties enforced on new tables, which cannot be changed afterwards.
+    pub(crate) overrides: HashMap<String, String>,
+}
+
+impl InheritedTableProperties {
+    /// Merge `table-default.*` and `table-override.*` properties of the given layers.
+    /// Layers must be ordered from the least specific (warehouse) to the most specific
+    /// (namespace of the table). Values of more specific layers take precedence.
+    pub(crate) fn from_layers<'a>(
+        layers: impl IntoIterator<Item = &'a HashMap<String, String>>,
+    ) -> Self {
+        let mut inherited = Self::default();
+        for (key, value) in layers.into_iter().flatten() {
+            if let Some(key) = key.strip_prefix(TABLE_DEFAULT_PREFIX) {
+                inherited.defaults.insert(key.to_string(), value.clone());
+            } else if let Some(key) = key.strip_prefix(TABLE_OVERRIDE_PREFIX) {
+                inherited.overrides.insert(key.to_string(), value.clone());
+            }
+        }
+        inherited
+    }
+
+    /// Apply the inherited properties to the properties of a create request.
+    /// Overrides take precedence over the request, which takes precedence over defaults.
+    pub(crate) fn apply(
+        &self,
+        properties: Option<HashMap<String, String>>,
+    ) -> Option<HashMap<String, String>> {
+        if self.defaults.is_empty() && self.overrides.is_empty() {
+            return properties;
+        }
+
+        let mut merged = self.defaults.clone();
+        merged.extend(properties.unwrap_or_default());
+        merged.extend(self.overrides.clone());
+        Some(merged)
+    }
+
+    /// Strip the `table-default.` or `table-override.` prefix of a property key.
+    /// Returns `None` if the key has neither prefix.
+    pub(crate) fn strip_prefix(key: &str) -> Option<&str> {
+        key.strip_prefix(TABLE_DEFAULT_PREFIX)
+            .or_else(|| key.strip_prefix(TABLE_OVERRIDE_PREFIX))
+    }
+
+    /// Validate the table properties behind `table-default.*` and `table-override.*` keys.
+    /// Other keys are ignored.
+    pub(crate) fn validate_keys<'a>(keys: impl IntoIterator<Item = &'a String>) -> Result<()> {
+        let table_keys = keys
+            .into_iter()
+            .filter_map(|k| Self::strip_prefix(k))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        if table_keys.iter().any(String::is_empty) {
+            return Err(ErrorModel::bad_request(
+                "Inherited table properties must specify a property name after the prefix",
+                "EmptyInheritedTableProperty",
+                None,
+            )
+            .into());
+        }
+        validate_table_properties(&table_keys)
+    }
+}
+
 pub(crate) fn validate_table_or_view_ident(table: &TableIdent) -> Result<()> {
     let TableIdent {
         ref namespace,
@@ -1510,6 +1879,18 @@ pub(crate) fn maybe_body_to_json(request: impl Serialize) -> serde_json::Value {
     }
 }
 
+fn parse_format_version(format_version: &str) -> Result<FormatVersion, ErrorModel> {
+    match format_version {
+        "v1" | "1" => Ok(FormatVersion::V1),
+        "v2" | "2" => Ok(FormatVersion::V2),
+        _ => Err(ErrorModel::bad_request(
+            format!("Invalid format version specified in table_properties: {format_version}"),
+            "InvalidFormatVersion",
+            None,
+        )),
+    }
+}
+
 pub(crate) fn create_table_request_into_table_metadata(
     table_id: TableIdentUuid,
     request: CreateTableRequest,
@@ -1538,15 +1919,7 @@ pub(crate) fn create_table_request_into_table_metadata(
     let format_version = properties
         .as_mut()
         .and_then(|props| props.remove(PROPERTY_FORMAT_VERSION))
-        .map(|s| match s.as_str() {
-            "v1" | "1" => Ok(FormatVersion::V1),
-            "v2" | "2" => Ok(FormatVersion::V2),
-            _ => Err(ErrorModel::bad_request(
-                format!("Invalid format version specified in table_properties: {s}"),
-                "InvalidFormatVersion",
-                None,
-            )),
-        })
+        .map(|s| parse_format_version(&s))
         .transpose()?
         .unwrap_or(FormatVersion::V2);
 
@@ -1574,7 +1947,7 @@ pub(crate) fn create_table_request_into_table_metadata(
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use crate::api::iceberg::types::{PageToken, Prefix};
     use crate::api::iceberg::v1::tables::Service as _;
     use crate::api::iceberg::v1::{
@@ -1584,10 +1957,11 @@ mod test {
     use crate::api::ApiContext;
     use crate::catalog::test::random_request_metadata;
     use crate::catalog::CatalogServer;
+    use crate::implementations::postgres::PostgresTransaction;
     use crate::implementations::postgres::{PostgresCatalog, SecretsState};
     use crate::service::authz::implementations::openfga::tests::ObjectHidingMock;
-    use crate::service::authz::AllowAllAuthorizer;
-    use crate::service::State;
+    use crate::service::authz::{AllowAllAuthorizer, RefProtectionRule, RefProtectionScope};
+    use crate::service::{Catalog, State, Transaction};
 
     use http::StatusCode;
     use iceberg::spec::{
@@ -1595,7 +1969,7 @@ mod test {
         SnapshotRetention, Summary, Transform, Type, UnboundPartitionField, UnboundPartitionSpec,
         MAIN_BRANCH, PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX,
     };
-    use iceberg::TableIdent;
+    use iceberg::{TableIdent, TableUpdate};
     use iceberg_ext::catalog::rest::{
         CommitTableRequest, CreateNamespaceResponse, CreateTableRequest, LoadTableResult,
     };
@@ -1616,6 +1990,86 @@ mod test {
         assert!(validate_table_properties(properties.iter()).is_ok());
     }
 
+    #[test]
+    fn test_inherited_table_properties() {
+        let warehouse = HashMap::from_iter([
+            ("table-default.format-version".to_string(), "1".to_string()),
+            ("table-default.owner".to_string(), "warehouse".to_string()),
+            ("unrelated".to_string(), "value".to_string()),
+        ]);
+        let parent = HashMap::from_iter([
+            ("table-default.format-version".to_string(), "2".to_string()),
+            (
+                "table-override.write.format.default".to_string(),
+                "parquet".to_string(),
+            ),
+        ]);
+        let child = HashMap::from_iter([("table-default.owner".to_string(), "child".to_string())]);
+
+        let inherited = super::InheritedTableProperties::from_layers([&warehouse, &parent, &child]);
+        assert_eq!(
+            inherited.defaults,
+            HashMap::from_iter([
+                ("format-version".to_string(), "2".to_string()),
+                ("owner".to_string(), "child".to_string()),
+            ])
+        );
+
+        let properties = inherited
+            .apply(Some(HashMap::from_iter([
+                ("owner".to_string(), "request".to_string()),
+                ("write.format.default".to_string(), "avro".to_string()),
+            ])))
+            .unwrap();
+        assert_eq!(
+            properties,
+            HashMap::from_iter([
+                ("format-version".to_string(), "2".to_string()),
+                ("owner".to_string(), "request".to_string()),
+                ("write.format.default".to_string(), "parquet".to_string()),
+            ])
+        );
+
+        let empty = super::InheritedTableProperties::from_layers([&HashMap::new()]);
+        assert_eq!(empty.apply(None), None);
+    }
+
+    #[test]
+    fn test_overridden_properties_cannot_be_modified() {
+        let overrides = HashMap::from_iter([("owner".to_string(), "admin".to_string())]);
+
+        let set_same = iceberg::TableUpdate::SetProperties {
+            updates: overrides.clone(),
+        };
+        super::validate_table_updates(&vec![set_same], &overrides).unwrap();
+
+        let set_other = iceberg::TableUpdate::SetProperties {
+            updates: HashMap::from_iter([("owner".to_string(), "me".to_string())]),
+        };
+        let err = super::validate_table_updates(&vec![set_other], &overrides).unwrap_err();
+        assert_eq!(err.error.r#type, "PropertyOverridden");
+
+        let remove = iceberg::TableUpdate::RemoveProperties {
+            removals: vec!["owner".to_string()],
+        };
+        let err = super::validate_table_updates(&vec![remove], &overrides).unwrap_err();
+        assert_eq!(err.error.r#type, "PropertyOverridden");
+    }
+
+    #[test]
+    fn test_overridden_format_version_cannot_be_upgraded() {
+        let upgrade = || iceberg::TableUpdate::UpgradeFormatVersion {
+            format_version: iceberg::spec::FormatVersion::V2,
+        };
+        let overrides = HashMap::from_iter([("format-version".to_string(), "1".to_string())]);
+        let err = super::validate_table_updates(&vec![upgrade()], &overrides).unwrap_err();
+        assert_eq!(err.error.r#type, "PropertyOverridden");
+
+        let overrides = HashMap::from_iter([("format-version".to_string(), "2".to_string())]);
+        super::validate_table_updates(&vec![upgrade()], &overrides).unwrap();
+        super::validate_table_updates(&vec![upgrade()], &HashMap::new()).unwrap();
+    }
+
     #[test]
     fn test_extract_count_from_metadata_location() {
         let location = Location::from_str("s3://path/to/table/metadata/00000-d0407fb2-1112-4944-bb88-c68ae697e2b4.gz.metadata.json").unwrap();
@@ -1655,7 +2109,7 @@ mod test {
         assert!(count.is_none());
     }
 
-    fn create_request(table_name: Option<String>) -> CreateTableRequest {
+    pub(crate) fn create_request(table_name: Option<String>) -> CreateTableRequest {
         CreateTableRequest {
             name: table_name.unwrap_or("my_table".to_string()),
             location: None,
@@ -1971,6 +2425,94 @@ mod test {
         assert_eq!(tab.metadata, builder.metadata);
     }
 
+    #[sqlx::test]
+    async fn test_ref_protection_commit(pool: PgPool) {
+        let (ctx, ns, ns_params, table) = commit_test_setup(pool).await;
+        let table_ident = TableIdent {
+            namespace: ns.namespace.clone(),
+            name: "tab-1".to_string(),
+        };
+        let warehouse_id =
+            crate::WarehouseIdent::from_str(ns_params.prefix.as_ref().unwrap().as_str()).unwrap();
+        let rule = RefProtectionRule {
+            rule_id: Uuid::now_v7(),
+            scope: RefProtectionScope::Warehouse,
+            ref_pattern: MAIN_BRANCH.to_string(),
+            allowed_committers: vec![],
+            prevent_deletion: true,
+            fast_forward_only: true,
+        };
+        let mut t = PostgresTransaction::begin_write(ctx.v1_state.catalog.clone())
+            .await
+            .unwrap();
+        PostgresCatalog::create_ref_protection_rule(warehouse_id, &rule, t.transaction())
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+
+        let last_updated_ms = table.metadata.last_updated_ms();
+        let snapshot =
+            |snapshot_id: i64, parent_snapshot_id: Option<i64>| TableUpdate::AddSnapshot {
+                snapshot: Snapshot::builder()
+                    .with_snapshot_id(snapshot_id)
+                    .with_parent_snapshot_id(parent_snapshot_id)
+                    .with_timestamp_ms(last_updated_ms + snapshot_id)
+                    .with_sequence_number(snapshot_id)
+                    .with_schema_id(0)
+                    .with_manifest_list(format!("/snap-{snapshot_id}.avro"))
+                    .with_summary(Summary {
+                        operation: Operation::Append,
+                        other: HashMap::new(),
+                    })
+                    .build(),
+            };
+        let set_main = |snapshot_id: i64| TableUpdate::SetSnapshotRef {
+            ref_name: MAIN_BRANCH.to_string(),
+            reference: SnapshotReference {
+                snapshot_id,
+                retention: SnapshotRetention::Branch {
+                    min_snapshots_to_keep: None,
+                    max_snapshot_age_ms: None,
+                    max_ref_age_ms: None,
+                },
+            },
+        };
+        let commit = |updates: Vec<TableUpdate>| {
+            super::commit_tables_internal(
+                ns_params.prefix.clone(),
+                super::CommitTransactionRequest {
+                    table_changes: vec![CommitTableRequest {
+                        identifier: Some(table_ident.clone()),
+                        requirements: vec![],
+                        updates,
+                    }],
+                },
+                ctx.clone(),
+                random_request_metadata(),
+            )
+        };
+
+        // Creating the protected branch is allowed
+        commit(vec![snapshot(1, None), set_main(1)]).await.unwrap();
+        // Adding a snapshot without moving the branch does not touch the rule
+        commit(vec![snapshot(2, None)]).await.unwrap();
+        // Moving the branch to a snapshot that does not descend from its head is not
+        let err = commit(vec![set_main(2)]).await.unwrap_err();
+        assert_eq!(err.error.code, StatusCode::FORBIDDEN.as_u16());
+        assert_eq!(err.error.r#type, "RefNotFastForward");
+        // Fast-forwarding is
+        commit(vec![snapshot(3, Some(1)), set_main(3)])
+            .await
+            .unwrap();
+        // Removing the branch is not
+        let err = commit(vec![TableUpdate::RemoveSnapshotRef {
+            ref_name: MAIN_BRANCH.to_string(),
+        }])
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "RefProtected");
+    }
+
     #[sqlx::test]
     async fn test_expire_metadata_log(pool: PgPool) {
         let (ctx, ns, ns_params, table) = commit_test_setup(pool).await;
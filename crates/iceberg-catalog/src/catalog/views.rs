@@ -2,8 +2,11 @@ mod commit;
 pub(crate) mod create;
 mod drop;
 mod exists;
+mod lineage;
 mod list;
 mod load;
+mod materialized;
+mod register;
 mod rename;
 
 use super::tables::validate_table_properties;
@@ -11,8 +14,11 @@ use super::CatalogServer;
 use crate::api::iceberg::types::DropParams;
 use crate::api::iceberg::v1::{
     ApiContext, CommitViewRequest, CreateViewRequest, DataAccess, ListTablesQuery,
-    ListTablesResponse, LoadViewResult, NamespaceParameters, Prefix, RenameTableRequest, Result,
-    ViewParameters,
+    ListTablesResponse, LoadViewResult, NamespaceParameters, Prefix, RegisterViewRequest,
+    RenameTableRequest, Result, ViewParameters,
+};
+use crate::api::{
+    CommitMaterializedViewRequest, CreateMaterializedViewRequest, LoadMaterializedViewResult,
 };
 use crate::request_metadata::RequestMetadata;
 use crate::service::authz::Authorizer;
@@ -46,6 +52,17 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         create::create_view(parameters, request, state, data_access, request_metadata).await
     }
 
+    /// Register a view in the given namespace using given metadata file location
+    async fn register_view(
+        parameters: NamespaceParameters,
+        request: RegisterViewRequest,
+        state: ApiContext<State<A, C, S>>,
+        data_access: DataAccess,
+        request_metadata: RequestMetadata,
+    ) -> Result<LoadViewResult> {
+        register::register_view(parameters, request, state, data_access, request_metadata).await
+    }
+
     /// Load a view from the catalog
     async fn load_view(
         parameters: ViewParameters,
@@ -95,6 +112,52 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     ) -> Result<()> {
         rename::rename_view(prefix, request, state, request_metadata).await
     }
+
+    /// Create a materialized view and its storage table
+    async fn create_materialized_view(
+        parameters: NamespaceParameters,
+        request: CreateMaterializedViewRequest,
+        state: ApiContext<State<A, C, S>>,
+        data_access: DataAccess,
+        request_metadata: RequestMetadata,
+    ) -> Result<LoadMaterializedViewResult> {
+        materialized::create_materialized_view(
+            parameters,
+            request,
+            state,
+            data_access,
+            request_metadata,
+        )
+        .await
+    }
+
+    /// Load a materialized view
+    async fn load_materialized_view(
+        parameters: ViewParameters,
+        state: ApiContext<State<A, C, S>>,
+        data_access: DataAccess,
+        request_metadata: RequestMetadata,
+    ) -> Result<LoadMaterializedViewResult> {
+        materialized::load_materialized_view(parameters, state, data_access, request_metadata).await
+    }
+
+    /// Commit updates to a materialized view and its storage table
+    async fn commit_materialized_view(
+        parameters: ViewParameters,
+        request: CommitMaterializedViewRequest,
+        state: ApiContext<State<A, C, S>>,
+        data_access: DataAccess,
+        request_metadata: RequestMetadata,
+    ) -> Result<LoadMaterializedViewResult> {
+        materialized::commit_materialized_view(
+            parameters,
+            request,
+            state,
+            data_access,
+            request_metadata,
+        )
+        .await
+    }
 }
 
 fn validate_view_properties<'a, I>(properties: I) -> Result<()>
@@ -202,7 +265,10 @@ mod test {
                         crate::implementations::postgres::task_queues::TabularExpirationQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap(),
                     ),
                     Arc::new(
-                        crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), CONFIG.queue_config.clone()).unwrap()
+                        crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::NamespaceExpirationQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), CONFIG.queue_config.clone()).unwrap()
                     )
                 )
             },
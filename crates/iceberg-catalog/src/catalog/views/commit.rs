@@ -4,26 +4,31 @@ use crate::api::iceberg::v1::{
 };
 use crate::catalog::compression_codec::CompressionCodec;
 use crate::catalog::io::write_metadata_file;
-use crate::catalog::require_warehouse_id;
 use crate::catalog::tables::{
     determine_table_ident, extract_count_from_metadata_location, maybe_body_to_json,
-    require_active_warehouse, validate_table_or_view_ident,
+    parse_location, require_active_warehouse, validate_table_or_view_ident,
 };
+use crate::catalog::views::lineage::update_view_dependencies;
 use crate::catalog::views::{parse_view_location, validate_view_updates};
+use crate::catalog::{maybe_get_secret, require_warehouse_id};
 use crate::request_metadata::RequestMetadata;
 use crate::service::authz::{CatalogViewAction, CatalogWarehouseAction};
-use crate::service::contract_verification::ContractVerification;
+use crate::service::contract_verification::{ContractVerification, ContractVerifiers};
 use crate::service::event_publisher::EventMetadata;
 use crate::service::storage::{StorageLocations as _, StoragePermissions};
+use crate::service::table_property_policy::modified_view_properties;
 use crate::service::{
     authz::Authorizer, secrets::SecretStore, Catalog, GetWarehouseResponse, State, Transaction,
     ViewMetadataWithLocation,
 };
-use crate::service::{TabularIdentUuid, ViewIdentUuid};
+use crate::service::{NamespaceIdentUuid, TabularIdentUuid, ViewIdentUuid};
 use http::StatusCode;
+use iceberg::io::FileIO;
 use iceberg::spec::{AppendViewVersion, ViewFormatVersion, ViewMetadata, ViewMetadataBuilder};
+use iceberg::TableIdent;
 use iceberg_ext::catalog::rest::ViewUpdate;
 use iceberg_ext::catalog::ViewRequirement;
+use iceberg_ext::configs::Location;
 use uuid::Uuid;
 
 /// Commit updates to a view
@@ -39,13 +44,7 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
     // ------------------- VALIDATIONS -------------------
     let warehouse_id = require_warehouse_id(parameters.prefix.clone())?;
 
-    let CommitViewRequest {
-        identifier,
-        requirements,
-        updates,
-    } = &request;
-
-    let identifier = determine_table_ident(parameters.view, identifier.as_ref())?;
+    let identifier = determine_table_ident(parameters.view, request.identifier.as_ref())?;
     validate_table_or_view_ident(&identifier)?;
 
     // ------------------- AUTHZ -------------------
@@ -70,8 +69,6 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
         .await?;
 
     // ------------------- BUSINESS LOGIC -------------------
-    validate_view_updates(updates)?;
-
     let namespace_id = C::namespace_to_id(warehouse_id, identifier.namespace(), t.transaction())
         .await?
         .ok_or(ErrorModel::not_found(
@@ -80,87 +77,38 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
             None,
         ))?;
 
-    let GetWarehouseResponse {
-        id: _,
-        name: _,
-        project_id: _,
-        storage_profile,
-        storage_secret_id,
-        status,
-        tabular_delete_profile: _,
-    } = C::require_warehouse(warehouse_id, t.transaction()).await?;
-    require_active_warehouse(status)?;
-
-    check_asserts(requirements.as_ref(), view_id)?;
-
-    let ViewMetadataWithLocation {
-        metadata_location: before_update_metadata_location,
-        metadata: before_update_metadata,
-    } = C::load_view(view_id, false, t.transaction()).await?;
-    let view_location = parse_view_location(&before_update_metadata.location)?;
-    let before_update_metadata_location = parse_view_location(&before_update_metadata_location)?;
-
-    state
-        .v1_state
-        .contract_verifiers
-        .check_view_updates(updates, &before_update_metadata)
-        .await?
-        .into_result()?;
-
-    // serialize body before moving it
-    let body = maybe_body_to_json(&request);
-
-    let requested_update_metadata = build_new_metadata(request, before_update_metadata)?;
-
-    let metadata_location = storage_profile.default_metadata_location(
-        &view_location,
-        &CompressionCodec::try_from_properties(requested_update_metadata.properties())?,
-        Uuid::now_v7(),
-        extract_count_from_metadata_location(&before_update_metadata_location).map_or(0, |v| v + 1),
-    );
+    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+    require_active_warehouse(warehouse.status)?;
 
-    C::update_view_metadata(
+    // We don't commit the transaction yet, first we need to write the metadata file.
+    let storage_secret =
+        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+    let file_io = warehouse.storage_profile.file_io(storage_secret.as_ref())?;
+
+    let CommittedView {
+        metadata,
+        metadata_location,
+        body,
+    } = commit_view_in_transaction::<C>(
+        &warehouse,
         namespace_id,
         view_id,
         &identifier,
-        &metadata_location,
-        requested_update_metadata.clone(),
-        &view_location,
-        t.transaction(),
-    )
-    .await?;
-
-    // We don't commit the transaction yet, first we need to write the metadata file.
-    let storage_secret = if let Some(secret_id) = &storage_secret_id {
-        Some(
-            state
-                .v1_state
-                .secrets
-                .get_secret_by_id(secret_id)
-                .await?
-                .secret,
-        )
-    } else {
-        None
-    };
-
-    let file_io = storage_profile.file_io(storage_secret.as_ref())?;
-    write_metadata_file(
-        &metadata_location,
-        &requested_update_metadata,
-        CompressionCodec::try_from_metadata(&requested_update_metadata)?,
+        request,
+        &state.v1_state.contract_verifiers,
         &file_io,
+        &mut t,
     )
     .await?;
 
-    tracing::debug!("Wrote new metadata file to: '{}'", metadata_location);
     // Generate the storage profile. This requires the storage secret
     // because the table config might contain vended-credentials based
     // on the `data_access` parameter.
     // ToDo: There is a small inefficiency here: If storage credentials
     // are not required because of i.e. remote-signing and if this
     // is a stage-create, we still fetch the secret.
-    let config = storage_profile
+    let config = warehouse
+        .storage_profile
         .generate_table_config(
             &data_access,
             storage_secret.as_ref(),
@@ -196,11 +144,119 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
 
     Ok(LoadViewResult {
         metadata_location: metadata_location.to_string(),
-        metadata: requested_update_metadata,
+        metadata,
         config: Some(config.into()),
     })
 }
 
+/// A view commit applied by [`commit_view_in_transaction`].
+pub(crate) struct CommittedView {
+    pub(crate) metadata: ViewMetadata,
+    pub(crate) metadata_location: Location,
+    /// Request body of the commit for events
+    pub(crate) body: serde_json::Value,
+}
+
+/// Apply the updates of a view commit and write the new metadata file
+/// without committing the transaction.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn commit_view_in_transaction<C: Catalog>(
+    warehouse: &GetWarehouseResponse,
+    namespace_id: NamespaceIdentUuid,
+    view_id: ViewIdentUuid,
+    identifier: &TableIdent,
+    request: CommitViewRequest,
+    contract_verifiers: &ContractVerifiers,
+    file_io: &FileIO,
+    transaction: &mut C::Transaction,
+) -> Result<CommittedView> {
+    let storage_profile = &warehouse.storage_profile;
+    validate_view_updates(&request.updates)?;
+    check_asserts(request.requirements.as_ref(), view_id)?;
+
+    let ViewMetadataWithLocation {
+        metadata_location: before_update_metadata_location,
+        metadata: before_update_metadata,
+    } = C::load_view(view_id, false, transaction.transaction()).await?;
+    let view_location = parse_view_location(&before_update_metadata.location)?;
+    let before_update_metadata_location = parse_view_location(&before_update_metadata_location)?;
+
+    contract_verifiers
+        .check_view_updates(&request.updates, &before_update_metadata)
+        .await?
+        .into_result()?;
+
+    // serialize body before moving it
+    let body = maybe_body_to_json(&request);
+    let modified_properties = modified_view_properties(&request.updates);
+
+    let mut requested_update_metadata = build_new_metadata(request, before_update_metadata)?;
+    warehouse.table_property_policy.require_compliance(
+        requested_update_metadata.properties(),
+        Some(&modified_properties),
+    )?;
+
+    // Overlaps with other tables and views are checked when the view is written to the DB.
+    let view_location = if requested_update_metadata.location == view_location.as_str() {
+        view_location
+    } else {
+        let mut location =
+            parse_location(&requested_update_metadata.location, StatusCode::BAD_REQUEST)?;
+        if !storage_profile.is_allowed_location(&location) {
+            return Err(ErrorModel::bad_request(
+                format!("Specified view location is not allowed: {location}"),
+                "InvalidTableLocation",
+                None,
+            )
+            .into());
+        }
+        location.without_trailing_slash();
+        requested_update_metadata.location = location.to_string();
+        location
+    };
+
+    let metadata_location = storage_profile.default_metadata_location(
+        &view_location,
+        &CompressionCodec::try_from_properties(requested_update_metadata.properties())?,
+        Uuid::now_v7(),
+        extract_count_from_metadata_location(&before_update_metadata_location).map_or(0, |v| v + 1),
+    );
+
+    C::update_view_metadata(
+        namespace_id,
+        view_id,
+        identifier,
+        &metadata_location,
+        requested_update_metadata.clone(),
+        &view_location,
+        transaction.transaction(),
+    )
+    .await?;
+    update_view_dependencies::<C>(
+        warehouse.id,
+        view_id,
+        &requested_update_metadata,
+        transaction,
+    )
+    .await?;
+
+    write_metadata_file(
+        &metadata_location,
+        &requested_update_metadata,
+        CompressionCodec::try_from_metadata(&requested_update_metadata)?,
+        file_io,
+    )
+    .await?;
+
+    tracing::debug!("Wrote new metadata file to: '{}'", metadata_location);
+
+    Ok(CommittedView {
+        metadata: requested_update_metadata,
+        metadata_location,
+        body,
+    })
+}
+
 fn check_asserts(
     requirements: Option<&Vec<ViewRequirement>>,
     view_id: ViewIdentUuid,
@@ -229,25 +285,27 @@ fn build_new_metadata(
     request: CommitViewRequest,
     before_update_metadata: ViewMetadata,
 ) -> Result<ViewMetadata> {
+    let previous_uuid = before_update_metadata.view_uuid;
+    let mut new_location = None;
     let mut m = ViewMetadataBuilder::new(before_update_metadata);
 
     for upd in request.updates {
         m = match upd {
-            ViewUpdate::AssignUuid { .. } => {
-                return Err(ErrorModel::builder()
-                    .code(StatusCode::BAD_REQUEST.into())
-                    .message("Assigning UUIDs is not supported".to_string())
-                    .r#type("AssignUuidNotSupported".to_string())
-                    .build()
+            ViewUpdate::AssignUuid { uuid } => {
+                if uuid != previous_uuid {
+                    return Err(ErrorModel::bad_request(
+                        format!("Cannot assign a new UUID to view '{previous_uuid}': {uuid}"),
+                        "AssignUuidNotAllowed",
+                        None,
+                    )
                     .into());
+                }
+                m
             }
-            ViewUpdate::SetLocation { .. } => {
-                return Err(ErrorModel::builder()
-                    .code(StatusCode::BAD_REQUEST.into())
-                    .message("Setting location is not supported".to_string())
-                    .r#type("SetLocationNotSupported".to_string())
-                    .build()
-                    .into());
+            // Validated against the storage profile by the caller.
+            ViewUpdate::SetLocation { location } => {
+                new_location = Some(location);
+                m
             }
 
             ViewUpdate::UpgradeFormatVersion { format_version } => match format_version {
@@ -288,22 +346,29 @@ fn build_new_metadata(
         }
     }
 
-    let requested_update_metadata = m.build().map_err(|e| {
+    let mut requested_update_metadata = m.build().map_err(|e| {
         ErrorModel::builder()
             .code(StatusCode::BAD_REQUEST.into())
             .message(format!("Error building metadata: {e}"))
             .r#type("BuildMetadataError".to_string())
             .build()
     })?;
+    if let Some(location) = new_location {
+        requested_update_metadata.location = location;
+    }
     Ok(requested_update_metadata)
 }
 
 #[cfg(test)]
 mod test {
     use crate::api::iceberg::v1::{views, DataAccess, Prefix, ViewParameters};
+    use crate::api::ApiContext;
+    use crate::implementations::postgres::{PostgresCatalog, SecretsState};
+    use crate::service::authz::AllowAllAuthorizer;
+    use crate::service::State;
 
-    use iceberg::TableIdent;
-    use iceberg_ext::catalog::rest::CommitViewRequest;
+    use iceberg::{NamespaceIdent, TableIdent};
+    use iceberg_ext::catalog::rest::{CommitViewRequest, LoadViewResult};
     use maplit::hashmap;
 
     use serde_json::json;
@@ -404,6 +469,134 @@ mod test {
         assert_eq!(err.error.r#type, "ViewUuidMismatch");
     }
 
+    async fn commit(
+        api_context: ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
+        prefix: &str,
+        namespace: &NamespaceIdent,
+        view_name: &str,
+        updates: serde_json::Value,
+    ) -> crate::api::Result<LoadViewResult> {
+        super::commit_view(
+            ViewParameters {
+                prefix: Some(Prefix(prefix.to_string())),
+                view: TableIdent::from_strs(
+                    namespace
+                        .clone()
+                        .inner()
+                        .into_iter()
+                        .chain([view_name.into()]),
+                )
+                .unwrap(),
+            },
+            serde_json::from_value(json!({ "requirements": [], "updates": updates })).unwrap(),
+            api_context,
+            DataAccess {
+                vended_credentials: true,
+                remote_signing: false,
+            },
+            crate::request_metadata::RequestMetadata::new_random(),
+        )
+        .await
+    }
+
+    #[sqlx::test]
+    async fn test_commit_view_set_location(pool: PgPool) {
+        let (api_context, namespace, whi) = setup(pool, None).await;
+        let prefix = whi.to_string();
+        let view = create_view(
+            api_context.clone(),
+            namespace.clone(),
+            create_view_request(Some("myview"), None),
+            Some(prefix.clone()),
+        )
+        .await
+        .unwrap();
+        let other_view = create_view(
+            api_context.clone(),
+            namespace.clone(),
+            create_view_request(Some("otherview"), None),
+            Some(prefix.clone()),
+        )
+        .await
+        .unwrap();
+
+        let (parent, _) = view.metadata.location.rsplit_once('/').unwrap();
+        let new_location = format!("{parent}/{}", Uuid::now_v7());
+        let res = commit(
+            api_context.clone(),
+            &prefix,
+            &namespace,
+            "myview",
+            json!([{"action": "set-location", "location": &new_location}]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.metadata.location, new_location);
+        assert!(res
+            .metadata_location
+            .starts_with(&format!("{new_location}/metadata/")));
+
+        let err = commit(
+            api_context.clone(),
+            &prefix,
+            &namespace,
+            "myview",
+            json!([{"action": "set-location", "location": "s3://other-bucket/myview"}]),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, 400);
+        assert_eq!(err.error.r#type, "InvalidTableLocation");
+
+        let err = commit(
+            api_context,
+            &prefix,
+            &namespace,
+            "myview",
+            json!([{"action": "set-location", "location": other_view.metadata.location}]),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "LocationAlreadyTaken");
+    }
+
+    #[sqlx::test]
+    async fn test_commit_view_assign_uuid(pool: PgPool) {
+        let (api_context, namespace, whi) = setup(pool, None).await;
+        let prefix = whi.to_string();
+        let view = create_view(
+            api_context.clone(),
+            namespace.clone(),
+            create_view_request(Some("myview"), None),
+            Some(prefix.clone()),
+        )
+        .await
+        .unwrap();
+
+        let res = commit(
+            api_context.clone(),
+            &prefix,
+            &namespace,
+            "myview",
+            json!([{"action": "assign-uuid", "uuid": view.metadata.view_uuid}]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.metadata.view_uuid, view.metadata.view_uuid);
+
+        let err = commit(
+            api_context,
+            &prefix,
+            &namespace,
+            "myview",
+            json!([{"action": "assign-uuid", "uuid": Uuid::now_v7()}]),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, 400);
+        assert_eq!(err.error.r#type, "AssignUuidNotAllowed");
+    }
+
     fn spark_commit_update_request(asserted_uuid: Option<Uuid>) -> CommitViewRequest {
         let uuid = asserted_uuid.map_or("019059cb-9277-7ff0-b71a-537df05b33f8".into(), |u| {
             u.to_string()
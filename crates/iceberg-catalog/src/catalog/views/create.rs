@@ -7,6 +7,7 @@ use crate::catalog::tables::{
     determine_tabular_location, maybe_body_to_json, require_active_warehouse,
     validate_table_or_view_ident,
 };
+use crate::catalog::views::lineage::update_view_dependencies;
 use crate::catalog::views::validate_view_properties;
 use crate::catalog::{maybe_get_secret, require_warehouse_id};
 use crate::request_metadata::RequestMetadata;
@@ -14,11 +15,15 @@ use crate::service::authz::{Authorizer, CatalogNamespaceAction, CatalogWarehouse
 use crate::service::event_publisher::EventMetadata;
 use crate::service::storage::{StorageLocations as _, StoragePermissions};
 use crate::service::TabularIdentUuid;
-use crate::service::{Catalog, SecretStore, State, Transaction};
+use crate::service::{
+    Catalog, GetNamespaceResponse, GetWarehouseResponse, SecretStore, State, Transaction,
+};
 use crate::service::{Result, ViewIdentUuid};
-use iceberg::spec::ViewMetadataBuilder;
+use iceberg::io::FileIO;
+use iceberg::spec::{ViewMetadata, ViewMetadataBuilder};
 use iceberg::{TableIdent, ViewCreation};
 use iceberg_ext::catalog::rest::{CreateViewRequest, ErrorModel, LoadViewResult};
+use iceberg_ext::configs::Location;
 use uuid::Uuid;
 
 // TODO: split up into smaller functions
@@ -71,16 +76,102 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
     // ------------------- BUSINESS LOGIC -------------------
     let namespace = C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
     let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
-    let storage_profile = warehouse.storage_profile;
+    let storage_profile = &warehouse.storage_profile;
     require_active_warehouse(warehouse.status)?;
 
+    // We don't commit the transaction yet, first we need to write the metadata file.
+    let storage_secret =
+        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+    let file_io = storage_profile.file_io(storage_secret.as_ref())?;
+
+    let CreatedView {
+        view_id,
+        metadata,
+        metadata_location,
+        view_location,
+        body,
+    } = create_view_in_transaction::<C>(&warehouse, &namespace, &view, request, &file_io, &mut t)
+        .await?;
+
+    // Generate the storage profile. This requires the storage secret
+    // because the table config might contain vended-credentials based
+    // on the `data_access` parameter.
+    // ToDo: There is a small inefficiency here: If storage credentials
+    // are not required because of i.e. remote-signing and if this
+    // is a stage-create, we still fetch the secret.
+    let config = storage_profile
+        .generate_table_config(
+            &data_access,
+            storage_secret.as_ref(),
+            &view_location,
+            StoragePermissions::Read,
+        )
+        .await?;
+
+    authorizer
+        .create_view(&request_metadata, view_id, namespace_id)
+        .await?;
+
+    t.commit().await?;
+
+    let _ = state
+        .v1_state
+        .publisher
+        .publish(
+            Uuid::now_v7(),
+            "createView",
+            body,
+            EventMetadata {
+                tabular_id: TabularIdentUuid::View(*view_id),
+                warehouse_id: *warehouse_id.as_uuid(),
+                name: view.name,
+                namespace: view.namespace.to_url_string(),
+                prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
+                num_events: 1,
+                sequence_number: 0,
+                trace_id: request_metadata.request_id,
+            },
+        )
+        .await;
+
+    let load_view_result = LoadViewResult {
+        metadata_location: metadata_location.to_string(),
+        metadata,
+        config: Some(config.into()),
+    };
+
+    Ok(load_view_result)
+}
+
+/// A view created by [`create_view_in_transaction`].
+pub(crate) struct CreatedView {
+    pub(crate) view_id: ViewIdentUuid,
+    pub(crate) metadata: ViewMetadata,
+    pub(crate) metadata_location: Location,
+    pub(crate) view_location: Location,
+    /// Request body of the creation for events
+    pub(crate) body: serde_json::Value,
+}
+
+/// Create a view and write its metadata file without committing the transaction.
+pub(crate) async fn create_view_in_transaction<C: Catalog>(
+    warehouse: &GetWarehouseResponse,
+    namespace: &GetNamespaceResponse,
+    view: &TableIdent,
+    request: CreateViewRequest,
+    file_io: &FileIO,
+    transaction: &mut C::Transaction,
+) -> Result<CreatedView> {
+    let storage_profile = &warehouse.storage_profile;
     let view_id: TabularIdentUuid = TabularIdentUuid::View(uuid::Uuid::now_v7());
 
     let view_location = determine_tabular_location(
-        &namespace,
+        namespace,
         request.location.clone(),
         view_id,
-        &storage_profile,
+        &request.name,
+        storage_profile,
+        warehouse.location_template.as_ref(),
     )?;
 
     // Update the request for event
@@ -117,78 +208,37 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
             Some(Box::new(e)),
         )
     })?;
+    let policy = &warehouse.table_property_policy;
+    policy.require_compliance(metadata.properties(), Some(&policy.view_keys()))?;
 
     C::create_view(
-        namespace_id,
-        &view,
+        namespace.namespace_id,
+        view,
         metadata.clone(),
         &metadata_location,
         &view_location,
-        t.transaction(),
+        transaction.transaction(),
+    )
+    .await?;
+    update_view_dependencies::<C>(
+        warehouse.id,
+        ViewIdentUuid::from(*view_id),
+        &metadata,
+        transaction,
     )
     .await?;
 
-    // We don't commit the transaction yet, first we need to write the metadata file.
-    let storage_secret =
-        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
-
-    let file_io = storage_profile.file_io(storage_secret.as_ref())?;
     let compression_codec = CompressionCodec::try_from_metadata(&metadata)?;
-    write_metadata_file(&metadata_location, &metadata, compression_codec, &file_io).await?;
+    write_metadata_file(&metadata_location, &metadata, compression_codec, file_io).await?;
     tracing::debug!("Wrote new metadata file to: '{}'", metadata_location);
 
-    // Generate the storage profile. This requires the storage secret
-    // because the table config might contain vended-credentials based
-    // on the `data_access` parameter.
-    // ToDo: There is a small inefficiency here: If storage credentials
-    // are not required because of i.e. remote-signing and if this
-    // is a stage-create, we still fetch the secret.
-    let config = storage_profile
-        .generate_table_config(
-            &data_access,
-            storage_secret.as_ref(),
-            &view_location,
-            StoragePermissions::Read,
-        )
-        .await?;
-
-    authorizer
-        .create_view(
-            &request_metadata,
-            ViewIdentUuid::from(metadata.view_uuid),
-            namespace_id,
-        )
-        .await?;
-
-    t.commit().await?;
-
-    let _ = state
-        .v1_state
-        .publisher
-        .publish(
-            Uuid::now_v7(),
-            "createView",
-            body,
-            EventMetadata {
-                tabular_id: TabularIdentUuid::View(*view_id),
-                warehouse_id: *warehouse_id.as_uuid(),
-                name: view.name,
-                namespace: view.namespace.to_url_string(),
-                prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
-                num_events: 1,
-                sequence_number: 0,
-                trace_id: request_metadata.request_id,
-            },
-        )
-        .await;
-
-    let load_view_result = LoadViewResult {
-        metadata_location: metadata_location.to_string(),
+    Ok(CreatedView {
+        view_id: ViewIdentUuid::from(*view_id),
         metadata,
-        config: Some(config.into()),
-    };
-
-    Ok(load_view_result)
+        metadata_location,
+        view_location,
+        body,
+    })
 }
 
 #[cfg(test)]
@@ -280,6 +330,63 @@ pub(crate) mod test {
             .expect("Recreate with same name but different ns should work.");
     }
 
+    pub(crate) async fn set_table_property_policy(
+        api_context: &ApiContext<
+            State<
+                AllowAllAuthorizer,
+                crate::implementations::postgres::PostgresCatalog,
+                SecretsState,
+            >,
+        >,
+        warehouse_id: crate::WarehouseIdent,
+        policy: serde_json::Value,
+    ) {
+        let mut t = crate::implementations::postgres::PostgresTransaction::begin_write(
+            api_context.v1_state.catalog.clone(),
+        )
+        .await
+        .unwrap();
+        crate::implementations::postgres::PostgresCatalog::set_warehouse_table_property_policy(
+            warehouse_id,
+            &serde_json::from_value(policy).unwrap(),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        t.commit().await.unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_create_view_violating_property_policy(pool: PgPool) {
+        let (api_context, namespace, whi) = crate::catalog::views::test::setup(pool, None).await;
+        set_table_property_policy(
+            &api_context,
+            whi,
+            json!({"rules": [
+                {"type": "required", "key": "format-version", "value": "2"},
+                {"type": "required", "key": "owner"},
+            ]}),
+        )
+        .await;
+
+        let mut rq = create_view_request(None, None);
+        let err = create_view(
+            api_context.clone(),
+            namespace.clone(),
+            rq.clone(),
+            Some(whi.to_string()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "TablePropertyPolicyViolation");
+
+        rq.properties
+            .insert("owner".to_string(), "analytics".to_string());
+        create_view(api_context, namespace, rq, Some(whi.to_string()))
+            .await
+            .unwrap();
+    }
+
     pub(crate) fn create_view_request(
         name: Option<&str>,
         location: Option<&str>,
@@ -6,16 +6,21 @@ use crate::api::ApiContext;
 use crate::catalog::require_warehouse_id;
 use crate::catalog::tables::validate_table_or_view_ident;
 use crate::request_metadata::RequestMetadata;
-use crate::service::authz::{Authorizer, CatalogViewAction, CatalogWarehouseAction};
+use crate::service::authz::{
+    Authorizer, CatalogTableAction, CatalogViewAction, CatalogWarehouseAction,
+};
 use crate::service::contract_verification::ContractVerification;
 use crate::service::event_publisher::EventMetadata;
 use crate::service::task_queue::tabular_expiration_queue::TabularExpirationInput;
 use crate::service::task_queue::tabular_purge_queue::TabularPurgeInput;
 use crate::service::TabularIdentUuid;
-use crate::service::{Catalog, SecretStore, State, Transaction};
+use crate::service::{Catalog, ListFlags, SecretStore, State, Transaction};
 use crate::service::{Result, ViewIdentUuid};
+use iceberg_ext::catalog::rest::ErrorModel;
 use uuid::Uuid;
 
+/// Drop a view. The storage table of a materialized view is dropped with it.
+#[allow(clippy::too_many_lines)]
 pub(crate) async fn drop_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
     parameters: ViewParameters,
     DropParams { purge_requested }: DropParams,
@@ -36,7 +41,7 @@ pub(crate) async fn drop_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             &CatalogWarehouseAction::CanUse,
         )
         .await?;
-    let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+    let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
     let view_id = C::view_to_id(warehouse_id, &view, t.transaction()).await; // Can't fail before authz
 
     let view_id: ViewIdentUuid = authorizer
@@ -48,6 +53,20 @@ pub(crate) async fn drop_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         )
         .await?;
 
+    let storage_table_id = C::get_materialized_view(view_id, t.transaction())
+        .await?
+        .map(|materialized_view| materialized_view.storage_table_id);
+    if let Some(storage_table_id) = storage_table_id {
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                warehouse_id,
+                Ok(Some(storage_table_id)),
+                &CatalogTableAction::CanDrop,
+            )
+            .await?;
+    }
+
     // ------------------- BUSINESS LOGIC -------------------
     let purge_requested = purge_requested.unwrap_or(false);
 
@@ -60,11 +79,44 @@ pub(crate) async fn drop_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         .await?
         .into_result()?;
 
+    let storage_table = match storage_table_id {
+        Some(storage_table_id) => {
+            state
+                .v1_state
+                .contract_verifiers
+                .check_drop(TabularIdentUuid::Table(*storage_table_id))
+                .await?
+                .into_result()?;
+            let storage_table = C::get_table_metadata_by_id(
+                warehouse_id,
+                storage_table_id,
+                ListFlags::all(),
+                state.v1_state.catalog.clone(),
+            )
+            .await?
+            .ok_or_else(|| {
+                ErrorModel::internal(
+                    format!("Storage table of materialized view '{view_id}' not found"),
+                    "StorageTableNotFound",
+                    None,
+                )
+            })?;
+            Some(storage_table)
+        }
+        None => None,
+    };
+
     tracing::debug!("Proceeding to delete view");
 
     match warehouse.tabular_delete_profile {
         TabularDeleteProfile::Hard {} => {
             let location = C::drop_view(view_id, t.transaction()).await?;
+            let storage_table_location = match &storage_table {
+                Some(storage_table) => {
+                    Some(C::drop_table(storage_table.table_id, t.transaction()).await?)
+                }
+                None => None,
+            };
             // committing here means maybe dangling data if the queue fails
             // OTOH committing after queuing means we may end up with a view pointing to deleted files
             // I feel that some undeleted files are less bad than a view that cannot be loaded
@@ -83,13 +135,39 @@ pub(crate) async fn drop_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                     })
                     .await?;
                 tracing::debug!("Queued purge task for dropped view '{view_id}'.");
+                if let (Some(storage_table), Some(location)) =
+                    (&storage_table, storage_table_location)
+                {
+                    state
+                        .v1_state
+                        .queues
+                        .queue_tabular_purge(TabularPurgeInput {
+                            tabular_location: location,
+                            tabular_id: *storage_table.table_id,
+                            warehouse_ident: warehouse_id,
+                            tabular_type: TabularType::Table,
+                            parent_id: None,
+                        })
+                        .await?;
+                }
             }
             authorizer.delete_view(view_id).await?;
+            if let Some(storage_table) = &storage_table {
+                authorizer.delete_table(storage_table.table_id).await?;
+            }
         }
         TabularDeleteProfile::Soft { expiration_seconds } => {
             C::mark_tabular_as_deleted(TabularIdentUuid::View(*view_id), t.transaction()).await?;
+            if let Some(storage_table) = &storage_table {
+                C::mark_tabular_as_deleted(
+                    TabularIdentUuid::Table(*storage_table.table_id),
+                    t.transaction(),
+                )
+                .await?;
+            }
             t.commit().await?;
 
+            let expire_at = chrono::Utc::now() + expiration_seconds;
             state
                 .v1_state
                 .queues
@@ -98,32 +176,56 @@ pub(crate) async fn drop_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                     warehouse_ident: warehouse_id,
                     tabular_type: TabularType::View,
                     purge: purge_requested,
-                    expire_at: chrono::Utc::now() + expiration_seconds,
+                    expire_at,
                 })
                 .await?;
             tracing::debug!("Queued expiration task for dropped view '{view_id}'.");
+            if let Some(storage_table) = &storage_table {
+                state
+                    .v1_state
+                    .queues
+                    .queue_tabular_expiration(TabularExpirationInput {
+                        tabular_id: *storage_table.table_id,
+                        warehouse_ident: warehouse_id,
+                        tabular_type: TabularType::Table,
+                        purge: purge_requested,
+                        expire_at,
+                    })
+                    .await?;
+            }
         }
     }
 
-    let _ = state
-        .v1_state
-        .publisher
-        .publish(
-            Uuid::now_v7(),
-            "dropView",
-            serde_json::Value::Null,
-            EventMetadata {
-                tabular_id: TabularIdentUuid::View(*view_id),
-                warehouse_id: *warehouse_id,
-                name: view.name.clone(),
-                namespace: view.namespace.to_url_string(),
-                prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
-                num_events: 1,
-                sequence_number: 0,
-                trace_id: request_metadata.request_id,
-            },
+    let mut events = vec![("dropView", TabularIdentUuid::View(*view_id), view)];
+    events.extend(storage_table.map(|storage_table| {
+        (
+            "dropTable",
+            TabularIdentUuid::Table(*storage_table.table_id),
+            storage_table.table,
         )
-        .await;
+    }));
+    let num_events = events.len();
+    for (sequence_number, (operation_id, tabular_id, ident)) in events.into_iter().enumerate() {
+        let _ = state
+            .v1_state
+            .publisher
+            .publish(
+                Uuid::now_v7(),
+                operation_id,
+                serde_json::Value::Null,
+                EventMetadata {
+                    tabular_id,
+                    warehouse_id: *warehouse_id,
+                    name: ident.name,
+                    namespace: ident.namespace.to_url_string(),
+                    prefix: prefix.clone().map(Prefix::into_string).unwrap_or_default(),
+                    num_events,
+                    sequence_number,
+                    trace_id: request_metadata.request_id,
+                },
+            )
+            .await;
+    }
 
     Ok(())
 }
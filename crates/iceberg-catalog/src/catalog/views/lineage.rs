@@ -0,0 +1,218 @@
+use crate::service::{Catalog, ListFlags, Result, TabularIdentUuid, Transaction, ViewIdentUuid};
+use crate::WarehouseIdent;
+use iceberg::spec::{ViewMetadata, ViewRepresentation};
+use iceberg::{NamespaceIdent, TableIdent};
+use sqlparser::ast::{ObjectName, Query, Visit, Visitor};
+use sqlparser::dialect::{dialect_from_str, GenericDialect};
+use sqlparser::parser::{Parser, ParserError};
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+/// Replace the stored dependencies of a view with the tables and views referenced
+/// by the SQL representations of its current version.
+/// References that cannot be resolved in the warehouse are ignored.
+pub(crate) async fn update_view_dependencies<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    view_id: ViewIdentUuid,
+    metadata: &ViewMetadata,
+    transaction: &mut C::Transaction,
+) -> Result<()> {
+    let version = metadata.current_version();
+    let default_catalog = version.default_catalog().map(String::as_str);
+    let default_namespace = version.default_namespace();
+
+    let mut references = Vec::new();
+    for representation in version.representations().iter() {
+        let ViewRepresentation::Sql(representation) = representation;
+        match referenced_tabulars(
+            &representation.sql,
+            &representation.dialect,
+            default_catalog,
+            default_namespace,
+        ) {
+            Ok(idents) => references.extend(idents),
+            Err(e) => tracing::debug!(
+                "Could not parse '{}' representation of view '{}' for lineage: {}",
+                representation.dialect,
+                view_id,
+                e
+            ),
+        }
+    }
+
+    let mut dependencies = Vec::new();
+    for ident in references {
+        let dependency = if let Some(table_id) = C::table_to_id(
+            warehouse_id,
+            &ident,
+            ListFlags::default(),
+            transaction.transaction(),
+        )
+        .await?
+        {
+            TabularIdentUuid::from(table_id)
+        } else if let Some(dependency_id) =
+            C::view_to_id(warehouse_id, &ident, transaction.transaction()).await?
+        {
+            if dependency_id == view_id {
+                continue;
+            }
+            TabularIdentUuid::from(dependency_id)
+        } else {
+            continue;
+        };
+        if !dependencies.contains(&dependency) {
+            dependencies.push(dependency);
+        }
+    }
+
+    C::set_view_dependencies(view_id, &dependencies, transaction.transaction()).await
+}
+
+/// Identifiers of all relations referenced by `sql`, qualified with the default
+/// namespace of the view. Names of common table expressions are not returned.
+fn referenced_tabulars(
+    sql: &str,
+    dialect: &str,
+    default_catalog: Option<&str>,
+    default_namespace: &NamespaceIdent,
+) -> std::result::Result<Vec<TableIdent>, ParserError> {
+    let dialect = dialect_from_str(dialect).unwrap_or_else(|| Box::new(GenericDialect {}));
+    let statements = Parser::parse_sql(dialect.as_ref(), sql)?;
+
+    let mut collector = RelationCollector::default();
+    for statement in &statements {
+        let _ = statement.visit(&mut collector);
+    }
+
+    let mut idents = Vec::new();
+    for relation in collector.relations {
+        let parts = relation
+            .0
+            .into_iter()
+            .map(|ident| ident.value)
+            .collect::<Vec<_>>();
+        if let [name] = parts.as_slice() {
+            if collector.ctes.contains(name) {
+                continue;
+            }
+        }
+        if let Some(ident) = resolve_reference(parts, default_catalog, default_namespace) {
+            if !idents.contains(&ident) {
+                idents.push(ident);
+            }
+        }
+    }
+    Ok(idents)
+}
+
+fn resolve_reference(
+    mut parts: Vec<String>,
+    default_catalog: Option<&str>,
+    default_namespace: &NamespaceIdent,
+) -> Option<TableIdent> {
+    // `catalog.namespace.name` - only references to the view's own catalog can be resolved.
+    if parts.len() > 2
+        && default_catalog.is_some_and(|catalog| parts[0].eq_ignore_ascii_case(catalog))
+    {
+        parts.remove(0);
+    }
+
+    let name = parts.pop()?;
+    let namespace = if parts.is_empty() {
+        default_namespace.clone()
+    } else {
+        NamespaceIdent::from_vec(parts).ok()?
+    };
+    Some(TableIdent::new(namespace, name))
+}
+
+#[derive(Debug, Default)]
+struct RelationCollector {
+    ctes: HashSet<String>,
+    relations: Vec<ObjectName>,
+}
+
+impl Visitor for RelationCollector {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                self.ctes.insert(cte.alias.name.value.clone());
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<Self::Break> {
+        self.relations.push(relation.clone());
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ident(namespace: &[&str], name: &str) -> TableIdent {
+        TableIdent::new(
+            NamespaceIdent::from_strs(namespace).unwrap(),
+            name.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_referenced_tabulars() {
+        let default_namespace = NamespaceIdent::new("default_ns".to_string());
+        let idents = referenced_tabulars(
+            "SELECT a.id, b.value FROM orders a JOIN sales.customers b ON a.id = b.id \
+             WHERE a.id IN (SELECT id FROM my_catalog.sales.returns)",
+            "spark",
+            Some("my_catalog"),
+            &default_namespace,
+        )
+        .unwrap();
+        assert_eq!(
+            idents,
+            vec![
+                ident(&["default_ns"], "orders"),
+                ident(&["sales"], "customers"),
+                ident(&["sales"], "returns"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_referenced_tabulars_skips_ctes() {
+        let default_namespace = NamespaceIdent::new("default_ns".to_string());
+        let idents = referenced_tabulars(
+            "WITH recent AS (SELECT * FROM events) SELECT * FROM recent",
+            "trino",
+            None,
+            &default_namespace,
+        )
+        .unwrap();
+        assert_eq!(idents, vec![ident(&["default_ns"], "events")]);
+    }
+
+    #[test]
+    fn test_referenced_tabulars_other_catalog() {
+        let default_namespace = NamespaceIdent::new("default_ns".to_string());
+        let idents = referenced_tabulars(
+            "SELECT * FROM other_catalog.ns.tbl",
+            "unknown-dialect",
+            Some("my_catalog"),
+            &default_namespace,
+        )
+        .unwrap();
+        // Resolved as the nested namespace `other_catalog.ns`
+        assert_eq!(idents, vec![ident(&["other_catalog", "ns"], "tbl")]);
+    }
+
+    #[test]
+    fn test_referenced_tabulars_invalid_sql() {
+        let default_namespace = NamespaceIdent::new("default_ns".to_string());
+        assert!(referenced_tabulars("SELECT * FROM (", "spark", None, &default_namespace).is_err());
+    }
+}
@@ -64,6 +64,9 @@ pub(crate) async fn load_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         storage_secret_id,
         status,
         tabular_delete_profile: _,
+        properties: _,
+        table_property_policy: _,
+        location_template: _,
     } = C::require_warehouse(warehouse_id, t.transaction()).await?;
     require_active_warehouse(status)?;
 
@@ -0,0 +1,861 @@
+use crate::api::iceberg::types::Prefix;
+use crate::api::iceberg::v1::{DataAccess, NamespaceParameters, ViewParameters};
+use crate::api::{
+    ApiContext, CommitMaterializedViewRequest, CreateMaterializedViewRequest,
+    LoadMaterializedViewResult, LoadViewResult, MaterializedViewFreshness, SourceTableSnapshot,
+};
+use crate::catalog::tables::{
+    commit_tables_in_transaction, create_table_in_transaction, determine_table_ident,
+    maybe_body_to_json, require_active_warehouse, validate_table_or_view_ident,
+    validate_table_properties, CreatedTable,
+};
+use crate::catalog::views::commit::{commit_view_in_transaction, CommittedView};
+use crate::catalog::views::create::{create_view_in_transaction, CreatedView};
+use crate::catalog::views::{load, validate_view_properties};
+use crate::catalog::{maybe_get_secret, require_warehouse_id};
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{
+    Authorizer, CatalogNamespaceAction, CatalogTableAction, CatalogViewAction,
+    CatalogWarehouseAction,
+};
+use crate::service::event_publisher::EventMetadata;
+use crate::service::storage::StoragePermissions;
+use crate::service::{
+    Catalog, ListFlags, MaterializedView, MaterializedViewSource, Result, SecretStore, State,
+    TableIdentUuid, TabularIdentUuid, Transaction, ViewIdentUuid, WarehouseIdent,
+};
+use iceberg::TableIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Create a materialized view and its storage table in one transaction.
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn create_materialized_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    parameters: NamespaceParameters,
+    request: CreateMaterializedViewRequest,
+    state: ApiContext<State<A, C, S>>,
+    data_access: DataAccess,
+    request_metadata: RequestMetadata,
+) -> Result<LoadMaterializedViewResult> {
+    // ------------------- VALIDATIONS -------------------
+    let NamespaceParameters { namespace, prefix } = parameters;
+    let warehouse_id = require_warehouse_id(prefix.clone())?;
+    let CreateMaterializedViewRequest {
+        view: view_request,
+        storage_table: table_request,
+        refresh_state,
+    } = request;
+    let view = TableIdent::new(namespace.clone(), view_request.name.clone());
+    let storage_table = TableIdent::new(namespace.clone(), table_request.name.clone());
+
+    validate_table_or_view_ident(&view)?;
+    validate_table_or_view_ident(&storage_table)?;
+    validate_view_properties(view_request.properties.keys())?;
+    if let Some(properties) = &table_request.properties {
+        validate_table_properties(properties.keys())?;
+    }
+    validate_refresh_state(&refresh_state)?;
+
+    if view_request.view_version.representations().is_empty() {
+        return Err(ErrorModel::bad_request(
+            "View must have at least one representation.",
+            "EmptyView",
+            None,
+        )
+        .into());
+    }
+    if table_request.stage_create.unwrap_or(false) {
+        return Err(ErrorModel::bad_request(
+            "The storage table of a materialized view cannot be staged.",
+            "StagedStorageTableNotSupported",
+            None,
+        )
+        .into());
+    }
+
+    // ------------------- AUTHZ -------------------
+    let authorizer = &state.v1_state.authz;
+    authorizer
+        .require_warehouse_action(
+            &request_metadata,
+            warehouse_id,
+            &CatalogWarehouseAction::CanUse,
+        )
+        .await?;
+    let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
+    let namespace_id = C::namespace_to_id(warehouse_id, &namespace, t.transaction()).await; // Cannot fail before authz;
+    let namespace_id = authorizer
+        .require_namespace_action(
+            &request_metadata,
+            warehouse_id,
+            namespace_id,
+            &CatalogNamespaceAction::CanCreateView,
+        )
+        .await?;
+    authorizer
+        .require_namespace_action(
+            &request_metadata,
+            warehouse_id,
+            Ok(Some(namespace_id)),
+            &CatalogNamespaceAction::CanCreateTable,
+        )
+        .await?;
+    let sources = require_sources::<C, A>(
+        warehouse_id,
+        &refresh_state,
+        authorizer,
+        &request_metadata,
+        &mut t,
+    )
+    .await?;
+
+    // ------------------- BUSINESS LOGIC -------------------
+    let namespace = C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
+    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+    let storage_profile = &warehouse.storage_profile;
+    require_active_warehouse(warehouse.status)?;
+
+    // We don't commit the transaction yet, first we need to write the metadata files.
+    let storage_secret =
+        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+    let file_io = storage_profile.file_io(storage_secret.as_ref())?;
+
+    let CreatedView {
+        view_id,
+        metadata,
+        metadata_location,
+        view_location,
+        body: view_body,
+    } = create_view_in_transaction::<C>(
+        &warehouse,
+        &namespace,
+        &view,
+        view_request,
+        &file_io,
+        &mut t,
+    )
+    .await?;
+    let CreatedTable {
+        table_id,
+        staged_table_id,
+        body: table_body,
+        ..
+    } = create_table_in_transaction::<C>(
+        &warehouse,
+        &namespace,
+        &storage_table,
+        table_request,
+        &file_io,
+        &mut t,
+    )
+    .await?;
+
+    C::set_materialized_view(view_id, table_id, &sources, t.transaction()).await?;
+    let materialized_view = require_materialized_view::<C>(view_id, &view, &mut t).await?;
+
+    let config = storage_profile
+        .generate_table_config(
+            &data_access,
+            storage_secret.as_ref(),
+            &view_location,
+            StoragePermissions::Read,
+        )
+        .await?;
+
+    authorizer
+        .create_view(&request_metadata, view_id, namespace_id)
+        .await?;
+    authorizer
+        .create_table(&request_metadata, table_id, namespace_id)
+        .await?;
+
+    t.commit().await?;
+
+    if let Some(staged_table_id) = staged_table_id {
+        authorizer.delete_table(staged_table_id).await.ok();
+    }
+
+    let prefix = prefix.map(Prefix::into_string).unwrap_or_default();
+    for (sequence_number, (operation_id, body, tabular_id, name)) in [
+        (
+            "createView",
+            view_body,
+            TabularIdentUuid::View(*view_id),
+            view.name.clone(),
+        ),
+        (
+            "createTable",
+            table_body,
+            TabularIdentUuid::Table(*table_id),
+            storage_table.name.clone(),
+        ),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let _ = state
+            .v1_state
+            .publisher
+            .publish(
+                Uuid::now_v7(),
+                operation_id,
+                body,
+                EventMetadata {
+                    tabular_id,
+                    warehouse_id: *warehouse_id,
+                    name,
+                    namespace: view.namespace.to_url_string(),
+                    prefix: prefix.clone(),
+                    num_events: 2,
+                    sequence_number,
+                    trace_id: request_metadata.request_id,
+                },
+            )
+            .await;
+    }
+
+    Ok(materialized_view_result(
+        LoadViewResult {
+            metadata_location: metadata_location.to_string(),
+            metadata,
+            config: Some(config.into()),
+        },
+        storage_table,
+        &materialized_view,
+    ))
+}
+
+/// Load a materialized view together with its storage table and freshness.
+pub(crate) async fn load_materialized_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    parameters: ViewParameters,
+    state: ApiContext<State<A, C, S>>,
+    data_access: DataAccess,
+    request_metadata: RequestMetadata,
+) -> Result<LoadMaterializedViewResult> {
+    let warehouse_id = require_warehouse_id(parameters.prefix.clone())?;
+    let view = parameters.view.clone();
+
+    // Validations and authz of the view are performed by `load_view`.
+    let load_view_result = load::load_view(
+        parameters,
+        state.clone(),
+        data_access,
+        request_metadata.clone(),
+    )
+    .await?;
+
+    let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
+    let view_id = C::view_to_id(warehouse_id, &view, t.transaction())
+        .await?
+        .ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("View '{}' does not exist.", ident_string(&view)),
+                "ViewNotFound",
+                None,
+            )
+        })?;
+    let mut materialized_view = require_materialized_view::<C>(view_id, &view, &mut t).await?;
+    t.commit().await?;
+
+    let authorizer = &state.v1_state.authz;
+    authorizer
+        .require_table_action(
+            &request_metadata,
+            warehouse_id,
+            Ok(Some(materialized_view.storage_table_id)),
+            &CatalogTableAction::CanGetMetadata,
+        )
+        .await?;
+    // Sources the caller may not see are not listed, but still count for the freshness.
+    let source_ids = materialized_view
+        .sources
+        .iter()
+        .map(|state| state.source.table_id)
+        .collect::<Vec<_>>();
+    let mask = authorizer
+        .are_allowed_table_actions(
+            &request_metadata,
+            warehouse_id,
+            &source_ids,
+            &CatalogTableAction::CanGetMetadata,
+        )
+        .await?;
+    let freshness = freshness(&materialized_view);
+    let mut mask = mask.into_iter();
+    materialized_view
+        .sources
+        .retain(|_| mask.next().unwrap_or(false));
+
+    let storage_table = C::get_table_metadata_by_id(
+        warehouse_id,
+        materialized_view.storage_table_id,
+        ListFlags::all(),
+        state.v1_state.catalog,
+    )
+    .await?
+    .ok_or_else(|| {
+        ErrorModel::internal(
+            format!(
+                "Storage table of materialized view '{}' not found",
+                ident_string(&view)
+            ),
+            "StorageTableNotFound",
+            None,
+        )
+    })?
+    .table;
+
+    Ok(LoadMaterializedViewResult {
+        freshness,
+        ..materialized_view_result(load_view_result, storage_table, &materialized_view)
+    })
+}
+
+/// Commit updates to a materialized view and its storage table in one transaction.
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn commit_materialized_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    parameters: ViewParameters,
+    request: CommitMaterializedViewRequest,
+    state: ApiContext<State<A, C, S>>,
+    data_access: DataAccess,
+    request_metadata: RequestMetadata,
+) -> Result<LoadMaterializedViewResult> {
+    // ------------------- VALIDATIONS -------------------
+    let ViewParameters { prefix, view } = parameters;
+    let warehouse_id = require_warehouse_id(prefix.clone())?;
+    let CommitMaterializedViewRequest {
+        view: view_request,
+        storage_table: mut table_request,
+        refresh_state,
+    } = request;
+
+    let view = determine_table_ident(view, view_request.identifier.as_ref())?;
+    validate_table_or_view_ident(&view)?;
+    let storage_table = table_request.identifier.clone().ok_or_else(|| {
+        ErrorModel::bad_request(
+            "Identifier of the storage table is required",
+            "TableIdentifierRequired",
+            None,
+        )
+    })?;
+    validate_table_or_view_ident(&storage_table)?;
+    if let Some(refresh_state) = &refresh_state {
+        validate_refresh_state(refresh_state)?;
+    }
+
+    // ------------------- AUTHZ -------------------
+    let authorizer = &state.v1_state.authz;
+    authorizer
+        .require_warehouse_action(
+            &request_metadata,
+            warehouse_id,
+            &CatalogWarehouseAction::CanUse,
+        )
+        .await?;
+    let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
+    let view_id = C::view_to_id(warehouse_id, &view, t.transaction()).await; // We can't fail before AuthZ;
+    let view_id = authorizer
+        .require_view_action(
+            &request_metadata,
+            warehouse_id,
+            view_id,
+            &CatalogViewAction::CanCommit,
+        )
+        .await?;
+    let table_id = C::table_to_id(
+        warehouse_id,
+        &storage_table,
+        ListFlags::default(),
+        t.transaction(),
+    )
+    .await; // We can't fail before AuthZ
+    let table_id = authorizer
+        .require_table_action(
+            &request_metadata,
+            warehouse_id,
+            table_id,
+            &CatalogTableAction::CanCommit,
+        )
+        .await?;
+    let sources = match &refresh_state {
+        Some(refresh_state) => Some(
+            require_sources::<C, A>(
+                warehouse_id,
+                refresh_state,
+                authorizer,
+                &request_metadata,
+                &mut t,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    // ------------------- BUSINESS LOGIC -------------------
+    let linked_table_id = require_materialized_view::<C>(view_id, &view, &mut t)
+        .await?
+        .storage_table_id;
+    if linked_table_id != table_id {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Table '{}' is not the storage table of materialized view '{}'",
+                ident_string(&storage_table),
+                ident_string(&view)
+            ),
+            "StorageTableMismatch",
+            None,
+        )
+        .into());
+    }
+
+    let namespace_id = C::namespace_to_id(warehouse_id, view.namespace(), t.transaction())
+        .await?
+        .ok_or(ErrorModel::not_found(
+            "Namespace does not exist",
+            "NamespaceNotFound",
+            None,
+        ))?;
+    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+    require_active_warehouse(warehouse.status)?;
+
+    // We don't commit the transaction yet, first we need to write the metadata files.
+    let storage_secret =
+        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+    let file_io = warehouse.storage_profile.file_io(storage_secret.as_ref())?;
+
+    let CommittedView {
+        metadata,
+        metadata_location,
+        body: view_body,
+    } = commit_view_in_transaction::<C>(
+        &warehouse,
+        namespace_id,
+        view_id,
+        &view,
+        view_request,
+        &state.v1_state.contract_verifiers,
+        &file_io,
+        &mut t,
+    )
+    .await?;
+
+    // The identifier is already resolved, make sure the request uses the same one.
+    table_request.identifier = Some(storage_table.clone());
+    let table_body = maybe_body_to_json(&table_request);
+    let (_, expired_metadata_logs) = commit_tables_in_transaction::<C, A>(
+        &warehouse,
+        vec![table_request],
+        &HashMap::from([(storage_table.clone(), table_id)]),
+        authorizer,
+        &state.v1_state.contract_verifiers,
+        &request_metadata,
+        &file_io,
+        &mut t,
+    )
+    .await?;
+
+    if let Some(sources) = sources {
+        C::set_materialized_view(view_id, table_id, &sources, t.transaction()).await?;
+    }
+    let materialized_view = require_materialized_view::<C>(view_id, &view, &mut t).await?;
+
+    let config = warehouse
+        .storage_profile
+        .generate_table_config(
+            &data_access,
+            storage_secret.as_ref(),
+            &metadata_location,
+            // TODO: This should be a permission based on authz
+            StoragePermissions::ReadWriteDelete,
+        )
+        .await?;
+
+    t.commit().await?;
+
+    // Delete files in parallel - if one delete fails, we still want to delete the rest
+    let deletions = futures::future::join_all(
+        expired_metadata_logs
+            .into_iter()
+            .map(|expired_metadata_log| file_io.delete(expired_metadata_log.metadata_file)),
+    )
+    .await;
+    for e in deletions.into_iter().filter_map(std::result::Result::err) {
+        tracing::warn!("Failed to delete metadata file: {:?}", e);
+    }
+
+    let prefix = prefix.map(Prefix::into_string).unwrap_or_default();
+    for (sequence_number, (operation_id, body, tabular_id, name)) in [
+        (
+            "commitView",
+            view_body,
+            TabularIdentUuid::View(*view_id),
+            view.name.clone(),
+        ),
+        (
+            "updateTable",
+            table_body,
+            TabularIdentUuid::Table(*table_id),
+            storage_table.name.clone(),
+        ),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let _ = state
+            .v1_state
+            .publisher
+            .publish(
+                Uuid::now_v7(),
+                operation_id,
+                body,
+                EventMetadata {
+                    tabular_id,
+                    warehouse_id: *warehouse_id,
+                    name,
+                    namespace: view.namespace.to_url_string(),
+                    prefix: prefix.clone(),
+                    num_events: 2,
+                    sequence_number,
+                    trace_id: request_metadata.request_id,
+                },
+            )
+            .await;
+    }
+
+    Ok(materialized_view_result(
+        LoadViewResult {
+            metadata_location: metadata_location.to_string(),
+            metadata,
+            config: Some(config.into()),
+        },
+        storage_table,
+        &materialized_view,
+    ))
+}
+
+fn validate_refresh_state(refresh_state: &[SourceTableSnapshot]) -> Result<()> {
+    let mut seen = HashSet::new();
+    for source in refresh_state {
+        validate_table_or_view_ident(&source.table)?;
+        if !seen.insert(&source.table) {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Source table '{}' is listed more than once",
+                    ident_string(&source.table)
+                ),
+                "DuplicateSourceTable",
+                None,
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Resolve the source tables of a refresh state.
+/// Requires permission to read the metadata of every source table.
+async fn require_sources<C: Catalog, A: Authorizer>(
+    warehouse_id: WarehouseIdent,
+    refresh_state: &[SourceTableSnapshot],
+    authorizer: &A,
+    request_metadata: &RequestMetadata,
+    t: &mut C::Transaction,
+) -> Result<Vec<MaterializedViewSource>> {
+    let mut sources = Vec::with_capacity(refresh_state.len());
+    for source in refresh_state {
+        let table_id = C::table_to_id(
+            warehouse_id,
+            &source.table,
+            ListFlags::default(),
+            t.transaction(),
+        )
+        .await; // We can't fail before AuthZ
+        let table_id: TableIdentUuid = authorizer
+            .require_table_action(
+                request_metadata,
+                warehouse_id,
+                table_id,
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+        sources.push(MaterializedViewSource {
+            table_id,
+            table: source.table.clone(),
+            snapshot_id: source.snapshot_id,
+        });
+    }
+    Ok(sources)
+}
+
+async fn require_materialized_view<C: Catalog>(
+    view_id: ViewIdentUuid,
+    view: &TableIdent,
+    t: &mut C::Transaction,
+) -> Result<MaterializedView> {
+    C::get_materialized_view(view_id, t.transaction())
+        .await?
+        .ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("View '{}' is not a materialized view", ident_string(view)),
+                "MaterializedViewNotFound",
+                None,
+            )
+            .into()
+        })
+}
+
+fn ident_string(ident: &TableIdent) -> String {
+    format!("{}.{}", ident.namespace.to_url_string(), ident.name)
+}
+
+fn materialized_view_result(
+    view: LoadViewResult,
+    storage_table: TableIdent,
+    materialized_view: &MaterializedView,
+) -> LoadMaterializedViewResult {
+    let stale_sources = materialized_view
+        .stale_sources()
+        .map(|source| SourceTableSnapshot {
+            table: source.table.clone(),
+            snapshot_id: source.snapshot_id,
+        })
+        .collect::<Vec<_>>();
+
+    LoadMaterializedViewResult {
+        view,
+        storage_table,
+        freshness: freshness(materialized_view),
+        stale_sources,
+    }
+}
+
+fn freshness(materialized_view: &MaterializedView) -> MaterializedViewFreshness {
+    if materialized_view.stale_sources().next().is_none() {
+        MaterializedViewFreshness::Fresh
+    } else {
+        MaterializedViewFreshness::Stale
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::api::iceberg::types::DropParams;
+    use crate::api::iceberg::v1::tables::Service as _;
+    use crate::api::iceberg::v1::TableParameters;
+    use crate::api::{CommitTableRequest, CommitViewRequest};
+    use crate::catalog::tables::test::create_request;
+    use crate::catalog::views::create::test::create_view_request;
+    use crate::catalog::views::test::setup;
+    use crate::catalog::CatalogServer;
+    use crate::implementations::postgres::{PostgresCatalog, SecretsState};
+    use crate::service::authz::AllowAllAuthorizer;
+    use iceberg::{NamespaceIdent, TableUpdate};
+    use sqlx::PgPool;
+
+    type TestServer = CatalogServer<PostgresCatalog, AllowAllAuthorizer, SecretsState>;
+
+    fn data_access() -> DataAccess {
+        DataAccess {
+            vended_credentials: true,
+            remote_signing: false,
+        }
+    }
+
+    fn commit_request(
+        namespace: &NamespaceIdent,
+        storage_table: &str,
+        snapshot_id: Option<i64>,
+    ) -> CommitMaterializedViewRequest {
+        CommitMaterializedViewRequest {
+            view: CommitViewRequest {
+                identifier: None,
+                requirements: None,
+                updates: vec![],
+            },
+            storage_table: CommitTableRequest {
+                identifier: Some(TableIdent::new(
+                    namespace.clone(),
+                    storage_table.to_string(),
+                )),
+                requirements: vec![],
+                updates: vec![TableUpdate::SetProperties {
+                    updates: HashMap::from([("refreshed".to_string(), "true".to_string())]),
+                }],
+            },
+            refresh_state: Some(vec![SourceTableSnapshot {
+                table: TableIdent::new(namespace.clone(), "source".to_string()),
+                snapshot_id,
+            }]),
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_materialized_view_lifecycle(pool: PgPool) {
+        let (api_context, namespace, whi) = setup(pool, None).await;
+        let prefix = Some(Prefix(whi.to_string()));
+        let namespace_parameters = NamespaceParameters {
+            namespace: namespace.clone(),
+            prefix: prefix.clone(),
+        };
+        let view_parameters = ViewParameters {
+            prefix: prefix.clone(),
+            view: TableIdent::new(namespace.clone(), "mv".to_string()),
+        };
+        TestServer::create_table(
+            namespace_parameters.clone(),
+            create_request(Some("source".to_string())),
+            data_access(),
+            api_context.clone(),
+            RequestMetadata::new_random(),
+        )
+        .await
+        .unwrap();
+
+        let created = create_materialized_view(
+            namespace_parameters,
+            CreateMaterializedViewRequest {
+                view: create_view_request(Some("mv"), None),
+                storage_table: create_request(Some("mv_storage".to_string())),
+                refresh_state: vec![SourceTableSnapshot {
+                    table: TableIdent::new(namespace.clone(), "source".to_string()),
+                    snapshot_id: None,
+                }],
+            },
+            api_context.clone(),
+            data_access(),
+            RequestMetadata::new_random(),
+        )
+        .await
+        .unwrap();
+        let storage_table = TableIdent::new(namespace.clone(), "mv_storage".to_string());
+        assert_eq!(created.storage_table, storage_table);
+        // The source table has no snapshot yet
+        assert_eq!(created.freshness, MaterializedViewFreshness::Fresh);
+
+        let loaded = load_materialized_view(
+            view_parameters.clone(),
+            api_context.clone(),
+            data_access(),
+            RequestMetadata::new_random(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(loaded.storage_table, storage_table);
+        assert_eq!(
+            loaded.view.metadata.view_uuid,
+            created.view.metadata.view_uuid
+        );
+        assert_eq!(loaded.freshness, MaterializedViewFreshness::Fresh);
+
+        // The storage table cannot be dropped on its own
+        let err = TestServer::drop_table(
+            TableParameters {
+                prefix: prefix.clone(),
+                table: storage_table.clone(),
+            },
+            DropParams {
+                purge_requested: None,
+            },
+            api_context.clone(),
+            RequestMetadata::new_random(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, 409);
+        assert_eq!(err.error.r#type, "StorageTableOfMaterializedView");
+
+        // Only the linked storage table can be committed with the view
+        let err = commit_materialized_view(
+            view_parameters.clone(),
+            commit_request(&namespace, "source", None),
+            api_context.clone(),
+            data_access(),
+            RequestMetadata::new_random(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "StorageTableMismatch");
+
+        // Snapshot 1 of the source table does not exist, so the view is stale
+        let committed = commit_materialized_view(
+            view_parameters.clone(),
+            commit_request(&namespace, "mv_storage", Some(1)),
+            api_context.clone(),
+            data_access(),
+            RequestMetadata::new_random(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(committed.freshness, MaterializedViewFreshness::Stale);
+        assert_eq!(
+            committed.stale_sources,
+            vec![SourceTableSnapshot {
+                table: TableIdent::new(namespace.clone(), "source".to_string()),
+                snapshot_id: Some(1),
+            }]
+        );
+
+        let loaded = load_materialized_view(
+            view_parameters,
+            api_context,
+            data_access(),
+            RequestMetadata::new_random(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(loaded.storage_table, storage_table);
+        assert_eq!(loaded.freshness, MaterializedViewFreshness::Stale);
+    }
+
+    #[sqlx::test]
+    async fn test_drop_materialized_view_drops_storage_table(pool: PgPool) {
+        let (api_context, namespace, whi) = setup(pool, None).await;
+        let prefix = Some(Prefix(whi.to_string()));
+        let created = create_materialized_view(
+            NamespaceParameters {
+                namespace: namespace.clone(),
+                prefix: prefix.clone(),
+            },
+            CreateMaterializedViewRequest {
+                view: create_view_request(Some("mv"), None),
+                storage_table: create_request(Some("mv_storage".to_string())),
+                refresh_state: vec![],
+            },
+            api_context.clone(),
+            data_access(),
+            RequestMetadata::new_random(),
+        )
+        .await
+        .unwrap();
+
+        crate::catalog::views::drop::drop_view(
+            ViewParameters {
+                prefix: prefix.clone(),
+                view: TableIdent::new(namespace.clone(), "mv".to_string()),
+            },
+            DropParams {
+                purge_requested: None,
+            },
+            api_context.clone(),
+            RequestMetadata::new_random(),
+        )
+        .await
+        .unwrap();
+
+        let err = TestServer::load_table(
+            TableParameters {
+                prefix,
+                table: created.storage_table,
+            },
+            data_access(),
+            api_context.clone(),
+            RequestMetadata::new_random(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, 404);
+    }
+}
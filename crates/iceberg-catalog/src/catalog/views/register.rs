@@ -0,0 +1,357 @@
+use crate::api::iceberg::types::Prefix;
+use crate::api::iceberg::v1::{DataAccess, NamespaceParameters};
+use crate::api::ApiContext;
+use crate::catalog::io::read_metadata_file;
+use crate::catalog::tables::{
+    maybe_body_to_json, parse_location, require_active_warehouse, validate_table_or_view_ident,
+};
+use crate::catalog::views::lineage::update_view_dependencies;
+use crate::catalog::views::validate_view_properties;
+use crate::catalog::{maybe_get_secret, require_warehouse_id};
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{Authorizer, CatalogNamespaceAction, CatalogWarehouseAction};
+use crate::service::event_publisher::EventMetadata;
+use crate::service::storage::StoragePermissions;
+use crate::service::TabularIdentUuid;
+use crate::service::{Catalog, SecretStore, State, Transaction};
+use crate::service::{Result, ViewIdentUuid};
+use http::StatusCode;
+use iceberg::spec::ViewMetadata;
+use iceberg::TableIdent;
+use iceberg_ext::catalog::rest::{ErrorModel, LoadViewResult, RegisterViewRequest};
+use uuid::Uuid;
+
+/// Register an existing view in the given namespace.
+///
+/// The view keeps the UUID, versions and version log of its metadata file.
+/// No new metadata file is written.
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn register_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    parameters: NamespaceParameters,
+    request: RegisterViewRequest,
+    state: ApiContext<State<A, C, S>>,
+    data_access: DataAccess,
+    request_metadata: RequestMetadata,
+) -> Result<LoadViewResult> {
+    // ------------------- VALIDATIONS -------------------
+    let NamespaceParameters { namespace, prefix } = parameters;
+    let warehouse_id = require_warehouse_id(prefix.clone())?;
+    let view = TableIdent::new(namespace.clone(), request.name.clone());
+
+    validate_table_or_view_ident(&view)?;
+    let metadata_location = parse_location(&request.metadata_location, StatusCode::BAD_REQUEST)?;
+
+    // ------------------- AUTHZ -------------------
+    let authorizer = &state.v1_state.authz;
+    authorizer
+        .require_warehouse_action(
+            &request_metadata,
+            warehouse_id,
+            &CatalogWarehouseAction::CanUse,
+        )
+        .await?;
+    let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
+    let namespace_id = C::namespace_to_id(warehouse_id, &namespace, t.transaction()).await; // Cannot fail before authz;
+    let namespace_id = authorizer
+        .require_namespace_action(
+            &request_metadata,
+            warehouse_id,
+            namespace_id,
+            &CatalogNamespaceAction::CanCreateView,
+        )
+        .await?;
+
+    // ------------------- BUSINESS LOGIC -------------------
+    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+    let storage_profile = warehouse.storage_profile;
+    require_active_warehouse(warehouse.status)?;
+
+    if !storage_profile.is_allowed_location(&metadata_location) {
+        return Err(ErrorModel::bad_request(
+            format!("Metadata location {metadata_location} is not within the storage profile"),
+            "InvalidMetadataLocation",
+            None,
+        )
+        .into());
+    }
+
+    let storage_secret =
+        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+    let file_io = storage_profile.file_io(storage_secret.as_ref())?;
+    let metadata: ViewMetadata = read_metadata_file(&file_io, &metadata_location).await?;
+
+    let mut view_location = parse_location(&metadata.location, StatusCode::BAD_REQUEST)?;
+    if !storage_profile.is_allowed_location(&view_location) {
+        return Err(ErrorModel::bad_request(
+            format!("Location of the registered view is not allowed: {view_location}"),
+            "InvalidTableLocation",
+            None,
+        )
+        .into());
+    }
+    // all locations are without a trailing slash
+    view_location.without_trailing_slash();
+    let mut metadata = metadata;
+    metadata.location = view_location.to_string();
+    let metadata = metadata; // make it immutable
+
+    validate_view_properties(metadata.properties.keys())?;
+    let policy = &warehouse.table_property_policy;
+    policy.require_compliance(metadata.properties(), Some(&policy.view_keys()))?;
+    if metadata.current_version().representations().is_empty() {
+        return Err(ErrorModel::bad_request(
+            "View must have at least one representation.",
+            "EmptyView",
+            None,
+        )
+        .into());
+    }
+
+    let view_id = ViewIdentUuid::from(metadata.view_uuid);
+    // Schemas, versions, representations and the version log are stored with the view.
+    // The tabular id is taken from the metadata, so registering the same view twice conflicts.
+    C::create_view(
+        namespace_id,
+        &view,
+        metadata.clone(),
+        &metadata_location,
+        &view_location,
+        t.transaction(),
+    )
+    .await?;
+    update_view_dependencies::<C>(warehouse_id, view_id, &metadata, &mut t).await?;
+
+    let config = storage_profile
+        .generate_table_config(
+            &data_access,
+            storage_secret.as_ref(),
+            &view_location,
+            StoragePermissions::Read,
+        )
+        .await?;
+
+    authorizer
+        .create_view(&request_metadata, view_id, namespace_id)
+        .await?;
+
+    t.commit().await?;
+
+    let _ = state
+        .v1_state
+        .publisher
+        .publish(
+            Uuid::now_v7(),
+            "registerView",
+            maybe_body_to_json(&request),
+            EventMetadata {
+                tabular_id: TabularIdentUuid::View(*view_id),
+                warehouse_id: *warehouse_id.as_uuid(),
+                name: view.name,
+                namespace: view.namespace.to_url_string(),
+                prefix: prefix.map(Prefix::into_string).unwrap_or_default(),
+                num_events: 1,
+                sequence_number: 0,
+                trace_id: request_metadata.request_id,
+            },
+        )
+        .await;
+
+    Ok(LoadViewResult {
+        metadata_location: metadata_location.to_string(),
+        metadata,
+        config: Some(config.into()),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::api::iceberg::v1::ViewParameters;
+    use crate::catalog::compression_codec::CompressionCodec;
+    use crate::catalog::io::write_metadata_file;
+    use crate::catalog::views::create::test::{
+        create_view, create_view_request, set_table_property_policy,
+    };
+    use crate::catalog::views::load::test::load_view;
+    use crate::catalog::views::test::setup;
+    use crate::implementations::postgres::{PostgresCatalog, SecretsState};
+    use crate::service::authz::AllowAllAuthorizer;
+    use crate::service::storage::{StorageProfile, TestProfile};
+    use iceberg::NamespaceIdent;
+    use sqlx::PgPool;
+
+    async fn register(
+        api_context: ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
+        prefix: &str,
+        namespace: &NamespaceIdent,
+        name: &str,
+        metadata_location: &str,
+    ) -> Result<LoadViewResult> {
+        super::register_view(
+            NamespaceParameters {
+                namespace: namespace.clone(),
+                prefix: Some(Prefix(prefix.to_string())),
+            },
+            RegisterViewRequest {
+                name: name.to_string(),
+                metadata_location: metadata_location.to_string(),
+            },
+            api_context,
+            DataAccess {
+                vended_credentials: true,
+                remote_signing: false,
+            },
+            RequestMetadata::new_random(),
+        )
+        .await
+    }
+
+    #[sqlx::test]
+    async fn test_register_view(pool: PgPool) {
+        let (api_context, namespace, whi) = setup(pool, None).await;
+        let prefix = whi.to_string();
+        let view = create_view(
+            api_context.clone(),
+            namespace.clone(),
+            create_view_request(Some("myview"), None),
+            Some(prefix.clone()),
+        )
+        .await
+        .unwrap();
+
+        // Copy the view to a new location with a new UUID, as if it came from another catalog.
+        let mut metadata = view.metadata.clone();
+        metadata.view_uuid = Uuid::now_v7();
+        let (parent, _) = metadata.location.rsplit_once('/').unwrap();
+        metadata.location = format!("{parent}/{}", metadata.view_uuid);
+        let metadata_location = format!(
+            "{}/metadata/00000-imported.metadata.json",
+            metadata.location
+        );
+        let file_io = StorageProfile::Test(TestProfile::default())
+            .file_io(None)
+            .unwrap();
+        write_metadata_file(
+            &parse_location(&metadata_location, StatusCode::BAD_REQUEST).unwrap(),
+            &metadata,
+            CompressionCodec::None,
+            &file_io,
+        )
+        .await
+        .unwrap();
+
+        let registered = register(
+            api_context.clone(),
+            &prefix,
+            &namespace,
+            "registered",
+            &metadata_location,
+        )
+        .await
+        .unwrap();
+        assert_eq!(registered.metadata_location, metadata_location);
+        assert_eq!(registered.metadata.view_uuid, metadata.view_uuid);
+
+        // Versions, schemas and the version log are read back from the catalog
+        let loaded = load_view(
+            api_context.clone(),
+            ViewParameters {
+                prefix: Some(Prefix(prefix.clone())),
+                view: TableIdent::new(namespace.clone(), "registered".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(loaded.metadata_location, metadata_location);
+        assert_eq!(loaded.metadata.view_uuid, metadata.view_uuid);
+        assert_eq!(
+            loaded.metadata.current_version_id,
+            metadata.current_version_id
+        );
+        assert_eq!(loaded.metadata.versions, metadata.versions);
+        assert_eq!(loaded.metadata.schemas, metadata.schemas);
+        assert_eq!(loaded.metadata.version_log, metadata.version_log);
+        assert_eq!(
+            loaded.metadata.current_version().representations(),
+            view.metadata.current_version().representations()
+        );
+
+        // The same view cannot be registered twice
+        let err = register(
+            api_context,
+            &prefix,
+            &namespace,
+            "registered-again",
+            &metadata_location,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, 409);
+    }
+
+    #[sqlx::test]
+    async fn test_register_view_violating_property_policy(pool: PgPool) {
+        let (api_context, namespace, whi) = setup(pool, None).await;
+        let prefix = whi.to_string();
+        let view = create_view(
+            api_context.clone(),
+            namespace.clone(),
+            create_view_request(Some("myview"), None),
+            Some(prefix.clone()),
+        )
+        .await
+        .unwrap();
+        set_table_property_policy(
+            &api_context,
+            whi,
+            serde_json::json!({"rules": [{"type": "required", "key": "owner"}]}),
+        )
+        .await;
+
+        let mut metadata = view.metadata;
+        metadata.view_uuid = Uuid::now_v7();
+        let metadata_location = format!(
+            "{}/metadata/00000-imported.metadata.json",
+            metadata.location
+        );
+        let file_io = StorageProfile::Test(TestProfile::default())
+            .file_io(None)
+            .unwrap();
+        write_metadata_file(
+            &parse_location(&metadata_location, StatusCode::BAD_REQUEST).unwrap(),
+            &metadata,
+            CompressionCodec::None,
+            &file_io,
+        )
+        .await
+        .unwrap();
+
+        let err = register(
+            api_context,
+            &prefix,
+            &namespace,
+            "registered",
+            &metadata_location,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "TablePropertyPolicyViolation");
+    }
+
+    #[sqlx::test]
+    async fn test_register_view_outside_storage_profile(pool: PgPool) {
+        let (api_context, namespace, whi) = setup(pool, None).await;
+        let err = register(
+            api_context,
+            &whi.to_string(),
+            &namespace,
+            "registered",
+            "s3://other-bucket/view/metadata/00000-imported.metadata.json",
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, 400);
+        assert_eq!(err.error.r#type, "InvalidMetadataLocation");
+    }
+}
@@ -4,7 +4,7 @@
 
 use anyhow::{anyhow, Context};
 use http::HeaderValue;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::convert::Infallible;
 use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
@@ -74,6 +74,8 @@ pub struct DynAppConfig {
     pub metrics_port: u16,
     /// Port to listen on.
     pub listen_port: u16,
+    /// TLS configuration. If not set, the server listens for plain HTTP.
+    pub tls: Option<TlsConfig>,
     /// If true (default), the NIL uuid is used as default project id.
     pub enable_default_project: bool,
     /// Template to obtain the "prefix" for a warehouse,
@@ -144,6 +146,13 @@ pub struct DynAppConfig {
         serialize_with = "serialize_audience"
     )]
     pub openid_additional_issuers: Option<Vec<String>>,
+    /// `OAuth2` Token Introspection (RFC 7662) endpoint used to verify opaque access tokens.
+    pub openid_introspection: Option<IntrospectionConfig>,
+    /// Per-issuer mapping of token claims to the identity of a principal.
+    /// The key is an arbitrary name, the issuer is matched against the `iss` claim.
+    /// If no mapping matches, built-in heuristics are used.
+    #[serde(default)]
+    pub openid_claim_mappings: HashMap<String, ClaimMapping>,
     pub enable_kubernetes_authentication: bool,
 
     // ------------- AUTHORIZATION - OPENFGA -------------
@@ -154,6 +163,14 @@ pub struct DynAppConfig {
         serialize_with = "serialize_openfga_config"
     )]
     pub openfga: Option<OpenFGAConfig>,
+    /// Time in seconds for which decisions of the `openfga` and `postgres` backends are cached.
+    /// The cache is cleared whenever permissions change. 0 disables the cache.
+    pub authz_decision_cache_ttl_seconds: u64,
+    /// Maximum number of cached decisions of the `openfga` and `postgres` backends.
+    pub authz_decision_cache_capacity: usize,
+
+    // ------------- AUTHORIZATION - OPA -------------
+    pub opa: Option<OpaConfig>,
 
     // ------------- Health -------------
     pub health_check_frequency_seconds: u64,
@@ -174,6 +191,15 @@ pub struct DynAppConfig {
         serialize_with = "duration_to_seconds"
     )]
     pub default_tabular_expiration_delay_seconds: chrono::Duration,
+    /// If `true`, tables referenced by active views cannot be dropped.
+    /// Otherwise dropping them only logs a warning.
+    pub block_drop_of_view_dependencies: bool,
+
+    // ------------- Contract Verification -------------
+    /// External data contract services that must approve changes to tables and views.
+    /// The key is an arbitrary name of the webhook.
+    #[serde(default)]
+    pub contract_webhooks: HashMap<String, ContractWebhookConfig>,
 
     // ------------- Internal -------------
     /// Optional server id. We recommend to not change this unless multiple catalogs
@@ -292,12 +318,53 @@ pub struct OpenFGAConfig {
     pub auth: OpenFGAAuth,
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Redact)]
+pub struct OpaConfig {
+    /// Base Url of the OPA server, i.e. `http://localhost:8181`
+    pub endpoint: Url,
+    /// Path of the rule that decides single checks, relative to `/v1/data/`.
+    #[serde(default = "default_opa_policy_path")]
+    pub policy_path: String,
+    /// Path of the rule that decides batch checks, relative to `/v1/data/`.
+    #[serde(default = "default_opa_batch_policy_path")]
+    pub batch_policy_path: String,
+    /// Bearer token to authenticate against OPA
+    #[redact]
+    pub bearer_token: Option<String>,
+    /// Time in seconds for which decisions are cached. 0 disables the cache.
+    #[serde(default = "default_opa_decision_cache_ttl_seconds")]
+    pub decision_cache_ttl_seconds: u64,
+    /// Maximum number of cached decisions
+    #[serde(default = "default_opa_decision_cache_capacity")]
+    pub decision_cache_capacity: usize,
+}
+
+fn default_opa_policy_path() -> String {
+    "lakekeeper/allow".to_string()
+}
+
+fn default_opa_batch_policy_path() -> String {
+    "lakekeeper/batch_allow".to_string()
+}
+
+fn default_opa_decision_cache_ttl_seconds() -> u64 {
+    30
+}
+
+fn default_opa_decision_cache_capacity() -> usize {
+    10_000
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum AuthZBackend {
     #[serde(alias = "allowall", alias = "AllowAll", alias = "ALLOWALL")]
     AllowAll,
     #[serde(alias = "openfga", alias = "OpenFGA", alias = "OPENFGA")]
     OpenFGA,
+    #[serde(alias = "opa", alias = "OPA")]
+    Opa,
+    #[serde(alias = "postgres", alias = "Postgres", alias = "POSTGRES")]
+    Postgres,
 }
 
 impl Default for AuthZBackend {
@@ -314,6 +381,135 @@ pub enum SecretBackend {
     Postgres,
 }
 
+#[derive(Clone, Serialize, Deserialize, PartialEq, Redact)]
+pub struct IntrospectionConfig {
+    /// Url of the introspection endpoint
+    pub endpoint: Url,
+    /// Client id used to authenticate against the introspection endpoint
+    pub client_id: String,
+    /// Client secret used to authenticate against the introspection endpoint
+    #[redact]
+    pub client_secret: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TlsConfig {
+    /// Path to the PEM encoded server certificate chain
+    pub cert_path: PathBuf,
+    /// Path to the PEM encoded private key of the server certificate
+    pub key_path: PathBuf,
+    /// Path to PEM encoded CA certificates used to verify client certificates.
+    /// Required if `client_auth` is not `disabled`.
+    pub client_ca_path: Option<PathBuf>,
+    /// Whether clients must present a certificate.
+    #[serde(default)]
+    pub client_auth: TlsClientAuth,
+    /// Which field of a client certificate identifies the principal.
+    #[serde(default)]
+    pub client_identity: CertificateIdentitySource,
+    /// Interval in seconds in which certificate files are checked for changes.
+    #[serde(default = "default_tls_reload_interval_seconds")]
+    pub reload_interval_seconds: u64,
+    /// Path to a PEM encoded client certificate presented by the `healthcheck`
+    /// command. Required if `client_auth` is `required`.
+    pub healthcheck_cert_path: Option<PathBuf>,
+    /// Path to the PEM encoded private key of `healthcheck_cert_path`.
+    pub healthcheck_key_path: Option<PathBuf>,
+}
+
+fn default_tls_reload_interval_seconds() -> u64 {
+    60
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum TlsClientAuth {
+    /// Client certificates are not requested.
+    #[default]
+    Disabled,
+    /// Client certificates are verified if presented. Clients without
+    /// certificate must authenticate with a bearer token.
+    Optional,
+    /// All clients must present a valid certificate.
+    Required,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum CertificateIdentitySource {
+    /// Use the common name (CN) of the certificate subject.
+    #[default]
+    SubjectCommonName,
+    /// Use the first DNS or email subject alternative name (SAN).
+    SubjectAlternativeName,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClaimMapping {
+    /// Issuer (`iss` claim) this mapping applies to
+    pub issuer: String,
+    /// Claim to use as the subject of the principal.
+    /// Falls back to `oid` / `sub` if not present in the token.
+    pub subject_claim: Option<String>,
+    /// Claim to use as the display name of the principal.
+    pub display_name_claim: Option<String>,
+    /// Claim to use as the email of the principal.
+    pub email_claim: Option<String>,
+    /// Rule to decide if the principal is an application (machine user).
+    /// If not set, built-in heuristics are used.
+    pub machine_user: Option<MachineUserRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum MachineUserRule {
+    /// The principal is an application if `claim` is present in the token.
+    ClaimPresent { claim: String },
+    /// The principal is an application if `claim` is missing in the token.
+    ClaimAbsent { claim: String },
+    /// The principal is an application if `claim` equals `value`.
+    ClaimEquals { claim: String, value: String },
+    /// The principal is an application if the subject equals the value of `claim`.
+    SubjectEqualsClaim { claim: String },
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Redact)]
+pub struct ContractWebhookConfig {
+    /// Url that proposed changes are `POST`ed to
+    pub url: Url,
+    /// Bearer token to authenticate against the webhook
+    #[redact]
+    pub bearer_token: Option<String>,
+    /// Time in milliseconds after which a request to the webhook is aborted.
+    #[serde(default = "default_contract_webhook_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Whether changes are allowed if the webhook fails or is unreachable.
+    #[serde(default)]
+    pub failure_policy: WebhookFailurePolicy,
+    /// Only changes in these warehouses are sent to the webhook.
+    /// If empty, changes in all warehouses are sent.
+    #[serde(default)]
+    pub warehouses: Vec<uuid::Uuid>,
+    /// Only changes in these namespaces and their children are sent to the webhook.
+    /// Nested namespaces are separated by `.`. If empty, changes in all namespaces are sent.
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+}
+
+fn default_contract_webhook_timeout_ms() -> u64 {
+    5000
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookFailurePolicy {
+    /// Changes are rejected if the webhook fails.
+    #[default]
+    FailClosed,
+    /// Changes are allowed if the webhook fails.
+    FailOpen,
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq, Redact)]
 pub struct KV2Config {
     pub url: Url,
@@ -360,16 +556,24 @@ impl Default for DynAppConfig {
             openid_provider_uri: None,
             openid_audience: None,
             openid_additional_issuers: None,
+            openid_introspection: None,
+            openid_claim_mappings: HashMap::new(),
             enable_kubernetes_authentication: false,
             listen_port: 8181,
+            tls: None,
             health_check_frequency_seconds: 10,
             health_check_jitter_millis: 500,
             kv2: None,
             authz_backend: AuthZBackend::AllowAll,
             openfga: None,
+            authz_decision_cache_ttl_seconds: 5,
+            authz_decision_cache_capacity: 10_000,
+            opa: None,
             secret_backend: SecretBackend::Postgres,
             queue_config: TaskQueueConfig::default(),
             default_tabular_expiration_delay_seconds: chrono::Duration::days(7),
+            block_drop_of_view_dependencies: false,
+            contract_webhooks: HashMap::new(),
             server_id: uuid::Uuid::nil(),
         }
     }
@@ -399,8 +603,24 @@ impl DynAppConfig {
         self.default_tabular_expiration_delay_seconds
     }
 
+    /// Get the claim mapping for the given issuer, if configured.
+    pub fn claim_mapping_for_issuer(&self, issuer: &str) -> Option<&ClaimMapping> {
+        self.openid_claim_mappings
+            .values()
+            .find(|mapping| mapping.issuer == issuer)
+    }
+
     pub fn authn_enabled(&self) -> bool {
         self.openid_provider_uri.is_some()
+            || self.openid_introspection.is_some()
+            || self.mtls_enabled()
+    }
+
+    /// Client certificates are verified and can be used to authenticate.
+    pub fn mtls_enabled(&self) -> bool {
+        self.tls
+            .as_ref()
+            .is_some_and(|tls| tls.client_auth != TlsClientAuth::Disabled)
     }
 }
 
@@ -673,6 +893,143 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_opa_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__AUTHZ_BACKEND", "opa");
+            jail.set_env("LAKEKEEPER_TEST__OPA__ENDPOINT", "http://localhost:8181");
+            jail.set_env("LAKEKEEPER_TEST__OPA__DECISION_CACHE_TTL_SECONDS", "5");
+            let config = get_config();
+            let opa_config = config.opa.unwrap();
+            assert_eq!(config.authz_backend, AuthZBackend::Opa);
+            assert_eq!(opa_config.endpoint.as_str(), "http://localhost:8181/");
+            assert_eq!(opa_config.policy_path, "lakekeeper/allow");
+            assert_eq!(opa_config.batch_policy_path, "lakekeeper/batch_allow");
+            assert_eq!(opa_config.decision_cache_ttl_seconds, 5);
+            assert_eq!(opa_config.bearer_token, None);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_openid_introspection_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENID_INTROSPECTION__ENDPOINT",
+                "https://idp.example.com/introspect",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENID_INTROSPECTION__CLIENT_ID",
+                "lakekeeper",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENID_INTROSPECTION__CLIENT_SECRET",
+                "secret",
+            );
+            let config = get_config();
+            let introspection = config.openid_introspection.clone().unwrap();
+            assert_eq!(
+                introspection.endpoint.as_str(),
+                "https://idp.example.com/introspect"
+            );
+            assert_eq!(introspection.client_id, "lakekeeper");
+            assert_eq!(introspection.client_secret, "secret");
+            assert!(config.authn_enabled());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_openid_claim_mappings() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENID_CLAIM_MAPPINGS__OKTA__ISSUER",
+                "https://example.okta.com/oauth2/default",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENID_CLAIM_MAPPINGS__OKTA__SUBJECT_CLAIM",
+                "uid",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENID_CLAIM_MAPPINGS__OKTA__MACHINE_USER__TYPE",
+                "claim-absent",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENID_CLAIM_MAPPINGS__OKTA__MACHINE_USER__CLAIM",
+                "uid",
+            );
+            let config = get_config();
+            let mapping = config
+                .claim_mapping_for_issuer("https://example.okta.com/oauth2/default")
+                .unwrap();
+            assert_eq!(mapping.subject_claim.as_deref(), Some("uid"));
+            assert_eq!(mapping.display_name_claim, None);
+            assert_eq!(
+                mapping.machine_user,
+                Some(MachineUserRule::ClaimAbsent {
+                    claim: "uid".to_string()
+                })
+            );
+            assert!(config
+                .claim_mapping_for_issuer("https://other.example.com")
+                .is_none());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_contract_webhooks() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__CONTRACT_WEBHOOKS__CONTRACTS__URL",
+                "https://contracts.example.com/verify",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__CONTRACT_WEBHOOKS__CONTRACTS__FAILURE_POLICY",
+                "fail-open",
+            );
+            jail.set_env(
+                "LAKEKEEPER_TEST__CONTRACT_WEBHOOKS__CONTRACTS__NAMESPACES",
+                "[finance, sales.eu]",
+            );
+            let config = get_config();
+            let webhook = config.contract_webhooks.get("contracts").unwrap();
+            assert_eq!(webhook.url.as_str(), "https://contracts.example.com/verify");
+            assert_eq!(webhook.failure_policy, WebhookFailurePolicy::FailOpen);
+            assert_eq!(webhook.timeout_ms, 5000);
+            assert!(webhook.warehouses.is_empty());
+            assert_eq!(webhook.namespaces, vec!["finance", "sales.eu"]);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_tls_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__TLS__CERT_PATH", "/certs/tls.crt");
+            jail.set_env("LAKEKEEPER_TEST__TLS__KEY_PATH", "/certs/tls.key");
+            jail.set_env("LAKEKEEPER_TEST__TLS__CLIENT_CA_PATH", "/certs/ca.crt");
+            jail.set_env("LAKEKEEPER_TEST__TLS__CLIENT_AUTH", "optional");
+            jail.set_env(
+                "LAKEKEEPER_TEST__TLS__CLIENT_IDENTITY",
+                "subject-alternative-name",
+            );
+            let config = get_config();
+            let tls = config.tls.clone().unwrap();
+            assert_eq!(tls.cert_path, PathBuf::from("/certs/tls.crt"));
+            assert_eq!(tls.client_auth, TlsClientAuth::Optional);
+            assert_eq!(
+                tls.client_identity,
+                CertificateIdentitySource::SubjectAlternativeName
+            );
+            assert_eq!(tls.reload_interval_seconds, 60);
+            assert_eq!(tls.healthcheck_cert_path, None);
+            assert!(config.mtls_enabled());
+            assert!(config.authn_enabled());
+            Ok(())
+        });
+    }
+
     #[test]
     #[should_panic(expected = "openfga client_secret is required when client_id is specified")]
     fn test_openfga_client_config_fails_without_token() {
@@ -0,0 +1,844 @@
+use crate::implementations::postgres::dbutils::DBErrorHandler;
+use crate::service::Result;
+use chrono::Utc;
+use iceberg_ext::catalog::rest::ErrorModel;
+
+/// A relationship tuple of the built-in postgres authorizer:
+/// `<user_type>:<user_id>[#<user_relation>]` is `<relation>` of `<object_type>:<object_id>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct AuthzTuple {
+    pub(crate) object_type: String,
+    pub(crate) object_id: String,
+    pub(crate) relation: String,
+    pub(crate) user_type: String,
+    pub(crate) user_id: String,
+    /// Empty for direct users
+    pub(crate) user_relation: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct AuthzTupleRow {
+    pub(crate) tuple_id: i64,
+    pub(crate) tuple: AuthzTuple,
+}
+
+/// Filter for [`read_tuples`]. `None` matches any value.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AuthzTupleFilter {
+    pub(crate) object_type: Option<String>,
+    pub(crate) object_id: Option<String>,
+    pub(crate) relation: Option<String>,
+    pub(crate) user_type: Option<String>,
+    pub(crate) user_id: Option<String>,
+    pub(crate) user_relation: Option<String>,
+}
+
+/// Get all users that are directly related to an object.
+/// Returns `(user_type, user_id, user_relation)`.
+pub(crate) async fn get_object_relation_users<'e, 'c: 'e, E>(
+    object_type: &str,
+    object_id: &str,
+    relation: &str,
+    connection: E,
+) -> Result<Vec<(String, String, String)>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let rows = sqlx::query!(
+        r#"
+        SELECT user_type, user_id, user_relation
+        FROM authz_tuple
+        WHERE object_type = $1 AND object_id = $2 AND relation = $3
+        "#,
+        object_type,
+        object_id,
+        relation
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching authorization tuples".to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.user_type, row.user_id, row.user_relation))
+        .collect())
+}
+
+/// Edges of the authorization model between relations, followed by [`get_relation_graph`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RelationEdges {
+    /// Resolving `relation` of an object requires `target_relation` of the same object.
+    computed_object_types: Vec<String>,
+    computed_relations: Vec<String>,
+    computed_target_relations: Vec<String>,
+    /// Resolving `relation` of an object requires `target_relation` of all users
+    /// related to the object by `tupleset`.
+    tupleset_object_types: Vec<String>,
+    tupleset_relations: Vec<String>,
+    tuplesets: Vec<String>,
+    tupleset_target_relations: Vec<String>,
+}
+
+impl RelationEdges {
+    pub(crate) fn push_computed(&mut self, object_type: &str, relation: &str, target: &str) {
+        self.computed_object_types.push(object_type.to_string());
+        self.computed_relations.push(relation.to_string());
+        self.computed_target_relations.push(target.to_string());
+    }
+
+    pub(crate) fn push_tupleset(
+        &mut self,
+        object_type: &str,
+        relation: &str,
+        tupleset: &str,
+        target: &str,
+    ) {
+        self.tupleset_object_types.push(object_type.to_string());
+        self.tupleset_relations.push(relation.to_string());
+        self.tuplesets.push(tupleset.to_string());
+        self.tupleset_target_relations.push(target.to_string());
+    }
+}
+
+/// A relation `<object_type>:<object_id>#<relation>` of the graph loaded by
+/// [`get_relation_graph`], with one of the users directly related by it.
+/// `user` is `None` if no user is directly related.
+#[derive(Debug, Clone)]
+pub(crate) struct RelationGraphRow {
+    pub(crate) object_type: String,
+    pub(crate) object_id: String,
+    pub(crate) relation: String,
+    /// `(user_type, user_id, user_relation)`
+    pub(crate) user: Option<(String, String, String)>,
+}
+
+struct RelationGraphRecord {
+    object_type: String,
+    object_id: String,
+    relation: String,
+    user_type: Option<String>,
+    user_id: Option<String>,
+    user_relation: Option<String>,
+}
+
+impl From<RelationGraphRecord> for RelationGraphRow {
+    fn from(row: RelationGraphRecord) -> Self {
+        Self {
+            object_type: row.object_type,
+            object_id: row.object_id,
+            relation: row.relation,
+            user: row
+                .user_type
+                .zip(row.user_id)
+                .zip(row.user_relation)
+                .map(|((user_type, user_id), user_relation)| (user_type, user_id, user_relation)),
+        }
+    }
+}
+
+/// Load all tuples that resolving the given relations can depend on, in a single query.
+///
+/// `relations` are `(object_type, object_id, relation)`. Starting at these, all
+/// relations they depend on are collected by following the `edges` of the model and
+/// the usersets (`<object>#<relation>`) of tuples. Every collected relation is returned,
+/// once for each user directly related by it, or once without a user.
+pub(crate) async fn get_relation_graph<'e, 'c: 'e, E>(
+    relations: &[(String, String, String)],
+    edges: &RelationEdges,
+    connection: E,
+) -> Result<Vec<RelationGraphRow>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let (types, (ids, relations)): (Vec<String>, (Vec<String>, Vec<String>)) = relations
+        .iter()
+        .cloned()
+        .map(|(typ, id, relation)| (typ, (id, relation)))
+        .unzip();
+    let rows = sqlx::query_as!(
+        RelationGraphRecord,
+        r#"
+        WITH RECURSIVE computed_edge(object_type, relation, target_relation) AS (
+            SELECT * FROM UNNEST($1::text[], $2::text[], $3::text[])
+        ),
+        tupleset_edge(object_type, relation, tupleset, target_relation) AS (
+            SELECT * FROM UNNEST($4::text[], $5::text[], $6::text[], $7::text[])
+        ),
+        node(object_type, object_id, relation) AS (
+            SELECT * FROM UNNEST($8::text[], $9::text[], $10::text[])
+            UNION
+            SELECT next.object_type, next.object_id, next.relation
+            FROM node n
+            CROSS JOIN LATERAL (
+                SELECT n.object_type, n.object_id, e.target_relation
+                FROM computed_edge e
+                WHERE e.object_type = n.object_type AND e.relation = n.relation
+                UNION ALL
+                SELECT t.user_type, t.user_id, e.target_relation
+                FROM tupleset_edge e
+                INNER JOIN authz_tuple t
+                    ON t.object_type = n.object_type
+                    AND t.object_id = n.object_id
+                    AND t.relation = e.tupleset
+                WHERE e.object_type = n.object_type AND e.relation = n.relation
+                UNION ALL
+                SELECT t.user_type, t.user_id, t.user_relation
+                FROM authz_tuple t
+                WHERE t.object_type = n.object_type
+                    AND t.object_id = n.object_id
+                    AND t.relation = n.relation
+                    AND t.user_relation <> ''
+            ) AS next(object_type, object_id, relation)
+        )
+        SELECT
+            n.object_type AS "object_type!",
+            n.object_id AS "object_id!",
+            n.relation AS "relation!",
+            t.user_type AS "user_type?",
+            t.user_id AS "user_id?",
+            t.user_relation AS "user_relation?"
+        FROM node n
+        LEFT JOIN authz_tuple t
+            ON t.object_type = n.object_type
+            AND t.object_id = n.object_id
+            AND t.relation = n.relation
+        "#,
+        &edges.computed_object_types,
+        &edges.computed_relations,
+        &edges.computed_target_relations,
+        &edges.tupleset_object_types,
+        &edges.tupleset_relations,
+        &edges.tuplesets,
+        &edges.tupleset_target_relations,
+        &types,
+        &ids,
+        &relations,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error loading authorization tuples".to_string()))?;
+
+    Ok(rows.into_iter().map(Into::into).collect())
+}
+
+/// Like [`get_relation_graph`], starting at `relation` of all objects of `object_type`
+/// that can be reached from one of the `subjects`.
+///
+/// `subjects` are `(type, id, relation)`, with an empty relation for direct users.
+/// Starting at the subjects, tuples are followed from their user to their object,
+/// which is the direction in which the model inherits relations. Each object reached is
+/// followed both as direct user and as userset (`<object>#<relation>`) of the tuple.
+/// This is a superset of the objects the subjects have any relation on.
+pub(crate) async fn get_reachable_relation_graph<'e, 'c: 'e, E>(
+    subjects: &[(String, String, String)],
+    object_type: &str,
+    relation: &str,
+    edges: &RelationEdges,
+    connection: E,
+) -> Result<Vec<RelationGraphRow>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let (types, (ids, relations)): (Vec<String>, (Vec<String>, Vec<String>)) = subjects
+        .iter()
+        .cloned()
+        .map(|(typ, id, relation)| (typ, (id, relation)))
+        .unzip();
+    let rows = sqlx::query_as!(
+        RelationGraphRecord,
+        r#"
+        WITH RECURSIVE reachable(object_type, object_id, relation) AS (
+            SELECT * FROM UNNEST($8::text[], $9::text[], $10::text[])
+            UNION
+            SELECT t.object_type, t.object_id, r.relation
+            FROM reachable n
+            INNER JOIN authz_tuple t
+                ON t.user_type = n.object_type
+                AND t.user_id = n.object_id
+                AND t.user_relation = n.relation
+            CROSS JOIN LATERAL (VALUES (t.relation), ('')) AS r(relation)
+        ),
+        computed_edge(object_type, relation, target_relation) AS (
+            SELECT * FROM UNNEST($1::text[], $2::text[], $3::text[])
+        ),
+        tupleset_edge(object_type, relation, tupleset, target_relation) AS (
+            SELECT * FROM UNNEST($4::text[], $5::text[], $6::text[], $7::text[])
+        ),
+        node(object_type, object_id, relation) AS (
+            SELECT DISTINCT object_type, object_id, $12::text
+            FROM reachable
+            WHERE object_type = $11::text AND relation = ''
+            UNION
+            SELECT next.object_type, next.object_id, next.relation
+            FROM node n
+            CROSS JOIN LATERAL (
+                SELECT n.object_type, n.object_id, e.target_relation
+                FROM computed_edge e
+                WHERE e.object_type = n.object_type AND e.relation = n.relation
+                UNION ALL
+                SELECT t.user_type, t.user_id, e.target_relation
+                FROM tupleset_edge e
+                INNER JOIN authz_tuple t
+                    ON t.object_type = n.object_type
+                    AND t.object_id = n.object_id
+                    AND t.relation = e.tupleset
+                WHERE e.object_type = n.object_type AND e.relation = n.relation
+                UNION ALL
+                SELECT t.user_type, t.user_id, t.user_relation
+                FROM authz_tuple t
+                WHERE t.object_type = n.object_type
+                    AND t.object_id = n.object_id
+                    AND t.relation = n.relation
+                    AND t.user_relation <> ''
+            ) AS next(object_type, object_id, relation)
+        )
+        SELECT
+            n.object_type AS "object_type!",
+            n.object_id AS "object_id!",
+            n.relation AS "relation!",
+            t.user_type AS "user_type?",
+            t.user_id AS "user_id?",
+            t.user_relation AS "user_relation?"
+        FROM node n
+        LEFT JOIN authz_tuple t
+            ON t.object_type = n.object_type
+            AND t.object_id = n.object_id
+            AND t.relation = n.relation
+        "#,
+        &edges.computed_object_types,
+        &edges.computed_relations,
+        &edges.computed_target_relations,
+        &edges.tupleset_object_types,
+        &edges.tupleset_relations,
+        &edges.tuplesets,
+        &edges.tupleset_target_relations,
+        &types,
+        &ids,
+        &relations,
+        object_type,
+        relation,
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error loading authorization tuples".to_string()))?;
+
+    Ok(rows.into_iter().map(Into::into).collect())
+}
+
+/// Read tuples matching the filter, ordered by insertion.
+/// Only tuples inserted after `after_tuple_id` are returned.
+pub(crate) async fn read_tuples<'e, 'c: 'e, E>(
+    filter: &AuthzTupleFilter,
+    after_tuple_id: Option<i64>,
+    limit: i64,
+    connection: E,
+) -> Result<Vec<AuthzTupleRow>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let rows = sqlx::query!(
+        r#"
+        SELECT tuple_id, object_type, object_id, relation, user_type, user_id, user_relation
+        FROM authz_tuple
+        WHERE ($1::text IS NULL OR object_type = $1)
+            AND ($2::text IS NULL OR object_id = $2)
+            AND ($3::text IS NULL OR relation = $3)
+            AND ($4::text IS NULL OR user_type = $4)
+            AND ($5::text IS NULL OR user_id = $5)
+            AND ($6::text IS NULL OR user_relation = $6)
+            AND ($7::bigint IS NULL OR tuple_id > $7)
+        ORDER BY tuple_id ASC
+        LIMIT $8
+        "#,
+        filter.object_type,
+        filter.object_id,
+        filter.relation,
+        filter.user_type,
+        filter.user_id,
+        filter.user_relation,
+        after_tuple_id,
+        limit
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error reading authorization tuples".to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| AuthzTupleRow {
+            tuple_id: row.tuple_id,
+            tuple: AuthzTuple {
+                object_type: row.object_type,
+                object_id: row.object_id,
+                relation: row.relation,
+                user_type: row.user_type,
+                user_id: row.user_id,
+                user_relation: row.user_relation,
+            },
+        })
+        .collect())
+}
+
+/// Write and delete tuples.
+///
+/// Like `OpenFGA`, writing a tuple that already exists or deleting a tuple
+/// that does not exist fails with a conflict.
+pub(crate) async fn write_tuples(
+    writes: &[AuthzTuple],
+    deletes: &[AuthzTuple],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    if !deletes.is_empty() {
+        let columns = TupleColumns::from(deletes);
+        let deleted = sqlx::query!(
+            r#"
+            DELETE FROM authz_tuple t
+            USING UNNEST($1::text[], $2::text[], $3::text[], $4::text[], $5::text[], $6::text[])
+                AS d(object_type, object_id, relation, user_type, user_id, user_relation)
+            WHERE t.object_type = d.object_type
+                AND t.object_id = d.object_id
+                AND t.relation = d.relation
+                AND t.user_type = d.user_type
+                AND t.user_id = d.user_id
+                AND t.user_relation = d.user_relation
+            "#,
+            &columns.object_types,
+            &columns.object_ids,
+            &columns.relations,
+            &columns.user_types,
+            &columns.user_ids,
+            &columns.user_relations,
+        )
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error deleting authorization tuples".to_string()))?
+        .rows_affected();
+
+        if deleted != deletes.len() as u64 {
+            return Err(ErrorModel::conflict(
+                "Cannot delete a tuple which does not exist",
+                "AuthorizationTupleNotFound",
+                None,
+            )
+            .into());
+        }
+    }
+
+    if !writes.is_empty() {
+        let columns = TupleColumns::from(writes);
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO authz_tuple (object_type, object_id, relation, user_type, user_id, user_relation)
+            SELECT * FROM UNNEST($1::text[], $2::text[], $3::text[], $4::text[], $5::text[], $6::text[])
+            ON CONFLICT DO NOTHING
+            "#,
+            &columns.object_types,
+            &columns.object_ids,
+            &columns.relations,
+            &columns.user_types,
+            &columns.user_ids,
+            &columns.user_relations,
+        )
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error writing authorization tuples".to_string()))?
+        .rows_affected();
+
+        if inserted != writes.len() as u64 {
+            return Err(ErrorModel::conflict(
+                "Cannot write a tuple which already exists",
+                "AuthorizationTupleAlreadyExists",
+                None,
+            )
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct TupleColumns {
+    object_types: Vec<String>,
+    object_ids: Vec<String>,
+    relations: Vec<String>,
+    user_types: Vec<String>,
+    user_ids: Vec<String>,
+    user_relations: Vec<String>,
+}
+
+impl From<&[AuthzTuple]> for TupleColumns {
+    fn from(tuples: &[AuthzTuple]) -> Self {
+        tuples
+            .iter()
+            .cloned()
+            .fold(TupleColumns::default(), |mut columns, tuple| {
+                columns.object_types.push(tuple.object_type);
+                columns.object_ids.push(tuple.object_id);
+                columns.relations.push(tuple.relation);
+                columns.user_types.push(tuple.user_type);
+                columns.user_ids.push(tuple.user_id);
+                columns.user_relations.push(tuple.user_relation);
+                columns
+            })
+    }
+}
+
+/// A grant of `relation` on `object` to `principal`, identified like an `OpenFGA` tuple.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Grant {
+    pub(crate) object: String,
+    pub(crate) relation: String,
+    pub(crate) principal: String,
+}
+
+/// Expiration of a time-bound grant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GrantExpiration {
+    pub(crate) grant: Grant,
+    pub(crate) expires_at: chrono::DateTime<Utc>,
+    /// Set once the tuple of the grant has been deleted
+    pub(crate) revoked_at: Option<chrono::DateTime<Utc>>,
+}
+
+/// Set the expiration of grants. Existing expirations of the same grants are replaced.
+pub(crate) async fn set_grant_expirations(
+    grants: &[Grant],
+    expires_at: chrono::DateTime<Utc>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    if grants.is_empty() {
+        return Ok(());
+    }
+    let columns = GrantColumns::from(grants);
+    sqlx::query!(
+        r#"
+        INSERT INTO authz_grant_expiration (object, relation, principal, expires_at)
+        SELECT *, $4::timestamptz FROM UNNEST($1::text[], $2::text[], $3::text[])
+        ON CONFLICT (object, relation, principal)
+        DO UPDATE SET expires_at = EXCLUDED.expires_at, revoked_at = NULL
+        "#,
+        &columns.objects,
+        &columns.relations,
+        &columns.principals,
+        expires_at,
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting grant expirations".to_string()))?;
+
+    Ok(())
+}
+
+/// Remove the expiration of grants, i.e. because they are revoked or granted permanently.
+pub(crate) async fn delete_grant_expirations(
+    grants: &[Grant],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    if grants.is_empty() {
+        return Ok(());
+    }
+    let columns = GrantColumns::from(grants);
+    sqlx::query!(
+        r#"
+        DELETE FROM authz_grant_expiration e
+        USING UNNEST($1::text[], $2::text[], $3::text[]) AS d(object, relation, principal)
+        WHERE e.object = d.object AND e.relation = d.relation AND e.principal = d.principal
+        "#,
+        &columns.objects,
+        &columns.relations,
+        &columns.principals,
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting grant expirations".to_string()))?;
+
+    Ok(())
+}
+
+/// List expirations of all grants on an object, including already revoked grants.
+pub(crate) async fn list_grant_expirations<'e, 'c: 'e, E>(
+    object: &str,
+    connection: E,
+) -> Result<Vec<GrantExpiration>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let rows = sqlx::query!(
+        r#"
+        SELECT object, relation, principal, expires_at, revoked_at
+        FROM authz_grant_expiration
+        WHERE object = $1
+        ORDER BY expires_at
+        "#,
+        object
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching grant expirations".to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| GrantExpiration {
+            grant: Grant {
+                object: row.object,
+                relation: row.relation,
+                principal: row.principal,
+            },
+            expires_at: row.expires_at,
+            revoked_at: row.revoked_at,
+        })
+        .collect())
+}
+
+/// Check if `grant` expired but is not revoked yet. The expiration is locked until the
+/// end of the transaction, so that the grant cannot be renewed while it is revoked.
+pub(crate) async fn lock_due_grant(
+    grant: &Grant,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<bool> {
+    let due = sqlx::query_scalar!(
+        r#"
+        SELECT 1 as "due!"
+        FROM authz_grant_expiration
+        WHERE object = $1 AND relation = $2 AND principal = $3
+            AND revoked_at IS NULL AND expires_at <= now()
+        FOR UPDATE
+        "#,
+        grant.object,
+        grant.relation,
+        grant.principal,
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching expired grant".to_string()))?;
+
+    Ok(due.is_some())
+}
+
+/// Record that the tuples of expired grants have been deleted.
+pub(crate) async fn mark_grants_revoked<'e, 'c: 'e, E>(
+    grants: &[Grant],
+    connection: E,
+) -> Result<()>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    if grants.is_empty() {
+        return Ok(());
+    }
+    let columns = GrantColumns::from(grants);
+    sqlx::query!(
+        r#"
+        UPDATE authz_grant_expiration e
+        SET revoked_at = now()
+        FROM UNNEST($1::text[], $2::text[], $3::text[]) AS d(object, relation, principal)
+        WHERE e.object = d.object AND e.relation = d.relation AND e.principal = d.principal
+            AND e.revoked_at IS NULL
+        "#,
+        &columns.objects,
+        &columns.relations,
+        &columns.principals,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error marking grants as revoked".to_string()))?;
+
+    Ok(())
+}
+
+/// Forget grants that were revoked before `revoked_before`.
+pub(crate) async fn purge_revoked_grants<'e, 'c: 'e, E>(
+    revoked_before: chrono::DateTime<Utc>,
+    connection: E,
+) -> Result<u64>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let purged = sqlx::query!(
+        r#"
+        DELETE FROM authz_grant_expiration
+        WHERE revoked_at < $1
+        "#,
+        revoked_before
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error purging revoked grants".to_string()))?
+    .rows_affected();
+
+    Ok(purged)
+}
+
+#[derive(Default)]
+struct GrantColumns {
+    objects: Vec<String>,
+    relations: Vec<String>,
+    principals: Vec<String>,
+}
+
+impl From<&[Grant]> for GrantColumns {
+    fn from(grants: &[Grant]) -> Self {
+        grants
+            .iter()
+            .cloned()
+            .fold(GrantColumns::default(), |mut columns, grant| {
+                columns.objects.push(grant.object);
+                columns.relations.push(grant.relation);
+                columns.principals.push(grant.principal);
+                columns
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tuple(object_id: &str, user_id: &str) -> AuthzTuple {
+        AuthzTuple {
+            object_type: "warehouse".to_string(),
+            object_id: object_id.to_string(),
+            relation: "select".to_string(),
+            user_type: "user".to_string(),
+            user_id: user_id.to_string(),
+            user_relation: String::new(),
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_write_read_delete_tuples(pool: sqlx::PgPool) {
+        let mut transaction = pool.begin().await.unwrap();
+        write_tuples(
+            &[
+                tuple("w1", "oidc~a"),
+                tuple("w1", "oidc~b"),
+                tuple("w2", "oidc~a"),
+            ],
+            &[],
+            &mut transaction,
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        let users = get_object_relation_users("warehouse", "w1", "select", &pool)
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 2);
+
+        let filter = AuthzTupleFilter {
+            user_type: Some("user".to_string()),
+            user_id: Some("oidc~a".to_string()),
+            ..Default::default()
+        };
+        let rows = read_tuples(&filter, None, 1, &pool).await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].tuple, tuple("w1", "oidc~a"));
+        let rows = read_tuples(&filter, Some(rows[0].tuple_id), 10, &pool)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].tuple, tuple("w2", "oidc~a"));
+
+        let mut transaction = pool.begin().await.unwrap();
+        write_tuples(&[], &[tuple("w1", "oidc~b")], &mut transaction)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        let users = get_object_relation_users("warehouse", "w1", "select", &pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            users,
+            vec![("user".to_string(), "oidc~a".to_string(), String::new())]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_duplicate_write_fails(pool: sqlx::PgPool) {
+        let mut transaction = pool.begin().await.unwrap();
+        write_tuples(&[tuple("w1", "oidc~a")], &[], &mut transaction)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut transaction = pool.begin().await.unwrap();
+        let err = write_tuples(&[tuple("w1", "oidc~a")], &[], &mut transaction)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, 409);
+
+        let mut transaction = pool.begin().await.unwrap();
+        let err = write_tuples(&[], &[tuple("w3", "oidc~a")], &mut transaction)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, 409);
+    }
+
+    fn grant(object: &str, principal: &str) -> Grant {
+        Grant {
+            object: object.to_string(),
+            relation: "select".to_string(),
+            principal: principal.to_string(),
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_grant_expiration_lifecycle(pool: sqlx::PgPool) {
+        let expired = grant("warehouse:w1", "user:oidc~a");
+        let upcoming = grant("warehouse:w1", "user:oidc~b");
+
+        let mut transaction = pool.begin().await.unwrap();
+        set_grant_expirations(
+            &[expired.clone()],
+            Utc::now() - chrono::Duration::minutes(1),
+            &mut transaction,
+        )
+        .await
+        .unwrap();
+        set_grant_expirations(
+            &[upcoming.clone()],
+            Utc::now() + chrono::Duration::hours(1),
+            &mut transaction,
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut transaction = pool.begin().await.unwrap();
+        assert!(lock_due_grant(&expired, &mut transaction).await.unwrap());
+        assert!(!lock_due_grant(&upcoming, &mut transaction).await.unwrap());
+        mark_grants_revoked(&[expired.clone()], &mut *transaction)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut transaction = pool.begin().await.unwrap();
+        assert!(!lock_due_grant(&expired, &mut transaction).await.unwrap());
+        transaction.commit().await.unwrap();
+
+        let expirations = list_grant_expirations("warehouse:w1", &pool).await.unwrap();
+        assert_eq!(expirations.len(), 2);
+        assert_eq!(expirations[0].grant, expired);
+        assert!(expirations[0].revoked_at.is_some());
+        assert_eq!(expirations[1].grant, upcoming);
+        assert!(expirations[1].revoked_at.is_none());
+
+        let purged = purge_revoked_grants(Utc::now() + chrono::Duration::minutes(1), &pool)
+            .await
+            .unwrap();
+        assert_eq!(purged, 1);
+
+        let mut transaction = pool.begin().await.unwrap();
+        delete_grant_expirations(&[upcoming], &mut transaction)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        assert!(list_grant_expirations("warehouse:w1", &pool)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,19 +1,25 @@
 use super::{
     bootstrap::{bootstrap, get_validation_data},
     namespace::{
-        create_namespace, drop_namespace, get_namespace, list_namespaces, namespace_to_id,
-        update_namespace_properties,
+        create_namespace, drop_deleted_namespace, drop_namespace, get_namespace,
+        get_namespace_hierarchy_properties, get_namespace_subtree, list_deleted_namespaces,
+        list_namespaces, mark_namespace_as_deleted, namespace_to_id, rename_namespace,
+        undrop_namespace, update_namespace_properties,
     },
     role::{create_role, delete_role, list_roles, update_role},
     tabular::table::{
-        drop_table, get_table_metadata_by_id, get_table_metadata_by_s3_location, list_tables,
-        load_tables, rename_table, table_ident_to_id, table_idents_to_ids,
+        create_ref_protection_rule, delete_ref_protection_rule, drop_table, get_column_policies,
+        get_row_filters, get_table_and_namespace_properties, get_table_metadata_by_id,
+        get_table_metadata_by_s3_location, get_table_ref_protection_rules,
+        list_ref_protection_rules, list_tables, load_tables, rename_table, set_column_policies,
+        set_row_filters, table_ident_to_id, table_idents_to_ids,
     },
     warehouse::{
         create_project, create_warehouse, delete_project, delete_warehouse,
         get_config_for_warehouse, get_project, get_warehouse, get_warehouse_by_name, list_projects,
         list_warehouses, rename_project, rename_warehouse, set_warehouse_deletion_profile,
-        set_warehouse_status, update_storage_profile,
+        set_warehouse_location_template, set_warehouse_properties, set_warehouse_status,
+        set_warehouse_table_property_policy, update_storage_profile,
     },
     CatalogState, PostgresTransaction,
 };
@@ -24,20 +30,24 @@ use crate::implementations::postgres::role::search_role;
 use crate::implementations::postgres::tabular::table::commit_table_transaction;
 use crate::implementations::postgres::tabular::table::create_table;
 use crate::implementations::postgres::tabular::{
-    clear_tabular_deleted_at, list_tabulars, mark_tabular_as_deleted,
+    clear_tabular_deleted_at, get_tabular_namespace, list_tabulars, mark_tabular_as_deleted,
 };
 use crate::implementations::postgres::user::{
     create_or_update_user, delete_user, list_users, search_user,
 };
 use crate::service::authn::UserId;
+use crate::service::authz::{ColumnPolicy, RefProtectionRule, RowFilter};
+use crate::service::table_property_policy::TablePropertyPolicy;
 use crate::service::task_queue::TaskId;
 use crate::service::{
-    storage::StorageProfile, Catalog, CreateNamespaceRequest, CreateNamespaceResponse,
-    CreateOrUpdateUserResponse, CreateTableResponse, DeletionDetails, GetNamespaceResponse,
-    GetProjectResponse, GetTableMetadataResponse, GetWarehouseResponse, ListFlags,
-    ListNamespacesQuery, LoadTableResponse, NamespaceIdent, NamespaceIdentUuid, ProjectIdent,
-    Result, RoleId, StartupValidationData, TableCreation, TableIdent, TableIdentUuid, Transaction,
-    WarehouseIdent, WarehouseStatus,
+    storage::{StorageProfile, TabularLocationTemplate},
+    Catalog, CreateNamespaceRequest, CreateNamespaceResponse, CreateOrUpdateUserResponse,
+    CreateTableResponse, DeletionDetails, DroppedTabular, GetNamespaceResponse, GetProjectResponse,
+    GetTableMetadataResponse, GetWarehouseResponse, LineageTabular, ListFlags, ListNamespacesQuery,
+    LoadTableResponse, MaterializedView, MaterializedViewSource, NamespaceIdent,
+    NamespaceIdentUuid, NamespaceSubtree, ProjectIdent, Result, RoleId, StartupValidationData,
+    TableAndNamespaceProperties, TableCreation, TableIdent, TableIdentUuid, Transaction,
+    UndroppedNamespace, WarehouseIdent, WarehouseStatus,
 };
 use crate::SecretIdent;
 use crate::{
@@ -51,7 +61,9 @@ use crate::{
 use crate::{api::management::v1::warehouse::TabularDeleteProfile, service::TabularIdentUuid};
 use crate::{
     implementations::postgres::tabular::view::{
-        create_view, drop_view, list_views, load_view, rename_view, view_ident_to_id,
+        create_view, drop_view, get_materialized_view, get_materialized_view_of_storage_table,
+        list_dependent_views, list_view_dependencies, list_views, load_view, rename_view,
+        set_materialized_view, set_view_dependencies, view_ident_to_id,
     },
     service::TabularIdentOwned,
 };
@@ -236,6 +248,14 @@ impl Catalog for super::PostgresCatalog {
         namespace_to_id(warehouse_id, namespace, transaction).await
     }
 
+    async fn get_namespace_hierarchy_properties<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace: &NamespaceIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<HashMap<String, String>>> {
+        get_namespace_hierarchy_properties(warehouse_id, namespace, transaction).await
+    }
+
     async fn drop_namespace<'a>(
         warehouse_id: WarehouseIdent,
         namespace_id: NamespaceIdentUuid,
@@ -244,6 +264,55 @@ impl Catalog for super::PostgresCatalog {
         drop_namespace(warehouse_id, namespace_id, transaction).await
     }
 
+    async fn get_namespace_subtree<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<NamespaceSubtree> {
+        get_namespace_subtree(warehouse_id, namespace_id, transaction).await
+    }
+
+    async fn rename_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        destination: &NamespaceIdent,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        rename_namespace(warehouse_id, namespace_id, destination, transaction).await
+    }
+
+    async fn mark_namespace_as_deleted<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        mark_namespace_as_deleted(warehouse_id, namespace_id, transaction).await
+    }
+
+    async fn undrop_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<UndroppedNamespace> {
+        undrop_namespace(warehouse_id, namespace_id, transaction).await
+    }
+
+    async fn list_deleted_namespaces<'a>(
+        warehouse_id: WarehouseIdent,
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<PaginatedMapping<NamespaceIdentUuid, (NamespaceIdent, DeletionDetails)>> {
+        list_deleted_namespaces(warehouse_id, pagination_query, transaction).await
+    }
+
+    async fn drop_deleted_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<Vec<DroppedTabular>> {
+        drop_deleted_namespace(warehouse_id, namespace_id, transaction).await
+    }
+
     async fn update_namespace_properties<'a>(
         warehouse_id: WarehouseIdent,
         namespace_id: NamespaceIdentUuid,
@@ -314,6 +383,20 @@ impl Catalog for super::PostgresCatalog {
         get_table_metadata_by_id(warehouse_id, table, list_flags, catalog_state).await
     }
 
+    async fn get_table_and_namespace_properties(
+        table_id: TableIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<Option<TableAndNamespaceProperties>> {
+        get_table_and_namespace_properties(table_id, catalog_state).await
+    }
+
+    async fn get_tabular_namespace(
+        tabular_id: TabularIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<Option<GetNamespaceResponse>> {
+        get_tabular_namespace(tabular_id, &catalog_state.read_pool()).await
+    }
+
     async fn get_table_metadata_by_s3_location(
         warehouse_id: WarehouseIdent,
         location: &Location,
@@ -362,6 +445,67 @@ impl Catalog for super::PostgresCatalog {
         commit_table_transaction(warehouse_id, commits, transaction).await
     }
 
+    async fn get_column_policies(
+        table_id: TableIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<Vec<ColumnPolicy>> {
+        get_column_policies(table_id, catalog_state).await
+    }
+
+    async fn set_column_policies<'a>(
+        table_id: TableIdentUuid,
+        policies: &[ColumnPolicy],
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_column_policies(table_id, policies, transaction).await
+    }
+
+    async fn get_row_filters(
+        table_id: TableIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<Vec<RowFilter>> {
+        get_row_filters(table_id, catalog_state).await
+    }
+
+    async fn set_row_filters<'a>(
+        table_id: TableIdentUuid,
+        filters: &[RowFilter],
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_row_filters(table_id, filters, transaction).await
+    }
+
+    async fn list_ref_protection_rules(
+        warehouse_id: WarehouseIdent,
+        catalog_state: Self::State,
+    ) -> Result<Vec<RefProtectionRule>> {
+        list_ref_protection_rules(warehouse_id, catalog_state).await
+    }
+
+    async fn get_table_ref_protection_rules<'a>(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<Vec<RefProtectionRule>> {
+        get_table_ref_protection_rules(warehouse_id, table_id, transaction).await
+    }
+
+    async fn create_ref_protection_rule<'a>(
+        warehouse_id: WarehouseIdent,
+        rule: &RefProtectionRule,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        create_ref_protection_rule(warehouse_id, rule, transaction).await
+    }
+
+    async fn delete_ref_protection_rule<'a>(
+        warehouse_id: WarehouseIdent,
+        rule_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        delete_ref_protection_rule(warehouse_id, rule_id, transaction).await
+    }
+
     async fn create_warehouse<'a>(
         warehouse_name: String,
         project_id: ProjectIdent,
@@ -459,6 +603,30 @@ impl Catalog for super::PostgresCatalog {
         set_warehouse_deletion_profile(warehouse_id, deletion_profile, &mut **transaction).await
     }
 
+    async fn set_warehouse_properties<'a>(
+        warehouse_id: WarehouseIdent,
+        properties: &HashMap<String, String>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_properties(warehouse_id, properties, &mut **transaction).await
+    }
+
+    async fn set_warehouse_table_property_policy<'a>(
+        warehouse_id: WarehouseIdent,
+        policy: &TablePropertyPolicy,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_table_property_policy(warehouse_id, policy, &mut **transaction).await
+    }
+
+    async fn set_warehouse_location_template<'a>(
+        warehouse_id: WarehouseIdent,
+        location_template: Option<&TabularLocationTemplate>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_location_template(warehouse_id, location_template, &mut **transaction).await
+    }
+
     async fn set_warehouse_status<'a>(
         warehouse_id: WarehouseIdent,
         status: WarehouseStatus,
@@ -543,6 +711,8 @@ impl Catalog for super::PostgresCatalog {
         location: &Location,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> Result<()> {
+        // The view is re-created, which removes the link to its storage table.
+        let materialized_view = get_materialized_view(view_id, transaction).await?;
         drop_view(view_id, transaction).await?;
         create_view(
             namespace_id,
@@ -552,7 +722,22 @@ impl Catalog for super::PostgresCatalog {
             metadata,
             location,
         )
-        .await
+        .await?;
+        if let Some(materialized_view) = materialized_view {
+            let sources = materialized_view
+                .sources
+                .into_iter()
+                .map(|state| state.source)
+                .collect::<Vec<_>>();
+            set_materialized_view(
+                view_id,
+                materialized_view.storage_table_id,
+                &sources,
+                transaction,
+            )
+            .await?;
+        }
+        Ok(())
     }
 
     async fn drop_view<'a>(
@@ -572,6 +757,51 @@ impl Catalog for super::PostgresCatalog {
         rename_view(warehouse_id, source_id, source, destination, transaction).await
     }
 
+    async fn set_materialized_view<'a>(
+        view_id: ViewIdentUuid,
+        storage_table_id: TableIdentUuid,
+        sources: &[MaterializedViewSource],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_materialized_view(view_id, storage_table_id, sources, transaction).await
+    }
+
+    async fn get_materialized_view<'a>(
+        view_id: ViewIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<MaterializedView>> {
+        get_materialized_view(view_id, transaction).await
+    }
+
+    async fn get_materialized_view_of_storage_table<'a>(
+        table_id: TableIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<ViewIdentUuid>> {
+        get_materialized_view_of_storage_table(table_id, transaction).await
+    }
+
+    async fn set_view_dependencies<'a>(
+        view_id: ViewIdentUuid,
+        dependencies: &[TabularIdentUuid],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_view_dependencies(view_id, dependencies, transaction).await
+    }
+
+    async fn list_view_dependencies<'a>(
+        view_id: ViewIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<LineageTabular>> {
+        list_view_dependencies(view_id, transaction).await
+    }
+
+    async fn list_dependent_views<'a>(
+        tabular_id: TabularIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<LineageTabular>> {
+        list_dependent_views(tabular_id, transaction).await
+    }
+
     async fn list_tabulars(
         warehouse_id: WarehouseIdent,
         namespace_id: Option<NamespaceIdentUuid>,
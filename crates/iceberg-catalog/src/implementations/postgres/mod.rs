@@ -1,3 +1,4 @@
+pub(crate) mod authz;
 mod bootstrap;
 mod catalog;
 pub(crate) mod dbutils;
@@ -1,9 +1,12 @@
 use super::dbutils::DBErrorHandler;
-use crate::api::iceberg::v1::{PaginatedMapping, MAX_PAGE_SIZE};
+use super::tabular::{try_parse_namespace_ident, TabularType};
+use crate::api::iceberg::v1::{PaginatedMapping, PaginationQuery, MAX_PAGE_SIZE};
 use crate::implementations::postgres::pagination::{PaginateToken, V1PaginateToken};
+use crate::service::task_queue::TaskId;
 use crate::service::{
-    CreateNamespaceRequest, CreateNamespaceResponse, ErrorModel, GetNamespaceResponse,
-    ListNamespacesQuery, NamespaceIdent, Result,
+    CreateNamespaceRequest, CreateNamespaceResponse, DeletionDetails, DroppedTabular, ErrorModel,
+    GetNamespaceResponse, ListNamespacesQuery, NamespaceIdent, NamespaceSubtree,
+    NamespaceSubtreeTabular, Result, TableIdent, TabularIdentUuid, UndroppedNamespace,
 };
 use crate::{catalog::namespace::MAX_NAMESPACE_DEPTH, service::NamespaceIdentUuid, WarehouseIdent};
 use chrono::Utc;
@@ -28,6 +31,7 @@ pub(crate) async fn get_namespace(
         FROM namespace n
         INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
         WHERE n.warehouse_id = $1 AND n.namespace_id = $2
+        AND n.deleted_at IS NULL
         AND w.status = 'active'
         "#,
         *warehouse_id,
@@ -60,6 +64,34 @@ pub(crate) async fn get_namespace(
     })
 }
 
+/// Get the names of the given namespaces, irrespective of the warehouse they
+/// belong to. Namespaces that do not exist are not part of the result.
+pub(crate) async fn get_namespace_names_by_id<'e, 'c: 'e, E>(
+    namespace_ids: &[NamespaceIdentUuid],
+    connection: E,
+) -> Result<HashMap<NamespaceIdentUuid, Vec<String>>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let ids = namespace_ids.iter().map(|id| **id).collect::<Vec<_>>();
+    let rows = sqlx::query!(
+        r#"
+        SELECT namespace_id, namespace_name as "namespace_name: Vec<String>"
+        FROM namespace
+        WHERE namespace_id = ANY($1)
+        "#,
+        &ids
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching namespace names".to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.namespace_id.into(), row.namespace_name))
+        .collect())
+}
+
 #[allow(clippy::too_many_lines)]
 pub(crate) async fn list_namespaces(
     warehouse_id: WarehouseIdent,
@@ -109,6 +141,7 @@ pub(crate) async fn list_namespaces(
             FROM namespace n
             INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
             WHERE n.warehouse_id = $1
+            AND n.deleted_at IS NULL
             AND w.status = 'active'
             AND array_length("namespace_name", 1) = $2 + 1
             AND "namespace_name"[1:$2] = $3
@@ -141,6 +174,7 @@ pub(crate) async fn list_namespaces(
             INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
             WHERE n.warehouse_id = $1
             AND array_length("namespace_name", 1) = 1
+            AND n.deleted_at IS NULL
             AND w.status = 'active'
             AND ((n.created_at > $2 OR $2 IS NULL) OR (n.created_at = $2 AND n.namespace_id > $3))
             ORDER BY n.created_at, n.namespace_id ASC
@@ -277,6 +311,7 @@ pub(crate) async fn namespace_to_id(
         FROM namespace n
         INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
         WHERE n.warehouse_id = $1 AND namespace_name = $2
+        AND n.deleted_at IS NULL
         AND w.status = 'active'
         "#,
         *warehouse_id,
@@ -296,6 +331,35 @@ pub(crate) async fn namespace_to_id(
     }
 }
 
+pub(crate) async fn get_namespace_hierarchy_properties(
+    warehouse_id: WarehouseIdent,
+    namespace: &NamespaceIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<HashMap<String, String>>> {
+    let properties = sqlx::query_scalar!(
+        r#"
+        SELECT namespace_properties as "properties: Json<Option<HashMap<String, String>>>"
+        FROM namespace n
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        WHERE n.warehouse_id = $1
+        AND n.namespace_name = ($2::text[])[1:array_length(n.namespace_name, 1)]
+        AND n.deleted_at IS NULL
+        AND w.status = 'active'
+        ORDER BY array_length(n.namespace_name, 1)
+        "#,
+        *warehouse_id,
+        &**namespace
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching namespace properties".to_string()))?;
+
+    Ok(properties
+        .into_iter()
+        .map(|p| p.0.unwrap_or_default())
+        .collect())
+}
+
 pub(crate) async fn drop_namespace(
     warehouse_id: WarehouseIdent,
     namespace_id: NamespaceIdentUuid,
@@ -314,6 +378,7 @@ pub(crate) async fn drop_namespace(
             FROM namespace n
             INNER JOIN namespace_name nn ON n.namespace_name[1:array_length(nn.namespace_name, 1)] = nn.namespace_name
             WHERE n.warehouse_id = $1 AND n.namespace_id != $2
+            AND n.deleted_at IS NULL
         ),
         deleted AS (
             DELETE FROM namespace
@@ -373,6 +438,488 @@ pub(crate) async fn drop_namespace(
     Ok(())
 }
 
+pub(crate) async fn get_namespace_subtree(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<NamespaceSubtree> {
+    let namespaces = sqlx::query!(
+        r#"
+        WITH namespace_name AS (
+            SELECT namespace_name
+            FROM namespace n
+            INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+            WHERE n.warehouse_id = $1 AND n.namespace_id = $2
+            AND n.deleted_at IS NULL
+            AND w.status = 'active'
+        )
+        SELECT n.namespace_id, n.namespace_name as "namespace_name: Vec<String>"
+        FROM namespace n
+        INNER JOIN namespace_name nn ON n.namespace_name[1:array_length(nn.namespace_name, 1)] = nn.namespace_name
+        WHERE n.warehouse_id = $1
+        AND n.deleted_at IS NULL
+        ORDER BY array_length(n.namespace_name, 1) DESC, n.namespace_name
+        "#,
+        *warehouse_id,
+        *namespace_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching namespace subtree".to_string()))?;
+
+    if namespaces.is_empty() {
+        return Err(ErrorModel::not_found(
+            format!("Namespace {namespace_id} not found in warehouse {warehouse_id}"),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    }
+
+    let namespace_ids = namespaces
+        .iter()
+        .map(|n| n.namespace_id)
+        .collect::<Vec<_>>();
+    let tabulars = sqlx::query!(
+        r#"
+        SELECT
+            t.tabular_id,
+            t.namespace_id,
+            t.name as "tabular_name",
+            n.namespace_name as "namespace_name: Vec<String>",
+            t.typ as "typ: TabularType",
+            te.task_id as "expiration_task_id?"
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        LEFT JOIN tabular_expirations te ON t.tabular_id = te.tabular_id
+        WHERE t.namespace_id = ANY($1)
+        ORDER BY t.tabular_id
+        "#,
+        &namespace_ids
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching tabulars of namespace subtree".to_string()))?;
+
+    Ok(NamespaceSubtree {
+        namespaces: namespaces
+            .into_iter()
+            .map(|n| {
+                Ok((
+                    n.namespace_id.into(),
+                    try_parse_namespace_ident(n.namespace_name)?,
+                ))
+            })
+            .collect::<Result<_>>()?,
+        tabulars: tabulars
+            .into_iter()
+            .map(|t| {
+                Ok(NamespaceSubtreeTabular {
+                    tabular_id: match t.typ {
+                        TabularType::Table => TabularIdentUuid::Table(t.tabular_id),
+                        TabularType::View => TabularIdentUuid::View(t.tabular_id),
+                    },
+                    namespace_id: t.namespace_id.into(),
+                    tabular_ident: TableIdent::new(
+                        try_parse_namespace_ident(t.namespace_name)?,
+                        t.tabular_name,
+                    ),
+                    expiration_task_id: t.expiration_task_id.map(TaskId::from),
+                })
+            })
+            .collect::<Result<_>>()?,
+    })
+}
+
+pub(crate) async fn rename_namespace(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    destination: &NamespaceIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    // Replace the prefix of the namespace itself and of all of its children.
+    let renamed = sqlx::query_scalar!(
+        r#"
+        WITH source AS (
+            SELECT namespace_name
+            FROM namespace n
+            INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+            WHERE n.warehouse_id = $1 AND n.namespace_id = $2
+            AND n.deleted_at IS NULL
+            AND w.status = 'active'
+        )
+        UPDATE namespace n
+        SET namespace_name = $3 || n.namespace_name[array_length(s.namespace_name, 1) + 1:]
+        FROM source s
+        WHERE n.warehouse_id = $1
+        AND n.namespace_name[1:array_length(s.namespace_name, 1)] = s.namespace_name
+        AND n.deleted_at IS NULL
+        RETURNING n.namespace_id
+        "#,
+        *warehouse_id,
+        *namespace_id,
+        &**destination
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => ErrorModel::conflict(
+            format!("Namespace {} already exists", destination.to_url_string()),
+            "NamespaceAlreadyExists",
+            Some(Box::new(e)),
+        ),
+        _ => e.into_error_model("Error renaming namespace".to_string()),
+    })?;
+
+    if renamed.is_empty() {
+        return Err(ErrorModel::not_found(
+            format!("Namespace {namespace_id} not found in warehouse {warehouse_id}"),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn mark_namespace_as_deleted(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    // `now()` is the start time of the transaction, so that namespaces and tabulars
+    // share the same deletion timestamp. It identifies them on undrop.
+    let deleted = sqlx::query_scalar!(
+        r#"
+        WITH source AS (
+            SELECT namespace_name
+            FROM namespace n
+            INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+            WHERE n.warehouse_id = $1 AND n.namespace_id = $2
+            AND n.deleted_at IS NULL
+            AND w.status = 'active'
+        )
+        UPDATE namespace n
+        SET deleted_at = now()
+        FROM source s
+        WHERE n.warehouse_id = $1
+        AND n.namespace_name[1:array_length(s.namespace_name, 1)] = s.namespace_name
+        AND n.deleted_at IS NULL
+        RETURNING n.namespace_id
+        "#,
+        *warehouse_id,
+        *namespace_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error marking namespace as deleted".to_string()))?;
+
+    if deleted.is_empty() {
+        return Err(ErrorModel::not_found(
+            format!("Namespace {namespace_id} not found in warehouse {warehouse_id}"),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE tabular
+        SET deleted_at = now()
+        WHERE namespace_id = ANY($1)
+        AND deleted_at IS NULL
+        "#,
+        &deleted
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error marking tabulars as deleted".to_string()))?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_lines)]
+pub(crate) async fn undrop_namespace(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<UndroppedNamespace> {
+    let source = sqlx::query!(
+        r#"
+        SELECT n.namespace_name as "namespace_name: Vec<String>", n.deleted_at as "deleted_at!"
+        FROM namespace n
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        WHERE n.warehouse_id = $1 AND n.namespace_id = $2
+        AND n.deleted_at IS NOT NULL
+        AND w.status = 'active'
+        "#,
+        *warehouse_id,
+        *namespace_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching deleted namespace".to_string()))?
+    .ok_or_else(|| {
+        ErrorModel::not_found(
+            format!("Deleted namespace {namespace_id} not found in warehouse {warehouse_id}"),
+            "NamespaceNotFound",
+            None,
+        )
+    })?;
+    let namespace = try_parse_namespace_ident(source.namespace_name)?;
+
+    let parent_id = if let Some(parent) = namespace.parent() {
+        let parent_id = namespace_to_id(warehouse_id, &parent, transaction)
+            .await?
+            .ok_or_else(|| {
+                ErrorModel::conflict(
+                    format!(
+                        "Parent namespace {} of {} does not exist",
+                        parent.to_url_string(),
+                        namespace.to_url_string()
+                    ),
+                    "NamespaceParentNotFound",
+                    None,
+                )
+            })?;
+        Some(parent_id)
+    } else {
+        None
+    };
+
+    let restored = sqlx::query_scalar!(
+        r#"
+        UPDATE namespace
+        SET deleted_at = NULL
+        WHERE warehouse_id = $1
+        AND namespace_name[1:array_length($2::text[], 1)] = $2
+        AND deleted_at = $3
+        RETURNING namespace_id
+        "#,
+        *warehouse_id,
+        &*namespace,
+        source.deleted_at
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => ErrorModel::conflict(
+            format!("Namespace {} already exists", namespace.to_url_string()),
+            "NamespaceAlreadyExists",
+            Some(Box::new(e)),
+        ),
+        _ => e.into_error_model("Error undropping namespace".to_string()),
+    })?;
+
+    let tabulars = sqlx::query_scalar!(
+        r#"
+        UPDATE tabular
+        SET deleted_at = NULL
+        WHERE namespace_id = ANY($1)
+        AND deleted_at = $2
+        RETURNING tabular_id
+        "#,
+        &restored,
+        source.deleted_at
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => ErrorModel::conflict(
+            "A table or view of the namespace has been recreated in the meantime",
+            "TabularAlreadyExists",
+            Some(Box::new(e)),
+        ),
+        _ => e.into_error_model("Error undropping tabulars of namespace".to_string()),
+    })?;
+
+    let namespace_expiration_tasks = sqlx::query_scalar!(
+        r#"
+        SELECT task_id FROM namespace_expirations
+        WHERE namespace_id = ANY($1)
+        "#,
+        &restored
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching namespace expiration tasks".to_string()))?;
+
+    let tabular_expiration_tasks = sqlx::query_scalar!(
+        r#"
+        SELECT task_id FROM tabular_expirations
+        WHERE tabular_id = ANY($1)
+        "#,
+        &tabulars
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching tabular expiration tasks".to_string()))?;
+
+    Ok(UndroppedNamespace {
+        namespace,
+        parent_id,
+        namespace_expiration_tasks: namespace_expiration_tasks
+            .into_iter()
+            .map(TaskId::from)
+            .collect(),
+        tabular_expiration_tasks: tabular_expiration_tasks
+            .into_iter()
+            .map(TaskId::from)
+            .collect(),
+    })
+}
+
+pub(crate) async fn list_deleted_namespaces(
+    warehouse_id: WarehouseIdent,
+    pagination_query: PaginationQuery,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<PaginatedMapping<NamespaceIdentUuid, (NamespaceIdent, DeletionDetails)>> {
+    let page_size = pagination_query
+        .page_size
+        .map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+    let token = pagination_query
+        .page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?;
+    let (token_ts, token_id) = token
+        .as_ref()
+        .map(
+            |PaginateToken::V1(V1PaginateToken { created_at, id }): &PaginateToken<Uuid>| {
+                (created_at, id)
+            },
+        )
+        .unzip();
+
+    let namespaces = sqlx::query!(
+        r#"
+        SELECT
+            n.namespace_id,
+            n.namespace_name as "namespace_name: Vec<String>",
+            n.created_at,
+            n.deleted_at as "deleted_at!",
+            t.suspend_until as "expiration_date?",
+            t.task_id as "expiration_task_id?"
+        FROM namespace n
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        LEFT JOIN namespace_expirations ne ON n.namespace_id = ne.namespace_id
+        LEFT JOIN task t ON ne.task_id = t.task_id
+        WHERE n.warehouse_id = $1
+        AND n.deleted_at IS NOT NULL
+        AND w.status = 'active'
+        AND ((n.created_at > $2 OR $2 IS NULL) OR (n.created_at = $2 AND n.namespace_id > $3))
+        ORDER BY n.created_at, n.namespace_id ASC
+        LIMIT $4
+        "#,
+        *warehouse_id,
+        token_ts,
+        token_id,
+        page_size
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching deleted namespaces".to_string()))?;
+
+    let mut namespace_map = PaginatedMapping::with_capacity(namespaces.len());
+    for namespace in namespaces {
+        let deletion_details = DeletionDetails {
+            expiration_task_id: namespace.expiration_task_id.ok_or(ErrorModel::internal(
+                "Cleanup task ID missing for deleted namespace",
+                "InternalDatabaseError",
+                None,
+            ))?,
+            expiration_date: namespace.expiration_date.ok_or(ErrorModel::internal(
+                "Cleanup date missing for deleted namespace",
+                "InternalDatabaseError",
+                None,
+            ))?,
+            deleted_at: namespace.deleted_at,
+            created_at: namespace.created_at,
+        };
+        namespace_map.insert(
+            namespace.namespace_id.into(),
+            (
+                try_parse_namespace_ident(namespace.namespace_name)?,
+                deletion_details,
+            ),
+            PaginateToken::V1(V1PaginateToken {
+                id: namespace.namespace_id,
+                created_at: namespace.created_at,
+            })
+            .to_string(),
+        );
+    }
+
+    Ok(namespace_map)
+}
+
+pub(crate) async fn drop_deleted_namespace(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<DroppedTabular>> {
+    let tabulars = sqlx::query!(
+        r#"
+        WITH deleted AS (
+            DELETE FROM tabular t
+            USING namespace n
+            WHERE t.namespace_id = n.namespace_id
+            AND n.warehouse_id = $1 AND n.namespace_id = $2
+            AND n.deleted_at IS NOT NULL
+            RETURNING t.tabular_id, t.typ, t.location
+        )
+        SELECT
+            d.tabular_id as "tabular_id!",
+            d.typ as "typ!: TabularType",
+            d.location as "location!",
+            te.task_id as "expiration_task_id?"
+        FROM deleted d
+        LEFT JOIN tabular_expirations te ON d.tabular_id = te.tabular_id
+        "#,
+        *warehouse_id,
+        *namespace_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error dropping tabulars of namespace".to_string()))?;
+
+    let deleted = sqlx::query!(
+        r#"
+        DELETE FROM namespace
+        WHERE warehouse_id = $1 AND namespace_id = $2
+        AND deleted_at IS NOT NULL
+        "#,
+        *warehouse_id,
+        *namespace_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error dropping namespace".to_string()))?;
+
+    if deleted.rows_affected() == 0 {
+        return Err(ErrorModel::not_found(
+            format!("Deleted namespace {namespace_id} not found in warehouse {warehouse_id}"),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    }
+
+    Ok(tabulars
+        .into_iter()
+        .map(|t| DroppedTabular {
+            tabular_id: match t.typ {
+                TabularType::Table => TabularIdentUuid::Table(t.tabular_id),
+                TabularType::View => TabularIdentUuid::View(t.tabular_id),
+            },
+            location: t.location,
+            expiration_task_id: t.expiration_task_id.map(TaskId::from),
+        })
+        .collect())
+}
+
 pub(crate) async fn update_namespace_properties(
     warehouse_id: WarehouseIdent,
     namespace_id: NamespaceIdentUuid,
@@ -393,6 +940,7 @@ pub(crate) async fn update_namespace_properties(
         UPDATE namespace
         SET namespace_properties = $1
         WHERE warehouse_id = $2 AND namespace_id = $3
+        AND deleted_at IS NULL
         AND warehouse_id IN (
             SELECT warehouse_id FROM warehouse WHERE status = 'active'
         )
@@ -551,6 +1099,52 @@ pub(crate) mod tests {
             .expect("Error dropping namespace");
     }
 
+    #[sqlx::test]
+    async fn test_get_namespace_hierarchy_properties(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        let parent = NamespaceIdent::from_vec(vec!["parent".to_string()]).unwrap();
+        let child =
+            NamespaceIdent::from_vec(vec!["parent".to_string(), "child".to_string()]).unwrap();
+        let sibling =
+            NamespaceIdent::from_vec(vec!["parent".to_string(), "sibling".to_string()]).unwrap();
+        for (namespace, value) in [
+            (&parent, "parent"),
+            (&child, "child"),
+            (&sibling, "sibling"),
+        ] {
+            let properties = HashMap::from_iter(vec![("key".to_string(), value.to_string())]);
+            initialize_namespace(state.clone(), warehouse_id, namespace, Some(properties)).await;
+        }
+
+        let mut transaction = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        let grandchild = NamespaceIdent::from_vec(vec![
+            "parent".to_string(),
+            "child".to_string(),
+            "grandchild".to_string(),
+        ])
+        .unwrap();
+        let properties = PostgresCatalog::get_namespace_hierarchy_properties(
+            warehouse_id,
+            &grandchild,
+            transaction.transaction(),
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        assert_eq!(
+            properties
+                .iter()
+                .map(|p| p["key"].as_str())
+                .collect::<Vec<_>>(),
+            vec!["parent", "child"]
+        );
+    }
+
     #[sqlx::test]
     async fn test_pagination(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -709,6 +1303,244 @@ pub(crate) mod tests {
             .unwrap();
     }
 
+    #[sqlx::test]
+    async fn test_get_namespace_subtree(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let parent = NamespaceIdent::from_vec(vec!["test".to_string()]).unwrap();
+        let (parent_id, _) = initialize_namespace(state.clone(), warehouse_id, &parent, None).await;
+        let child =
+            NamespaceIdent::from_vec(vec!["test".to_string(), "child".to_string()]).unwrap();
+        let (child_id, _) = initialize_namespace(state.clone(), warehouse_id, &child, None).await;
+        let sibling = NamespaceIdent::from_vec(vec!["test2".to_string()]).unwrap();
+        initialize_namespace(state.clone(), warehouse_id, &sibling, None).await;
+        let table =
+            initialize_table(warehouse_id, state.clone(), true, Some(child.clone()), None).await;
+        initialize_table(warehouse_id, state.clone(), false, Some(sibling), None).await;
+
+        let mut transaction = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        let subtree = get_namespace_subtree(warehouse_id, parent_id, transaction.transaction())
+            .await
+            .unwrap();
+        assert_eq!(
+            subtree.namespaces,
+            vec![(child_id, child.clone()), (parent_id, parent)]
+        );
+        assert_eq!(
+            subtree.tabulars,
+            vec![NamespaceSubtreeTabular {
+                tabular_id: TabularIdentUuid::Table(*table.table_id),
+                namespace_id: child_id,
+                tabular_ident: table.table_ident,
+                expiration_task_id: None,
+            }]
+        );
+
+        let err = get_namespace_subtree(
+            warehouse_id,
+            NamespaceIdentUuid::default(),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "NamespaceNotFound");
+    }
+
+    #[sqlx::test]
+    async fn test_rename_namespace(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let team = NamespaceIdent::from_vec(vec!["team_a".to_string()]).unwrap();
+        let (team_id, _) = initialize_namespace(state.clone(), warehouse_id, &team, None).await;
+        let raw = NamespaceIdent::from_vec(vec!["team_a".to_string(), "raw".to_string()]).unwrap();
+        let (raw_id, _) = initialize_namespace(state.clone(), warehouse_id, &raw, None).await;
+        let domain = NamespaceIdent::from_vec(vec!["domain".to_string()]).unwrap();
+        initialize_namespace(state.clone(), warehouse_id, &domain, None).await;
+        let table =
+            initialize_table(warehouse_id, state.clone(), false, Some(raw.clone()), None).await;
+
+        let destination =
+            NamespaceIdent::from_vec(vec!["domain".to_string(), "sales".to_string()]).unwrap();
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        rename_namespace(
+            warehouse_id,
+            team_id,
+            &destination,
+            transaction.transaction(),
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut transaction = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        let subtree = get_namespace_subtree(warehouse_id, team_id, transaction.transaction())
+            .await
+            .unwrap();
+        let moved_raw = NamespaceIdent::from_vec(vec![
+            "domain".to_string(),
+            "sales".to_string(),
+            "raw".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(
+            subtree.namespaces,
+            vec![(raw_id, moved_raw.clone()), (team_id, destination.clone())]
+        );
+        assert_eq!(
+            subtree.tabulars,
+            vec![NamespaceSubtreeTabular {
+                tabular_id: TabularIdentUuid::Table(*table.table_id),
+                namespace_id: raw_id,
+                tabular_ident: TableIdent::new(moved_raw, table.table_ident.name),
+                expiration_task_id: None,
+            }]
+        );
+        assert!(
+            namespace_to_id(warehouse_id, &team, transaction.transaction())
+                .await
+                .unwrap()
+                .is_none()
+        );
+        drop(transaction);
+
+        // Moving onto an existing namespace fails
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let err = rename_namespace(warehouse_id, raw_id, &domain, transaction.transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT);
+        drop(transaction);
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let err = rename_namespace(
+            warehouse_id,
+            NamespaceIdentUuid::default(),
+            &team,
+            transaction.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "NamespaceNotFound");
+    }
+
+    #[sqlx::test]
+    async fn test_soft_delete_and_undrop_namespace(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let team = NamespaceIdent::from_vec(vec!["team_a".to_string()]).unwrap();
+        let (team_id, _) = initialize_namespace(state.clone(), warehouse_id, &team, None).await;
+        let raw = NamespaceIdent::from_vec(vec!["team_a".to_string(), "raw".to_string()]).unwrap();
+        let (raw_id, _) = initialize_namespace(state.clone(), warehouse_id, &raw, None).await;
+        let table =
+            initialize_table(warehouse_id, state.clone(), false, Some(raw.clone()), None).await;
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        mark_namespace_as_deleted(warehouse_id, team_id, transaction.transaction())
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut transaction = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        assert!(
+            namespace_to_id(warehouse_id, &raw, transaction.transaction())
+                .await
+                .unwrap()
+                .is_none()
+        );
+        drop(transaction);
+
+        // The name is free again
+        let (new_team_id, _) = initialize_namespace(state.clone(), warehouse_id, &team, None).await;
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let err = undrop_namespace(warehouse_id, team_id, transaction.transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.r#type, "NamespaceAlreadyExists");
+        drop(transaction);
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        drop_namespace(warehouse_id, new_team_id, transaction.transaction())
+            .await
+            .unwrap();
+        // The parent is still deleted
+        let err = undrop_namespace(warehouse_id, raw_id, transaction.transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.r#type, "NamespaceParentNotFound");
+        let undropped = undrop_namespace(warehouse_id, team_id, transaction.transaction())
+            .await
+            .unwrap();
+        assert_eq!(undropped.namespace, team);
+        assert_eq!(undropped.parent_id, None);
+        transaction.commit().await.unwrap();
+
+        let mut transaction = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        let subtree = get_namespace_subtree(warehouse_id, team_id, transaction.transaction())
+            .await
+            .unwrap();
+        assert_eq!(
+            subtree.namespaces,
+            vec![(raw_id, raw.clone()), (team_id, team.clone())]
+        );
+        assert_eq!(
+            subtree.tabulars,
+            vec![NamespaceSubtreeTabular {
+                tabular_id: TabularIdentUuid::Table(*table.table_id),
+                namespace_id: raw_id,
+                tabular_ident: table.table_ident.clone(),
+                expiration_task_id: None,
+            }]
+        );
+        drop(transaction);
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        mark_namespace_as_deleted(warehouse_id, team_id, transaction.transaction())
+            .await
+            .unwrap();
+        let dropped = drop_deleted_namespace(warehouse_id, raw_id, transaction.transaction())
+            .await
+            .unwrap();
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(
+            dropped[0].tabular_id,
+            TabularIdentUuid::Table(*table.table_id)
+        );
+        let dropped = drop_deleted_namespace(warehouse_id, team_id, transaction.transaction())
+            .await
+            .unwrap();
+        assert!(dropped.is_empty());
+        let err = drop_deleted_namespace(warehouse_id, team_id, transaction.transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.r#type, "NamespaceNotFound");
+        transaction.commit().await.unwrap();
+    }
+
     #[sqlx::test]
     async fn test_case_insensitive_but_preserve_case(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -13,11 +13,12 @@ use crate::api::iceberg::v1::{PaginatedMapping, PaginationQuery, MAX_PAGE_SIZE};
 
 use crate::implementations::postgres::pagination::{PaginateToken, V1PaginateToken};
 use crate::service::task_queue::TaskId;
-use crate::service::DeletionDetails;
+use crate::service::{DeletionDetails, GetNamespaceResponse};
 use crate::service::{TabularIdentBorrowed, TabularIdentOwned, TabularIdentUuid};
 use chrono::Utc;
 use iceberg_ext::configs::Location;
 use sqlx::postgres::PgArguments;
+use sqlx::types::Json;
 use sqlx::{Arguments, Execute, FromRow, Postgres, QueryBuilder};
 use std::collections::{HashMap, HashSet};
 use std::default::Default;
@@ -33,6 +34,93 @@ pub(crate) enum TabularType {
     View,
 }
 
+/// Location of a tabular in the namespace hierarchy.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TabularParent {
+    pub(crate) namespace_id: NamespaceIdentUuid,
+    pub(crate) namespace_name: Vec<String>,
+    pub(crate) name: String,
+}
+
+/// Get the namespace of a tabular, irrespective of the warehouse it belongs to.
+pub(crate) async fn get_tabular_namespace<'e, 'c: 'e, E>(
+    tabular_id: TabularIdentUuid,
+    connection: E,
+) -> Result<Option<GetNamespaceResponse>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            n.namespace_id,
+            n.warehouse_id,
+            n.namespace_name as "namespace_name: Vec<String>",
+            n.namespace_properties as "properties: Json<Option<HashMap<String, String>>>"
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        WHERE t.tabular_id = $1
+        "#,
+        *tabular_id
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching namespace of tabular".to_string()))?;
+
+    row.map(|row| {
+        Ok(GetNamespaceResponse {
+            namespace: NamespaceIdent::from_vec(row.namespace_name).map_err(|e| {
+                ErrorModel::internal(
+                    "Error converting namespace",
+                    "NamespaceConversionError",
+                    Some(Box::new(e)),
+                )
+            })?,
+            namespace_id: row.namespace_id.into(),
+            warehouse_id: row.warehouse_id.into(),
+            properties: row.properties.0,
+        })
+    })
+    .transpose()
+}
+
+/// Get the parent namespace and name of the given tabulars, irrespective of the
+/// warehouse they belong to. Tabulars that do not exist are not part of the result.
+pub(crate) async fn get_tabular_parents_by_id<'e, 'c: 'e, E>(
+    tabular_ids: &[Uuid],
+    connection: E,
+) -> Result<HashMap<Uuid, TabularParent>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.tabular_id, t.name, n.namespace_id, n.namespace_name as "namespace_name: Vec<String>"
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        WHERE t.tabular_id = ANY($1)
+        "#,
+        tabular_ids
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching tabular parents".to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.tabular_id,
+                TabularParent {
+                    namespace_id: row.namespace_id.into(),
+                    namespace_name: row.namespace_name,
+                    name: row.name,
+                },
+            )
+        })
+        .collect())
+}
+
 pub(crate) async fn tabular_ident_to_id<'a, 'e, 'c: 'e, E>(
     warehouse_id: WarehouseIdent,
     table: &TabularIdentBorrowed<'a>,
@@ -53,6 +141,7 @@ where
         INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
         WHERE n.namespace_name = $1 AND t.name = $2
         AND n.warehouse_id = $3
+        AND n.deleted_at IS NULL
         AND w.status = 'active'
         AND t.typ = $4
         AND (t.deleted_at IS NULL OR $5)
@@ -204,7 +293,7 @@ fn append_dynamic_filters(
     query_builder: &mut QueryBuilder<'_, Postgres>,
     args: &mut PgArguments,
 ) -> Result<()> {
-    query_builder.push(r" AND (n.namespace_name, t.name, t.typ) IN ");
+    query_builder.push(r" AND n.deleted_at IS NULL AND (n.namespace_name, t.name, t.typ) IN ");
     query_builder.push("(");
 
     let mut arg_idx = args.len() + 1;
@@ -372,7 +461,7 @@ where
         LEFT JOIN tabular_expirations te ON t.tabular_id = te.tabular_id
         LEFT JOIN task tt ON te.task_id = tt.task_id
         WHERE n.warehouse_id = $1
-            AND (namespace_name = $2 OR $2 IS NULL)
+            AND ((namespace_name = $2 AND n.deleted_at IS NULL) OR $2 IS NULL)
             AND (n.namespace_id = $11 OR $11 IS NULL)
             AND w.status = 'active'
             AND (t.typ = $3 OR $3 IS NULL)
@@ -513,6 +602,7 @@ pub(crate) async fn rename_tabular(
                 SELECT namespace_id
                 FROM namespace
                 WHERE warehouse_id = $2 AND namespace_name = $3
+                AND deleted_at IS NULL
             )
             WHERE tabular_id = $4 AND typ = $5 AND metadata_location IS NOT NULL
                 AND ti.name = $6
@@ -579,6 +669,30 @@ pub(crate) async fn clear_tabular_deleted_at(
     tabular_ids: &[Uuid],
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> Result<Vec<TaskId>> {
+    let in_deleted_namespace = sqlx::query_scalar!(
+        r#"
+        SELECT t.tabular_id
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        WHERE t.tabular_id = any($1)
+        AND n.deleted_at IS NOT NULL
+        "#,
+        tabular_ids
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching namespaces of tabulars"))?;
+    if !in_deleted_namespace.is_empty() {
+        return Err(ErrorModel::conflict(
+            format!(
+                "Tabulars {in_deleted_namespace:?} are contained in a deleted namespace. Undrop the namespace first."
+            ),
+            "NamespaceDeleted",
+            None,
+        )
+        .into());
+    }
+
     let deleted = sqlx::query!(
         r#"
         UPDATE tabular t
@@ -694,7 +808,7 @@ pub(crate) async fn drop_tabular<'a>(
     Ok(location)
 }
 
-fn try_parse_namespace_ident(namespace: Vec<String>) -> Result<NamespaceIdent> {
+pub(crate) fn try_parse_namespace_ident(namespace: Vec<String>) -> Result<NamespaceIdent> {
     NamespaceIdent::from_vec(namespace).map_err(|e| {
         ErrorModel::internal(
             "Error parsing namespace",
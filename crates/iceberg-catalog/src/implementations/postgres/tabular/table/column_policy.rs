@@ -0,0 +1,180 @@
+use crate::implementations::postgres::{dbutils::DBErrorHandler as _, CatalogState};
+use crate::service::authn::UserId;
+use crate::service::authz::{ColumnPolicy, ColumnPolicyEffect, PolicyPrincipal};
+use crate::service::{Result, RoleId, TableIdentUuid};
+
+#[derive(Debug, Copy, Clone, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "column_policy_effect", rename_all = "kebab-case")]
+enum DbColumnPolicyEffect {
+    Deny,
+    Mask,
+}
+
+impl From<ColumnPolicyEffect> for DbColumnPolicyEffect {
+    fn from(effect: ColumnPolicyEffect) -> Self {
+        match effect {
+            ColumnPolicyEffect::Deny => Self::Deny,
+            ColumnPolicyEffect::Mask => Self::Mask,
+        }
+    }
+}
+
+impl From<DbColumnPolicyEffect> for ColumnPolicyEffect {
+    fn from(effect: DbColumnPolicyEffect) -> Self {
+        match effect {
+            DbColumnPolicyEffect::Deny => Self::Deny,
+            DbColumnPolicyEffect::Mask => Self::Mask,
+        }
+    }
+}
+
+pub(crate) async fn get_column_policies(
+    table_id: TableIdentUuid,
+    catalog_state: CatalogState,
+) -> Result<Vec<ColumnPolicy>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT field_id,
+               effect as "effect: DbColumnPolicyEffect",
+               exempt_users,
+               exempt_roles
+        FROM column_policy
+        WHERE table_id = $1
+        ORDER BY field_id
+        "#,
+        *table_id
+    )
+    .fetch_all(&catalog_state.read_pool())
+    .await
+    .map_err(|e| e.into_error_model("Error fetching column policies"))?;
+
+    rows.into_iter()
+        .map(|row| {
+            let users = row
+                .exempt_users
+                .into_iter()
+                .map(|user| UserId::try_from(user).map(PolicyPrincipal::User))
+                .collect::<Result<Vec<_>>>()?;
+            let roles = row
+                .exempt_roles
+                .into_iter()
+                .map(|role| PolicyPrincipal::Role(RoleId::new(role)));
+            Ok(ColumnPolicy {
+                field_id: row.field_id,
+                effect: row.effect.into(),
+                exempt: users.into_iter().chain(roles).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Replace all column policies of a table.
+pub(crate) async fn set_column_policies(
+    table_id: TableIdentUuid,
+    policies: &[ColumnPolicy],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"DELETE FROM column_policy WHERE table_id = $1"#,
+        *table_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting column policies"))?;
+
+    for policy in policies {
+        let users = policy
+            .exempt
+            .iter()
+            .filter_map(|principal| match principal {
+                PolicyPrincipal::User(user_id) => Some(user_id.to_string()),
+                PolicyPrincipal::Role(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let roles = policy
+            .exempt
+            .iter()
+            .filter_map(|principal| match principal {
+                PolicyPrincipal::Role(role_id) => Some(**role_id),
+                PolicyPrincipal::User(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO column_policy (table_id, field_id, effect, exempt_users, exempt_roles)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            *table_id,
+            policy.field_id,
+            DbColumnPolicyEffect::from(policy.effect) as _,
+            &users,
+            &roles,
+        )
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error inserting column policy"))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::implementations::postgres::tabular::table::tests::initialize_table;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+
+    #[sqlx::test]
+    async fn test_set_and_get_column_policies(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+
+        let policies = get_column_policies(table.table_id, state.clone())
+            .await
+            .unwrap();
+        assert!(policies.is_empty());
+
+        let role_id = RoleId::default();
+        let policies = vec![
+            ColumnPolicy {
+                field_id: 1,
+                effect: ColumnPolicyEffect::Mask,
+                exempt: vec![],
+            },
+            ColumnPolicy {
+                field_id: 2,
+                effect: ColumnPolicyEffect::Deny,
+                exempt: vec![
+                    PolicyPrincipal::User(UserId::oidc("alice").unwrap()),
+                    PolicyPrincipal::Role(role_id),
+                ],
+            },
+        ];
+        let mut transaction = pool.begin().await.unwrap();
+        set_column_policies(table.table_id, &policies, &mut transaction)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(
+            get_column_policies(table.table_id, state.clone())
+                .await
+                .unwrap(),
+            policies
+        );
+
+        // Setting policies replaces existing ones
+        let mut transaction = pool.begin().await.unwrap();
+        set_column_policies(table.table_id, &policies[1..], &mut transaction)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(
+            get_column_policies(table.table_id, state.clone())
+                .await
+                .unwrap(),
+            policies[1..].to_vec()
+        );
+    }
+}
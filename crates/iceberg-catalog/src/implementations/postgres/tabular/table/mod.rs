@@ -1,15 +1,24 @@
+mod column_policy;
 mod commit;
 mod common;
 mod create;
+mod ref_protection;
+mod row_filter;
 
+pub(crate) use column_policy::{get_column_policies, set_column_policies};
 pub(crate) use commit::commit_table_transaction;
 pub(crate) use create::create_table;
+pub(crate) use ref_protection::{
+    create_ref_protection_rule, delete_ref_protection_rule, get_table_ref_protection_rules,
+    list_ref_protection_rules,
+};
+pub(crate) use row_filter::{get_row_filters, set_row_filters};
 
 use crate::implementations::postgres::{dbutils::DBErrorHandler as _, CatalogState};
 use crate::{
     service::{
         storage::StorageProfile, ErrorModel, GetTableMetadataResponse, LoadTableResponse, Result,
-        TableIdent, TableIdentUuid,
+        TableAndNamespaceProperties, TableIdent, TableIdentUuid,
     },
     SecretIdent, WarehouseIdent,
 };
@@ -682,6 +691,43 @@ pub(crate) async fn get_table_metadata_by_id(
     }))
 }
 
+pub(crate) async fn get_table_and_namespace_properties(
+    table_id: TableIdentUuid,
+    catalog_state: CatalogState,
+) -> Result<Option<TableAndNamespaceProperties>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT
+            n.namespace_properties as "namespace_properties: Json<Option<HashMap<String, String>>>",
+            tp.keys as "keys: Vec<String>",
+            tp.values as "values: Vec<String>"
+        FROM "table" t
+        INNER JOIN tabular ti ON t.table_id = ti.tabular_id
+        INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
+        LEFT JOIN LATERAL (
+            SELECT ARRAY_AGG(key) as keys, ARRAY_AGG(value) as values
+            FROM table_properties
+            WHERE table_id = t.table_id
+        ) tp ON true
+        WHERE t.table_id = $1
+        "#,
+        *table_id
+    )
+    .fetch_optional(&catalog_state.read_pool())
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table properties"))?;
+
+    Ok(row.map(|row| TableAndNamespaceProperties {
+        table_properties: row
+            .keys
+            .unwrap_or_default()
+            .into_iter()
+            .zip(row.values.unwrap_or_default())
+            .collect(),
+        namespace_properties: row.namespace_properties.0.unwrap_or_default(),
+    }))
+}
+
 pub(crate) async fn get_table_metadata_by_s3_location(
     warehouse_id: WarehouseIdent,
     location: &Location,
@@ -1743,4 +1789,68 @@ pub(crate) mod tests {
         .unwrap()
         .is_none());
     }
+
+    #[sqlx::test]
+    async fn test_get_table_and_namespace_properties(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = NamespaceIdent::from_vec(vec!["my_namespace".to_string()]).unwrap();
+        initialize_namespace(
+            state.clone(),
+            warehouse_id,
+            &namespace,
+            Some(HashMap::from_iter([(
+                "owner".to_string(),
+                "me".to_string(),
+            )])),
+        )
+        .await;
+        let table =
+            initialize_table(warehouse_id, state.clone(), false, Some(namespace), None).await;
+
+        assert!(get_table_and_namespace_properties(
+            TableIdentUuid::from(Uuid::now_v7()),
+            state.clone()
+        )
+        .await
+        .unwrap()
+        .is_none());
+
+        sqlx::query!(
+            r#"INSERT INTO table_properties (table_id, key, value) VALUES ($1, 'format', 'parquet')
+               ON CONFLICT (key, table_id) DO UPDATE SET value = EXCLUDED.value"#,
+            *table.table_id
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let properties = get_table_and_namespace_properties(table.table_id, state.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            properties
+                .table_properties
+                .get("format")
+                .map(String::as_str),
+            Some("parquet")
+        );
+        assert_eq!(
+            properties
+                .namespace_properties
+                .get("owner")
+                .map(String::as_str),
+            Some("me")
+        );
+
+        let tabular_namespace = crate::implementations::postgres::tabular::get_tabular_namespace(
+            table.table_id.into(),
+            &pool,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(tabular_namespace.warehouse_id, warehouse_id);
+        assert_eq!(tabular_namespace.namespace, table.namespace);
+    }
 }
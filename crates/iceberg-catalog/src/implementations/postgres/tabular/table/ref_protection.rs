@@ -0,0 +1,280 @@
+use crate::implementations::postgres::{dbutils::DBErrorHandler as _, CatalogState};
+use crate::service::authz::{RefProtectionRule, RefProtectionScope};
+use crate::service::{ErrorModel, Result, RoleId, TableIdentUuid};
+use crate::WarehouseIdent;
+
+struct RefProtectionRuleRow {
+    rule_id: uuid::Uuid,
+    namespace_id: Option<uuid::Uuid>,
+    table_id: Option<uuid::Uuid>,
+    ref_pattern: String,
+    allowed_committers: Vec<uuid::Uuid>,
+    prevent_deletion: bool,
+    fast_forward_only: bool,
+}
+
+impl From<RefProtectionRuleRow> for RefProtectionRule {
+    fn from(row: RefProtectionRuleRow) -> Self {
+        let scope = match (row.namespace_id, row.table_id) {
+            (_, Some(table_id)) => RefProtectionScope::Table { table_id },
+            (Some(namespace_id), None) => RefProtectionScope::Namespace { namespace_id },
+            (None, None) => RefProtectionScope::Warehouse,
+        };
+        Self {
+            rule_id: row.rule_id,
+            scope,
+            ref_pattern: row.ref_pattern,
+            allowed_committers: row
+                .allowed_committers
+                .into_iter()
+                .map(RoleId::new)
+                .collect(),
+            prevent_deletion: row.prevent_deletion,
+            fast_forward_only: row.fast_forward_only,
+        }
+    }
+}
+
+/// List all ref protection rules of a warehouse, ordered by id.
+pub(crate) async fn list_ref_protection_rules(
+    warehouse_id: WarehouseIdent,
+    catalog_state: CatalogState,
+) -> Result<Vec<RefProtectionRule>> {
+    let rows = sqlx::query_as!(
+        RefProtectionRuleRow,
+        r#"
+        SELECT rule_id, namespace_id, table_id, ref_pattern, allowed_committers,
+               prevent_deletion, fast_forward_only
+        FROM ref_protection_rule
+        WHERE warehouse_id = $1
+        ORDER BY rule_id
+        "#,
+        *warehouse_id
+    )
+    .fetch_all(&catalog_state.read_pool())
+    .await
+    .map_err(|e| e.into_error_model("Error fetching ref protection rules"))?;
+
+    Ok(rows.into_iter().map(Into::into).collect())
+}
+
+/// Get the ref protection rules that apply to a table: rules of its warehouse,
+/// of its namespace or any parent namespace, and of the table itself.
+pub(crate) async fn get_table_ref_protection_rules(
+    warehouse_id: WarehouseIdent,
+    table_id: TableIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<RefProtectionRule>> {
+    let rows = sqlx::query_as!(
+        RefProtectionRuleRow,
+        r#"
+        WITH table_namespace AS (
+            SELECT n.namespace_name
+            FROM tabular ti
+            INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
+            WHERE ti.tabular_id = $2 AND n.warehouse_id = $1
+        )
+        SELECT r.rule_id, r.namespace_id, r.table_id, r.ref_pattern, r.allowed_committers,
+               r.prevent_deletion, r.fast_forward_only
+        FROM ref_protection_rule r
+        LEFT JOIN namespace rn ON r.namespace_id = rn.namespace_id
+        WHERE r.warehouse_id = $1
+        AND (
+            (r.namespace_id IS NULL AND r.table_id IS NULL)
+            OR r.table_id = $2
+            OR EXISTS (
+                SELECT 1 FROM table_namespace tn
+                WHERE tn.namespace_name[1:array_length(rn.namespace_name, 1)] = rn.namespace_name
+            )
+        )
+        ORDER BY r.rule_id
+        "#,
+        *warehouse_id,
+        *table_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching ref protection rules of table"))?;
+
+    Ok(rows.into_iter().map(Into::into).collect())
+}
+
+pub(crate) async fn create_ref_protection_rule(
+    warehouse_id: WarehouseIdent,
+    rule: &RefProtectionRule,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let (namespace_id, table_id) = match rule.scope {
+        RefProtectionScope::Warehouse => (None, None),
+        RefProtectionScope::Namespace { namespace_id } => (Some(namespace_id), None),
+        RefProtectionScope::Table { table_id } => (None, Some(table_id)),
+    };
+    let allowed_committers = rule
+        .allowed_committers
+        .iter()
+        .map(|role_id| **role_id)
+        .collect::<Vec<_>>();
+
+    // Namespaces and tables must belong to the warehouse of the rule
+    let inserted = sqlx::query_scalar!(
+        r#"
+        INSERT INTO ref_protection_rule (rule_id, warehouse_id, namespace_id, table_id, ref_pattern, allowed_committers, prevent_deletion, fast_forward_only)
+        SELECT $1::uuid, $2::uuid, $3::uuid, $4::uuid, $5::text, $6::uuid[], $7::boolean, $8::boolean
+        WHERE EXISTS (SELECT 1 FROM warehouse WHERE warehouse_id = $2)
+        AND ($3::uuid IS NULL OR EXISTS (
+            SELECT 1 FROM namespace WHERE namespace_id = $3 AND warehouse_id = $2
+        ))
+        AND ($4::uuid IS NULL OR EXISTS (
+            SELECT 1
+            FROM "table" t
+            INNER JOIN tabular ti ON t.table_id = ti.tabular_id
+            INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
+            WHERE t.table_id = $4 AND n.warehouse_id = $2
+        ))
+        RETURNING rule_id
+        "#,
+        rule.rule_id,
+        *warehouse_id,
+        namespace_id,
+        table_id,
+        rule.ref_pattern,
+        &allowed_committers,
+        rule.prevent_deletion,
+        rule.fast_forward_only,
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error creating ref protection rule"))?;
+
+    if inserted.is_none() {
+        return Err(ErrorModel::not_found(
+            format!("Scope of ref protection rule not found in warehouse {warehouse_id}"),
+            "RefProtectionScopeNotFound",
+            None,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn delete_ref_protection_rule(
+    warehouse_id: WarehouseIdent,
+    rule_id: uuid::Uuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let deleted = sqlx::query!(
+        r#"
+        DELETE FROM ref_protection_rule
+        WHERE warehouse_id = $1 AND rule_id = $2
+        "#,
+        *warehouse_id,
+        rule_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting ref protection rule"))?;
+
+    if deleted.rows_affected() == 0 {
+        return Err(ErrorModel::not_found(
+            format!("Ref protection rule {rule_id} not found in warehouse {warehouse_id}"),
+            "RefProtectionRuleNotFound",
+            None,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::implementations::postgres::tabular::table::tests::initialize_table;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+
+    fn rule(scope: RefProtectionScope) -> RefProtectionRule {
+        RefProtectionRule {
+            rule_id: uuid::Uuid::now_v7(),
+            scope,
+            ref_pattern: "main".to_string(),
+            allowed_committers: vec![RoleId::default()],
+            prevent_deletion: true,
+            fast_forward_only: false,
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_table_ref_protection_rules(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let other_table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let namespace_id = sqlx::query_scalar!(
+            "SELECT namespace_id FROM tabular WHERE tabular_id = $1",
+            *table.table_id
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let mut rules = vec![
+            rule(RefProtectionScope::Warehouse),
+            rule(RefProtectionScope::Namespace { namespace_id }),
+            rule(RefProtectionScope::Table {
+                table_id: *table.table_id,
+            }),
+            rule(RefProtectionScope::Table {
+                table_id: *other_table.table_id,
+            }),
+        ];
+        let other_table_rule = rules.pop().unwrap();
+        rules.sort_by_key(|rule| rule.rule_id);
+        let mut transaction = pool.begin().await.unwrap();
+        for rule in rules.iter().chain([&other_table_rule]) {
+            create_ref_protection_rule(warehouse_id, rule, &mut transaction)
+                .await
+                .unwrap();
+        }
+        transaction.commit().await.unwrap();
+
+        let mut all_rules = list_ref_protection_rules(warehouse_id, state.clone())
+            .await
+            .unwrap();
+        assert_eq!(all_rules.len(), 4);
+        all_rules.retain(|rule| rule.rule_id != other_table_rule.rule_id);
+        assert_eq!(all_rules, rules);
+        let mut transaction = pool.begin().await.unwrap();
+        let table_rules =
+            get_table_ref_protection_rules(warehouse_id, table.table_id, &mut transaction)
+                .await
+                .unwrap();
+        assert_eq!(table_rules, rules);
+
+        delete_ref_protection_rule(warehouse_id, rules[0].rule_id, &mut transaction)
+            .await
+            .unwrap();
+        let err = delete_ref_protection_rule(warehouse_id, rules[0].rule_id, &mut transaction)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.r#type, "RefProtectionRuleNotFound");
+    }
+
+    #[sqlx::test]
+    async fn test_rule_scope_must_exist_in_warehouse(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        let mut transaction = pool.begin().await.unwrap();
+        let err = create_ref_protection_rule(
+            warehouse_id,
+            &rule(RefProtectionScope::Table {
+                table_id: uuid::Uuid::now_v7(),
+            }),
+            &mut transaction,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "RefProtectionScopeNotFound");
+    }
+}
@@ -0,0 +1,138 @@
+use crate::implementations::postgres::{dbutils::DBErrorHandler as _, CatalogState};
+use crate::service::authz::{RowFilter, RowFilterExpression};
+use crate::service::{ErrorModel, Result, RoleId, TableIdentUuid};
+use sqlx::types::Json;
+
+pub(crate) async fn get_row_filters(
+    table_id: TableIdentUuid,
+    catalog_state: CatalogState,
+) -> Result<Vec<RowFilter>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT role_id,
+               filter as "filter: Json<RowFilterExpression>"
+        FROM row_filter_policy
+        WHERE table_id = $1
+        ORDER BY role_id
+        "#,
+        *table_id
+    )
+    .fetch_all(&catalog_state.read_pool())
+    .await
+    .map_err(|e| e.into_error_model("Error fetching row filters"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| RowFilter {
+            role_id: RoleId::new(row.role_id),
+            filter: row.filter.0,
+        })
+        .collect())
+}
+
+/// Replace all row filters of a table.
+pub(crate) async fn set_row_filters(
+    table_id: TableIdentUuid,
+    filters: &[RowFilter],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"DELETE FROM row_filter_policy WHERE table_id = $1"#,
+        *table_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting row filters"))?;
+
+    for filter in filters {
+        sqlx::query!(
+            r#"
+            INSERT INTO row_filter_policy (table_id, role_id, filter)
+            VALUES ($1, $2, $3)
+            "#,
+            *table_id,
+            *filter.role_id,
+            Json(&filter.filter) as _,
+        )
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => {
+                ErrorModel::not_found(
+                    format!("Role {} not found", filter.role_id),
+                    "RoleNotFound",
+                    Some(Box::new(db_error)),
+                )
+            }
+            e => e.into_error_model("Error inserting row filter"),
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::implementations::postgres::role::create_role;
+    use crate::implementations::postgres::tabular::table::tests::initialize_table;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+    use crate::ProjectIdent;
+    use http::StatusCode;
+
+    #[sqlx::test]
+    async fn test_set_and_get_row_filters(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let role = create_role(
+            RoleId::default(),
+            ProjectIdent::from(uuid::Uuid::nil()),
+            "Role 1",
+            None,
+            &state.write_pool(),
+        )
+        .await
+        .unwrap();
+
+        let filters = get_row_filters(table.table_id, state.clone())
+            .await
+            .unwrap();
+        assert!(filters.is_empty());
+
+        let filters = vec![RowFilter {
+            role_id: role.id,
+            filter: serde_json::from_value(serde_json::json!({
+                "type": "eq",
+                "term": "region",
+                "value": "EU"
+            }))
+            .unwrap(),
+        }];
+        let mut transaction = pool.begin().await.unwrap();
+        set_row_filters(table.table_id, &filters, &mut transaction)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(
+            get_row_filters(table.table_id, state.clone())
+                .await
+                .unwrap(),
+            filters
+        );
+
+        // Filters for unknown roles are rejected
+        let mut transaction = pool.begin().await.unwrap();
+        let err = set_row_filters(
+            table.table_id,
+            &[RowFilter {
+                role_id: RoleId::default(),
+                filter: filters[0].filter.clone(),
+            }],
+            &mut transaction,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,199 @@
+use crate::implementations::postgres::dbutils::DBErrorHandler as _;
+use crate::implementations::postgres::tabular::TabularType;
+use crate::service::{ErrorModel, LineageTabular, Result, TabularIdentUuid, ViewIdentUuid};
+use iceberg::{NamespaceIdent, TableIdent};
+use uuid::Uuid;
+
+/// Replace the tables and views a view depends on.
+pub(crate) async fn set_view_dependencies(
+    view_id: ViewIdentUuid,
+    dependencies: &[TabularIdentUuid],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"DELETE FROM view_dependency WHERE view_id = $1"#,
+        *view_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting dependencies of view"))?;
+
+    let dependency_ids = dependencies
+        .iter()
+        .map(|d| *d.as_ref())
+        .collect::<Vec<Uuid>>();
+    sqlx::query!(
+        r#"
+        INSERT INTO view_dependency (view_id, dependency_id)
+        SELECT $1, dependency_id FROM UNNEST($2::uuid[]) AS dependency_id
+        ON CONFLICT DO NOTHING
+        "#,
+        *view_id,
+        &dependency_ids
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error inserting dependencies of view"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn list_view_dependencies(
+    view_id: ViewIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<LineageTabular>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.tabular_id,
+               t.typ AS "typ: TabularType",
+               t.name,
+               n.namespace_name
+        FROM view_dependency d
+        INNER JOIN tabular t ON t.tabular_id = d.dependency_id AND t.deleted_at IS NULL
+        INNER JOIN namespace n ON n.namespace_id = t.namespace_id
+        WHERE d.view_id = $1
+        ORDER BY n.namespace_name, t.name
+        "#,
+        *view_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching dependencies of view"))?;
+
+    rows.into_iter()
+        .map(|row| lineage_tabular(row.tabular_id, row.typ, row.namespace_name, row.name))
+        .collect()
+}
+
+pub(crate) async fn list_dependent_views(
+    tabular_id: TabularIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<LineageTabular>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.tabular_id,
+               t.typ AS "typ: TabularType",
+               t.name,
+               n.namespace_name
+        FROM view_dependency d
+        INNER JOIN tabular t ON t.tabular_id = d.view_id AND t.deleted_at IS NULL
+        INNER JOIN namespace n ON n.namespace_id = t.namespace_id
+        WHERE d.dependency_id = $1
+        ORDER BY n.namespace_name, t.name
+        "#,
+        *tabular_id.as_ref()
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching dependent views"))?;
+
+    rows.into_iter()
+        .map(|row| lineage_tabular(row.tabular_id, row.typ, row.namespace_name, row.name))
+        .collect()
+}
+
+fn lineage_tabular(
+    tabular_id: Uuid,
+    typ: TabularType,
+    namespace_name: Vec<String>,
+    name: String,
+) -> Result<LineageTabular> {
+    let namespace = NamespaceIdent::from_vec(namespace_name).map_err(|e| {
+        ErrorModel::internal(
+            "Error parsing namespace of view dependency",
+            "NamespaceParseError",
+            Some(Box::new(e)),
+        )
+    })?;
+    let tabular_id = match typ {
+        TabularType::Table => TabularIdentUuid::Table(tabular_id),
+        TabularType::View => TabularIdentUuid::View(tabular_id),
+    };
+    Ok(LineageTabular {
+        tabular_id,
+        ident: TableIdent::new(namespace, name),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::implementations::postgres::namespace::tests::initialize_namespace;
+    use crate::implementations::postgres::tabular::table::tests::initialize_table;
+    use crate::implementations::postgres::tabular::view::create_view;
+    use crate::implementations::postgres::tabular::view::tests::view_request;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+    use crate::implementations::postgres::{CatalogState, PostgresTransaction};
+    use crate::service::Transaction as _;
+    use iceberg_ext::configs::Location;
+
+    #[sqlx::test]
+    async fn test_view_dependencies(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = NamespaceIdent::new("my_namespace".to_string());
+        let (namespace_id, _) =
+            initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        let table = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace.clone()),
+            Some("my_table".to_string()),
+        )
+        .await;
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let view_id = ViewIdentUuid::from(Uuid::now_v7());
+        let location = "s3://my_bucket/my_view".parse::<Location>().unwrap();
+        create_view(
+            namespace_id,
+            &"s3://my_bucket/my_view/metadata/00000-metadata.gz.json"
+                .parse()
+                .unwrap(),
+            t.transaction(),
+            "my_view",
+            view_request(Some(*view_id), &location),
+            &location,
+        )
+        .await
+        .unwrap();
+
+        set_view_dependencies(view_id, &[table.table_id.into()], t.transaction())
+            .await
+            .unwrap();
+
+        let upstream = list_view_dependencies(view_id, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(
+            upstream,
+            vec![LineageTabular {
+                tabular_id: table.table_id.into(),
+                ident: table.table_ident.clone(),
+            }]
+        );
+
+        let downstream = list_dependent_views(table.table_id.into(), t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(
+            downstream,
+            vec![LineageTabular {
+                tabular_id: view_id.into(),
+                ident: TableIdent::new(namespace.clone(), "my_view".to_string()),
+            }]
+        );
+
+        set_view_dependencies(view_id, &[], t.transaction())
+            .await
+            .unwrap();
+        assert!(list_dependent_views(table.table_id.into(), t.transaction())
+            .await
+            .unwrap()
+            .is_empty());
+        t.commit().await.unwrap();
+    }
+}
@@ -0,0 +1,250 @@
+use crate::implementations::postgres::dbutils::DBErrorHandler as _;
+use crate::service::{
+    ErrorModel, MaterializedView, MaterializedViewSource, MaterializedViewSourceState, Result,
+    TableIdentUuid, ViewIdentUuid,
+};
+use iceberg::spec::MAIN_BRANCH;
+use iceberg::{NamespaceIdent, TableIdent};
+
+/// Link a view to its storage table and replace the recorded source snapshots.
+pub(crate) async fn set_materialized_view(
+    view_id: ViewIdentUuid,
+    storage_table_id: TableIdentUuid,
+    sources: &[MaterializedViewSource],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO materialized_view (view_id, storage_table_id)
+        VALUES ($1, $2)
+        ON CONFLICT (view_id) DO UPDATE SET storage_table_id = EXCLUDED.storage_table_id
+        "#,
+        *view_id,
+        *storage_table_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| {
+        e.into_error_model("Storage table is already used by another materialized view")
+    })?;
+
+    sqlx::query!(
+        r#"DELETE FROM materialized_view_source WHERE view_id = $1"#,
+        *view_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting sources of materialized view"))?;
+
+    for source in sources {
+        sqlx::query!(
+            r#"
+            INSERT INTO materialized_view_source
+                (view_id, source_table_id, namespace_name, table_name, snapshot_id)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            *view_id,
+            *source.table_id,
+            &*source.table.namespace,
+            source.table.name,
+            source.snapshot_id
+        )
+        .execute(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error inserting source of materialized view"))?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn get_materialized_view(
+    view_id: ViewIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<MaterializedView>> {
+    let Some(storage_table_id) = sqlx::query_scalar!(
+        r#"SELECT storage_table_id FROM materialized_view WHERE view_id = $1"#,
+        *view_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching materialized view"))?
+    else {
+        return Ok(None);
+    };
+
+    // Dropped source tables are reported as missing, soft-deleted ones included.
+    let rows = sqlx::query!(
+        r#"
+        SELECT s.source_table_id,
+               s.namespace_name,
+               s.table_name,
+               s.snapshot_id,
+               t.tabular_id IS NOT NULL AS "exists!",
+               r.snapshot_id AS "current_snapshot_id?"
+        FROM materialized_view_source s
+        LEFT JOIN tabular t ON t.tabular_id = s.source_table_id AND t.deleted_at IS NULL
+        LEFT JOIN table_refs r ON r.table_id = t.tabular_id AND r.table_ref_name = $2
+        WHERE s.view_id = $1
+        ORDER BY s.source_table_id
+        "#,
+        *view_id,
+        MAIN_BRANCH
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching sources of materialized view"))?;
+
+    let sources = rows
+        .into_iter()
+        .map(|row| {
+            let namespace = NamespaceIdent::from_vec(row.namespace_name).map_err(|e| {
+                ErrorModel::internal(
+                    "Error parsing namespace of materialized view source",
+                    "NamespaceParseError",
+                    Some(Box::new(e)),
+                )
+            })?;
+            Ok(MaterializedViewSourceState {
+                source: MaterializedViewSource {
+                    table_id: row.source_table_id.into(),
+                    table: TableIdent::new(namespace, row.table_name),
+                    snapshot_id: row.snapshot_id,
+                },
+                exists: row.exists,
+                current_snapshot_id: row.current_snapshot_id,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Some(MaterializedView {
+        storage_table_id: storage_table_id.into(),
+        sources,
+    }))
+}
+
+pub(crate) async fn get_materialized_view_of_storage_table(
+    table_id: TableIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<ViewIdentUuid>> {
+    let view_id = sqlx::query_scalar!(
+        r#"
+        SELECT mv.view_id
+        FROM materialized_view mv
+        INNER JOIN tabular t ON t.tabular_id = mv.view_id
+        WHERE mv.storage_table_id = $1 AND t.deleted_at IS NULL
+        "#,
+        *table_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching materialized view of storage table"))?;
+
+    Ok(view_id.map(Into::into))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::implementations::postgres::namespace::tests::initialize_namespace;
+    use crate::implementations::postgres::tabular::table::tests::initialize_table;
+    use crate::implementations::postgres::tabular::view::create_view;
+    use crate::implementations::postgres::tabular::view::tests::view_request;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+    use crate::implementations::postgres::{CatalogState, PostgresTransaction};
+    use crate::service::Transaction as _;
+    use iceberg_ext::configs::Location;
+    use uuid::Uuid;
+
+    #[sqlx::test]
+    async fn test_materialized_view_link(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = NamespaceIdent::new("my_namespace".to_string());
+        let (namespace_id, _) =
+            initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        let storage_table = initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(namespace.clone()),
+            Some("storage".to_string()),
+        )
+        .await;
+        let source_table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let view_id = ViewIdentUuid::from(Uuid::now_v7());
+        let location = "s3://my_bucket/my_view".parse::<Location>().unwrap();
+        create_view(
+            namespace_id,
+            &"s3://my_bucket/my_view/metadata/00000-metadata.gz.json"
+                .parse()
+                .unwrap(),
+            t.transaction(),
+            "my_view",
+            view_request(Some(*view_id), &location),
+            &location,
+        )
+        .await
+        .unwrap();
+
+        assert!(get_materialized_view(view_id, t.transaction())
+            .await
+            .unwrap()
+            .is_none());
+
+        let source = MaterializedViewSource {
+            table_id: source_table.table_id,
+            table: source_table.table_ident.clone(),
+            snapshot_id: Some(42),
+        };
+        set_materialized_view(
+            view_id,
+            storage_table.table_id,
+            &[source.clone()],
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+
+        let materialized_view = get_materialized_view(view_id, t.transaction())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(materialized_view.storage_table_id, storage_table.table_id);
+        // The source table has no snapshot 42
+        assert_eq!(
+            materialized_view.stale_sources().collect::<Vec<_>>(),
+            vec![&source]
+        );
+
+        let source = MaterializedViewSource {
+            snapshot_id: None,
+            ..source
+        };
+        set_materialized_view(view_id, storage_table.table_id, &[source], t.transaction())
+            .await
+            .unwrap();
+        let materialized_view = get_materialized_view(view_id, t.transaction())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(materialized_view.stale_sources().count(), 0);
+
+        assert_eq!(
+            get_materialized_view_of_storage_table(storage_table.table_id, t.transaction())
+                .await
+                .unwrap(),
+            Some(view_id)
+        );
+        assert_eq!(
+            get_materialized_view_of_storage_table(source_table.table_id, t.transaction())
+                .await
+                .unwrap(),
+            None
+        );
+        t.commit().await.unwrap();
+    }
+}
@@ -1,4 +1,6 @@
+mod lineage;
 mod load;
+mod materialized;
 
 use crate::implementations::postgres::dbutils::DBErrorHandler as _;
 use crate::{
@@ -18,7 +20,11 @@ use chrono::{DateTime, Utc};
 use iceberg::spec::{SchemaRef, ViewMetadata, ViewRepresentation, ViewVersionId, ViewVersionRef};
 use iceberg::NamespaceIdent;
 use iceberg_ext::configs::Location;
+pub(crate) use lineage::{list_dependent_views, list_view_dependencies, set_view_dependencies};
 pub(crate) use load::load_view;
+pub(crate) use materialized::{
+    get_materialized_view, get_materialized_view_of_storage_table, set_materialized_view,
+};
 use serde::Deserialize;
 use sqlx::{FromRow, Postgres, Transaction};
 use std::collections::HashMap;
@@ -307,6 +313,7 @@ async fn create_view_version(
         SELECT namespace_id
         FROM namespace
         WHERE namespace_name = $1
+        AND deleted_at IS NULL
         "#,
         &default_ns
     )
@@ -531,7 +538,7 @@ pub(crate) mod tests {
     use sqlx::PgPool;
     use uuid::Uuid;
 
-    fn view_request(view_id: Option<Uuid>, location: &Location) -> ViewMetadata {
+    pub(crate) fn view_request(view_id: Option<Uuid>, location: &Location) -> ViewMetadata {
         serde_json::from_value(json!({
   "format-version": 1,
   "view-uuid": view_id.unwrap_or_else(Uuid::now_v7).to_string(),
@@ -0,0 +1,209 @@
+use crate::implementations::postgres::dbutils::DBErrorHandler;
+use crate::implementations::postgres::task_queues::{
+    pick_task, queue_task, record_failure, record_success,
+};
+use crate::service::task_queue::grant_expiration_queue::{
+    GrantExpirationInput, GrantExpirationTask,
+};
+use crate::service::task_queue::{TaskFilter, TaskQueue, TaskQueueConfig};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::cancel_pending_tasks;
+
+super::impl_pg_task_queue!(GrantExpirationQueue);
+
+#[async_trait]
+impl TaskQueue for GrantExpirationQueue {
+    type Task = GrantExpirationTask;
+    type Input = GrantExpirationInput;
+
+    fn config(&self) -> &TaskQueueConfig {
+        &self.pg_queue.config
+    }
+
+    fn queue_name(&self) -> &'static str {
+        "grant_expiration"
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn enqueue(
+        &self,
+        GrantExpirationInput {
+            object,
+            relation,
+            principal,
+            expire_at,
+        }: GrantExpirationInput,
+    ) -> crate::api::Result<()> {
+        let mut transaction = self
+            .pg_queue
+            .read_write
+            .write_pool
+            .begin()
+            .await
+            .map_err(|e| {
+                e.into_error_model("failed to begin transaction for grant expiration queue")
+            })?;
+
+        tracing::debug!(
+            "Queuing expiration of '{relation}' on '{object}' for '{principal}' at {expire_at}"
+        );
+
+        // A grant that is renewed with a different expiration gets a new task.
+        // Tasks of outdated expirations find nothing to revoke.
+        let idempotency_key = Uuid::new_v5(
+            &Uuid::NAMESPACE_OID,
+            format!(
+                "{object}#{relation}@{principal}@{}",
+                expire_at.timestamp_micros()
+            )
+            .as_bytes(),
+        );
+
+        let Some(task_id) = queue_task(
+            &mut transaction,
+            self.queue_name(),
+            None,
+            idempotency_key,
+            None,
+            Some(expire_at),
+        )
+        .await?
+        else {
+            tracing::debug!("Task already exists");
+            transaction.commit().await.map_err(|e| {
+                tracing::error!(?e, "failed to commit");
+                e.into_error_model("failed to commit transaction enqueuing task")
+            })?;
+            return Ok(());
+        };
+
+        sqlx::query!(
+            r#"INSERT INTO authz_grant_expiration_task(task_id, object, relation, principal)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (task_id) DO NOTHING"#,
+            task_id,
+            object,
+            relation,
+            principal,
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "failed to insert into authz_grant_expiration_task");
+            e.into_error_model("failed to insert into grant expiration tasks")
+        })?;
+
+        transaction.commit().await.map_err(|e| {
+            tracing::error!(?e, "failed to commit");
+            e.into_error_model("failed to commit transaction inserting grant expiration task")
+        })?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn pick_new_task(&self) -> crate::api::Result<Option<Self::Task>> {
+        let task = pick_task(
+            &self.pg_queue.read_write.write_pool,
+            self.queue_name(),
+            &self.pg_queue.max_age,
+        )
+        .await?;
+
+        let Some(task) = task else {
+            tracing::debug!("No task found");
+            return Ok(None);
+        };
+
+        let grant = sqlx::query!(
+            r#"
+            SELECT object, relation, principal
+            FROM authz_grant_expiration_task
+            WHERE task_id = $1
+            "#,
+            task.task_id
+        )
+        .fetch_one(&self.pg_queue.read_write.read_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "error selecting grant expiration");
+            e.into_error_model("failed to read task after picking one up")
+        })?;
+
+        Ok(Some(GrantExpirationTask {
+            object: grant.object,
+            relation: grant.relation,
+            principal: grant.principal,
+            task,
+        }))
+    }
+
+    async fn record_success(&self, id: Uuid) -> crate::api::Result<()> {
+        record_success(id, &self.pg_queue.read_write.write_pool).await
+    }
+
+    async fn record_failure(&self, id: Uuid, error_details: &str) -> crate::api::Result<()> {
+        record_failure(
+            &self.pg_queue.read_write.write_pool,
+            id,
+            self.config().max_retries,
+            error_details,
+        )
+        .await
+    }
+
+    async fn cancel_pending_tasks(&self, filter: TaskFilter) -> crate::api::Result<()> {
+        cancel_pending_tasks(&self.pg_queue, filter, self.queue_name()).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::setup;
+    use crate::service::task_queue::grant_expiration_queue::GrantExpirationInput;
+    use crate::service::task_queue::{TaskQueue, TaskQueueConfig};
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn test_queue_grant_expiration_task(pool: PgPool) {
+        let config = TaskQueueConfig::default();
+        let pg_queue = setup(pool, config);
+        let queue = super::GrantExpirationQueue { pg_queue };
+        let input = GrantExpirationInput {
+            object: format!("warehouse:{}", uuid::Uuid::now_v7()),
+            relation: "select".to_string(),
+            principal: "user:oidc~a".to_string(),
+            expire_at: chrono::Utc::now() + chrono::Duration::hours(1),
+        };
+        queue.enqueue(input.clone()).await.unwrap();
+        queue.enqueue(input.clone()).await.unwrap();
+
+        assert!(
+            queue.pick_new_task().await.unwrap().is_none(),
+            "The task should be suspended until the grant expires"
+        );
+
+        let renewed = GrantExpirationInput {
+            expire_at: chrono::Utc::now() - chrono::Duration::seconds(1),
+            ..input.clone()
+        };
+        queue.enqueue(renewed).await.unwrap();
+
+        let task = queue
+            .pick_new_task()
+            .await
+            .unwrap()
+            .expect("There should be a task");
+        assert_eq!(task.object, input.object);
+        assert_eq!(task.relation, input.relation);
+        assert_eq!(task.principal, input.principal);
+
+        let task = queue.pick_new_task().await.unwrap();
+        assert!(
+            task.is_none(),
+            "There should only be one due task, idempotency didn't work."
+        );
+    }
+}
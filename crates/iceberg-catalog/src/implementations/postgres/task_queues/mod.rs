@@ -1,3 +1,5 @@
+mod grant_expiration_queue;
+mod namespace_expiration_queue;
 mod tabular_expiration_queue;
 mod tabular_purge_queue;
 
@@ -5,6 +7,8 @@ use crate::implementations::postgres::dbutils::DBErrorHandler;
 use crate::implementations::postgres::ReadWrite;
 use crate::service::task_queue::{Task, TaskFilter, TaskQueueConfig, TaskStatus};
 use crate::WarehouseIdent;
+pub use grant_expiration_queue::GrantExpirationQueue;
+pub use namespace_expiration_queue::NamespaceExpirationQueue;
 pub use tabular_expiration_queue::TabularExpirationQueue;
 pub use tabular_purge_queue::TabularPurgeQueue;
 
@@ -60,7 +64,7 @@ async fn queue_task(
     queue_name: &str,
     parenet_task_id: Option<Uuid>,
     idempotency_key: Uuid,
-    warehouse_ident: WarehouseIdent,
+    warehouse_ident: Option<WarehouseIdent>,
     suspend_until: Option<DateTime<Utc>>,
 ) -> Result<Option<Uuid>, IcebergErrorResponse> {
     let task_id = Uuid::now_v7();
@@ -84,7 +88,7 @@ async fn queue_task(
         queue_name,
         parenet_task_id,
         idempotency_key,
-        *warehouse_ident,
+        warehouse_ident.map(|w| *w),
         suspend_until
     )
     .fetch_optional(conn)
@@ -293,7 +297,7 @@ mod test {
             "test",
             None,
             idempotency_key,
-            TEST_WAREHOUSE,
+            Some(TEST_WAREHOUSE),
             None,
         )
         .await
@@ -304,7 +308,7 @@ mod test {
             "test",
             None,
             idempotency_key,
-            TEST_WAREHOUSE,
+            Some(TEST_WAREHOUSE),
             None,
         )
         .await
@@ -316,7 +320,7 @@ mod test {
             "test",
             None,
             Uuid::new_v5(&TEST_WAREHOUSE, b"test2"),
-            TEST_WAREHOUSE,
+            Some(TEST_WAREHOUSE),
             None,
         )
         .await
@@ -339,7 +343,7 @@ mod test {
             "test",
             None,
             Uuid::new_v5(&TEST_WAREHOUSE, b"test"),
-            TEST_WAREHOUSE,
+            Some(TEST_WAREHOUSE),
             None,
         )
         .await
@@ -390,7 +394,7 @@ mod test {
             "test",
             None,
             Uuid::new_v5(&TEST_WAREHOUSE, b"test"),
-            TEST_WAREHOUSE,
+            Some(TEST_WAREHOUSE),
             None,
         )
         .await
@@ -428,7 +432,7 @@ mod test {
             "test",
             None,
             Uuid::new_v5(&TEST_WAREHOUSE, b"test"),
-            TEST_WAREHOUSE,
+            Some(TEST_WAREHOUSE),
             Some(Utc::now() + chrono::Duration::milliseconds(500)),
         )
         .await
@@ -469,7 +473,7 @@ mod test {
             "test",
             None,
             Uuid::new_v5(&TEST_WAREHOUSE, b"test"),
-            TEST_WAREHOUSE,
+            Some(TEST_WAREHOUSE),
             None,
         )
         .await
@@ -514,7 +518,7 @@ mod test {
             "test",
             None,
             Uuid::new_v5(&TEST_WAREHOUSE, b"test"),
-            TEST_WAREHOUSE,
+            Some(TEST_WAREHOUSE),
             None,
         )
         .await
@@ -526,7 +530,7 @@ mod test {
             "test",
             None,
             Uuid::new_v5(&TEST_WAREHOUSE, b"test2"),
-            TEST_WAREHOUSE,
+            Some(TEST_WAREHOUSE),
             None,
         )
         .await
@@ -0,0 +1,205 @@
+use crate::implementations::postgres::dbutils::DBErrorHandler;
+use crate::implementations::postgres::task_queues::{
+    pick_task, queue_task, record_failure, record_success,
+};
+use crate::implementations::postgres::DeletionKind;
+use crate::service::task_queue::namespace_expiration_queue::{
+    NamespaceExpirationInput, NamespaceExpirationTask,
+};
+use crate::service::task_queue::{TaskFilter, TaskQueue, TaskQueueConfig};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::cancel_pending_tasks;
+
+super::impl_pg_task_queue!(NamespaceExpirationQueue);
+
+#[async_trait]
+impl TaskQueue for NamespaceExpirationQueue {
+    type Task = NamespaceExpirationTask;
+    type Input = NamespaceExpirationInput;
+
+    fn config(&self) -> &TaskQueueConfig {
+        &self.pg_queue.config
+    }
+
+    fn queue_name(&self) -> &'static str {
+        "namespace_expiration"
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn enqueue(
+        &self,
+        NamespaceExpirationInput {
+            namespace_id,
+            warehouse_ident,
+            purge,
+            expire_at,
+        }: NamespaceExpirationInput,
+    ) -> crate::api::Result<()> {
+        let mut transaction = self
+            .pg_queue
+            .read_write
+            .write_pool
+            .begin()
+            .await
+            .map_err(|e| e.into_error_model("failed to begin transaction for expiration queue"))?;
+
+        tracing::info!(
+            "Queuing expiration for namespace '{namespace_id}' under warehouse: '{warehouse_ident}'",
+        );
+
+        let idempotency_key = Uuid::new_v5(&warehouse_ident, namespace_id.as_bytes());
+
+        let Some(task_id) = queue_task(
+            &mut transaction,
+            self.queue_name(),
+            None,
+            idempotency_key,
+            Some(warehouse_ident),
+            Some(expire_at),
+        )
+        .await?
+        else {
+            tracing::debug!("Task already exists");
+            transaction.commit().await.map_err(|e| {
+                tracing::error!(?e, "failed to commit");
+                e.into_error_model("failed to commit transaction enqueuing task")
+            })?;
+            return Ok(());
+        };
+
+        let it = sqlx::query!(
+            r#"INSERT INTO namespace_expirations(task_id, namespace_id, warehouse_id, deletion_kind)
+            VALUES ($1, $2, $3, $4)
+            -- we update the deletion kind since our caller may now want to purge instead of just delete
+            ON CONFLICT (task_id) DO UPDATE SET deletion_kind = $4
+            RETURNING task_id"#,
+            task_id,
+            namespace_id,
+            *warehouse_ident,
+            if purge {
+                DeletionKind::Purge
+            } else {
+                DeletionKind::Default
+            } as _
+        )
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "failed to insert into namespace_expirations");
+            e.into_error_model("failed to insert into namespace expirations")
+        })?;
+
+        if let Some(row) = it {
+            tracing::debug!("Queued expiration task: {:?}", row.task_id);
+        } else {
+            tracing::debug!("Expiration task already exists.");
+        }
+
+        transaction.commit().await.map_err(|e| {
+            tracing::error!(?e, "failed to commit");
+            e.into_error_model("failed to commit transaction inserting namespace expiration task")
+        })?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn pick_new_task(&self) -> crate::api::Result<Option<Self::Task>> {
+        let task = pick_task(
+            &self.pg_queue.read_write.write_pool,
+            self.queue_name(),
+            &self.pg_queue.max_age,
+        )
+        .await?;
+
+        let Some(task) = task else {
+            tracing::info!("No task found");
+            return Ok(None);
+        };
+
+        let expiration = sqlx::query!(
+            r#"
+            SELECT namespace_id, warehouse_id, deletion_kind as "deletion_kind: DeletionKind"
+            FROM namespace_expirations
+            WHERE task_id = $1
+            "#,
+            task.task_id
+        )
+        .fetch_one(&self.pg_queue.read_write.read_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "error selecting namespace expiration");
+            e.into_error_model("failed to read task after picking one up")
+        })?;
+
+        tracing::info!("Expiration task: {:?}", expiration);
+        Ok(Some(NamespaceExpirationTask {
+            deletion_kind: expiration.deletion_kind.into(),
+            namespace_id: expiration.namespace_id,
+            warehouse_ident: expiration.warehouse_id.into(),
+            task,
+        }))
+    }
+
+    async fn record_success(&self, id: Uuid) -> crate::api::Result<()> {
+        record_success(id, &self.pg_queue.read_write.write_pool).await
+    }
+
+    async fn record_failure(&self, id: Uuid, error_details: &str) -> crate::api::Result<()> {
+        record_failure(
+            &self.pg_queue.read_write.write_pool,
+            id,
+            self.config().max_retries,
+            error_details,
+        )
+        .await
+    }
+
+    async fn cancel_pending_tasks(&self, filter: TaskFilter) -> crate::api::Result<()> {
+        cancel_pending_tasks(&self.pg_queue, filter, self.queue_name()).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::setup;
+    use crate::service::task_queue::namespace_expiration_queue::NamespaceExpirationInput;
+    use crate::service::task_queue::{TaskQueue, TaskQueueConfig};
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn test_queue_namespace_expiration_task(pool: PgPool) {
+        let config = TaskQueueConfig::default();
+        let pg_queue = setup(pool, config);
+        let queue = super::NamespaceExpirationQueue { pg_queue };
+        let input = NamespaceExpirationInput {
+            namespace_id: uuid::Uuid::new_v4(),
+            warehouse_ident: uuid::Uuid::new_v4().into(),
+            purge: false,
+            expire_at: chrono::Utc::now(),
+        };
+        queue.enqueue(input.clone()).await.unwrap();
+        queue.enqueue(input.clone()).await.unwrap();
+
+        let task = queue
+            .pick_new_task()
+            .await
+            .unwrap()
+            .expect("There should be a task");
+
+        assert_eq!(task.warehouse_ident, input.warehouse_ident);
+        assert_eq!(task.namespace_id, input.namespace_id);
+        assert_eq!(
+            task.deletion_kind,
+            crate::implementations::postgres::DeletionKind::Default.into()
+        );
+
+        let task = queue.pick_new_task().await.unwrap();
+        assert!(
+            task.is_none(),
+            "There should only be one task, idempotency didn't work."
+        );
+    }
+}
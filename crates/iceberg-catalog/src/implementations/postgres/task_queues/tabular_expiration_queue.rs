@@ -60,7 +60,7 @@ impl TaskQueue for TabularExpirationQueue {
             self.queue_name(),
             None,
             idempotency_key,
-            warehouse_ident,
+            Some(warehouse_ident),
             Some(expire_at),
         )
         .await?
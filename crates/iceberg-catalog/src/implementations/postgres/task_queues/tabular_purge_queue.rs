@@ -110,7 +110,7 @@ impl TaskQueue for TabularPurgeQueue {
             self.queue_name(),
             parent_id,
             idempotency_key,
-            warehouse_ident,
+            Some(warehouse_ident),
             None,
         )
         .await?
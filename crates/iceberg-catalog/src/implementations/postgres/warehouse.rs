@@ -1,9 +1,13 @@
 use super::dbutils::DBErrorHandler as _;
 use crate::api::{CatalogConfig, ErrorModel, Result};
+use crate::service::table_property_policy::TablePropertyPolicy;
 use crate::service::{GetProjectResponse, GetWarehouseResponse, WarehouseStatus};
-use crate::{service::storage::StorageProfile, ProjectIdent, SecretIdent, WarehouseIdent};
+use crate::{
+    service::storage::{StorageProfile, TabularLocationTemplate},
+    ProjectIdent, SecretIdent, WarehouseIdent,
+};
 use sqlx::Error;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 
 use super::CatalogState;
@@ -70,6 +74,113 @@ pub(super) async fn set_warehouse_deletion_profile<
     Ok(())
 }
 
+pub(super) async fn set_warehouse_properties<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseIdent,
+    properties: &HashMap<String, String>,
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET properties = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        Json(properties) as _,
+        *warehouse_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse properties"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(super) async fn set_warehouse_table_property_policy<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseIdent,
+    policy: &TablePropertyPolicy,
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET table_property_policy = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        Json(policy) as _,
+        *warehouse_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse table property policy"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(super) async fn set_warehouse_location_template<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseIdent,
+    location_template: Option<&TabularLocationTemplate>,
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET location_template = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        location_template.map(TabularLocationTemplate::as_str),
+        *warehouse_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse location template"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+fn parse_location_template(template: Option<String>) -> Result<Option<TabularLocationTemplate>> {
+    template
+        .map(TabularLocationTemplate::try_new)
+        .transpose()
+        .map_err(|e| {
+            ErrorModel::internal(
+                "Stored location template is invalid",
+                "InvalidStoredLocationTemplate",
+                Some(Box::new(e)),
+            )
+            .into()
+        })
+}
+
 pub(super) async fn get_config_for_warehouse(
     warehouse_id: WarehouseIdent,
     catalog_state: CatalogState,
@@ -278,6 +389,9 @@ pub(crate) async fn list_warehouses<
         status: WarehouseStatus,
         tabular_delete_mode: DbTabularDeleteProfile,
         tabular_expiration_seconds: Option<i64>,
+        properties: Json<HashMap<String, String>>,
+        table_property_policy: Json<TablePropertyPolicy>,
+        location_template: Option<String>,
     }
 
     let include_status = include_status.unwrap_or_else(|| vec![WarehouseStatus::Active]);
@@ -291,7 +405,10 @@ pub(crate) async fn list_warehouses<
                 storage_secret_id,
                 status AS "status: WarehouseStatus",
                 tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
-                tabular_expiration_seconds
+                tabular_expiration_seconds,
+                properties as "properties: Json<HashMap<String, String>>",
+                table_property_policy as "table_property_policy: Json<TablePropertyPolicy>",
+                location_template
             FROM warehouse
             WHERE project_id = $1
             AND status = ANY($2)
@@ -329,6 +446,9 @@ pub(crate) async fn list_warehouses<
                 storage_secret_id: warehouse.storage_secret_id.map(std::convert::Into::into),
                 status: warehouse.status,
                 tabular_delete_profile,
+                properties: warehouse.properties.0,
+                table_property_policy: warehouse.table_property_policy.0,
+                location_template: parse_location_template(warehouse.location_template)?,
             })
         })
         .collect::<Result<Vec<_>>>()
@@ -347,7 +467,10 @@ pub(crate) async fn get_warehouse<'a>(
             storage_secret_id,
             status AS "status: WarehouseStatus",
             tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
-            tabular_expiration_seconds
+            tabular_expiration_seconds,
+            properties as "properties: Json<HashMap<String, String>>",
+            table_property_policy as "table_property_policy: Json<TablePropertyPolicy>",
+            location_template
         FROM warehouse
         WHERE warehouse_id = $1
         "#,
@@ -381,6 +504,9 @@ pub(crate) async fn get_warehouse<'a>(
             storage_secret_id: warehouse.storage_secret_id.map(std::convert::Into::into),
             status: warehouse.status,
             tabular_delete_profile,
+            properties: warehouse.properties.0,
+            table_property_policy: warehouse.table_property_policy.0,
+            location_template: parse_location_template(warehouse.location_template)?,
         }))
     } else {
         Ok(None)
@@ -11,7 +11,10 @@ mod config;
 pub mod service;
 pub use service::{ProjectIdent, SecretIdent, WarehouseIdent};
 
-pub use config::{AuthZBackend, OpenFGAAuth, SecretBackend, CONFIG, DEFAULT_PROJECT_ID};
+pub use config::{
+    AuthZBackend, CertificateIdentitySource, OpenFGAAuth, SecretBackend, TlsClientAuth, TlsConfig,
+    CONFIG, DEFAULT_PROJECT_ID,
+};
 
 pub mod implementations;
 
@@ -25,6 +25,16 @@ impl RequestMetadata {
         }
     }
 
+    /// Metadata for operations that are not triggered by an API request,
+    /// for example by the command line interface.
+    #[must_use]
+    pub fn new_unauthenticated() -> Self {
+        Self {
+            request_id: Uuid::now_v7(),
+            auth_details: AuthDetails::Unauthenticated,
+        }
+    }
+
     #[must_use]
     pub fn actor(&self) -> &Actor {
         self.auth_details.actor()
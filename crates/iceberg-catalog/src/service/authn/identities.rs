@@ -11,6 +11,8 @@ pub enum UserId {
     OIDC(String),
     /// K8s principal
     Kubernetes(String),
+    /// Principal authenticated by a TLS client certificate
+    Certificate(String),
 }
 
 impl TryFrom<String> for UserId {
@@ -20,6 +22,7 @@ impl TryFrom<String> for UserId {
         match s.split_once('~') {
             Some(("oidc", user_id)) => Ok(UserId::oidc(user_id)?),
             Some(("kubernetes", user_id)) => Ok(UserId::kubernetes(user_id)?),
+            Some(("certificate", user_id)) => Ok(UserId::certificate(user_id)?),
             _ => Err(ErrorModel::bad_request(
                 format!("Invalid user id format: {s}"),
                 "InvalidUserId",
@@ -35,6 +38,7 @@ impl std::fmt::Display for UserId {
         match self {
             UserId::OIDC(user_id) => write!(f, "oidc~{user_id}"),
             UserId::Kubernetes(user_id) => write!(f, "kubernetes~{user_id}"),
+            UserId::Certificate(user_id) => write!(f, "certificate~{user_id}"),
         }
     }
 }
@@ -56,6 +60,25 @@ impl UserId {
         Ok(Self::Kubernetes(subject.to_string()))
     }
 
+    /// Certificate subjects are typically DNS names or email addresses,
+    /// so `.` and `@` are allowed in addition to the usual characters.
+    pub(crate) fn certificate(subject: &str) -> api::Result<Self> {
+        Self::validate_len(subject)?;
+        if subject.is_empty()
+            || subject
+                .chars()
+                .any(|c| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '@')))
+        {
+            return Err(ErrorModel::bad_request(
+                "certificate subject contains illegal characters. Only alphanumeric + - _ . @ are legal.",
+                "InvalidUserIdError",
+                None,
+            )
+            .into());
+        }
+        Ok(Self::Certificate(subject.to_string()))
+    }
+
     fn validate_len(subject: &str) -> api::Result<()> {
         if subject.len() >= 128 {
             return Err(ErrorModel::bad_request(
@@ -202,6 +225,23 @@ impl Principal {
     }
 }
 
+#[cfg(test)]
+impl Principal {
+    /// A human principal without any claims besides its id.
+    pub(crate) fn new_test(user_id: UserId) -> Self {
+        Self {
+            actor: Actor::Principal(user_id.clone()),
+            user_id,
+            name: None,
+            display_name: None,
+            application_id: None,
+            issuer: "test".to_string(),
+            email: None,
+            idtyp: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -222,6 +262,14 @@ mod tests {
         let user_id: UserId = serde_json::from_str(r#""kubernetes~123""#).unwrap();
         assert_eq!(user_id, UserId::Kubernetes("123".to_string()));
 
+        let user_id = UserId::try_from("certificate~spark.example.com".to_string()).unwrap();
+        assert_eq!(
+            user_id,
+            UserId::Certificate("spark.example.com".to_string())
+        );
+        assert_eq!(user_id.to_string(), "certificate~spark.example.com");
+        UserId::certificate("spiffe://example.com/spark").unwrap_err();
+
         serde_json::from_str::<UserId>(r#""nonexistant~123""#).unwrap_err();
     }
 }
@@ -1,5 +1,7 @@
 use super::{ProjectIdent, RoleId, WarehouseIdent};
 use crate::api::Result;
+use crate::config::{ClaimMapping, MachineUserRule};
+use crate::CONFIG;
 use iceberg_ext::catalog::rest::ErrorModel;
 use k8s_openapi::api::authentication::v1::TokenReviewStatus;
 use serde::Deserialize;
@@ -9,8 +11,11 @@ mod identities;
 mod verification;
 
 pub use identities::{Principal, UserId};
+use verification::IntrospectionResponse;
 pub(crate) use verification::{auth_middleware_fn, VerifierChain};
-pub use verification::{IdpVerifier, K8sVerifier};
+pub use verification::{
+    CertificateVerifier, IdpVerifier, IntrospectionVerifier, K8sVerifier, TlsConnectionInfo,
+};
 
 #[derive(Debug, Clone)]
 pub enum AuthDetails {
@@ -46,12 +51,124 @@ impl AuthDetails {
         Err(ErrorModel::unauthorized("No user in token review", "UnauthorizedError", None).into())
     }
 
+    fn try_from_introspection(response: IntrospectionResponse, endpoint: &str) -> Result<Self> {
+        let mapping = CONFIG.claim_mapping_for_issuer(response.iss.as_deref().unwrap_or(endpoint));
+        Self::try_from_introspection_with_mapping(response, endpoint, mapping)
+    }
+
+    fn try_from_introspection_with_mapping(
+        response: IntrospectionResponse,
+        endpoint: &str,
+        mapping: Option<&ClaimMapping>,
+    ) -> Result<Self> {
+        let mapped_subject = mapping
+            .and_then(|m| m.subject_claim.as_deref())
+            .and_then(|claim| claim_string(&response.raw, claim));
+        let mapped_display_name = mapping
+            .and_then(|m| m.display_name_claim.as_deref())
+            .and_then(|claim| claim_string(&response.raw, claim));
+        let mapped_email = mapping
+            .and_then(|m| m.email_claim.as_deref())
+            .and_then(|claim| claim_string(&response.raw, claim));
+        let mapped_is_application = mapping.and_then(|m| m.machine_user.as_ref()).map(|rule| {
+            matches_machine_user_rule(
+                &response.raw,
+                response.sub.as_deref().unwrap_or_default(),
+                rule,
+            )
+        });
+
+        let IntrospectionResponse {
+            active: _,
+            sub,
+            username,
+            client_id,
+            aud: _,
+            iss,
+            exp: _,
+            email,
+            name,
+            raw: _,
+        } = response;
+
+        // Without a machine user rule: Tokens obtained via the client credentials flow often
+        // have no `sub`, or a `sub` that equals the `client_id`. Such tokens belong to applications.
+        let is_application = mapped_is_application.unwrap_or(match (&sub, &client_id) {
+            (None, _) => true,
+            (Some(sub), Some(client_id)) => sub == client_id,
+            (Some(_), None) => false,
+        });
+        let Some(subject) = mapped_subject
+            .as_deref()
+            .or(sub.as_deref())
+            .or(client_id.as_deref())
+        else {
+            return Err(ErrorModel::unauthorized(
+                "Introspection response contains neither sub nor client_id",
+                "UnauthorizedError",
+                None,
+            )
+            .into());
+        };
+        let user_id = UserId::oidc(subject)?;
+        let application_id = client_id.clone().or_else(|| {
+            mapped_is_application
+                .unwrap_or_default()
+                .then(|| subject.to_string())
+        });
+
+        Ok(AuthDetails::Principal(Principal {
+            actor: Actor::Principal(user_id.clone()),
+            user_id,
+            name,
+            display_name: mapped_display_name.or(username).or_else(|| {
+                if is_application {
+                    client_id
+                } else {
+                    None
+                }
+            }),
+            issuer: iss.unwrap_or_else(|| endpoint.to_string()),
+            email: mapped_email.or(email),
+            application_id,
+            idtyp: Some(if is_application { "app" } else { "user" }.to_string()),
+        }))
+    }
+
     fn try_from_jwt_claims(claims: Claims) -> Result<Self> {
+        let mapping = CONFIG.claim_mapping_for_issuer(&claims.iss);
+        Self::try_from_jwt_claims_with_mapping(claims, mapping)
+    }
+
+    fn try_from_jwt_claims_with_mapping(
+        claims: Claims,
+        mapping: Option<&ClaimMapping>,
+    ) -> Result<Self> {
+        let mapped_subject = mapping
+            .and_then(|m| m.subject_claim.as_deref())
+            .and_then(|claim| claims.get_string(claim));
+        let mapped_display_name = mapping
+            .and_then(|m| m.display_name_claim.as_deref())
+            .and_then(|claim| claims.get_string(claim));
+        let mapped_email = mapping
+            .and_then(|m| m.email_claim.as_deref())
+            .and_then(|claim| claims.get_string(claim));
+        let mapped_idtyp = mapping.and_then(|m| m.machine_user.as_ref()).map(|rule| {
+            if claims.matches_machine_user_rule(rule) {
+                "app"
+            } else {
+                "user"
+            }
+            .to_string()
+        });
+
         // For azure, the oid claim is permanent to the user account
         // accross all Entra ID applications. sub is only unique for one client.
         // To enable collaboration between projects, we use oid as the user id if
         // provided.
-        let sub = if let Some(oid) = &claims.oid {
+        let sub = if let Some(mapped_subject) = &mapped_subject {
+            mapped_subject.as_str()
+        } else if let Some(oid) = &claims.oid {
             oid.as_str()
         } else {
             claims.sub.as_str()
@@ -72,22 +189,27 @@ impl AuthDetails {
                     (None, None) => None,
                 });
 
-        let preferred_username = claims
-            .name
-            .clone()
-            // Keycloak
-            .or(claims.preferred_username)
-            // Azure
-            .or(claims.app_displayname)
-            // Humans
-            .or(name.clone());
-
-        let email = claims.email.or(claims.upn);
+        let preferred_username = mapped_display_name.or_else(|| {
+            claims
+                .name
+                .clone()
+                // Keycloak
+                .or(claims.preferred_username)
+                // Azure
+                .or(claims.app_displayname)
+                // Humans
+                .or(name.clone())
+        });
+
+        let email = mapped_email.or(claims.email).or(claims.upn);
         let application_id = claims
             .appid
             .or(claims.app_id)
             .or(claims.application_id)
-            .or(claims.client_id);
+            .or(claims.client_id)
+            // If the mapping classifies the principal as an application, the subject
+            // is the best remaining identifier for the application.
+            .or_else(|| (mapped_idtyp.as_deref() == Some("app")).then(|| sub.to_string()));
 
         let principal = Principal {
             actor: Actor::Principal(user_id.clone()),
@@ -97,9 +219,11 @@ impl AuthDetails {
             issuer: claims.iss,
             email,
             application_id,
-            idtyp: claims
-                .idtyp
-                .or(claims.family_name.map(|_| "user".to_string())),
+            idtyp: mapped_idtyp.or_else(|| {
+                claims
+                    .idtyp
+                    .or(claims.family_name.map(|_| "user".to_string()))
+            }),
         };
 
         Ok(Self::Principal(principal))
@@ -181,12 +305,370 @@ struct Claims {
     )]
     email: Option<String>,
     upn: Option<String>,
+    /// All claims of the token. Used for configurable claim mappings.
+    #[serde(skip)]
+    raw: serde_json::Value,
+}
+
+impl Claims {
+    fn try_from_value(value: serde_json::Value) -> serde_json::Result<Self> {
+        let mut claims = Claims::deserialize(&value)?;
+        claims.raw = value;
+        Ok(claims)
+    }
+
+    fn get_string(&self, claim: &str) -> Option<String> {
+        claim_string(&self.raw, claim)
+    }
+
+    fn matches_machine_user_rule(&self, rule: &MachineUserRule) -> bool {
+        matches_machine_user_rule(&self.raw, &self.sub, rule)
+    }
+}
+
+/// Get a claim as string. Numbers and booleans are converted to strings.
+fn claim_string(claims: &serde_json::Value, claim: &str) -> Option<String> {
+    match claims.get(claim)? {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn has_claim(claims: &serde_json::Value, claim: &str) -> bool {
+    claims.get(claim).is_some_and(|v| !v.is_null())
+}
+
+fn matches_machine_user_rule(
+    claims: &serde_json::Value,
+    subject: &str,
+    rule: &MachineUserRule,
+) -> bool {
+    match rule {
+        MachineUserRule::ClaimPresent { claim } => has_claim(claims, claim),
+        MachineUserRule::ClaimAbsent { claim } => !has_claim(claims, claim),
+        MachineUserRule::ClaimEquals { claim, value } => {
+            claim_string(claims, claim).is_some_and(|v| &v == value)
+        }
+        MachineUserRule::SubjectEqualsClaim { claim } => {
+            claim_string(claims, claim).is_some_and(|v| v == subject)
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::api::management::v1::user::UserType;
-    use crate::service::authn::Claims;
+    use crate::config::{ClaimMapping, MachineUserRule};
+    use crate::service::authn::{Claims, IntrospectionResponse};
+
+    fn okta_mapping() -> ClaimMapping {
+        ClaimMapping {
+            issuer: "https://example.okta.com/oauth2/default".to_string(),
+            subject_claim: Some("uid".to_string()),
+            display_name_claim: None,
+            email_claim: Some("sub".to_string()),
+            machine_user: Some(MachineUserRule::SubjectEqualsClaim {
+                claim: "cid".to_string(),
+            }),
+        }
+    }
+
+    fn auth0_mapping() -> ClaimMapping {
+        ClaimMapping {
+            issuer: "https://example.eu.auth0.com/".to_string(),
+            subject_claim: Some("https://lakekeeper.example.com/user_id".to_string()),
+            display_name_claim: Some("https://lakekeeper.example.com/name".to_string()),
+            email_claim: Some("https://lakekeeper.example.com/email".to_string()),
+            machine_user: Some(MachineUserRule::ClaimEquals {
+                claim: "gty".to_string(),
+                value: "client-credentials".to_string(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_human_discovery_okta_mapping() {
+        let claims = Claims::try_from_value(serde_json::json!({
+          "ver": 1,
+          "jti": "AT.1234",
+          "iss": "https://example.okta.com/oauth2/default",
+          "aud": "api://lakekeeper",
+          "iat": 1_730_049_088,
+          "exp": 1_730_054_207,
+          "cid": "0oa1lakekeeperui",
+          "uid": "00u1abcd2EFGHijk3l4m",
+          "scp": ["openid", "email", "profile"],
+          "auth_time": 1_730_049_080,
+          "sub": "peter@example.com"
+        }))
+        .unwrap();
+
+        let auth_details =
+            super::AuthDetails::try_from_jwt_claims_with_mapping(claims, Some(&okta_mapping()))
+                .unwrap();
+        let principal = match auth_details {
+            super::AuthDetails::Principal(principal) => principal,
+            super::AuthDetails::Unauthenticated => panic!("Expected principal"),
+        };
+        let (name, user_type) = principal.get_name_and_type().unwrap();
+        assert_eq!(name, "peter@example.com");
+        assert_eq!(user_type, UserType::Human);
+        assert_eq!(principal.user_id().to_string(), "oidc~00u1abcd2EFGHijk3l4m");
+        assert_eq!(principal.email(), Some("peter@example.com"));
+    }
+
+    #[test]
+    fn test_machine_discovery_okta_mapping() {
+        let claims = Claims::try_from_value(serde_json::json!({
+          "ver": 1,
+          "jti": "AT.5678",
+          "iss": "https://example.okta.com/oauth2/default",
+          "aud": "api://lakekeeper",
+          "iat": 1_730_049_088,
+          "exp": 1_730_054_207,
+          "cid": "0oa1sparkmachine",
+          "scp": ["lakekeeper"],
+          "sub": "0oa1sparkmachine"
+        }))
+        .unwrap();
+
+        let auth_details =
+            super::AuthDetails::try_from_jwt_claims_with_mapping(claims, Some(&okta_mapping()))
+                .unwrap();
+        let principal = match auth_details {
+            super::AuthDetails::Principal(principal) => principal,
+            super::AuthDetails::Unauthenticated => panic!("Expected principal"),
+        };
+        let (name, user_type) = principal.get_name_and_type().unwrap();
+        // No uid claim, fall back to sub
+        assert_eq!(principal.user_id().to_string(), "oidc~0oa1sparkmachine");
+        assert_eq!(name, "0oa1sparkmachine");
+        assert_eq!(user_type, UserType::Application);
+    }
+
+    #[test]
+    fn test_human_discovery_auth0_mapping() {
+        let claims = Claims::try_from_value(serde_json::json!({
+          "https://lakekeeper.example.com/user_id": "65a1b2c3d4e5f60718293a4b",
+          "https://lakekeeper.example.com/name": "Peter Cold",
+          "https://lakekeeper.example.com/email": "peter@example.com",
+          "iss": "https://example.eu.auth0.com/",
+          "sub": "auth0|65a1b2c3d4e5f60718293a4b",
+          "aud": ["https://lakekeeper.example.com", "https://example.eu.auth0.com/userinfo"],
+          "iat": 1_730_049_088,
+          "exp": 1_730_054_207,
+          "scope": "openid profile email",
+          "azp": "aBcDeFgHiJkLmNoPqRsTuVwXyZ012345"
+        }))
+        .unwrap();
+
+        let auth_details =
+            super::AuthDetails::try_from_jwt_claims_with_mapping(claims, Some(&auth0_mapping()))
+                .unwrap();
+        let principal = match auth_details {
+            super::AuthDetails::Principal(principal) => principal,
+            super::AuthDetails::Unauthenticated => panic!("Expected principal"),
+        };
+        let (name, user_type) = principal.get_name_and_type().unwrap();
+        assert_eq!(name, "Peter Cold");
+        assert_eq!(user_type, UserType::Human);
+        assert_eq!(
+            principal.user_id().to_string(),
+            "oidc~65a1b2c3d4e5f60718293a4b"
+        );
+        assert_eq!(principal.email(), Some("peter@example.com"));
+    }
+
+    #[test]
+    fn test_machine_discovery_auth0_mapping() {
+        let claims = Claims::try_from_value(serde_json::json!({
+          "https://lakekeeper.example.com/user_id": "spark-etl",
+          "https://lakekeeper.example.com/name": "Spark ETL",
+          "iss": "https://example.eu.auth0.com/",
+          "sub": "aBcDeFgHiJkLmNoPqRsTuVwXyZ012345@clients",
+          "aud": "https://lakekeeper.example.com",
+          "iat": 1_730_049_088,
+          "exp": 1_730_054_207,
+          "gty": "client-credentials",
+          "azp": "aBcDeFgHiJkLmNoPqRsTuVwXyZ012345"
+        }))
+        .unwrap();
+
+        let auth_details =
+            super::AuthDetails::try_from_jwt_claims_with_mapping(claims, Some(&auth0_mapping()))
+                .unwrap();
+        let principal = match auth_details {
+            super::AuthDetails::Principal(principal) => principal,
+            super::AuthDetails::Unauthenticated => panic!("Expected principal"),
+        };
+        let (name, user_type) = principal.get_name_and_type().unwrap();
+        assert_eq!(name, "Spark ETL");
+        assert_eq!(user_type, UserType::Application);
+        assert_eq!(principal.user_id().to_string(), "oidc~spark-etl");
+    }
+
+    #[test]
+    fn test_human_discovery_introspection_okta_mapping() {
+        let response = IntrospectionResponse::try_from_value(serde_json::json!({
+          "active": true,
+          "scope": "openid email profile",
+          "username": "peter@example.com",
+          "exp": 1_730_054_207,
+          "iat": 1_730_049_088,
+          "sub": "peter@example.com",
+          "aud": "api://lakekeeper",
+          "iss": "https://example.okta.com/oauth2/default",
+          "client_id": "0oa1lakekeeperui",
+          "uid": "00u1abcd2EFGHijk3l4m",
+          "token_type": "Bearer"
+        }))
+        .unwrap();
+
+        let auth_details = super::AuthDetails::try_from_introspection_with_mapping(
+            response,
+            "https://example.okta.com/oauth2/default/v1/introspect",
+            Some(&okta_mapping()),
+        )
+        .unwrap();
+        let principal = match auth_details {
+            super::AuthDetails::Principal(principal) => principal,
+            super::AuthDetails::Unauthenticated => panic!("Expected principal"),
+        };
+        let (name, user_type) = principal.get_name_and_type().unwrap();
+        assert_eq!(name, "peter@example.com");
+        assert_eq!(user_type, UserType::Human);
+        assert_eq!(principal.user_id().to_string(), "oidc~00u1abcd2EFGHijk3l4m");
+        assert_eq!(principal.email(), Some("peter@example.com"));
+    }
+
+    #[test]
+    fn test_machine_discovery_introspection_auth0_mapping() {
+        let response = IntrospectionResponse::try_from_value(serde_json::json!({
+          "active": true,
+          "iss": "https://example.eu.auth0.com/",
+          "sub": "sparkMachineClient@clients",
+          "client_id": "sparkMachineClient",
+          "exp": 1_730_054_207,
+          "gty": "client-credentials"
+        }))
+        .unwrap();
+
+        let auth_details = super::AuthDetails::try_from_introspection_with_mapping(
+            response,
+            "https://example.eu.auth0.com/oauth/introspect",
+            Some(&auth0_mapping()),
+        )
+        .unwrap();
+        let principal = match auth_details {
+            super::AuthDetails::Principal(principal) => principal,
+            super::AuthDetails::Unauthenticated => panic!("Expected principal"),
+        };
+        let (name, user_type) = principal.get_name_and_type().unwrap();
+        assert_eq!(name, "sparkMachineClient");
+        assert_eq!(user_type, UserType::Application);
+        assert_eq!(
+            principal.user_id().to_string(),
+            "oidc~sparkMachineClient@clients"
+        );
+    }
+
+    #[test]
+    fn test_mapping_machine_rule_overrides_heuristics() {
+        // Keycloak user token that would be classified as human by the family_name heuristic
+        let claims = Claims::try_from_value(serde_json::json!({
+          "iss": "http://localhost:30080/realms/iceberg",
+          "sub": "f1616ed0-18d8-48ea-9fb3-832f42db0b1b",
+          "azp": "iceberg-machine-client",
+          "preferred_username": "service-account-iceberg-machine-client",
+          "family_name": "Machine",
+          "client_id": "iceberg-machine-client"
+        }))
+        .unwrap();
+        let mapping = ClaimMapping {
+            issuer: "http://localhost:30080/realms/iceberg".to_string(),
+            subject_claim: None,
+            display_name_claim: None,
+            email_claim: None,
+            machine_user: Some(MachineUserRule::ClaimPresent {
+                claim: "client_id".to_string(),
+            }),
+        };
+
+        let auth_details =
+            super::AuthDetails::try_from_jwt_claims_with_mapping(claims, Some(&mapping)).unwrap();
+        let principal = match auth_details {
+            super::AuthDetails::Principal(principal) => principal,
+            super::AuthDetails::Unauthenticated => panic!("Expected principal"),
+        };
+        let (name, user_type) = principal.get_name_and_type().unwrap();
+        assert_eq!(name, "service-account-iceberg-machine-client");
+        assert_eq!(user_type, UserType::Application);
+    }
+
+    #[test]
+    fn test_human_discovery_introspection() {
+        let response: IntrospectionResponse = serde_json::from_value(serde_json::json!({
+          "active": true,
+          "scope": "openid email profile",
+          "client_id": "lakekeeper-ui",
+          "username": "peter",
+          "token_type": "Bearer",
+          "exp": 1_730_054_207,
+          "iat": 1_730_049_088,
+          "sub": "00u1abcd2EFGHijk3l4m",
+          "iss": "https://example.okta.com/oauth2/default",
+          "email": "peter@example.com"
+        }))
+        .unwrap();
+
+        let auth_details = super::AuthDetails::try_from_introspection(
+            response,
+            "https://example.okta.com/oauth2/default/v1/introspect",
+        )
+        .unwrap();
+        let principal = match auth_details {
+            super::AuthDetails::Principal(principal) => principal,
+            super::AuthDetails::Unauthenticated => panic!("Expected principal"),
+        };
+        let (name, user_type) = principal.get_name_and_type().unwrap();
+        assert_eq!(name, "peter");
+        assert_eq!(user_type, UserType::Human);
+        assert_eq!(principal.user_id().to_string(), "oidc~00u1abcd2EFGHijk3l4m");
+        assert_eq!(
+            principal.issuer(),
+            "https://example.okta.com/oauth2/default"
+        );
+        assert_eq!(principal.email(), Some("peter@example.com"));
+    }
+
+    #[test]
+    fn test_machine_discovery_introspection() {
+        let response: IntrospectionResponse = serde_json::from_value(serde_json::json!({
+          "active": true,
+          "scope": "lakekeeper",
+          "client_id": "spark-machine-client",
+          "token_type": "Bearer",
+          "exp": 1_730_054_207
+        }))
+        .unwrap();
+
+        let auth_details = super::AuthDetails::try_from_introspection(
+            response,
+            "https://idp.example.com/introspect",
+        )
+        .unwrap();
+        let principal = match auth_details {
+            super::AuthDetails::Principal(principal) => principal,
+            super::AuthDetails::Unauthenticated => panic!("Expected principal"),
+        };
+        let (name, user_type) = principal.get_name_and_type().unwrap();
+        assert_eq!(name, "spark-machine-client");
+        assert_eq!(user_type, UserType::Application);
+        assert_eq!(principal.user_id().to_string(), "oidc~spark-machine-client");
+        assert_eq!(principal.issuer(), "https://idp.example.com/introspect");
+    }
 
     #[test]
     fn test_machine_discovery_entra() {
@@ -0,0 +1,176 @@
+use crate::api;
+use crate::config::CertificateIdentitySource;
+use crate::service::authn::{Actor, Principal, UserId};
+use crate::service::AuthDetails;
+use http::StatusCode;
+use iceberg_ext::catalog::rest::ErrorModel;
+use x509_parser::certificate::X509Certificate;
+use x509_parser::extensions::GeneralName;
+use x509_parser::prelude::FromDer;
+
+/// Information about the TLS connection a request was received on.
+///
+/// Inserted into the request extensions by the TLS acceptor of the server.
+#[derive(Debug, Clone, Default)]
+pub struct TlsConnectionInfo {
+    /// DER encoded end-entity certificate presented by the client, if any.
+    pub client_certificate: Option<Vec<u8>>,
+}
+
+/// Verifier for TLS client certificates (mTLS).
+///
+/// The certificate chain is validated during the TLS handshake. This verifier
+/// only maps the already validated certificate to a principal.
+#[derive(Debug, Clone)]
+pub struct CertificateVerifier {
+    identity_source: CertificateIdentitySource,
+}
+
+impl CertificateVerifier {
+    #[must_use]
+    pub fn new(identity_source: CertificateIdentitySource) -> Self {
+        Self { identity_source }
+    }
+
+    pub(super) fn typ(&self) -> &'static str {
+        "certificate"
+    }
+
+    pub(super) fn decode(&self, certificate: &[u8]) -> api::Result<AuthDetails, ErrorModel> {
+        let (_, certificate) = X509Certificate::from_der(certificate).map_err(|e| {
+            ErrorModel::builder()
+                .message("Failed to parse client certificate.")
+                .code(StatusCode::UNAUTHORIZED.into())
+                .r#type("UnauthorizedError")
+                .source(Some(Box::new(e)))
+                .build()
+        })?;
+
+        let subject = match self.identity_source {
+            CertificateIdentitySource::SubjectCommonName => certificate
+                .subject()
+                .iter_common_name()
+                .next()
+                .and_then(|cn| cn.as_str().ok())
+                .map(str::to_string),
+            CertificateIdentitySource::SubjectAlternativeName => certificate
+                .subject_alternative_name()
+                .ok()
+                .flatten()
+                .and_then(|san| {
+                    san.value.general_names.iter().find_map(|name| match name {
+                        GeneralName::DNSName(name) | GeneralName::RFC822Name(name) => {
+                            Some((*name).to_string())
+                        }
+                        _ => None,
+                    })
+                }),
+        }
+        .ok_or_else(|| {
+            let expected = match self.identity_source {
+                CertificateIdentitySource::SubjectCommonName => "subject common name",
+                CertificateIdentitySource::SubjectAlternativeName => {
+                    "DNS or email subject alternative name"
+                }
+            };
+            ErrorModel::builder()
+                .message(format!("Client certificate does not contain a {expected}."))
+                .code(StatusCode::UNAUTHORIZED.into())
+                .r#type("UnauthorizedError")
+                .build()
+        })?;
+
+        let user_id = UserId::certificate(&subject).map_err(|e| e.error)?;
+
+        Ok(AuthDetails::Principal(Principal {
+            actor: Actor::Principal(user_id.clone()),
+            user_id,
+            name: Some(subject.clone()),
+            display_name: Some(subject.clone()),
+            application_id: Some(subject),
+            issuer: certificate.issuer().to_string(),
+            email: None,
+            idtyp: Some("app".to_string()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use x509_parser::pem::parse_x509_pem;
+
+    const CLIENT_CERTIFICATE: &[u8] = include_bytes!("../../../../tests/certs/client.crt");
+    const EMAIL_CLIENT_CERTIFICATE: &[u8] =
+        include_bytes!("../../../../tests/certs/client-email.crt");
+
+    fn decode(
+        identity_source: CertificateIdentitySource,
+        pem: &[u8],
+    ) -> api::Result<Principal, ErrorModel> {
+        let (_, pem) = parse_x509_pem(pem).unwrap();
+        match CertificateVerifier::new(identity_source).decode(&pem.contents)? {
+            AuthDetails::Principal(principal) => Ok(principal),
+            AuthDetails::Unauthenticated => panic!("Expected a principal"),
+        }
+    }
+
+    #[test]
+    fn test_subject_common_name() {
+        let principal = decode(
+            CertificateIdentitySource::SubjectCommonName,
+            CLIENT_CERTIFICATE,
+        )
+        .unwrap();
+        assert_eq!(
+            principal.user_id(),
+            &UserId::certificate("my-client").unwrap()
+        );
+        assert_eq!(principal.name(), Some("my-client"));
+        assert_eq!(principal.issuer(), "CN=Lakekeeper Test CA");
+    }
+
+    #[test]
+    fn test_subject_alternative_name_dns() {
+        let principal = decode(
+            CertificateIdentitySource::SubjectAlternativeName,
+            CLIENT_CERTIFICATE,
+        )
+        .unwrap();
+        assert_eq!(
+            principal.user_id(),
+            &UserId::certificate("client.example.com").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_subject_alternative_name_email() {
+        let principal = decode(
+            CertificateIdentitySource::SubjectAlternativeName,
+            EMAIL_CLIENT_CERTIFICATE,
+        )
+        .unwrap();
+        assert_eq!(
+            principal.user_id(),
+            &UserId::certificate("machine@example.com").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_missing_common_name() {
+        let error = decode(
+            CertificateIdentitySource::SubjectCommonName,
+            EMAIL_CLIENT_CERTIFICATE,
+        )
+        .unwrap_err();
+        assert_eq!(error.code, StatusCode::UNAUTHORIZED.as_u16());
+    }
+
+    #[test]
+    fn test_invalid_certificate() {
+        let error = CertificateVerifier::new(CertificateIdentitySource::SubjectCommonName)
+            .decode(b"not a certificate")
+            .unwrap_err();
+        assert_eq!(error.code, StatusCode::UNAUTHORIZED.as_u16());
+    }
+}
@@ -18,7 +18,15 @@ use url::Url;
 #[async_trait::async_trait]
 impl Verifier for IdpVerifier {
     async fn decode(&self, token: &str) -> api::Result<AuthDetails, ErrorModel> {
-        let claims = IdpVerifier::decode::<Claims>(self, token).await?;
+        let claims = IdpVerifier::decode::<serde_json::Value>(self, token).await?;
+        let claims = Claims::try_from_value(claims).map_err(|e| {
+            ErrorModel::builder()
+                .message("Failed to parse token claims.")
+                .code(StatusCode::UNAUTHORIZED.into())
+                .r#type("UnauthorizedError")
+                .source(Some(Box::new(e)))
+                .build()
+        })?;
         AuthDetails::try_from_jwt_claims(claims).map_err(|e| e.error)
     }
 
@@ -0,0 +1,350 @@
+use crate::api;
+use crate::service::authn::verification::Verifier;
+use crate::service::AuthDetails;
+use http::StatusCode;
+use iceberg_ext::catalog::rest::ErrorModel;
+use lru::LruCache;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// Maximum number of active tokens kept in the introspection cache.
+const CACHE_CAPACITY: usize = 10_000;
+
+/// Verifier for opaque access tokens using `OAuth2` Token Introspection (RFC 7662).
+///
+/// Only opaque tokens are introspected; JWTs are never sent to the introspection endpoint.
+/// Positive introspection results are cached until the `exp` of the token.
+/// The cache is keyed by the SHA-256 of the token, so tokens are not kept in memory.
+/// Inactive tokens are never cached.
+#[derive(Clone)]
+pub struct IntrospectionVerifier {
+    client: reqwest::Client,
+    endpoint: Url,
+    client_id: String,
+    client_secret: String,
+    audience: Option<Vec<String>>,
+    cache: Arc<Mutex<LruCache<[u8; 32], CachedIntrospection>>>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedIntrospection {
+    details: AuthDetails,
+    expires_at: i64,
+}
+
+#[async_trait::async_trait]
+impl Verifier for IntrospectionVerifier {
+    async fn decode(&self, token: &str) -> api::Result<AuthDetails, ErrorModel> {
+        IntrospectionVerifier::decode(self, token).await
+    }
+
+    fn typ(&self) -> &'static str {
+        "introspection"
+    }
+}
+
+impl std::fmt::Debug for IntrospectionVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IntrospectionVerifier")
+            .field("endpoint", &self.endpoint)
+            .field("client_id", &self.client_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IntrospectionVerifier {
+    /// Create a new verifier for the given introspection endpoint.
+    ///
+    /// `client_id` and `client_secret` are sent as HTTP Basic credentials
+    /// to authenticate Lakekeeper against the introspection endpoint.
+    /// If `audience` is set, the `aud` or `client_id` of the introspected token
+    /// must match one of the given values.
+    #[must_use]
+    pub fn new(
+        endpoint: Url,
+        client_id: String,
+        client_secret: String,
+        audience: Option<Vec<String>>,
+    ) -> Self {
+        tracing::info!("Created IntrospectionVerifier for endpoint: {endpoint}");
+        Self {
+            client: reqwest::Client::new(),
+            endpoint,
+            client_id,
+            client_secret,
+            audience,
+            cache: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(CACHE_CAPACITY).expect("Cache capacity is non-zero"),
+            ))),
+        }
+    }
+
+    async fn decode(&self, token: &str) -> api::Result<AuthDetails, ErrorModel> {
+        // JWTs are verified by the idp or k8s verifier. They must not be sent
+        // to a third party introspection endpoint.
+        if is_jwt(token) {
+            return Err(ErrorModel::builder()
+                .message("Only opaque tokens are introspected.")
+                .code(StatusCode::UNAUTHORIZED.into())
+                .r#type("UnauthorizedError")
+                .build());
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        if let Some(details) = self.get_cached(token, now) {
+            return Ok(details);
+        }
+
+        let response = self
+            .client
+            .post(self.endpoint.clone())
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .form(&[("token", token), ("token_type_hint", "access_token")])
+            .send()
+            .await
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "Failed to send token to introspection endpoint",
+                    "InternalServerError",
+                    Some(Box::new(e)),
+                )
+            })?
+            .error_for_status()
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "Introspection endpoint returned an error",
+                    "InternalServerError",
+                    Some(Box::new(e)),
+                )
+            })?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "Failed to parse introspection response",
+                    "InternalServerError",
+                    Some(Box::new(e)),
+                )
+            })?;
+        let response = IntrospectionResponse::try_from_value(response).map_err(|e| {
+            ErrorModel::internal(
+                "Failed to parse introspection response",
+                "InternalServerError",
+                Some(Box::new(e)),
+            )
+        })?;
+
+        if !response.active {
+            return Err(ErrorModel::builder()
+                .message("Token is not active.")
+                .code(StatusCode::UNAUTHORIZED.into())
+                .r#type("UnauthorizedError")
+                .build());
+        }
+
+        if response.exp.is_some_and(|exp| exp <= now) {
+            return Err(ErrorModel::builder()
+                .message("Token is expired.")
+                .code(StatusCode::UNAUTHORIZED.into())
+                .r#type("UnauthorizedError")
+                .build());
+        }
+
+        if !self.audience_allowed(&response) {
+            return Err(ErrorModel::builder()
+                .message("Token audience is not allowed.")
+                .code(StatusCode::UNAUTHORIZED.into())
+                .r#type("UnauthorizedError")
+                .build());
+        }
+
+        let expires_at = response.exp;
+        let details = AuthDetails::try_from_introspection(response, self.endpoint.as_str())
+            .map_err(|e| e.error)?;
+
+        if let Some(expires_at) = expires_at {
+            self.put_cached(token, details.clone(), expires_at);
+        }
+
+        Ok(details)
+    }
+
+    fn audience_allowed(&self, response: &IntrospectionResponse) -> bool {
+        let Some(audience) = &self.audience else {
+            return true;
+        };
+        response
+            .aud
+            .iter()
+            .chain(response.client_id.as_ref())
+            .any(|aud| audience.contains(aud))
+    }
+
+    fn get_cached(&self, token: &str, now: i64) -> Option<AuthDetails> {
+        let mut cache = self
+            .cache
+            .lock()
+            .map_err(|e| tracing::error!("Introspection cache lock poisoned: {e}"))
+            .ok()?;
+        let key = cache_key(token);
+        match cache.get(&key) {
+            Some(entry) if entry.expires_at > now => Some(entry.details.clone()),
+            Some(_) => {
+                cache.pop(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn put_cached(&self, token: &str, details: AuthDetails, expires_at: i64) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.put(
+                cache_key(token),
+                CachedIntrospection {
+                    details,
+                    expires_at,
+                },
+            );
+        }
+    }
+}
+
+/// Response of an RFC 7662 introspection endpoint.
+#[derive(Debug, Deserialize, Clone)]
+pub(crate) struct IntrospectionResponse {
+    pub(crate) active: bool,
+    pub(crate) sub: Option<String>,
+    pub(crate) username: Option<String>,
+    pub(crate) client_id: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_audience")]
+    pub(crate) aud: Vec<String>,
+    pub(crate) iss: Option<String>,
+    pub(crate) exp: Option<i64>,
+    pub(crate) email: Option<String>,
+    pub(crate) name: Option<String>,
+    /// The full response. Used for configurable claim mappings.
+    #[serde(skip)]
+    pub(crate) raw: serde_json::Value,
+}
+
+impl IntrospectionResponse {
+    pub(crate) fn try_from_value(value: serde_json::Value) -> serde_json::Result<Self> {
+        let mut response = IntrospectionResponse::deserialize(&value)?;
+        response.raw = value;
+        Ok(response)
+    }
+}
+
+/// `aud` is either a single string or an array of strings.
+fn deserialize_audience<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Audience {
+        Single(String),
+        Multiple(Vec<String>),
+    }
+
+    Ok(match Option::<Audience>::deserialize(deserializer)? {
+        Some(Audience::Single(aud)) => vec![aud],
+        Some(Audience::Multiple(aud)) => aud,
+        None => vec![],
+    })
+}
+
+fn cache_key(token: &str) -> [u8; 32] {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+fn is_jwt(token: &str) -> bool {
+    jsonwebtoken::decode_header(token).is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn details(name: &str) -> AuthDetails {
+        AuthDetails::try_from_introspection(
+            serde_json::from_value(serde_json::json!({
+                "active": true,
+                "sub": "my-user",
+                "username": name,
+            }))
+            .unwrap(),
+            "https://idp.example.com/introspect",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cache_respects_expiry() {
+        let verifier = IntrospectionVerifier::new(
+            "https://idp.example.com/introspect".parse().unwrap(),
+            "lakekeeper".to_string(),
+            "secret".to_string(),
+            None,
+        );
+
+        verifier.put_cached("token-1", details("peter"), 100);
+        assert!(verifier
+            .cache
+            .lock()
+            .unwrap()
+            .contains(&cache_key("token-1")));
+        assert!(verifier.get_cached("token-1", 50).is_some());
+        assert!(verifier.get_cached("token-1", 100).is_none());
+        // Expired entries are evicted
+        assert!(verifier.get_cached("token-1", 50).is_none());
+        assert!(verifier.get_cached("token-2", 50).is_none());
+    }
+
+    #[test]
+    fn test_audience() {
+        let verifier = IntrospectionVerifier::new(
+            "https://idp.example.com/introspect".parse().unwrap(),
+            "lakekeeper".to_string(),
+            "secret".to_string(),
+            Some(vec!["lakekeeper".to_string()]),
+        );
+        let response = |value: serde_json::Value| -> IntrospectionResponse {
+            serde_json::from_value(value).unwrap()
+        };
+
+        assert!(verifier.audience_allowed(&response(
+            serde_json::json!({"active": true, "aud": "lakekeeper"})
+        )));
+        assert!(verifier.audience_allowed(&response(
+            serde_json::json!({"active": true, "aud": ["other", "lakekeeper"]})
+        )));
+        assert!(verifier.audience_allowed(&response(
+            serde_json::json!({"active": true, "client_id": "lakekeeper"})
+        )));
+        assert!(!verifier.audience_allowed(&response(
+            serde_json::json!({"active": true, "aud": "other", "client_id": "other"})
+        )));
+        assert!(!verifier.audience_allowed(&response(serde_json::json!({"active": true}))));
+    }
+
+    #[tokio::test]
+    async fn test_jwts_are_not_introspected() {
+        let verifier = IntrospectionVerifier::new(
+            "https://idp.example.com/introspect".parse().unwrap(),
+            "lakekeeper".to_string(),
+            "secret".to_string(),
+            None,
+        );
+        // Header of a HS256 JWT
+        let token = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJzdWIiOiIxMjM0NTY3ODkwIn0.signature";
+        assert!(is_jwt(token));
+        assert!(!is_jwt("opaque-token"));
+        let error = verifier.decode(token).await.unwrap_err();
+        assert_eq!(error.code, StatusCode::UNAUTHORIZED.as_u16());
+    }
+}
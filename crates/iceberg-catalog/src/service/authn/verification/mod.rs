@@ -12,37 +12,56 @@ use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
 use std::fmt::Debug;
 use std::sync::Arc;
 
+mod certificate;
 mod idp;
+mod introspection;
 mod kubernetes;
 
+pub use certificate::{CertificateVerifier, TlsConnectionInfo};
 pub use idp::IdpVerifier;
+pub(crate) use introspection::IntrospectionResponse;
+pub use introspection::IntrospectionVerifier;
 pub use kubernetes::K8sVerifier;
 
-/// `VerifierChain` chains idp and k8s verifier.
+/// `VerifierChain` chains idp, k8s, introspection and client certificate verifier.
 #[derive(Clone, Debug)]
 pub(crate) struct VerifierChain {
     idp_verifier: Option<IdpVerifier>,
+    introspection_verifier: Option<IntrospectionVerifier>,
     k8s_verifier: Option<K8sVerifier>,
+    certificate_verifier: Option<CertificateVerifier>,
 }
 
 impl VerifierChain {
-    /// Create a new verifier chain with the idp and k8s verifier
+    /// Create a new verifier chain with the idp, k8s, introspection and certificate verifier
     ///
     /// You must provide at least one verifier. The authentication middleware will first try to
-    /// decode the token using the idp provider and then the k8s provider.
+    /// decode the token using the idp provider, then the k8s provider and then the
+    /// introspection endpoint. Only opaque tokens are sent to the introspection endpoint.
+    /// Requests without a bearer token are authenticated using the TLS client certificate
+    /// if a `certificate_verifier` is provided.
     ///
     /// # Errors
-    /// - If neither `idp_verifier` nor `k8s_verifier` is provided
+    /// - If neither `idp_verifier`, `introspection_verifier`, `k8s_verifier` nor
+    ///   `certificate_verifier` is provided
     pub(crate) fn try_new(
         idp_verifier: Option<IdpVerifier>,
+        introspection_verifier: Option<IntrospectionVerifier>,
         k8s_verifier: Option<K8sVerifier>,
+        certificate_verifier: Option<CertificateVerifier>,
     ) -> anyhow::Result<Self> {
-        if idp_verifier.is_none() && k8s_verifier.is_none() {
+        if idp_verifier.is_none()
+            && introspection_verifier.is_none()
+            && k8s_verifier.is_none()
+            && certificate_verifier.is_none()
+        {
             return Err(anyhow::anyhow!("At least one verifier must be provided"));
         }
         Ok(Self {
             idp_verifier,
+            introspection_verifier,
             k8s_verifier,
+            certificate_verifier,
         })
     }
 
@@ -54,6 +73,9 @@ impl VerifierChain {
         if let Some(k8s) = self.k8s_verifier {
             verifiers.push(Arc::new(k8s) as _);
         }
+        if let Some(introspection) = self.introspection_verifier {
+            verifiers.push(Arc::new(introspection) as _);
+        }
         verifiers
     }
 }
@@ -61,11 +83,38 @@ impl VerifierChain {
 pub(crate) async fn auth_middleware_fn(
     State(verifiers): State<VerifierChain>,
     authorization: Option<TypedHeader<Authorization<Bearer>>>,
+    tls_info: Option<Extension<TlsConnectionInfo>>,
     Extension(mut metadata): Extension<RequestMetadata>,
     mut request: Request,
     next: Next,
 ) -> Response {
+    let client_certificate = tls_info.and_then(|Extension(info)| info.client_certificate);
     let Some(authorization) = authorization else {
+        if let (Some(verifier), Some(certificate)) = (
+            verifiers.certificate_verifier.as_ref(),
+            client_certificate.as_deref(),
+        ) {
+            return match verifier.decode(certificate) {
+                Ok(details) => {
+                    metadata.auth_details = details;
+                    request.extensions_mut().insert(metadata);
+                    next.run(request).await
+                }
+                Err(e) => {
+                    tracing::error!(
+                        ?e,
+                        "Failed to decode client certificate with verifier: '{}' due to: '{e}'",
+                        verifier.typ(),
+                    );
+                    IcebergErrorResponse::from(ErrorModel::unauthorized(
+                        "Unauthorized",
+                        "UnauthorizedError",
+                        None,
+                    ))
+                    .into_response()
+                }
+            };
+        }
         tracing::debug!("Missing authorization header");
         return IcebergErrorResponse::from(ErrorModel::unauthorized(
             "Missing authorization header.",
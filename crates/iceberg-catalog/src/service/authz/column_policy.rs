@@ -0,0 +1,228 @@
+//! Column-level access policies.
+//!
+//! Policies are stored by the catalog per table and are keyed by the field id of the
+//! column, so that they survive column renames. Which policies restrict the actor of a
+//! request is decided by the [`Authorizer`].
+
+use super::Authorizer;
+use crate::api::iceberg::v1::Result;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authn::UserId;
+use crate::service::{Actor, Catalog, RoleId, TableIdentUuid};
+use crate::WarehouseIdent;
+use iceberg_ext::configs::table::{custom, TableProperties};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Table config key listing the field ids a principal must not read
+pub const DENIED_FIELD_IDS_KEY: &str = "lakekeeper.column-policy.denied-field-ids";
+/// Table config key listing the field ids a principal may only read masked
+pub const MASKED_FIELD_IDS_KEY: &str = "lakekeeper.column-policy.masked-field-ids";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColumnPolicyEffect {
+    /// The column must not be read.
+    Deny,
+    /// The column must only be read masked, i.e. with all values replaced by null.
+    Mask,
+}
+
+/// A principal that is exempt from a column policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum PolicyPrincipal {
+    #[schema(value_type = String)]
+    #[schema(title = "PolicyPrincipalUser")]
+    /// Id of the user
+    User(UserId),
+    #[schema(value_type = uuid::Uuid)]
+    #[schema(title = "PolicyPrincipalRole")]
+    /// Id of the role. Applies to all assignees of the role.
+    Role(RoleId),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ColumnPolicy {
+    /// Field id of the column in the table schema.
+    pub field_id: i32,
+    /// Effect of the policy on principals that are not exempt.
+    pub effect: ColumnPolicyEffect,
+    /// Principals the policy does not apply to.
+    #[serde(default)]
+    pub exempt: Vec<PolicyPrincipal>,
+}
+
+impl ColumnPolicy {
+    /// Returns true if the actor itself is listed as exempt.
+    /// Assignments of users to roles are not considered.
+    #[must_use]
+    pub fn exempts_actor_directly(&self, actor: &Actor) -> bool {
+        self.exempt
+            .iter()
+            .any(|principal| match (principal, actor) {
+                (PolicyPrincipal::User(user_id), Actor::Principal(principal))
+                | (PolicyPrincipal::User(user_id), Actor::Role { principal, .. }) => {
+                    user_id == principal
+                }
+                (PolicyPrincipal::Role(role_id), Actor::Role { assumed_role, .. }) => {
+                    role_id == assumed_role
+                }
+                (PolicyPrincipal::Role(_), Actor::Principal(_)) | (_, Actor::Anonymous) => false,
+            })
+    }
+}
+
+/// Columns of a table the actor of a request is restricted on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnRestrictions {
+    pub denied_field_ids: Vec<i32>,
+    pub masked_field_ids: Vec<i32>,
+}
+
+impl ColumnRestrictions {
+    #[must_use]
+    pub fn from_policies(policies: &[ColumnPolicy]) -> Self {
+        let mut restrictions = Self::default();
+        for policy in policies {
+            match policy.effect {
+                ColumnPolicyEffect::Deny => restrictions.denied_field_ids.push(policy.field_id),
+                ColumnPolicyEffect::Mask => restrictions.masked_field_ids.push(policy.field_id),
+            }
+        }
+        restrictions.denied_field_ids.sort_unstable();
+        restrictions.masked_field_ids.sort_unstable();
+        restrictions
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.denied_field_ids.is_empty() && self.masked_field_ids.is_empty()
+    }
+
+    /// Table config that tells query engines which columns to remove or mask.
+    #[must_use]
+    pub fn to_table_config(&self) -> TableProperties {
+        let mut config = TableProperties::default();
+        for (key, field_ids) in [
+            (DENIED_FIELD_IDS_KEY, &self.denied_field_ids),
+            (MASKED_FIELD_IDS_KEY, &self.masked_field_ids),
+        ] {
+            if !field_ids.is_empty() {
+                config.insert(&custom::CustomConfig {
+                    key: key.to_string(),
+                    value: field_ids.iter().join(","),
+                });
+            }
+        }
+        config
+    }
+}
+
+/// Get the column restrictions of the actor of the request on a table.
+///
+/// # Errors
+/// - Loading the policies from the catalog fails
+/// - The authorizer fails to evaluate the exemptions
+pub(crate) async fn get_column_restrictions<C: Catalog, A: Authorizer>(
+    authorizer: &A,
+    metadata: &RequestMetadata,
+    warehouse_id: WarehouseIdent,
+    table_id: TableIdentUuid,
+    catalog_state: C::State,
+) -> Result<ColumnRestrictions> {
+    let policies = C::get_column_policies(table_id, catalog_state).await?;
+    if policies.is_empty() {
+        return Ok(ColumnRestrictions::default());
+    }
+    let restricting = authorizer
+        .restricting_column_policies(metadata, warehouse_id, table_id, policies)
+        .await?;
+    Ok(ColumnRestrictions::from_policies(&restricting))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(
+        field_id: i32,
+        effect: ColumnPolicyEffect,
+        exempt: Vec<PolicyPrincipal>,
+    ) -> ColumnPolicy {
+        ColumnPolicy {
+            field_id,
+            effect,
+            exempt,
+        }
+    }
+
+    #[test]
+    fn test_exempts_actor_directly() {
+        let user = UserId::oidc("alice").unwrap();
+        let other = UserId::oidc("bob").unwrap();
+        let role = RoleId::default();
+        let policy = policy(
+            1,
+            ColumnPolicyEffect::Deny,
+            vec![
+                PolicyPrincipal::User(user.clone()),
+                PolicyPrincipal::Role(role),
+            ],
+        );
+
+        assert!(policy.exempts_actor_directly(&Actor::Principal(user)));
+        assert!(policy.exempts_actor_directly(&Actor::Role {
+            principal: other.clone(),
+            assumed_role: role,
+        }));
+        assert!(!policy.exempts_actor_directly(&Actor::Principal(other.clone())));
+        assert!(!policy.exempts_actor_directly(&Actor::Role {
+            principal: other,
+            assumed_role: RoleId::default(),
+        }));
+        assert!(!policy.exempts_actor_directly(&Actor::Anonymous));
+    }
+
+    #[test]
+    fn test_restrictions_to_table_config() {
+        let restrictions = ColumnRestrictions::from_policies(&[
+            policy(3, ColumnPolicyEffect::Deny, vec![]),
+            policy(1, ColumnPolicyEffect::Deny, vec![]),
+            policy(2, ColumnPolicyEffect::Mask, vec![]),
+        ]);
+        assert!(!restrictions.is_empty());
+
+        let config = restrictions.to_table_config();
+        assert_eq!(
+            config.get_custom_prop(DENIED_FIELD_IDS_KEY),
+            Some("1,3".to_string())
+        );
+        assert_eq!(
+            config.get_custom_prop(MASKED_FIELD_IDS_KEY),
+            Some("2".to_string())
+        );
+        assert!(ColumnRestrictions::default()
+            .to_table_config()
+            .inner()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_policy_serde() {
+        let policy: ColumnPolicy = serde_json::from_value(serde_json::json!({
+            "field-id": 4,
+            "effect": "mask",
+            "exempt": [{"user": "oidc~alice"}]
+        }))
+        .unwrap();
+        assert_eq!(policy.field_id, 4);
+        assert_eq!(policy.effect, ColumnPolicyEffect::Mask);
+        assert_eq!(
+            policy.exempt,
+            vec![PolicyPrincipal::User(UserId::oidc("alice").unwrap())]
+        );
+    }
+}
@@ -200,6 +200,25 @@ impl Authorizer for AllowAllAuthorizer {
         Ok(())
     }
 
+    async fn move_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        _namespace_id: NamespaceIdentUuid,
+        _old_parent: NamespaceParent,
+        _new_parent: NamespaceParent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn undrop_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        _namespace_id: NamespaceIdentUuid,
+        _parent: NamespaceParent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     async fn create_table(
         &self,
         _metadata: &RequestMetadata,
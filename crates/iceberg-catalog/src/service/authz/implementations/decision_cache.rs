@@ -0,0 +1,90 @@
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Caches authorization decisions for a short time to avoid repeated
+/// round trips to the authorization backend.
+#[derive(Debug)]
+pub(crate) struct DecisionCache {
+    entries: Mutex<LruCache<String, (bool, Instant)>>,
+    ttl: Duration,
+}
+
+impl DecisionCache {
+    pub(crate) fn new(capacity: NonZeroUsize, ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            ttl,
+        }
+    }
+
+    /// Create a cache if both `ttl_seconds` and `capacity` are greater than 0.
+    pub(crate) fn from_config(ttl_seconds: u64, capacity: usize) -> Option<Arc<Self>> {
+        NonZeroUsize::new(capacity)
+            .filter(|_| ttl_seconds > 0)
+            .map(|capacity| Arc::new(Self::new(capacity, Duration::from_secs(ttl_seconds))))
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<bool> {
+        let mut entries = self
+            .entries
+            .lock()
+            .map_err(|e| tracing::error!("Authorization decision cache lock poisoned: {e}"))
+            .ok()?;
+        match entries.get(key) {
+            Some((allowed, inserted_at)) if inserted_at.elapsed() < self.ttl => Some(*allowed),
+            Some(_) => {
+                entries.pop(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn put(&self, key: String, allowed: bool) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.put(key, (allowed, Instant::now()));
+        }
+    }
+
+    /// Remove all cached decisions.
+    ///
+    /// Only the cache of this process is cleared. Other replicas keep their
+    /// decisions until the TTL expires.
+    pub(crate) fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decision_cache_expires() {
+        let cache = DecisionCache::new(NonZeroUsize::new(2).unwrap(), Duration::from_secs(60));
+        cache.put("a".to_string(), true);
+        cache.put("b".to_string(), false);
+        assert_eq!(cache.get("a"), Some(true));
+        assert_eq!(cache.get("b"), Some(false));
+        assert_eq!(cache.get("c"), None);
+
+        let cache = DecisionCache::new(NonZeroUsize::new(2).unwrap(), Duration::ZERO);
+        cache.put("a".to_string(), true);
+        assert_eq!(cache.get("a"), None);
+    }
+
+    #[test]
+    fn test_decision_cache_clear() {
+        let cache = DecisionCache::from_config(60, 10).unwrap();
+        cache.put("a".to_string(), true);
+        cache.clear();
+        assert_eq!(cache.get("a"), None);
+
+        assert!(DecisionCache::from_config(0, 10).is_none());
+        assert!(DecisionCache::from_config(60, 0).is_none());
+    }
+}
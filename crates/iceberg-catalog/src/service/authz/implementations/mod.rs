@@ -1,4 +1,5 @@
 use crate::{
+    implementations::postgres::get_writer_pool,
     service::{
         authz::ErrorModel,
         health::{Health, HealthExt},
@@ -7,7 +8,9 @@ use crate::{
 };
 
 pub(super) mod allow_all;
+pub(crate) mod decision_cache;
 
+pub mod opa;
 pub mod openfga;
 
 /// Get the default authorizer from the configuration
@@ -19,10 +22,36 @@ pub mod openfga;
 pub async fn get_default_authorizer_from_config() -> Result<Authorizers, ErrorModel> {
     match &CONFIG.authz_backend {
         AuthZBackend::AllowAll => Ok(allow_all::AllowAllAuthorizer.into()),
-        AuthZBackend::OpenFGA => Ok(openfga::new_authorizer_from_config().await?),
+        AuthZBackend::OpenFGA => {
+            let authorizer = openfga::new_authorizer_from_config().await?;
+            Ok(Authorizers::OpenFGA(
+                authorizer.with_grant_ledger(get_authorizer_pool().await?)?,
+            ))
+        }
+        AuthZBackend::Opa => Ok(opa::new_authorizer_from_config().await?.into()),
+        AuthZBackend::Postgres => Ok(Authorizers::OpenFGA(openfga::new_postgres_authorizer(
+            get_authorizer_pool().await?,
+        )?)),
     }
 }
 
+/// Pool for the tuples of the postgres authorizer and the expirations of time-bound grants.
+async fn get_authorizer_pool() -> Result<sqlx::PgPool, ErrorModel> {
+    get_writer_pool(
+        CONFIG
+            .to_pool_opts()
+            .max_connections(CONFIG.pg_write_pool_connections),
+    )
+    .await
+    .map_err(|e| {
+        ErrorModel::internal(
+            "Failed to create pool for authorizer",
+            "AuthorizerPoolError",
+            Some(e.into()),
+        )
+    })
+}
+
 /// Migrate the default authorizer to a new model version.
 ///
 /// # Errors
@@ -30,7 +59,10 @@ pub async fn get_default_authorizer_from_config() -> Result<Authorizers, ErrorMo
 /// Authorizer implementation
 pub async fn migrate_default_authorizer() -> std::result::Result<(), ErrorModel> {
     match &CONFIG.authz_backend {
-        AuthZBackend::AllowAll => Ok(()),
+        // OPA does not store any state in Lakekeeper that would require a migration
+        AuthZBackend::AllowAll | AuthZBackend::Opa => Ok(()),
+        // Tuples of the postgres authorizer are migrated with the catalog database
+        AuthZBackend::Postgres => Ok(()),
         AuthZBackend::OpenFGA => {
             let mut client = openfga::new_client_from_config().await?;
             let store_name = None;
@@ -61,6 +93,7 @@ pub enum FgaType {
 pub enum Authorizers {
     AllowAll(allow_all::AllowAllAuthorizer),
     OpenFGA(openfga::OpenFGAAuthorizer),
+    Opa(opa::OpaAuthorizer),
 }
 
 impl From<allow_all::AllowAllAuthorizer> for Authorizers {
@@ -69,12 +102,19 @@ impl From<allow_all::AllowAllAuthorizer> for Authorizers {
     }
 }
 
+impl From<opa::OpaAuthorizer> for Authorizers {
+    fn from(authorizer: opa::OpaAuthorizer) -> Self {
+        Self::Opa(authorizer)
+    }
+}
+
 #[async_trait::async_trait]
 impl HealthExt for Authorizers {
     async fn health(&self) -> Vec<Health> {
         match self {
             Self::AllowAll(authorizer) => authorizer.health().await,
             Self::OpenFGA(authorizer) => authorizer.health().await,
+            Self::Opa(authorizer) => authorizer.health().await,
         }
     }
 
@@ -82,6 +122,7 @@ impl HealthExt for Authorizers {
         match self {
             Self::AllowAll(authorizer) => authorizer.update_health().await,
             Self::OpenFGA(authorizer) => authorizer.update_health().await,
+            Self::Opa(authorizer) => authorizer.update_health().await,
         }
     }
 }
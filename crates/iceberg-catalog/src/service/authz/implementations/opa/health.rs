@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+
+use super::OpaAuthorizer;
+use crate::service::health::{Health, HealthExt, HealthStatus};
+
+#[async_trait]
+impl HealthExt for OpaAuthorizer {
+    async fn health(&self) -> Vec<Health> {
+        self.health.read().await.clone()
+    }
+    async fn update_health(&self) {
+        let check_result = self
+            .client
+            .get(self.health_url.clone())
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status);
+
+        let health = match check_result {
+            Ok(_) => Health::now("opa", HealthStatus::Healthy),
+            Err(e) => {
+                tracing::error!("OPA health check failed: {:?}", e);
+                Health::now("opa", HealthStatus::Unhealthy)
+            }
+        };
+
+        let mut lock = self.health.write().await;
+        lock.clear();
+        lock.extend([health]);
+    }
+}
@@ -0,0 +1,1021 @@
+use crate::api::iceberg::v1::Result;
+use crate::api::ApiContext;
+use crate::config::OpaConfig;
+use crate::implementations::postgres::{
+    get_reader_pool, namespace::get_namespace_names_by_id, tabular::get_tabular_parents_by_id,
+    warehouse::list_projects,
+};
+use crate::request_metadata::RequestMetadata;
+use crate::service::authn::{Actor, UserId};
+use crate::service::authz::implementations::decision_cache::DecisionCache;
+use crate::service::authz::{
+    Authorizer, CatalogNamespaceAction, CatalogProjectAction, CatalogRoleAction,
+    CatalogServerAction, CatalogTableAction, CatalogUserAction, CatalogViewAction,
+    CatalogWarehouseAction, ErrorModel, ListProjectsResponse, NamespaceParent,
+};
+use crate::service::health::Health;
+use crate::service::{
+    Catalog, NamespaceIdentUuid, ProjectIdent, RoleId, SecretStore, State, TableIdentUuid,
+    ViewIdentUuid, WarehouseIdent,
+};
+use crate::CONFIG;
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use url::Url;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+mod health;
+
+/// Create a new [`OpaAuthorizer`] from the global configuration.
+///
+/// # Errors
+/// - OPA is not configured
+/// - The configured policy paths are invalid
+/// - The read pool for resolving namespace paths cannot be created
+pub async fn new_authorizer_from_config() -> std::result::Result<OpaAuthorizer, ErrorModel> {
+    let config = CONFIG.opa.clone().ok_or_else(|| {
+        ErrorModel::internal(
+            "OPA is selected as authorization backend, but not configured",
+            "OpaConfigMissing",
+            None,
+        )
+    })?;
+    let read_pool = get_reader_pool(
+        CONFIG
+            .to_pool_opts()
+            .max_connections(CONFIG.pg_read_pool_connections),
+    )
+    .await
+    .map_err(|e| {
+        ErrorModel::internal(
+            "Failed to create read pool for OPA authorizer",
+            "OpaReadPoolError",
+            Some(e.into()),
+        )
+    })?;
+    OpaAuthorizer::new(config, read_pool)
+}
+
+/// Authorizer that delegates all decisions to an Open Policy Agent server.
+///
+/// Each check is sent as `input` to the configured policy. Lakekeeper does not
+/// store any permissions itself, so all create / delete hooks are no-ops.
+#[derive(Clone)]
+pub struct OpaAuthorizer {
+    client: reqwest::Client,
+    policy_url: Url,
+    batch_policy_url: Url,
+    health_url: Url,
+    bearer_token: Option<String>,
+    read_pool: sqlx::PgPool,
+    cache: Option<Arc<DecisionCache>>,
+    pub(crate) health: Arc<RwLock<Vec<Health>>>,
+}
+
+impl Debug for OpaAuthorizer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpaAuthorizer")
+            .field("policy_url", &self.policy_url)
+            .field("batch_policy_url", &self.batch_policy_url)
+            .field("health", &self.health)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, OpenApi)]
+#[openapi()]
+pub(super) struct ApiDoc;
+
+impl OpaAuthorizer {
+    /// Create a new authorizer for the given configuration.
+    ///
+    /// # Errors
+    /// - `policy_path` or `batch_policy_path` is not a valid OPA rule path
+    pub fn new(
+        config: OpaConfig,
+        read_pool: sqlx::PgPool,
+    ) -> std::result::Result<Self, ErrorModel> {
+        let mut endpoint = config.endpoint;
+        if !endpoint.path().ends_with('/') {
+            endpoint.set_path(&format!("{}/", endpoint.path()));
+        }
+        let data_url = |name: &str, path: &str| {
+            validate_policy_path(name, path)?;
+            endpoint
+                .join(&format!("v1/data/{}", path.trim_matches('/')))
+                .map_err(|e| {
+                    ErrorModel::internal(
+                        format!("Invalid OPA {name} '{path}'"),
+                        "OpaConfigInvalid",
+                        Some(Box::new(e)),
+                    )
+                })
+        };
+        let cache = DecisionCache::from_config(
+            config.decision_cache_ttl_seconds,
+            config.decision_cache_capacity,
+        );
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            policy_url: data_url("policy_path", &config.policy_path)?,
+            batch_policy_url: data_url("batch_policy_path", &config.batch_policy_path)?,
+            health_url: endpoint.join("health").expect("health is a valid url path"),
+            bearer_token: config.bearer_token,
+            read_pool,
+            cache,
+            health: Arc::default(),
+        })
+    }
+
+    async fn check(
+        &self,
+        metadata: &RequestMetadata,
+        action: impl ToString,
+        resource: OpaResource,
+    ) -> Result<bool> {
+        let input = OpaInput::new(metadata.actor(), action, resource);
+        Ok(self.check_all(vec![input]).await?.pop().unwrap_or(false))
+    }
+
+    /// Decide all inputs, using the cache where possible.
+    /// A single uncached input is sent to the policy, multiple inputs
+    /// are sent as one batch.
+    ///
+    /// Inputs are cached including the names of namespaces, tables and views,
+    /// so renaming or moving a namespace invalidates all cached decisions below it.
+    async fn check_all(&self, inputs: Vec<OpaInput>) -> Result<Vec<bool>> {
+        let inputs = self.resolve_names(inputs).await?;
+        let keys = inputs
+            .iter()
+            .map(|input| serde_json::to_string(input).unwrap_or_default())
+            .collect::<Vec<_>>();
+        let mut decisions = keys
+            .iter()
+            .map(|key| self.cache.as_ref().and_then(|cache| cache.get(key)))
+            .collect::<Vec<_>>();
+
+        let (missing_idx, missing): (Vec<usize>, Vec<OpaInput>) = inputs
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| decisions[*i].is_none())
+            .unzip();
+
+        let fetched = match missing.len() {
+            0 => vec![],
+            1 => vec![self.query_single(&missing[0]).await?],
+            _ => self.query_batch(&missing).await?,
+        };
+
+        for (i, allowed) in missing_idx.into_iter().zip(fetched) {
+            if let Some(cache) = &self.cache {
+                cache.put(keys[i].clone(), allowed);
+            }
+            decisions[i] = Some(allowed);
+        }
+
+        Ok(decisions.into_iter().map(|d| d.unwrap_or(false)).collect())
+    }
+
+    /// Add namespace paths and tabular names to the resources of `inputs`.
+    async fn resolve_names(&self, mut inputs: Vec<OpaInput>) -> Result<Vec<OpaInput>> {
+        let mut namespace_ids = vec![];
+        let mut tabular_ids = vec![];
+        for input in &inputs {
+            match &input.resource {
+                OpaResource::Namespace { id, .. } => {
+                    namespace_ids.push(NamespaceIdentUuid::from(*id))
+                }
+                OpaResource::Table(tabular) | OpaResource::View(tabular) => {
+                    tabular_ids.push(tabular.id);
+                }
+                _ => {}
+            }
+        }
+
+        let namespaces = if namespace_ids.is_empty() {
+            HashMap::new()
+        } else {
+            get_namespace_names_by_id(&namespace_ids, &self.read_pool).await?
+        };
+        let parents = if tabular_ids.is_empty() {
+            HashMap::new()
+        } else {
+            get_tabular_parents_by_id(&tabular_ids, &self.read_pool).await?
+        };
+
+        for input in &mut inputs {
+            match &mut input.resource {
+                OpaResource::Namespace { id, namespace, .. } => {
+                    *namespace = namespaces.get(&NamespaceIdentUuid::from(*id)).cloned();
+                }
+                OpaResource::Table(tabular) | OpaResource::View(tabular) => {
+                    let parent = parents.get(&tabular.id);
+                    tabular.namespace_id = parent.map(|p| *p.namespace_id);
+                    tabular.namespace = parent.map(|p| p.namespace_name.clone());
+                    tabular.name = parent.map(|p| p.name.clone());
+                }
+                _ => {}
+            }
+        }
+        Ok(inputs)
+    }
+
+    async fn query_single(&self, input: &OpaInput) -> Result<bool> {
+        let response: OpaResponse<bool> =
+            self.query(&self.policy_url, &OpaRequest { input }).await?;
+        // An undefined decision is a deny
+        Ok(response.result.unwrap_or(false))
+    }
+
+    async fn query_batch(&self, inputs: &[OpaInput]) -> Result<Vec<bool>> {
+        let response: OpaResponse<Vec<bool>> = self
+            .query(
+                &self.batch_policy_url,
+                &OpaRequest {
+                    input: OpaBatchInput { checks: inputs },
+                },
+            )
+            .await?;
+
+        match response.result {
+            None => Ok(vec![false; inputs.len()]),
+            Some(result) if result.len() == inputs.len() => Ok(result),
+            Some(result) => Err(ErrorModel::internal(
+                format!(
+                    "OPA batch policy returned {} decisions for {} checks",
+                    result.len(),
+                    inputs.len()
+                ),
+                "OpaBatchSizeMismatch",
+                None,
+            )
+            .into()),
+        }
+    }
+
+    async fn query<T: Serialize + Sync, R: for<'de> Deserialize<'de>>(
+        &self,
+        url: &Url,
+        body: &T,
+    ) -> Result<R> {
+        let mut request = self.client.post(url.clone()).json(body);
+        if let Some(token) = &self.bearer_token {
+            request = request.bearer_auth(token);
+        }
+
+        request
+            .send()
+            .await
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "Failed to send request to OPA",
+                    "OpaRequestError",
+                    Some(Box::new(e)),
+                )
+            })?
+            .error_for_status()
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "OPA returned an error",
+                    "OpaRequestError",
+                    Some(Box::new(e)),
+                )
+            })?
+            .json::<R>()
+            .await
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "Failed to parse OPA response",
+                    "OpaResponseError",
+                    Some(Box::new(e)),
+                )
+                .into()
+            })
+    }
+
+    async fn check_resources(
+        &self,
+        metadata: &RequestMetadata,
+        action: impl ToString,
+        resources: Vec<OpaResource>,
+    ) -> Result<Vec<bool>> {
+        let action = action.to_string();
+        let inputs = resources
+            .into_iter()
+            .map(|resource| OpaInput::new(metadata.actor(), &action, resource))
+            .collect();
+        self.check_all(inputs).await
+    }
+}
+
+#[async_trait::async_trait]
+impl Authorizer for OpaAuthorizer {
+    fn api_doc() -> utoipa::openapi::OpenApi {
+        ApiDoc::openapi()
+    }
+
+    fn new_router<C: Catalog, S: SecretStore>(&self) -> Router<ApiContext<State<Self, C, S>>> {
+        Router::new()
+    }
+
+    async fn can_bootstrap(&self, metadata: &RequestMetadata) -> Result<()> {
+        if &Actor::Anonymous == metadata.actor() {
+            return Err(ErrorModel::unauthorized(
+                "Anonymous users cannot bootstrap the catalog",
+                "AnonymousBootstrap",
+                None,
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    async fn bootstrap(&self, _metadata: &RequestMetadata, _is_operator: bool) -> Result<()> {
+        // Permissions are managed in the OPA policies.
+        Ok(())
+    }
+
+    async fn list_projects(&self, metadata: &RequestMetadata) -> Result<ListProjectsResponse> {
+        let project_ids = list_projects(None, &self.read_pool)
+            .await?
+            .into_iter()
+            .map(|project| project.project_id)
+            .collect::<Vec<_>>();
+        let resources = project_ids
+            .iter()
+            .map(|id| OpaResource::Project { id: **id })
+            .collect();
+        let allowed = self
+            .check_resources(metadata, CatalogProjectAction::CanIncludeInList, resources)
+            .await?;
+
+        Ok(ListProjectsResponse::Projects(
+            project_ids
+                .into_iter()
+                .zip(allowed)
+                .filter_map(|(id, allowed)| allowed.then_some(id))
+                .collect(),
+        ))
+    }
+
+    async fn can_search_users(&self, metadata: &RequestMetadata) -> Result<bool> {
+        self.is_allowed_server_action(metadata, &CatalogServerAction::CanListUsers)
+            .await
+    }
+
+    async fn is_allowed_user_action(
+        &self,
+        metadata: &RequestMetadata,
+        user_id: &UserId,
+        action: &CatalogUserAction,
+    ) -> Result<bool> {
+        self.check(
+            metadata,
+            action,
+            OpaResource::User {
+                id: user_id.clone(),
+            },
+        )
+        .await
+    }
+
+    async fn is_allowed_role_action(
+        &self,
+        metadata: &RequestMetadata,
+        role_id: RoleId,
+        action: &CatalogRoleAction,
+    ) -> Result<bool> {
+        self.check(metadata, action, OpaResource::Role { id: role_id })
+            .await
+    }
+
+    async fn is_allowed_server_action(
+        &self,
+        metadata: &RequestMetadata,
+        action: &CatalogServerAction,
+    ) -> Result<bool> {
+        self.check(metadata, action, OpaResource::Server).await
+    }
+
+    async fn is_allowed_project_action(
+        &self,
+        metadata: &RequestMetadata,
+        project_id: ProjectIdent,
+        action: &CatalogProjectAction,
+    ) -> Result<bool> {
+        self.check(metadata, action, OpaResource::Project { id: *project_id })
+            .await
+    }
+
+    async fn is_allowed_warehouse_action(
+        &self,
+        metadata: &RequestMetadata,
+        warehouse_id: WarehouseIdent,
+        action: &CatalogWarehouseAction,
+    ) -> Result<bool> {
+        self.check(
+            metadata,
+            action,
+            OpaResource::Warehouse { id: *warehouse_id },
+        )
+        .await
+    }
+
+    async fn is_allowed_namespace_action(
+        &self,
+        metadata: &RequestMetadata,
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        action: &CatalogNamespaceAction,
+    ) -> Result<bool> {
+        Ok(self
+            .are_allowed_namespace_actions(metadata, warehouse_id, &[namespace_id], action)
+            .await?
+            .pop()
+            .unwrap_or(false))
+    }
+
+    async fn is_allowed_table_action(
+        &self,
+        metadata: &RequestMetadata,
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        action: &CatalogTableAction,
+    ) -> Result<bool> {
+        Ok(self
+            .are_allowed_table_actions(metadata, warehouse_id, &[table_id], action)
+            .await?
+            .pop()
+            .unwrap_or(false))
+    }
+
+    async fn is_allowed_view_action(
+        &self,
+        metadata: &RequestMetadata,
+        warehouse_id: WarehouseIdent,
+        view_id: ViewIdentUuid,
+        action: &CatalogViewAction,
+    ) -> Result<bool> {
+        Ok(self
+            .are_allowed_view_actions(metadata, warehouse_id, &[view_id], action)
+            .await?
+            .pop()
+            .unwrap_or(false))
+    }
+
+    async fn are_allowed_namespace_actions(
+        &self,
+        metadata: &RequestMetadata,
+        warehouse_id: WarehouseIdent,
+        namespace_ids: &[NamespaceIdentUuid],
+        action: &CatalogNamespaceAction,
+    ) -> Result<Vec<bool>> {
+        let resources = namespace_ids
+            .iter()
+            .map(|id| OpaResource::Namespace {
+                id: **id,
+                warehouse_id: *warehouse_id,
+                namespace: None,
+            })
+            .collect();
+        self.check_resources(metadata, action, resources).await
+    }
+
+    async fn are_allowed_table_actions(
+        &self,
+        metadata: &RequestMetadata,
+        warehouse_id: WarehouseIdent,
+        table_ids: &[TableIdentUuid],
+        action: &CatalogTableAction,
+    ) -> Result<Vec<bool>> {
+        let resources = table_ids
+            .iter()
+            .map(|id| OpaResource::Table(OpaTabular::new(**id, warehouse_id)))
+            .collect();
+        self.check_resources(metadata, action, resources).await
+    }
+
+    async fn are_allowed_view_actions(
+        &self,
+        metadata: &RequestMetadata,
+        warehouse_id: WarehouseIdent,
+        view_ids: &[ViewIdentUuid],
+        action: &CatalogViewAction,
+    ) -> Result<Vec<bool>> {
+        let resources = view_ids
+            .iter()
+            .map(|id| OpaResource::View(OpaTabular::new(**id, warehouse_id)))
+            .collect();
+        self.check_resources(metadata, action, resources).await
+    }
+
+    async fn delete_user(&self, _metadata: &RequestMetadata, _user_id: UserId) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_role(
+        &self,
+        _metadata: &RequestMetadata,
+        _role_id: RoleId,
+        _parent_project_id: ProjectIdent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_role(&self, _metadata: &RequestMetadata, _role_id: RoleId) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_project(
+        &self,
+        _metadata: &RequestMetadata,
+        _project_id: ProjectIdent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_project(
+        &self,
+        _metadata: &RequestMetadata,
+        _project_id: ProjectIdent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_warehouse(
+        &self,
+        _metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+        _parent_project_id: ProjectIdent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_warehouse(
+        &self,
+        _metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        _namespace_id: NamespaceIdentUuid,
+        _parent: NamespaceParent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        _namespace_id: NamespaceIdentUuid,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn move_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        _namespace_id: NamespaceIdentUuid,
+        _old_parent: NamespaceParent,
+        _new_parent: NamespaceParent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn undrop_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        _namespace_id: NamespaceIdentUuid,
+        _parent: NamespaceParent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_table(
+        &self,
+        _metadata: &RequestMetadata,
+        _table_id: TableIdentUuid,
+        _parent: NamespaceIdentUuid,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_table(&self, _table_id: TableIdentUuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_view(
+        &self,
+        _metadata: &RequestMetadata,
+        _view_id: ViewIdentUuid,
+        _parent: NamespaceIdentUuid,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_view(&self, _view_id: ViewIdentUuid) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct OpaRequest<T> {
+    input: T,
+}
+
+#[derive(Debug, Serialize)]
+struct OpaBatchInput<'a> {
+    checks: &'a [OpaInput],
+}
+
+#[derive(Debug, Deserialize)]
+struct OpaResponse<T> {
+    result: Option<T>,
+}
+
+/// Input of a single decision as seen by the policy.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+struct OpaInput {
+    principal: OpaPrincipal,
+    action: String,
+    resource: OpaResource,
+    server_id: Uuid,
+}
+
+impl OpaInput {
+    fn new(actor: &Actor, action: impl ToString, resource: OpaResource) -> Self {
+        Self {
+            principal: OpaPrincipal::from(actor),
+            action: action.to_string(),
+            resource,
+            server_id: CONFIG.server_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+struct OpaPrincipal {
+    /// `None` for anonymous requests
+    user_id: Option<UserId>,
+    assumed_role: Option<RoleId>,
+}
+
+impl From<&Actor> for OpaPrincipal {
+    fn from(actor: &Actor) -> Self {
+        match actor {
+            Actor::Anonymous => Self {
+                user_id: None,
+                assumed_role: None,
+            },
+            Actor::Principal(user_id) => Self {
+                user_id: Some(user_id.clone()),
+                assumed_role: None,
+            },
+            Actor::Role {
+                principal,
+                assumed_role,
+            } => Self {
+                user_id: Some(principal.clone()),
+                assumed_role: Some(*assumed_role),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum OpaResource {
+    Server,
+    User {
+        id: UserId,
+    },
+    Role {
+        id: RoleId,
+    },
+    Project {
+        id: Uuid,
+    },
+    Warehouse {
+        id: Uuid,
+    },
+    Namespace {
+        id: Uuid,
+        warehouse_id: Uuid,
+        /// `None` if the namespace does not exist (anymore)
+        namespace: Option<Vec<String>>,
+    },
+    Table(OpaTabular),
+    View(OpaTabular),
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+struct OpaTabular {
+    id: Uuid,
+    warehouse_id: Uuid,
+    namespace_id: Option<Uuid>,
+    namespace: Option<Vec<String>>,
+    name: Option<String>,
+}
+
+impl OpaTabular {
+    /// Tabular without names. Names are resolved before the policy is queried.
+    fn new(id: Uuid, warehouse_id: WarehouseIdent) -> Self {
+        Self {
+            id,
+            warehouse_id: *warehouse_id,
+            namespace_id: None,
+            namespace: None,
+            name: None,
+        }
+    }
+}
+
+/// Policy paths are `/` separated rule references, i.e. `lakekeeper/allow`.
+fn validate_policy_path(name: &str, path: &str) -> std::result::Result<(), ErrorModel> {
+    let valid = path.trim_matches('/').split('/').all(|segment| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(ErrorModel::internal(
+            format!(
+                "Invalid OPA {name} '{path}': expected '/' separated rule names, i.e. 'lakekeeper/allow'"
+            ),
+            "OpaConfigInvalid",
+            None,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_serialization() {
+        let table_id = Uuid::now_v7();
+        let warehouse_id = Uuid::now_v7();
+        let namespace_id = Uuid::now_v7();
+        let input = OpaInput::new(
+            &Actor::Principal(UserId::oidc("my-user").unwrap()),
+            CatalogTableAction::CanReadData,
+            OpaResource::Table(OpaTabular {
+                id: table_id,
+                warehouse_id,
+                namespace_id: Some(namespace_id),
+                namespace: Some(vec!["finance".to_string(), "reports".to_string()]),
+                name: Some("revenue".to_string()),
+            }),
+        );
+
+        assert_eq!(
+            serde_json::to_value(&input).unwrap(),
+            serde_json::json!({
+                "principal": {
+                    "user_id": "oidc~my-user",
+                    "assumed_role": null
+                },
+                "action": "can_read_data",
+                "resource": {
+                    "type": "table",
+                    "id": table_id,
+                    "warehouse_id": warehouse_id,
+                    "namespace_id": namespace_id,
+                    "namespace": ["finance", "reports"],
+                    "name": "revenue"
+                },
+                "server_id": CONFIG.server_id
+            })
+        );
+    }
+
+    #[test]
+    fn test_anonymous_server_input() {
+        let input = OpaInput::new(
+            &Actor::Anonymous,
+            CatalogServerAction::CanCreateProject,
+            OpaResource::Server,
+        );
+        let value = serde_json::to_value(&input).unwrap();
+        assert_eq!(value["principal"]["user_id"], serde_json::Value::Null);
+        assert_eq!(value["resource"], serde_json::json!({"type": "server"}));
+        assert_eq!(value["action"], "can_create_project");
+    }
+
+    fn config(endpoint: Url) -> OpaConfig {
+        OpaConfig {
+            endpoint,
+            policy_path: "lakekeeper/allow".to_string(),
+            batch_policy_path: "lakekeeper/batch_allow".to_string(),
+            bearer_token: None,
+            decision_cache_ttl_seconds: 60,
+            decision_cache_capacity: 100,
+        }
+    }
+
+    #[test]
+    fn test_validate_policy_path() {
+        assert!(validate_policy_path("policy_path", "lakekeeper/allow").is_ok());
+        assert!(validate_policy_path("policy_path", "/lakekeeper/v2/allow/").is_ok());
+        for invalid in ["", "lakekeeper//allow", "lakekeeper/allow?x=1", "../allow"] {
+            let error = validate_policy_path("policy_path", invalid).unwrap_err();
+            assert_eq!(error.r#type, "OpaConfigInvalid");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_policy_path_is_config_error() {
+        let pool = sqlx::PgPool::connect_lazy("postgres://localhost:1/unused").unwrap();
+        let mut config = config("http://localhost:1".parse().unwrap());
+        config.batch_policy_path = "lakekeeper/batch allow".to_string();
+        let error = OpaAuthorizer::new(config, pool).unwrap_err();
+        assert_eq!(error.r#type, "OpaConfigInvalid");
+    }
+
+    #[sqlx::test]
+    async fn test_cached_decisions_are_keyed_by_path(pool: sqlx::PgPool) {
+        use crate::implementations::postgres::namespace::tests::initialize_namespace;
+        use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+        use crate::implementations::postgres::CatalogState;
+        use iceberg::NamespaceIdent;
+
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let (namespace_id, _) = initialize_namespace(
+            state,
+            warehouse_id,
+            &NamespaceIdent::new("finance".to_string()),
+            None,
+        )
+        .await;
+
+        // OPA is not reachable, so only cached decisions can succeed.
+        let authorizer =
+            OpaAuthorizer::new(config("http://localhost:1".parse().unwrap()), pool).unwrap();
+        let metadata = RequestMetadata::new_random();
+        let key = |namespace: &str| {
+            serde_json::to_string(&OpaInput::new(
+                metadata.actor(),
+                CatalogNamespaceAction::CanGetMetadata,
+                OpaResource::Namespace {
+                    id: *namespace_id,
+                    warehouse_id: *warehouse_id,
+                    namespace: Some(vec![namespace.to_string()]),
+                },
+            ))
+            .unwrap()
+        };
+
+        // A decision for the namespace under its previous name is not used
+        authorizer
+            .cache
+            .as_ref()
+            .unwrap()
+            .put(key("marketing"), true);
+        assert!(authorizer
+            .is_allowed_namespace_action(
+                &metadata,
+                warehouse_id,
+                namespace_id,
+                &CatalogNamespaceAction::CanGetMetadata,
+            )
+            .await
+            .is_err());
+
+        authorizer.cache.as_ref().unwrap().put(key("finance"), true);
+        assert!(authorizer
+            .is_allowed_namespace_action(
+                &metadata,
+                warehouse_id,
+                namespace_id,
+                &CatalogNamespaceAction::CanGetMetadata,
+            )
+            .await
+            .unwrap());
+    }
+
+    #[needs_env_var::needs_env_var(TEST_OPA = 1)]
+    mod opa {
+        use super::super::*;
+        use crate::implementations::postgres::namespace::tests::initialize_namespace;
+        use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+        use crate::implementations::postgres::CatalogState;
+        use crate::service::authn::{AuthDetails, Principal};
+        use iceberg::NamespaceIdent;
+
+        const POLICY: &str = r#"
+package lakekeeper
+
+import rego.v1
+
+default allow := false
+
+allow if {
+    input.resource.namespace[0] == "finance"
+    input.principal.user_id in data.groups.finance
+}
+
+batch_allow := [allowed |
+    some check in input.checks
+    allowed := allow with input as check
+]
+"#;
+
+        async fn load_policy(endpoint: &Url) {
+            let client = reqwest::Client::new();
+            client
+                .put(endpoint.join("v1/policies/lakekeeper").unwrap())
+                .body(POLICY)
+                .send()
+                .await
+                .unwrap()
+                .error_for_status()
+                .unwrap();
+            client
+                .put(endpoint.join("v1/data/groups").unwrap())
+                .json(&serde_json::json!({"finance": ["oidc~peter"]}))
+                .send()
+                .await
+                .unwrap()
+                .error_for_status()
+                .unwrap();
+        }
+
+        #[sqlx::test]
+        async fn test_namespace_decisions_with_rego_policy(pool: sqlx::PgPool) {
+            let config = CONFIG.opa.clone().expect("OPA is configured");
+            load_policy(&config.endpoint).await;
+
+            let state = CatalogState::from_pools(pool.clone(), pool.clone());
+            let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+            let (finance, _) = initialize_namespace(
+                state.clone(),
+                warehouse_id,
+                &NamespaceIdent::new("finance".to_string()),
+                None,
+            )
+            .await;
+            let (marketing, _) = initialize_namespace(
+                state.clone(),
+                warehouse_id,
+                &NamespaceIdent::new("marketing".to_string()),
+                None,
+            )
+            .await;
+
+            let authorizer = OpaAuthorizer::new(config, pool).unwrap();
+            let peter = RequestMetadata {
+                request_id: Uuid::now_v7(),
+                auth_details: AuthDetails::Principal(Principal::new_test(
+                    UserId::oidc("peter").unwrap(),
+                )),
+            };
+
+            // Batch policy
+            let allowed = authorizer
+                .are_allowed_namespace_actions(
+                    &peter,
+                    warehouse_id,
+                    &[finance, marketing],
+                    &CatalogNamespaceAction::CanGetMetadata,
+                )
+                .await
+                .unwrap();
+            assert_eq!(allowed, vec![true, false]);
+
+            // Single policy
+            assert!(authorizer
+                .is_allowed_namespace_action(
+                    &peter,
+                    warehouse_id,
+                    finance,
+                    &CatalogNamespaceAction::CanCreateTable,
+                )
+                .await
+                .unwrap());
+            assert!(!authorizer
+                .is_allowed_namespace_action(
+                    &RequestMetadata::new_random(),
+                    warehouse_id,
+                    finance,
+                    &CatalogNamespaceAction::CanCreateTable,
+                )
+                .await
+                .unwrap());
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::explain::{AccessPathStep, AccessReason};
 use super::relations::{
     APINamespaceAction as NamespaceAction, APINamespaceRelation as NamespaceRelation,
     APIProjectAction as ProjectAction, APIProjectRelation as ProjectRelation,
@@ -16,11 +17,21 @@ use super::relations::{
 use super::OPENFGA_SERVER;
 use crate::api::ApiContext;
 use crate::request_metadata::RequestMetadata;
-use crate::service::authz::implementations::openfga::entities::OpenFgaEntity;
+use crate::service::authz::implementations::openfga::entities::{
+    OpenFgaEntity, ParseOpenFgaEntity,
+};
+use crate::service::authz::implementations::openfga::export::{
+    export_assignments, import_assignments, AuthzDocument, AuthzScope, EntityRef,
+    ExportedAssignment, ImportOptions, ImportReport, PrincipalRef,
+};
 use crate::service::authz::implementations::openfga::service_ext::MAX_TUPLES_PER_WRITE;
 use crate::service::authz::implementations::openfga::{
     OpenFGAAuthorizer, OpenFGAError, OpenFGAResult,
 };
+use crate::service::authz::{
+    CatalogNamespaceAction, CatalogProjectAction, CatalogRoleAction, CatalogServerAction,
+    CatalogTableAction, CatalogViewAction, CatalogWarehouseAction, ErrorModel,
+};
 use crate::service::{
     Actor, Catalog, NamespaceIdentUuid, Result, RoleId, SecretStore, State, TableIdentUuid,
     ViewIdentUuid,
@@ -104,6 +115,8 @@ struct GetRoleAssignmentsQuery {
 #[serde(rename_all = "kebab-case")]
 struct GetRoleAssignmentsResponse {
     assignments: Vec<RoleAssignment>,
+    /// Expirations of time-bound assignments, including recently expired ones
+    expirations: Vec<AssignmentExpiration>,
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -118,6 +131,8 @@ struct GetServerAssignmentsQuery {
 #[serde(rename_all = "kebab-case")]
 struct GetServerAssignmentsResponse {
     assignments: Vec<ServerAssignment>,
+    /// Expirations of time-bound assignments, including recently expired ones
+    expirations: Vec<AssignmentExpiration>,
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -132,6 +147,8 @@ pub(super) struct GetProjectAssignmentsQuery {
 #[serde(rename_all = "kebab-case")]
 struct GetProjectAssignmentsResponse {
     assignments: Vec<ProjectAssignment>,
+    /// Expirations of time-bound assignments, including recently expired ones
+    expirations: Vec<AssignmentExpiration>,
     #[schema(value_type = uuid::Uuid)]
     project_id: ProjectIdent,
 }
@@ -148,6 +165,8 @@ pub(super) struct GetWarehouseAssignmentsQuery {
 #[serde(rename_all = "kebab-case")]
 struct GetWarehouseAssignmentsResponse {
     assignments: Vec<WarehouseAssignment>,
+    /// Expirations of time-bound assignments, including recently expired ones
+    expirations: Vec<AssignmentExpiration>,
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -162,6 +181,8 @@ pub(super) struct GetNamespaceAssignmentsQuery {
 #[serde(rename_all = "kebab-case")]
 struct GetNamespaceAssignmentsResponse {
     assignments: Vec<NamespaceAssignment>,
+    /// Expirations of time-bound assignments, including recently expired ones
+    expirations: Vec<AssignmentExpiration>,
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -176,6 +197,8 @@ pub(super) struct GetTableAssignmentsQuery {
 #[serde(rename_all = "kebab-case")]
 struct GetTableAssignmentsResponse {
     assignments: Vec<TableAssignment>,
+    /// Expirations of time-bound assignments, including recently expired ones
+    expirations: Vec<AssignmentExpiration>,
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -190,6 +213,8 @@ pub(super) struct GetViewAssignmentsQuery {
 #[serde(rename_all = "kebab-case")]
 struct GetViewAssignmentsResponse {
     assignments: Vec<ViewAssignment>,
+    /// Expirations of time-bound assignments, including recently expired ones
+    expirations: Vec<AssignmentExpiration>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -199,6 +224,10 @@ struct UpdateServerAssignmentsRequest {
     writes: Vec<ServerAssignment>,
     #[serde(default)]
     deletes: Vec<ServerAssignment>,
+    /// Time at which all `writes` of this request are revoked again.
+    /// If not specified, the grants are permanent.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -208,6 +237,10 @@ struct UpdateProjectAssignmentsRequest {
     writes: Vec<ProjectAssignment>,
     #[serde(default)]
     deletes: Vec<ProjectAssignment>,
+    /// Time at which all `writes` of this request are revoked again.
+    /// If not specified, the grants are permanent.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -217,6 +250,10 @@ struct UpdateWarehouseAssignmentsRequest {
     writes: Vec<WarehouseAssignment>,
     #[serde(default)]
     deletes: Vec<WarehouseAssignment>,
+    /// Time at which all `writes` of this request are revoked again.
+    /// If not specified, the grants are permanent.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -226,6 +263,10 @@ struct UpdateNamespaceAssignmentsRequest {
     writes: Vec<NamespaceAssignment>,
     #[serde(default)]
     deletes: Vec<NamespaceAssignment>,
+    /// Time at which all `writes` of this request are revoked again.
+    /// If not specified, the grants are permanent.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -235,6 +276,10 @@ struct UpdateTableAssignmentsRequest {
     writes: Vec<TableAssignment>,
     #[serde(default)]
     deletes: Vec<TableAssignment>,
+    /// Time at which all `writes` of this request are revoked again.
+    /// If not specified, the grants are permanent.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -244,6 +289,10 @@ struct UpdateViewAssignmentsRequest {
     writes: Vec<ViewAssignment>,
     #[serde(default)]
     deletes: Vec<ViewAssignment>,
+    /// Time at which all `writes` of this request are revoked again.
+    /// If not specified, the grants are permanent.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -253,6 +302,10 @@ struct UpdateRoleAssignmentsRequest {
     writes: Vec<RoleAssignment>,
     #[serde(default)]
     deletes: Vec<RoleAssignment>,
+    /// Time at which all `writes` of this request are revoked again.
+    /// If not specified, the grants are permanent.
+    #[serde(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
@@ -274,6 +327,41 @@ struct SetManagedAccessRequest {
     managed_access: bool,
 }
 
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+struct ExplainAccessQuery {
+    /// The user or role to explain access for.
+    /// If not specified, explains access of the current user.
+    #[serde(default)]
+    principal: Option<UserOrRole>,
+    /// The action to explain. Either a catalog action, i.e. `can_read_data`,
+    /// or a management action, i.e. `grant_select`.
+    action: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+struct ExplainAccessResponse {
+    /// Whether the principal is allowed to perform the action
+    allowed: bool,
+    /// Relations that grant the action, starting with the requested action
+    /// and ending with the tuple that grants it. Empty if the action is not allowed.
+    path: Vec<AccessPathStep>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+struct AssignmentExpiration {
+    /// Type of the assignment, i.e. `select`
+    r#type: String,
+    /// The user or role the assignment is granted to
+    principal: UserOrRole,
+    /// Time at which the assignment is revoked
+    expires_at: chrono::DateTime<chrono::Utc>,
+    /// Whether the assignment has expired and is no longer granted
+    expired: bool,
+}
+
 /// Get my access to the default project
 #[utoipa::path(
     get,
@@ -728,11 +816,20 @@ async fn get_role_assignments_by_id<C: Catalog, S: SecretStore>(
             &role_id.to_openfga(),
         )
         .await?;
+    let expirations = get_expirations(
+        &authorizer,
+        query.relations.as_deref(),
+        &role_id.to_openfga(),
+    )
+    .await?;
     let assignments = get_relations(authorizer, query.relations, &role_id.to_openfga()).await?;
 
     Ok((
         StatusCode::OK,
-        Json(GetRoleAssignmentsResponse { assignments }),
+        Json(GetRoleAssignmentsResponse {
+            assignments,
+            expirations,
+        }),
     ))
 }
 
@@ -759,11 +856,16 @@ async fn get_server_assignments<C: Catalog, S: SecretStore>(
             &OPENFGA_SERVER,
         )
         .await?;
+    let expirations =
+        get_expirations(&authorizer, query.relations.as_deref(), &OPENFGA_SERVER).await?;
     let assignments = get_relations(authorizer, query.relations, &OPENFGA_SERVER).await?;
 
     Ok((
         StatusCode::OK,
-        Json(GetServerAssignmentsResponse { assignments }),
+        Json(GetServerAssignmentsResponse {
+            assignments,
+            expirations,
+        }),
     ))
 }
 
@@ -795,12 +897,19 @@ async fn get_project_assignments<C: Catalog, S: SecretStore>(
             &project_id.to_openfga(),
         )
         .await?;
+    let expirations = get_expirations(
+        &authorizer,
+        query.relations.as_deref(),
+        &project_id.to_openfga(),
+    )
+    .await?;
     let assignments = get_relations(authorizer, query.relations, &project_id.to_openfga()).await?;
 
     Ok((
         StatusCode::OK,
         Json(GetProjectAssignmentsResponse {
             assignments,
+            expirations,
             project_id,
         }),
     ))
@@ -833,12 +942,19 @@ async fn get_project_assignments_by_id<C: Catalog, S: SecretStore>(
             &project_id.to_openfga(),
         )
         .await?;
+    let expirations = get_expirations(
+        &authorizer,
+        query.relations.as_deref(),
+        &project_id.to_openfga(),
+    )
+    .await?;
     let assignments = get_relations(authorizer, query.relations, &project_id.to_openfga()).await?;
 
     Ok((
         StatusCode::OK,
         Json(GetProjectAssignmentsResponse {
             assignments,
+            expirations,
             project_id,
         }),
     ))
@@ -868,11 +984,15 @@ async fn get_warehouse_assignments_by_id<C: Catalog, S: SecretStore>(
     authorizer
         .require_action(&metadata, AllWarehouseRelation::CanReadAssignments, &object)
         .await?;
+    let expirations = get_expirations(&authorizer, query.relations.as_deref(), &object).await?;
     let assignments = get_relations(authorizer, query.relations, &object).await?;
 
     Ok((
         StatusCode::OK,
-        Json(GetWarehouseAssignmentsResponse { assignments }),
+        Json(GetWarehouseAssignmentsResponse {
+            assignments,
+            expirations,
+        }),
     ))
 }
 
@@ -904,11 +1024,15 @@ async fn get_namespace_assignments_by_id<C: Catalog, S: SecretStore>(
             &object,
         )
         .await?;
+    let expirations = get_expirations(&authorizer, query.relations.as_deref(), &object).await?;
     let assignments = get_relations(authorizer, query.relations, &object).await?;
 
     Ok((
         StatusCode::OK,
-        Json(GetNamespaceAssignmentsResponse { assignments }),
+        Json(GetNamespaceAssignmentsResponse {
+            assignments,
+            expirations,
+        }),
     ))
 }
 
@@ -936,11 +1060,15 @@ async fn get_table_assignments_by_id<C: Catalog, S: SecretStore>(
     authorizer
         .require_action(&metadata, AllTableRelations::CanReadAssignments, &object)
         .await?;
+    let expirations = get_expirations(&authorizer, query.relations.as_deref(), &object).await?;
     let assignments = get_relations(authorizer, query.relations, &object).await?;
 
     Ok((
         StatusCode::OK,
-        Json(GetTableAssignmentsResponse { assignments }),
+        Json(GetTableAssignmentsResponse {
+            assignments,
+            expirations,
+        }),
     ))
 }
 
@@ -968,11 +1096,15 @@ async fn get_view_assignments_by_id<C: Catalog, S: SecretStore>(
     authorizer
         .require_action(&metadata, AllViewRelations::CanReadAssignments, &object)
         .await?;
+    let expirations = get_expirations(&authorizer, query.relations.as_deref(), &object).await?;
     let assignments = get_relations(authorizer, query.relations, &object).await?;
 
     Ok((
         StatusCode::OK,
-        Json(GetViewAssignmentsResponse { assignments }),
+        Json(GetViewAssignmentsResponse {
+            assignments,
+            expirations,
+        }),
     ))
 }
 
@@ -997,6 +1129,7 @@ async fn update_server_assignments<C: Catalog, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &OPENFGA_SERVER,
     )
     .await?;
@@ -1030,6 +1163,7 @@ async fn update_project_assignments<C: Catalog, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &project_id.to_openfga(),
     )
     .await?;
@@ -1062,6 +1196,7 @@ async fn update_project_assignments_by_id<C: Catalog, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &project_id.to_openfga(),
     )
     .await?;
@@ -1094,6 +1229,7 @@ async fn update_warehouse_assignments_by_id<C: Catalog, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &warehouse_id.to_openfga(),
     )
     .await?;
@@ -1126,6 +1262,7 @@ async fn update_namespace_assignments_by_id<C: Catalog, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &namespace_id.to_openfga(),
     )
     .await?;
@@ -1158,6 +1295,7 @@ async fn update_table_assignments_by_id<C: Catalog, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &table_id.to_openfga(),
     )
     .await?;
@@ -1190,6 +1328,7 @@ async fn update_view_assignments_by_id<C: Catalog, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &view_id.to_openfga(),
     )
     .await?;
@@ -1231,6 +1370,7 @@ async fn update_role_assignments_by_id<C: Catalog, S: SecretStore>(
         metadata.actor(),
         request.writes,
         request.deletes,
+        request.expires_at,
         &role_id.to_openfga(),
     )
     .await?;
@@ -1238,6 +1378,365 @@ async fn update_role_assignments_by_id<C: Catalog, S: SecretStore>(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Explain why a principal is allowed or not allowed to perform an action on the server
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/server/explain",
+    params(
+        ExplainAccessQuery,
+    ),
+    responses(
+            (status = 200, body = ExplainAccessResponse),
+    )
+)]
+async fn explain_server_access<C: Catalog, S: SecretStore>(
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Query(query): Query<ExplainAccessQuery>,
+) -> Result<(StatusCode, Json<ExplainAccessResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let response = explain_access::<CatalogServerAction, ServerAction>(
+        authorizer,
+        metadata.actor(),
+        &OPENFGA_SERVER,
+        query,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// Explain why a principal is allowed or not allowed to perform an action on a project
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/project/{project_id}/explain",
+    params(
+        ExplainAccessQuery,
+        ("project_id" = uuid::Uuid, Path, description = "Project ID"),
+    ),
+    responses(
+            (status = 200, body = ExplainAccessResponse),
+    )
+)]
+async fn explain_project_access_by_id<C: Catalog, S: SecretStore>(
+    Path(project_id): Path<ProjectIdent>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Query(query): Query<ExplainAccessQuery>,
+) -> Result<(StatusCode, Json<ExplainAccessResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let response = explain_access::<CatalogProjectAction, ProjectAction>(
+        authorizer,
+        metadata.actor(),
+        &project_id.to_openfga(),
+        query,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// Explain why a principal is allowed or not allowed to perform an action on a warehouse
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/warehouse/{warehouse_id}/explain",
+    params(
+        ExplainAccessQuery,
+        ("warehouse_id" = uuid::Uuid, Path, description = "Warehouse ID"),
+    ),
+    responses(
+            (status = 200, body = ExplainAccessResponse),
+    )
+)]
+async fn explain_warehouse_access_by_id<C: Catalog, S: SecretStore>(
+    Path(warehouse_id): Path<WarehouseIdent>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Query(query): Query<ExplainAccessQuery>,
+) -> Result<(StatusCode, Json<ExplainAccessResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let response = explain_access::<CatalogWarehouseAction, WarehouseAction>(
+        authorizer,
+        metadata.actor(),
+        &warehouse_id.to_openfga(),
+        query,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// Explain why a principal is allowed or not allowed to perform an action on a namespace
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/namespace/{namespace_id}/explain",
+    params(
+        ExplainAccessQuery,
+        ("namespace_id" = uuid::Uuid, Path, description = "Namespace ID"),
+    ),
+    responses(
+            (status = 200, body = ExplainAccessResponse),
+    )
+)]
+async fn explain_namespace_access_by_id<C: Catalog, S: SecretStore>(
+    Path(namespace_id): Path<NamespaceIdentUuid>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Query(query): Query<ExplainAccessQuery>,
+) -> Result<(StatusCode, Json<ExplainAccessResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let response = explain_access::<CatalogNamespaceAction, NamespaceAction>(
+        authorizer,
+        metadata.actor(),
+        &namespace_id.to_openfga(),
+        query,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// Explain why a principal is allowed or not allowed to perform an action on a table
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/table/{table_id}/explain",
+    params(
+        ExplainAccessQuery,
+        ("table_id" = uuid::Uuid, Path, description = "Table ID"),
+    ),
+    responses(
+            (status = 200, body = ExplainAccessResponse),
+    )
+)]
+async fn explain_table_access_by_id<C: Catalog, S: SecretStore>(
+    Path(table_id): Path<TableIdentUuid>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Query(query): Query<ExplainAccessQuery>,
+) -> Result<(StatusCode, Json<ExplainAccessResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let response = explain_access::<CatalogTableAction, TableAction>(
+        authorizer,
+        metadata.actor(),
+        &table_id.to_openfga(),
+        query,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// Explain why a principal is allowed or not allowed to perform an action on a view
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/view/{view_id}/explain",
+    params(
+        ExplainAccessQuery,
+        ("view_id" = uuid::Uuid, Path, description = "View ID"),
+    ),
+    responses(
+            (status = 200, body = ExplainAccessResponse),
+    )
+)]
+async fn explain_view_access_by_id<C: Catalog, S: SecretStore>(
+    Path(view_id): Path<ViewIdentUuid>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Query(query): Query<ExplainAccessQuery>,
+) -> Result<(StatusCode, Json<ExplainAccessResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let response = explain_access::<CatalogViewAction, ViewAction>(
+        authorizer,
+        metadata.actor(),
+        &view_id.to_openfga(),
+        query,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// Explain why a principal is allowed or not allowed to perform an action on a role
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/role/{role_id}/explain",
+    params(
+        ExplainAccessQuery,
+        ("role_id" = uuid::Uuid, Path, description = "Role ID"),
+    ),
+    responses(
+            (status = 200, body = ExplainAccessResponse),
+    )
+)]
+async fn explain_role_access_by_id<C: Catalog, S: SecretStore>(
+    Path(role_id): Path<RoleId>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Query(query): Query<ExplainAccessQuery>,
+) -> Result<(StatusCode, Json<ExplainAccessResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let response = explain_access::<CatalogRoleAction, RoleAction>(
+        authorizer,
+        metadata.actor(),
+        &role_id.to_openfga(),
+        query,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// Export all assignments of a project, its roles and its warehouses
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/project/{project_id}/export",
+    params(("project_id" = uuid::Uuid, Path, description = "Project ID")),
+    responses(
+            (status = 200, body = AuthzDocument),
+    )
+)]
+async fn export_project_assignments<C: Catalog, S: SecretStore>(
+    Path(project_id): Path<ProjectIdent>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+) -> Result<(StatusCode, Json<AuthzDocument>)> {
+    let authorizer = api_context.v1_state.authz;
+    authorizer
+        .require_action(
+            &metadata,
+            AllProjectRelations::CanReadAssignments,
+            &project_id.to_openfga(),
+        )
+        .await?;
+    let document = export_assignments::<C>(
+        &authorizer,
+        AuthzScope::Project(project_id),
+        Some(metadata.actor()),
+        api_context.v1_state.catalog,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(document)))
+}
+
+/// Export all assignments of a warehouse and its namespaces, tables and views
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/warehouse/{warehouse_id}/export",
+    params(("warehouse_id" = uuid::Uuid, Path, description = "Warehouse ID")),
+    responses(
+            (status = 200, body = AuthzDocument),
+    )
+)]
+async fn export_warehouse_assignments<C: Catalog, S: SecretStore>(
+    Path(warehouse_id): Path<WarehouseIdent>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+) -> Result<(StatusCode, Json<AuthzDocument>)> {
+    let authorizer = api_context.v1_state.authz;
+    authorizer
+        .require_action(
+            &metadata,
+            AllWarehouseRelation::CanReadAssignments,
+            &warehouse_id.to_openfga(),
+        )
+        .await?;
+    let document = export_assignments::<C>(
+        &authorizer,
+        AuthzScope::Warehouse(warehouse_id),
+        Some(metadata.actor()),
+        api_context.v1_state.catalog,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(document)))
+}
+
+/// Import assignments into a project
+///
+/// Entities and roles are matched by name. By default, only the changes are
+/// returned. Set `apply` to apply them.
+#[utoipa::path(
+    post,
+    tag = "permissions",
+    path = "/management/v1/permissions/project/{project_id}/import",
+    params(
+        ImportOptions,
+        ("project_id" = uuid::Uuid, Path, description = "Project ID"),
+    ),
+    request_body = AuthzDocument,
+    responses(
+            (status = 200, body = ImportReport),
+    )
+)]
+async fn import_project_assignments<C: Catalog, S: SecretStore>(
+    Path(project_id): Path<ProjectIdent>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Query(options): Query<ImportOptions>,
+    Json(document): Json<AuthzDocument>,
+) -> Result<(StatusCode, Json<ImportReport>)> {
+    let authorizer = api_context.v1_state.authz;
+    let report = import_assignments::<C>(
+        &authorizer,
+        AuthzScope::Project(project_id),
+        document,
+        options,
+        Some(metadata.actor()),
+        api_context.v1_state.catalog,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(report)))
+}
+
+/// Import assignments into a warehouse
+///
+/// The document must be exported from a warehouse, which may have a different name.
+/// By default, only the changes are returned. Set `apply` to apply them.
+#[utoipa::path(
+    post,
+    tag = "permissions",
+    path = "/management/v1/permissions/warehouse/{warehouse_id}/import",
+    params(
+        ImportOptions,
+        ("warehouse_id" = uuid::Uuid, Path, description = "Warehouse ID"),
+    ),
+    request_body = AuthzDocument,
+    responses(
+            (status = 200, body = ImportReport),
+    )
+)]
+async fn import_warehouse_assignments<C: Catalog, S: SecretStore>(
+    Path(warehouse_id): Path<WarehouseIdent>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Query(options): Query<ImportOptions>,
+    Json(document): Json<AuthzDocument>,
+) -> Result<(StatusCode, Json<ImportReport>)> {
+    let authorizer = api_context.v1_state.authz;
+    let report = import_assignments::<C>(
+        &authorizer,
+        AuthzScope::Warehouse(warehouse_id),
+        document,
+        options,
+        Some(metadata.actor()),
+        api_context.v1_state.catalog,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(report)))
+}
+
 #[derive(Debug, OpenApi)]
 #[openapi(
     servers(
@@ -1255,6 +1754,15 @@ async fn update_role_assignments_by_id<C: Catalog, S: SecretStore>(
         (name = "permissions", description = "Manage Permissions"),
     ),
     paths(
+        explain_namespace_access_by_id,
+        explain_project_access_by_id,
+        explain_role_access_by_id,
+        explain_server_access,
+        explain_table_access_by_id,
+        explain_view_access_by_id,
+        explain_warehouse_access_by_id,
+        export_project_assignments,
+        export_warehouse_assignments,
         get_namespace_access_by_id,
         get_namespace_assignments_by_id,
         get_namespace_by_id,
@@ -1273,6 +1781,8 @@ async fn update_role_assignments_by_id<C: Catalog, S: SecretStore>(
         get_warehouse_access_by_id,
         get_warehouse_assignments_by_id,
         get_warehouse_by_id,
+        import_project_assignments,
+        import_warehouse_assignments,
         set_namespace_managed_access,
         set_warehouse_managed_access,
         update_namespace_assignments_by_id,
@@ -1285,6 +1795,13 @@ async fn update_role_assignments_by_id<C: Catalog, S: SecretStore>(
         update_warehouse_assignments_by_id,
     ),
     components(schemas(
+        AccessPathStep,
+        AccessReason,
+        AssignmentExpiration,
+        AuthzDocument,
+        EntityRef,
+        ExplainAccessResponse,
+        ExportedAssignment,
         GetNamespaceAccessResponse,
         GetNamespaceAssignmentsResponse,
         GetNamespaceAuthPropertiesResponse,
@@ -1301,12 +1818,14 @@ async fn update_role_assignments_by_id<C: Catalog, S: SecretStore>(
         GetWarehouseAccessResponse,
         GetWarehouseAssignmentsResponse,
         GetWarehouseAuthPropertiesResponse,
+        ImportReport,
         NamespaceAction,
         NamespaceAssignment,
         NamespaceRelation,
         ProjectAction,
         ProjectAssignment,
         ProjectRelation,
+        PrincipalRef,
         RoleAction,
         RoleAssignment,
         RoleRelation,
@@ -1380,6 +1899,47 @@ pub(super) fn new_v1_router<C: Catalog, S: SecretStore>(
             "/permissions/view/:table_id/access",
             get(get_view_access_by_id),
         )
+        .route(
+            "/permissions/role/:role_id/explain",
+            get(explain_role_access_by_id),
+        )
+        .route("/permissions/server/explain", get(explain_server_access))
+        .route(
+            "/permissions/project/:project_id/explain",
+            get(explain_project_access_by_id),
+        )
+        .route(
+            "/permissions/warehouse/:warehouse_id/explain",
+            get(explain_warehouse_access_by_id),
+        )
+        .route(
+            "/permissions/namespace/:namespace_id/explain",
+            get(explain_namespace_access_by_id),
+        )
+        .route(
+            "/permissions/table/:table_id/explain",
+            get(explain_table_access_by_id),
+        )
+        .route(
+            "/permissions/view/:view_id/explain",
+            get(explain_view_access_by_id),
+        )
+        .route(
+            "/permissions/project/:project_id/export",
+            get(export_project_assignments),
+        )
+        .route(
+            "/permissions/warehouse/:warehouse_id/export",
+            get(export_warehouse_assignments),
+        )
+        .route(
+            "/permissions/project/:project_id/import",
+            post(import_project_assignments),
+        )
+        .route(
+            "/permissions/warehouse/:warehouse_id/import",
+            post(import_warehouse_assignments),
+        )
         .route(
             "/permissions/role/:role_id/assignments",
             get(get_role_assignments_by_id).post(update_role_assignments_by_id),
@@ -1414,7 +1974,7 @@ pub(super) fn new_v1_router<C: Catalog, S: SecretStore>(
         )
 }
 
-async fn get_relations<RA: Assignment>(
+pub(super) async fn get_relations<RA: Assignment>(
     authorizer: OpenFGAAuthorizer,
     query_relations: Option<Vec<RA::Relation>>,
     object: &str,
@@ -1445,14 +2005,46 @@ async fn get_relations<RA: Assignment>(
     Ok(relations)
 }
 
-async fn get_allowed_actions<A: ReducedRelation + IntoEnumIterator>(
-    authorizer: OpenFGAAuthorizer,
+async fn get_expirations<R: ReducedRelation>(
+    authorizer: &OpenFGAAuthorizer,
+    query_relations: Option<&[R]>,
+    object: &str,
+) -> Result<Vec<AssignmentExpiration>> {
+    let relations = query_relations
+        .map(|relations| {
+            relations
+                .iter()
+                .map(|r| r.to_openfga().to_string())
+                .collect::<HashSet<_>>()
+        })
+        .unwrap_or_else(|| R::iter().map(|r| r.to_openfga().to_string()).collect());
+    let now = chrono::Utc::now();
+
+    authorizer
+        .get_grant_expirations(object)
+        .await?
+        .into_iter()
+        .filter(|e| relations.contains(&e.grant.relation))
+        .map(|e| {
+            Ok(AssignmentExpiration {
+                principal: UserOrRole::parse_from_openfga(&e.grant.principal)?,
+                r#type: e.grant.relation,
+                expires_at: e.expires_at,
+                expired: e.revoked_at.is_some() || e.expires_at <= now,
+            })
+        })
+        .collect()
+}
+
+/// Check that the actor may query the access of `for_principal` on `object`.
+/// Returns the `OpenFGA` user whose access is queried.
+async fn authorize_access_query(
+    authorizer: &OpenFGAAuthorizer,
     actor: &Actor,
     object: &str,
     for_principal: Option<&UserOrRole>,
-) -> OpenFGAResult<Vec<A>> {
+) -> OpenFGAResult<String> {
     let openfga_actor = actor.to_openfga();
-    let openfga_object = object.to_string();
 
     if for_principal.is_some() || actor == &Actor::Anonymous {
         // AuthZ
@@ -1460,10 +2052,10 @@ async fn get_allowed_actions<A: ReducedRelation + IntoEnumIterator>(
             user: openfga_actor.clone(),
             // This is identical for all entities and checked in unittests. Hence we use `RoleAction`
             relation: RoleAction::ReadAssignments.to_openfga().to_string(),
-            object: openfga_object.clone(),
+            object: object.to_string(),
         };
 
-        let allowed = authorizer.clone().check(key).await?;
+        let allowed = authorizer.check(key).await?;
         if !allowed {
             return Err(OpenFGAError::Unauthorized {
                 user: openfga_actor.clone(),
@@ -1473,10 +2065,68 @@ async fn get_allowed_actions<A: ReducedRelation + IntoEnumIterator>(
         }
     }
 
-    let actions = A::iter().collect::<Vec<_>>();
-    let for_principal = for_principal
+    Ok(for_principal
         .map(super::entities::OpenFgaEntity::to_openfga)
-        .unwrap_or(openfga_actor.to_string());
+        .unwrap_or(openfga_actor))
+}
+
+/// Explain an action on `object`. Valid actions are the checks of the
+/// catalog (`A`) and of the permission API (`B`).
+async fn explain_access<A, B>(
+    authorizer: OpenFGAAuthorizer,
+    actor: &Actor,
+    object: &str,
+    query: ExplainAccessQuery,
+) -> Result<ExplainAccessResponse>
+where
+    A: ReducedRelation + std::fmt::Display,
+    B: ReducedRelation<OpenFgaRelation = A::OpenFgaRelation> + Serialize,
+{
+    // Catalog actions (`can_read_data`) and management actions (`grant_select`)
+    let relation = A::iter()
+        .find(|action| action.to_string() == query.action)
+        .map(|action| action.to_openfga())
+        .or_else(|| {
+            B::iter()
+                .find(|action| {
+                    serde_json::to_value(action)
+                        .ok()
+                        .as_ref()
+                        .and_then(|v| v.as_str())
+                        == Some(query.action.as_str())
+                })
+                .map(|action| action.to_openfga())
+        })
+        .ok_or_else(|| {
+            ErrorModel::bad_request(
+                format!("Unknown action `{}` for object {object}", query.action),
+                "UnknownAction",
+                None,
+            )
+        })?;
+
+    let principal =
+        authorize_access_query(&authorizer, actor, object, query.principal.as_ref()).await?;
+    let path = authorizer
+        .explain(&principal, &relation.to_string(), object)
+        .await?;
+
+    Ok(ExplainAccessResponse {
+        allowed: path.is_some(),
+        path: path.unwrap_or_default(),
+    })
+}
+
+async fn get_allowed_actions<A: ReducedRelation + IntoEnumIterator>(
+    authorizer: OpenFGAAuthorizer,
+    actor: &Actor,
+    object: &str,
+    for_principal: Option<&UserOrRole>,
+) -> OpenFGAResult<Vec<A>> {
+    let openfga_object = object.to_string();
+    let for_principal = authorize_access_query(&authorizer, actor, object, for_principal).await?;
+
+    let actions = A::iter().collect::<Vec<_>>();
 
     let actions = actions.iter().map(|action| async {
         let key = CheckRequestTupleKey {
@@ -1503,11 +2153,12 @@ async fn checked_write<RA: Assignment>(
     actor: &Actor,
     writes: Vec<RA>,
     deletes: Vec<RA>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
     object: &str,
-) -> OpenFGAResult<()> {
+) -> Result<()> {
     // Fail fast
     if actor == &Actor::Anonymous {
-        return Err(OpenFGAError::AuthenticationRequired);
+        return Err(OpenFGAError::AuthenticationRequired.into());
     }
     let all_modifications = writes.iter().chain(deletes.iter()).collect::<Vec<_>>();
     // Fail fast for too many writes
@@ -1516,7 +2167,8 @@ async fn checked_write<RA: Assignment>(
         return Err(OpenFGAError::TooManyWrites {
             actual: num_modifications,
             max: MAX_TUPLES_PER_WRITE,
-        });
+        }
+        .into());
     }
 
     // ---------------------------- AUTHZ CHECKS ----------------------------
@@ -1565,7 +2217,9 @@ async fn checked_write<RA: Assignment>(
             object: object.to_string(),
         })
         .collect();
-    authorizer.write(Some(writes), Some(deletes)).await
+    authorizer
+        .write_with_expiration(writes, deletes, expires_at)
+        .await
 }
 
 async fn get_managed_access<T: OpenFgaEntity>(
@@ -1678,6 +2332,7 @@ mod tests {
                 &Actor::Principal(user_id.clone()),
                 vec![RoleAssignment::Assignee(role_id.into())],
                 vec![],
+                None,
                 &role_id.to_openfga(),
             )
             .await;
@@ -1896,6 +2551,7 @@ mod tests {
                 &Actor::Principal(user1_id.clone()),
                 vec![ServerAssignment::Admin(user2_id.into())],
                 vec![],
+                None,
                 &OPENFGA_SERVER,
             )
             .await
@@ -1937,6 +2593,7 @@ mod tests {
                     RoleAssignment::Assignee(role_id_2.into()),
                 ],
                 vec![],
+                None,
                 &role_id_1.to_openfga(),
             )
             .await
@@ -1980,6 +2637,7 @@ mod tests {
                     ProjectAssignment::DataAdmin(UserOrRole::User(user_id_assignee.clone())),
                 ],
                 vec![],
+                None,
                 &project_id.to_openfga(),
             )
             .await
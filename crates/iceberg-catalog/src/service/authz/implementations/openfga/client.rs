@@ -3,8 +3,9 @@
 use super::{
     ClientHelper as _, ModelVersion, OpenFGAAuthorizer, OpenFGAError, OpenFGAResult, AUTH_CONFIG,
 };
+use crate::service::authz::implementations::decision_cache::DecisionCache;
 use crate::service::authz::implementations::openfga::migration::get_auth_model_id;
-use crate::{service::authz::implementations::Authorizers, OpenFGAAuth};
+use crate::{OpenFGAAuth, CONFIG};
 use http::{HeaderMap, Request};
 use openfga_rs::tonic::body::BoxBody;
 use openfga_rs::tonic::transport::{Channel, Endpoint};
@@ -74,9 +75,9 @@ pub(crate) async fn new_client_from_config(
 /// - Server connection fails
 /// - Store (name) not found (from crate Config)
 /// - Active Authorization model not found
-pub async fn new_authorizer_from_config() -> OpenFGAResult<Authorizers> {
+pub async fn new_authorizer_from_config() -> OpenFGAResult<OpenFGAAuthorizer> {
     let client = new_client_from_config().await?;
-    Ok(Authorizers::OpenFGA(new_authorizer(client, None).await?))
+    new_authorizer(client, None).await
 }
 
 /// Create a new `OpenFGA` authorizer with the given client.
@@ -100,6 +101,11 @@ pub(crate) async fn new_authorizer(
         client: Arc::new(client),
         store_id,
         authorization_model_id,
+        cache: DecisionCache::from_config(
+            CONFIG.authz_decision_cache_ttl_seconds,
+            CONFIG.authz_decision_cache_capacity,
+        ),
+        grant_ledger: None,
         health: Arc::new(RwLock::new(vec![])),
     })
 }
@@ -4,8 +4,8 @@ use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
 use openfga_rs::tonic::metadata::errors::InvalidMetadataValue;
 use openfga_rs::tonic::{self, Code};
 use openfga_rs::{
-    authentication::CredentialRefreshError, CheckRequest, ReadRequest, ReadRequestTupleKey,
-    WriteRequest,
+    authentication::CredentialRefreshError, CheckRequest, ExpandRequest, ReadRequest,
+    ReadRequestTupleKey, WriteRequest,
 };
 
 pub type OpenFGAResult<T> = Result<T, OpenFGAError>;
@@ -36,6 +36,16 @@ pub enum OpenFGAError {
         check_request: Box<CheckRequest>,
         source: tonic::Status,
     },
+    #[error("Expanding relation failed")]
+    ExpandFailed {
+        expand_request: Box<ExpandRequest>,
+        source: tonic::Status,
+    },
+    #[error("Batch authorization check of {num_checks} tuples failed")]
+    BatchCheckFailed {
+        num_checks: usize,
+        source: tonic::Status,
+    },
     #[error("Store creation failed: {0}")]
     StoreCreationFailed(tonic::Status),
     #[error("Store {store} not found. Please ensure to run migration first.")]
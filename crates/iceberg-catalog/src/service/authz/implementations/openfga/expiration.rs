@@ -0,0 +1,317 @@
+//! Time-bound grants.
+//!
+//! Tuples do not expire on their own. When a grant with an expiration is written, the
+//! expiration is recorded in a ledger in the catalog database and a `grant_expiration`
+//! task is queued for it. Once the grant expires, the task deletes its tuple and marks
+//! the grant as revoked.
+
+use super::OpenFGAAuthorizer;
+use crate::implementations::postgres::authz::{self as ledger, Grant};
+use crate::implementations::postgres::dbutils::DBErrorHandler;
+use crate::implementations::postgres::task_queues::GrantExpirationQueue as PgGrantExpirationQueue;
+use crate::implementations::postgres::ReadWrite;
+use crate::service::authz::ErrorModel;
+use crate::service::task_queue::grant_expiration_queue::{
+    grant_expiration_task, GrantExpirationInput, GrantExpirationQueue,
+};
+use crate::service::Result;
+use crate::CONFIG;
+use chrono::{DateTime, Utc};
+use openfga_rs::{ConsistencyPreference, ReadRequestTupleKey, TupleKey, TupleKeyWithoutCondition};
+use std::sync::Arc;
+
+/// Revoked grants remain visible in the assignment listing for this many days
+const REVOKED_GRANT_RETENTION_DAYS: i64 = 30;
+
+/// Expirations of time-bound grants and the queue that revokes them.
+#[derive(Debug, Clone)]
+pub(crate) struct GrantLedger {
+    pool: sqlx::PgPool,
+    queue: GrantExpirationQueue,
+}
+
+impl GrantLedger {
+    pub(crate) fn new(pool: sqlx::PgPool) -> std::result::Result<Self, ErrorModel> {
+        let queue = PgGrantExpirationQueue::from_config(
+            ReadWrite::from_pools(pool.clone(), pool.clone()),
+            CONFIG.queue_config.clone(),
+        )
+        .map_err(|e| {
+            ErrorModel::internal(
+                "Failed to create grant expiration queue",
+                "GrantExpirationQueueError",
+                Some(e.into()),
+            )
+        })?;
+        Ok(Self {
+            pool,
+            queue: Arc::new(queue),
+        })
+    }
+}
+
+impl OpenFGAAuthorizer {
+    /// Record expirations of time-bound grants in the given database.
+    ///
+    /// # Errors
+    /// - The configuration of the grant expiration queue is invalid
+    pub fn with_grant_ledger(
+        mut self,
+        pool: sqlx::PgPool,
+    ) -> std::result::Result<Self, ErrorModel> {
+        self.grant_ledger = Some(GrantLedger::new(pool)?);
+        Ok(self)
+    }
+
+    /// Write and delete tuples. If `expires_at` is set, the written tuples are
+    /// deleted again once it has passed. Writes without `expires_at` are permanent.
+    pub(super) async fn write_with_expiration(
+        &self,
+        writes: Vec<TupleKey>,
+        deletes: Vec<TupleKeyWithoutCondition>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        self.record_grants(writes, deletes, expires_at, true).await
+    }
+
+    /// Change the expiration of grants whose tuples already exist.
+    /// Without `expires_at`, the grants become permanent.
+    pub(super) async fn set_grant_expirations(
+        &self,
+        grants: Vec<TupleKey>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        self.record_grants(grants, vec![], expires_at, false).await
+    }
+
+    /// Record the expirations of `writes` and `deletes` in the ledger.
+    /// If `write_tuples` is set, the tuples are written in the same step.
+    async fn record_grants(
+        &self,
+        writes: Vec<TupleKey>,
+        deletes: Vec<TupleKeyWithoutCondition>,
+        expires_at: Option<DateTime<Utc>>,
+        write_tuples: bool,
+    ) -> Result<()> {
+        let Some(grant_ledger) = &self.grant_ledger else {
+            if expires_at.is_some() {
+                return Err(ErrorModel::bad_request(
+                    "Time-bound grants are not supported by the configured authorizer",
+                    "GrantExpirationNotSupported",
+                    None,
+                )
+                .into());
+            }
+            if write_tuples {
+                self.write(Some(writes), Some(deletes)).await?;
+            }
+            return Ok(());
+        };
+        if let Some(expires_at) = expires_at.filter(|e| *e <= Utc::now()) {
+            return Err(ErrorModel::bad_request(
+                format!("Expiration `{expires_at}` of the grant is not in the future"),
+                "InvalidGrantExpiration",
+                None,
+            )
+            .into());
+        }
+
+        let written = writes
+            .iter()
+            .map(|t| Grant {
+                object: t.object.clone(),
+                relation: t.relation.clone(),
+                principal: t.user.clone(),
+            })
+            .collect::<Vec<_>>();
+        let deleted = deletes
+            .iter()
+            .map(|t| Grant {
+                object: t.object.clone(),
+                relation: t.relation.clone(),
+                principal: t.user.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        // Tasks are queued first, so that every recorded expiration has a task.
+        // A task finds nothing to revoke if its expiration is never recorded.
+        if let Some(expires_at) = expires_at {
+            for grant in &written {
+                grant_ledger
+                    .queue
+                    .enqueue(GrantExpirationInput {
+                        object: grant.object.clone(),
+                        relation: grant.relation.clone(),
+                        principal: grant.principal.clone(),
+                        expire_at: expires_at,
+                    })
+                    .await?;
+            }
+        }
+
+        // The ledger is only committed if the tuples are written, so that a failed write
+        // never changes the expiration of an existing grant.
+        let mut transaction = grant_ledger
+            .pool
+            .begin()
+            .await
+            .map_err(|e| e.into_error_model("Error starting grant expiration transaction"))?;
+        ledger::delete_grant_expirations(&deleted, &mut transaction).await?;
+        match expires_at {
+            Some(expires_at) => {
+                ledger::set_grant_expirations(&written, expires_at, &mut transaction).await?;
+            }
+            None => ledger::delete_grant_expirations(&written, &mut transaction).await?,
+        }
+        if write_tuples {
+            self.write(Some(writes), Some(deletes)).await?;
+        }
+        transaction.commit().await.map_err(|e| {
+            tracing::error!(
+                "Tuples were written but their expirations could not be committed: {e}"
+            );
+            e.into_error_model("Error committing grant expirations")
+        })?;
+
+        Ok(())
+    }
+
+    /// Get expirations of all grants on `object`, including recently revoked grants.
+    pub(super) async fn get_grant_expirations(
+        &self,
+        object: &str,
+    ) -> Result<Vec<ledger::GrantExpiration>> {
+        match &self.grant_ledger {
+            Some(grant_ledger) => ledger::list_grant_expirations(object, &grant_ledger.pool).await,
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Delete the tuple of `grant` if it expired and mark it as revoked.
+    /// Returns `false` if the grant was renewed, removed or is already revoked.
+    ///
+    /// # Errors
+    /// - Reading or updating the ledger fails
+    /// - Deleting the tuple fails
+    pub(crate) async fn revoke_expired_grant(&self, grant: &Grant) -> Result<bool> {
+        let Some(grant_ledger) = &self.grant_ledger else {
+            return Ok(false);
+        };
+
+        let mut transaction = grant_ledger
+            .pool
+            .begin()
+            .await
+            .map_err(|e| e.into_error_model("Error starting grant revocation transaction"))?;
+        if !ledger::lock_due_grant(grant, &mut transaction).await? {
+            return Ok(false);
+        }
+
+        // The tuple might already be deleted, i.e. by a previous attempt
+        let tuples = self
+            .read(
+                1,
+                ReadRequestTupleKey {
+                    user: grant.principal.clone(),
+                    relation: grant.relation.clone(),
+                    object: grant.object.clone(),
+                },
+                None,
+                ConsistencyPreference::HigherConsistency,
+            )
+            .await?;
+        if !tuples.tuples.is_empty() {
+            self.write(
+                None,
+                Some(vec![TupleKeyWithoutCondition {
+                    user: grant.principal.clone(),
+                    relation: grant.relation.clone(),
+                    object: grant.object.clone(),
+                }]),
+            )
+            .await?;
+        }
+        ledger::mark_grants_revoked(std::slice::from_ref(grant), &mut *transaction).await?;
+        transaction
+            .commit()
+            .await
+            .map_err(|e| e.into_error_model("Error committing grant revocation"))?;
+
+        ledger::purge_revoked_grants(
+            Utc::now() - chrono::Duration::days(REVOKED_GRANT_RETENTION_DAYS),
+            &grant_ledger.pool,
+        )
+        .await?;
+
+        Ok(true)
+    }
+
+    /// Spawn the worker of the grant expiration queue.
+    /// Does nothing if no grant ledger is configured.
+    pub fn spawn_grant_expiration_task(&self) {
+        let Some(grant_ledger) = &self.grant_ledger else {
+            return;
+        };
+        tokio::task::spawn(grant_expiration_task(
+            grant_ledger.queue.clone(),
+            self.clone(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::authz::implementations::openfga::new_postgres_authorizer;
+
+    #[sqlx::test]
+    async fn test_revoke_expired_grant(pool: sqlx::PgPool) {
+        let authorizer = new_postgres_authorizer(pool.clone()).unwrap();
+        let grant = Grant {
+            object: format!("warehouse:{}", uuid::Uuid::now_v7()),
+            relation: "select".to_string(),
+            principal: "user:oidc~a".to_string(),
+        };
+        let tuple_key = ReadRequestTupleKey {
+            user: grant.principal.clone(),
+            relation: grant.relation.clone(),
+            object: grant.object.clone(),
+        };
+
+        authorizer
+            .write_with_expiration(
+                vec![TupleKey {
+                    user: grant.principal.clone(),
+                    relation: grant.relation.clone(),
+                    object: grant.object.clone(),
+                    condition: None,
+                }],
+                vec![],
+                Some(Utc::now() + chrono::Duration::hours(1)),
+            )
+            .await
+            .unwrap();
+        assert!(!authorizer.revoke_expired_grant(&grant).await.unwrap());
+
+        sqlx::query("UPDATE authz_grant_expiration SET expires_at = now() - interval '1 minute'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(authorizer.revoke_expired_grant(&grant).await.unwrap());
+
+        let tuples = authorizer
+            .read(1, tuple_key, None, ConsistencyPreference::HigherConsistency)
+            .await
+            .unwrap();
+        assert!(tuples.tuples.is_empty());
+        let expirations = authorizer
+            .get_grant_expirations(&grant.object)
+            .await
+            .unwrap();
+        assert_eq!(expirations.len(), 1);
+        assert!(expirations[0].revoked_at.is_some());
+
+        // Already revoked
+        assert!(!authorizer.revoke_expired_grant(&grant).await.unwrap());
+    }
+}
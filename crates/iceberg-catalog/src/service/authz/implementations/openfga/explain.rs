@@ -0,0 +1,354 @@
+//! Explain why a principal has a relation on an object.
+//!
+//! The relation is expanded with `OpenFGA`'s `Expand` API level by level, from the
+//! requested relation down to the tuple that grants it. Each level is pruned with a check,
+//! so only branches that actually grant the relation are expanded further.
+
+use super::{OpenFGAAuthorizer, OpenFGAResult};
+use futures::future::BoxFuture;
+use openfga_rs::{userset_tree, CheckRequestTupleKey};
+use serde::Serialize;
+use std::borrow::Borrow;
+use std::collections::HashSet;
+
+/// Maximum depth of nested relations, same as `OpenFGA`'s default resolution depth.
+const MAX_EXPLAIN_DEPTH: usize = 25;
+
+/// A single step of the relation path that grants an action.
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct AccessPathStep {
+    /// Object the relation is evaluated on, i.e. `namespace:<id>`
+    pub(super) object: String,
+    /// Relation the principal has on the object
+    pub(super) relation: String,
+    /// Why the principal has the relation
+    pub(super) reason: AccessReason,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub(super) enum AccessReason {
+    /// Implied by the relation of the next step on the same object
+    Computed,
+    /// Inherited from the object of the next step, which is related via `tupleset`,
+    /// i.e. the `parent` namespace or warehouse
+    Inherited { tupleset: String },
+    /// Granted to a set of users the principal belongs to, i.e. `role:<id>#assignee`.
+    /// The next steps explain the membership.
+    Userset { userset: String },
+    /// Granted directly to the principal
+    Direct { user: String },
+    /// Granted to all users of a type, i.e. `user:*`
+    Public { user: String },
+}
+
+impl OpenFGAAuthorizer {
+    /// Explain why `user` has `relation` on `object`.
+    ///
+    /// Returns `None` if the relation is not granted. Otherwise returns the path from
+    /// the requested relation to the tuple that grants it.
+    pub(super) async fn explain(
+        &self,
+        user: &str,
+        relation: &str,
+        object: &str,
+    ) -> OpenFGAResult<Option<Vec<AccessPathStep>>> {
+        let mut explainer = Explainer {
+            authorizer: self,
+            user,
+            visited: HashSet::new(),
+        };
+        if !explainer.is_granted(object, relation).await? {
+            return Ok(None);
+        }
+        Ok(Some(
+            explainer
+                .explain(object, relation, 0)
+                .await?
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+struct Explainer<'a> {
+    authorizer: &'a OpenFGAAuthorizer,
+    user: &'a str,
+    visited: HashSet<(String, String)>,
+}
+
+impl Explainer<'_> {
+    async fn is_granted(&self, object: &str, relation: &str) -> OpenFGAResult<bool> {
+        self.authorizer
+            .check(CheckRequestTupleKey {
+                user: self.user.to_string(),
+                relation: relation.to_string(),
+                object: object.to_string(),
+            })
+            .await
+    }
+
+    /// Find the path that grants `relation` on `object`.
+    fn explain<'b>(
+        &'b mut self,
+        object: &'b str,
+        relation: &'b str,
+        depth: usize,
+    ) -> BoxFuture<'b, OpenFGAResult<Option<Vec<AccessPathStep>>>> {
+        Box::pin(async move {
+            if depth > MAX_EXPLAIN_DEPTH
+                || !self
+                    .visited
+                    .insert((object.to_string(), relation.to_string()))
+            {
+                return Ok(None);
+            }
+            // Depth 0 is checked by the caller
+            if depth > 0 && !self.is_granted(object, relation).await? {
+                return Ok(None);
+            }
+
+            let Some(node) = self.authorizer.expand(object, relation).await? else {
+                return Ok(None);
+            };
+            self.explain_node(object, relation, &node, depth).await
+        })
+    }
+
+    fn explain_node<'b>(
+        &'b mut self,
+        object: &'b str,
+        relation: &'b str,
+        node: &'b userset_tree::Node,
+        depth: usize,
+    ) -> BoxFuture<'b, OpenFGAResult<Option<Vec<AccessPathStep>>>> {
+        Box::pin(async move {
+            match &node.value {
+                Some(userset_tree::node::Value::Leaf(leaf)) => match &leaf.value {
+                    Some(userset_tree::leaf::Value::Users(users)) => {
+                        self.explain_users(object, relation, &users.users, depth)
+                            .await
+                    }
+                    Some(userset_tree::leaf::Value::Computed(computed)) => {
+                        let Some((computed_object, computed_relation)) =
+                            computed.userset.rsplit_once('#')
+                        else {
+                            return Ok(None);
+                        };
+                        Ok(self
+                            .explain(computed_object, computed_relation, depth + 1)
+                            .await?
+                            .map(|path| prepend(object, relation, AccessReason::Computed, path)))
+                    }
+                    Some(userset_tree::leaf::Value::TupleToUserset(ttu)) => {
+                        let tupleset = ttu
+                            .tupleset
+                            .rsplit_once('#')
+                            .map_or(ttu.tupleset.as_str(), |(_, tupleset)| tupleset);
+                        for computed in &ttu.computed {
+                            let Some((parent, parent_relation)) = computed.userset.rsplit_once('#')
+                            else {
+                                continue;
+                            };
+                            if let Some(path) =
+                                self.explain(parent, parent_relation, depth + 1).await?
+                            {
+                                let reason = AccessReason::Inherited {
+                                    tupleset: tupleset.to_string(),
+                                };
+                                return Ok(Some(prepend(object, relation, reason, path)));
+                            }
+                        }
+                        Ok(None)
+                    }
+                    None => Ok(None),
+                },
+                // An intersection only grants if all children do, so any granting
+                // child explains the grant.
+                Some(
+                    userset_tree::node::Value::Union(nodes)
+                    | userset_tree::node::Value::Intersection(nodes),
+                ) => {
+                    for child in &nodes.nodes {
+                        if let Some(path) =
+                            self.explain_node(object, relation, child, depth).await?
+                        {
+                            return Ok(Some(path));
+                        }
+                    }
+                    Ok(None)
+                }
+                Some(userset_tree::node::Value::Difference(difference)) => match &difference.base {
+                    Some(base) => {
+                        self.explain_node(object, relation, base.borrow(), depth)
+                            .await
+                    }
+                    None => Ok(None),
+                },
+                None => Ok(None),
+            }
+        })
+    }
+
+    /// Explain users that are directly related to the object.
+    async fn explain_users(
+        &mut self,
+        object: &str,
+        relation: &str,
+        users: &[String],
+        depth: usize,
+    ) -> OpenFGAResult<Option<Vec<AccessPathStep>>> {
+        let user_type = self.user.split_once(':').map(|(t, _)| t);
+        let user_is_userset = self.user.contains('#');
+
+        let mut usersets = vec![];
+        for user in users {
+            if user == self.user {
+                return Ok(Some(vec![step(
+                    object,
+                    relation,
+                    AccessReason::Direct { user: user.clone() },
+                )]));
+            }
+            match user.split_once('#') {
+                Some((userset_object, userset_relation)) => {
+                    usersets.push((userset_object, userset_relation));
+                }
+                None => {
+                    let is_wildcard = user
+                        .split_once(':')
+                        .is_some_and(|(t, id)| id == "*" && Some(t) == user_type);
+                    if is_wildcard && !user_is_userset {
+                        return Ok(Some(vec![step(
+                            object,
+                            relation,
+                            AccessReason::Public { user: user.clone() },
+                        )]));
+                    }
+                }
+            }
+        }
+
+        for (userset_object, userset_relation) in usersets {
+            if let Some(path) = self
+                .explain(userset_object, userset_relation, depth + 1)
+                .await?
+            {
+                let reason = AccessReason::Userset {
+                    userset: format!("{userset_object}#{userset_relation}"),
+                };
+                return Ok(Some(prepend(object, relation, reason, path)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn step(object: &str, relation: &str, reason: AccessReason) -> AccessPathStep {
+    AccessPathStep {
+        object: object.to_string(),
+        relation: relation.to_string(),
+        reason,
+    }
+}
+
+fn prepend(
+    object: &str,
+    relation: &str,
+    reason: AccessReason,
+    path: Vec<AccessPathStep>,
+) -> Vec<AccessPathStep> {
+    std::iter::once(step(object, relation, reason))
+        .chain(path)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::authz::implementations::openfga::{
+        postgres::new_postgres_authorizer, relations::TableRelation,
+    };
+    use needs_env_var::needs_env_var;
+    use openfga_rs::TupleKey;
+
+    fn tuple(user: &str, relation: &str, object: &str) -> TupleKey {
+        TupleKey {
+            user: user.to_string(),
+            relation: relation.to_string(),
+            object: object.to_string(),
+            condition: None,
+        }
+    }
+
+    async fn explain_inherited_role_grant(authorizer: &OpenFGAAuthorizer) {
+        let namespace = format!("namespace:{}", uuid::Uuid::now_v7());
+        let table = format!("table:{}", uuid::Uuid::now_v7());
+        let role = format!("role:{}", uuid::Uuid::now_v7());
+        let user = format!("user:oidc~{}", uuid::Uuid::now_v7());
+
+        authorizer
+            .write(
+                Some(vec![
+                    tuple(&namespace, "parent", &table),
+                    tuple(&table, "child", &namespace),
+                    tuple(&format!("{role}#assignee"), "modify", &namespace),
+                    tuple(&user, "assignee", &role),
+                ]),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let path = authorizer
+            .explain(&user, &TableRelation::CanWriteData.to_string(), &table)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(path.first().unwrap().object, table);
+        assert!(path.iter().any(|s| s.object == namespace
+            && s.relation == "modify"
+            && s.reason
+                == AccessReason::Userset {
+                    userset: format!("{role}#assignee")
+                }));
+        assert_eq!(
+            path.last().unwrap(),
+            &step(
+                &role,
+                "assignee",
+                AccessReason::Direct { user: user.clone() }
+            )
+        );
+
+        let other_user = format!("user:oidc~{}", uuid::Uuid::now_v7());
+        assert!(authorizer
+            .explain(
+                &other_user,
+                &TableRelation::CanWriteData.to_string(),
+                &table
+            )
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_explain_inherited_role_grant_postgres(pool: sqlx::PgPool) {
+        let authorizer = new_postgres_authorizer(pool).unwrap();
+        explain_inherited_role_grant(&authorizer).await;
+    }
+
+    #[needs_env_var(TEST_OPENFGA = 1)]
+    mod openfga {
+        use super::explain_inherited_role_grant;
+        use crate::service::authz::implementations::openfga::migration::tests::authorizer_for_empty_store;
+
+        #[tokio::test]
+        async fn test_explain_inherited_role_grant() {
+            let (_, authorizer) = authorizer_for_empty_store().await;
+            explain_inherited_role_grant(&authorizer).await;
+        }
+    }
+}
@@ -0,0 +1,1050 @@
+//! Export and import of assignments.
+//!
+//! Assignments are exported as a portable document that references entities by name
+//! instead of by id, so that it can be imported into another project or server.
+//! Structural tuples, such as the parent of a table, are owned by the catalog and are
+//! not part of the document.
+
+use super::api::get_relations;
+use super::entities::OpenFgaEntity;
+use super::relations::{
+    Assignment, GrantableRelation, NamespaceAssignment, NamespaceRelation, ProjectAssignment,
+    ProjectRelation, ReducedRelation, RoleAssignment, RoleRelation, TableAssignment, TableRelation,
+    ViewAssignment, ViewRelation, WarehouseAssignment, WarehouseRelation,
+};
+use super::service_ext::MAX_TUPLES_PER_WRITE;
+use super::{OpenFGAAuthorizer, OpenFGAError};
+use crate::api::iceberg::types::PageToken;
+use crate::api::iceberg::v1::{NamespaceIdent, PaginationQuery, TableIdent};
+use crate::catalog::{list_all_namespaces, list_all_tabulars};
+use crate::service::authn::UserId;
+use crate::service::authz::ErrorModel;
+use crate::service::{
+    Actor, Catalog, ListFlags, Result, RoleId, TableIdentUuid, TabularIdentOwned, TabularIdentUuid,
+    Transaction, ViewIdentUuid,
+};
+use crate::{ProjectIdent, WarehouseIdent};
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
+use openfga_rs::{CheckRequestTupleKey, ReadRequestTupleKey, TupleKey, TupleKeyWithoutCondition};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use strum::IntoEnumIterator;
+
+/// Version of the [`AuthzDocument`] format
+pub const AUTHZ_DOCUMENT_VERSION: u32 = 1;
+/// Page size used to list entities of the catalog
+const LIST_PAGE_SIZE: i64 = 1000;
+/// Number of entities whose assignments are read concurrently
+const MAX_CONCURRENT_READS: usize = 16;
+
+/// Portable set of assignments of a project or warehouse.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuthzDocument {
+    /// Version of the document format
+    pub version: u32,
+    /// Name of the warehouse the document was exported from.
+    /// Only set for exports of a single warehouse.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warehouse: Option<String>,
+    #[serde(default)]
+    pub assignments: Vec<ExportedAssignment>,
+    /// Warehouses and namespaces with managed access
+    #[serde(default)]
+    pub managed_access: Vec<EntityRef>,
+}
+
+/// Assignment of `relation` on `entity` to `principal`.
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExportedAssignment {
+    pub entity: EntityRef,
+    /// Type of the assignment, i.e. `select`
+    pub relation: String,
+    pub principal: PrincipalRef,
+    /// Expiration of a time-bound assignment. Assignments without expiration are permanent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl ExportedAssignment {
+    /// Identifies the assignment independent of its expiration.
+    fn key(&self) -> (EntityRef, String, PrincipalRef) {
+        (
+            self.entity.clone(),
+            self.relation.clone(),
+            self.principal.clone(),
+        )
+    }
+}
+
+/// Entity referenced by name.
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, utoipa::ToSchema,
+)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum EntityRef {
+    Project,
+    Role {
+        role: String,
+    },
+    Warehouse {
+        warehouse: String,
+    },
+    Namespace {
+        warehouse: String,
+        namespace: Vec<String>,
+    },
+    Table {
+        warehouse: String,
+        namespace: Vec<String>,
+        name: String,
+    },
+    View {
+        warehouse: String,
+        namespace: Vec<String>,
+        name: String,
+    },
+}
+
+impl EntityRef {
+    fn with_warehouse(self, name: &str) -> Self {
+        let warehouse = name.to_string();
+        match self {
+            EntityRef::Project | EntityRef::Role { .. } => self,
+            EntityRef::Warehouse { .. } => EntityRef::Warehouse { warehouse },
+            EntityRef::Namespace { namespace, .. } => EntityRef::Namespace {
+                warehouse,
+                namespace,
+            },
+            EntityRef::Table {
+                namespace, name, ..
+            } => EntityRef::Table {
+                warehouse,
+                namespace,
+                name,
+            },
+            EntityRef::View {
+                namespace, name, ..
+            } => EntityRef::View {
+                warehouse,
+                namespace,
+                name,
+            },
+        }
+    }
+
+    /// Relation required to read the assignments of the entity.
+    fn can_read_assignments(&self) -> String {
+        match self {
+            EntityRef::Project => ProjectRelation::CanReadAssignments.to_string(),
+            EntityRef::Role { .. } => RoleRelation::CanReadAssignments.to_string(),
+            EntityRef::Warehouse { .. } => WarehouseRelation::CanReadAssignments.to_string(),
+            EntityRef::Namespace { .. } => NamespaceRelation::CanReadAssignments.to_string(),
+            EntityRef::Table { .. } => TableRelation::CanReadAssignments.to_string(),
+            EntityRef::View { .. } => ViewRelation::CanReadAssignments.to_string(),
+        }
+    }
+}
+
+/// User referenced by id or role referenced by name.
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum PrincipalRef {
+    /// Id of the user, i.e. `oidc~<subject>`
+    User(String),
+    /// Name of the role
+    Role(String),
+}
+
+/// Project or warehouse to export from or import into.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuthzScope {
+    Project(ProjectIdent),
+    Warehouse(WarehouseIdent),
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOptions {
+    /// Apply the changes. By default, only the changes that would be applied are returned.
+    #[serde(default)]
+    pub apply: bool,
+    /// Delete assignments and managed access that are not part of the document.
+    #[serde(default)]
+    pub prune: bool,
+}
+
+/// Changes of an import.
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ImportReport {
+    /// Whether the changes were applied
+    pub applied: bool,
+    /// Assignments that are added
+    pub writes: Vec<ExportedAssignment>,
+    /// Existing assignments whose expiration is changed
+    pub updates: Vec<ExportedAssignment>,
+    /// Assignments that are removed. Only populated if `prune` is set.
+    pub deletes: Vec<ExportedAssignment>,
+    /// Entities on which managed access is enabled
+    pub enable_managed_access: Vec<EntityRef>,
+    /// Entities on which managed access is disabled. Only populated if `prune` is set.
+    pub disable_managed_access: Vec<EntityRef>,
+    /// Assignments of the document whose entity or principal does not exist
+    pub unresolved: Vec<ExportedAssignment>,
+    /// Assignments of the document that already expired
+    pub expired: Vec<ExportedAssignment>,
+}
+
+/// Export all assignments of a project or warehouse.
+///
+/// If `actor` is set, the actor must be allowed to read the assignments of every
+/// exported entity. Privileges that are not inherited, such as `pass_grants`, may
+/// allow reading the assignments of the scope but not of the objects in it.
+///
+/// # Errors
+/// - The project or warehouse does not exist
+/// - The actor is not allowed to read the assignments of an entity
+/// - Reading from the catalog or the authorizer fails
+pub async fn export_assignments<C: Catalog>(
+    authorizer: &OpenFGAAuthorizer,
+    scope: AuthzScope,
+    actor: Option<&Actor>,
+    catalog_state: C::State,
+) -> Result<AuthzDocument> {
+    if actor == Some(&Actor::Anonymous) {
+        return Err(OpenFGAError::AuthenticationRequired.into());
+    }
+
+    let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let (project_id, warehouses) = match scope {
+        AuthzScope::Project(project_id) => (
+            project_id,
+            C::list_warehouses(project_id, None, t.transaction()).await?,
+        ),
+        AuthzScope::Warehouse(warehouse_id) => {
+            let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+            (warehouse.project_id, vec![warehouse])
+        }
+    };
+    t.commit().await?;
+
+    let roles = list_roles::<C>(project_id, catalog_state.clone()).await?;
+    let role_names = roles
+        .iter()
+        .map(|(name, id)| (*id, name.clone()))
+        .collect::<HashMap<_, _>>();
+
+    let mut objects: Vec<(EntityRef, String)> = vec![];
+    if let AuthzScope::Project(_) = scope {
+        objects.push((EntityRef::Project, project_id.to_openfga()));
+        objects.extend(
+            roles
+                .iter()
+                .map(|(name, id)| (EntityRef::Role { role: name.clone() }, id.to_openfga())),
+        );
+    }
+    let mut managed_access_candidates = vec![];
+    for warehouse in &warehouses {
+        let warehouse_ref = EntityRef::Warehouse {
+            warehouse: warehouse.name.clone(),
+        };
+        objects.push((warehouse_ref.clone(), warehouse.id.to_openfga()));
+        managed_access_candidates.push((warehouse_ref, warehouse.id.to_openfga()));
+
+        for (namespace_id, namespace) in
+            list_all_namespaces::<C>(warehouse.id, catalog_state.clone()).await?
+        {
+            let namespace_ref = EntityRef::Namespace {
+                warehouse: warehouse.name.clone(),
+                namespace: namespace.inner(),
+            };
+            objects.push((namespace_ref.clone(), namespace_id.to_openfga()));
+            managed_access_candidates.push((namespace_ref, namespace_id.to_openfga()));
+        }
+        for (tabular_id, tabular) in
+            list_all_tabulars::<C>(warehouse.id, catalog_state.clone()).await?
+        {
+            let (entity, object) = match (tabular_id, tabular) {
+                (TabularIdentUuid::Table(id), TabularIdentOwned::Table(ident)) => (
+                    EntityRef::Table {
+                        warehouse: warehouse.name.clone(),
+                        namespace: ident.namespace.inner(),
+                        name: ident.name,
+                    },
+                    TableIdentUuid::from(id).to_openfga(),
+                ),
+                (TabularIdentUuid::View(id), TabularIdentOwned::View(ident)) => (
+                    EntityRef::View {
+                        warehouse: warehouse.name.clone(),
+                        namespace: ident.namespace.inner(),
+                        name: ident.name,
+                    },
+                    ViewIdentUuid::from(id).to_openfga(),
+                ),
+                _ => continue,
+            };
+            objects.push((entity, object));
+        }
+    }
+
+    if let Some(actor) = actor {
+        let required = objects
+            .iter()
+            .map(|(entity, object)| (entity.can_read_assignments(), object.clone()))
+            .collect::<BTreeSet<_>>();
+        require_all(authorizer, actor, required).await?;
+    }
+
+    let now = Utc::now();
+    let assignments = futures::stream::iter(objects)
+        .map(|(entity, object)| async move {
+            let assignments = read_assignments(authorizer, &entity, &object).await?;
+            let expirations = authorizer
+                .get_grant_expirations(&object)
+                .await?
+                .into_iter()
+                .filter(|e| e.revoked_at.is_none())
+                .map(|e| ((e.grant.relation, e.grant.principal), e.expires_at))
+                .collect::<HashMap<_, _>>();
+            Result::Ok(assignments.into_iter().map(move |(relation, user)| {
+                let expires_at = expirations.get(&(relation.clone(), user.clone())).copied();
+                (entity.clone(), relation, user, expires_at)
+            }))
+        })
+        .buffered(MAX_CONCURRENT_READS)
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .flatten()
+        // Expired grants are about to be revoked
+        .filter(|(_, _, _, expires_at)| !expires_at.is_some_and(|e| e <= now))
+        .filter_map(|(entity, relation, user, expires_at)| {
+            let principal = principal_ref(&user, &role_names);
+            if principal.is_none() {
+                tracing::warn!(
+                    "Skipping assignment of `{relation}` on {entity:?} to `{user}` during export: principal cannot be exported"
+                );
+            }
+            Some(ExportedAssignment {
+                entity,
+                relation,
+                principal: principal?,
+                expires_at,
+            })
+        })
+        .collect::<BTreeSet<_>>();
+
+    let managed_access = futures::stream::iter(managed_access_candidates)
+        .map(|(entity, object)| async move {
+            let managed = authorizer
+                .read_all(ReadRequestTupleKey {
+                    user: String::new(),
+                    relation: NamespaceRelation::ManagedAccess.to_string(),
+                    object,
+                })
+                .await?;
+            Result::Ok(Some(entity).filter(|_| !managed.is_empty()))
+        })
+        .buffered(MAX_CONCURRENT_READS)
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .flatten()
+        .collect::<BTreeSet<_>>();
+
+    Ok(AuthzDocument {
+        version: AUTHZ_DOCUMENT_VERSION,
+        warehouse: match scope {
+            AuthzScope::Project(_) => None,
+            AuthzScope::Warehouse(_) => warehouses.first().map(|w| w.name.clone()),
+        },
+        assignments: assignments.into_iter().collect(),
+        managed_access: managed_access.into_iter().collect(),
+    })
+}
+
+/// Import assignments into a project or warehouse.
+///
+/// Entities and roles are matched by name. When importing into a warehouse, the
+/// document must be exported from a warehouse, which may have a different name.
+/// If `actor` is set, the actor must be allowed to grant every changed assignment.
+///
+/// # Errors
+/// - The document is invalid or does not match the scope
+/// - The actor is not allowed to perform a change
+/// - Reading from the catalog or reading or writing the authorizer fails
+pub async fn import_assignments<C: Catalog>(
+    authorizer: &OpenFGAAuthorizer,
+    scope: AuthzScope,
+    document: AuthzDocument,
+    options: ImportOptions,
+    actor: Option<&Actor>,
+    catalog_state: C::State,
+) -> Result<ImportReport> {
+    if document.version != AUTHZ_DOCUMENT_VERSION {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Unsupported document version {}, expected {AUTHZ_DOCUMENT_VERSION}",
+                document.version
+            ),
+            "UnsupportedAuthzDocumentVersion",
+            None,
+        )
+        .into());
+    }
+    if actor == Some(&Actor::Anonymous) {
+        return Err(OpenFGAError::AuthenticationRequired.into());
+    }
+
+    // Changes are authorized individually, reading the current assignments is not.
+    let current = export_assignments::<C>(authorizer, scope, None, catalog_state.clone()).await?;
+    let (assignments, managed_access) = match (scope, &current.warehouse) {
+        (AuthzScope::Project(_), _) => (document.assignments, document.managed_access),
+        (AuthzScope::Warehouse(_), Some(target)) => {
+            if document.warehouse.is_none() {
+                return Err(ErrorModel::bad_request(
+                    "Only documents exported from a warehouse can be imported into a warehouse",
+                    "AuthzDocumentScopeMismatch",
+                    None,
+                )
+                .into());
+            }
+            (
+                document
+                    .assignments
+                    .into_iter()
+                    .map(|a| ExportedAssignment {
+                        entity: a.entity.with_warehouse(target),
+                        ..a
+                    })
+                    .collect(),
+                document
+                    .managed_access
+                    .into_iter()
+                    .map(|e| e.with_warehouse(target))
+                    .collect(),
+            )
+        }
+        (AuthzScope::Warehouse(warehouse_id), None) => {
+            return Err(ErrorModel::internal(
+                format!("Export of warehouse {warehouse_id} has no warehouse name"),
+                "AuthzExportFailed",
+                None,
+            )
+            .into())
+        }
+    };
+
+    let now = Utc::now();
+    let (expired, assignments): (Vec<_>, Vec<_>) = assignments
+        .into_iter()
+        .partition(|a| a.expires_at.is_some_and(|e| e <= now));
+    let desired = assignments
+        .into_iter()
+        .map(|a| (a.key(), a))
+        .collect::<BTreeMap<_, _>>();
+    let existing = current
+        .assignments
+        .into_iter()
+        .map(|a| (a.key(), a))
+        .collect::<BTreeMap<_, _>>();
+    let desired_managed = managed_access.into_iter().collect::<BTreeSet<_>>();
+    let existing_managed = current.managed_access.into_iter().collect::<BTreeSet<_>>();
+
+    let project_id = match scope {
+        AuthzScope::Project(project_id) => project_id,
+        AuthzScope::Warehouse(warehouse_id) => {
+            let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+            let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+            t.commit().await?;
+            warehouse.project_id
+        }
+    };
+    let mut resolver = Resolver::<C> {
+        project_id,
+        roles: list_roles::<C>(project_id, catalog_state.clone())
+            .await?
+            .into_iter()
+            .collect(),
+        warehouses: HashMap::new(),
+        catalog_state,
+    };
+
+    let mut report = ImportReport {
+        applied: false,
+        writes: vec![],
+        updates: vec![],
+        deletes: vec![],
+        enable_managed_access: vec![],
+        disable_managed_access: vec![],
+        unresolved: vec![],
+        expired,
+    };
+    let mut writes = vec![];
+    let mut updates = vec![];
+    let mut deletes = vec![];
+
+    for (key, assignment) in &desired {
+        let (changes, report_changes) = match existing.get(key) {
+            None => (&mut writes, &mut report.writes),
+            Some(current) if current.expires_at != assignment.expires_at => {
+                (&mut updates, &mut report.updates)
+            }
+            Some(_) => continue,
+        };
+        match resolver.resolve(assignment).await? {
+            Some(tuple) => {
+                changes.push(tuple);
+                report_changes.push(assignment.clone());
+            }
+            None => report.unresolved.push(assignment.clone()),
+        }
+    }
+    if options.prune {
+        for (key, assignment) in &existing {
+            if desired.contains_key(key) {
+                continue;
+            }
+            if let Some(tuple) = resolver.resolve(assignment).await? {
+                deletes.push(tuple);
+                report.deletes.push(assignment.clone());
+            }
+        }
+    }
+
+    let mut enable_managed = vec![];
+    let mut disable_managed = vec![];
+    for entity in desired_managed.difference(&existing_managed) {
+        if let Some(object) = resolver.resolve_entity(entity).await? {
+            enable_managed.push(object);
+            report.enable_managed_access.push(entity.clone());
+        }
+    }
+    if options.prune {
+        for entity in existing_managed.difference(&desired_managed) {
+            if let Some(object) = resolver.resolve_entity(entity).await? {
+                disable_managed.push(object);
+                report.disable_managed_access.push(entity.clone());
+            }
+        }
+    }
+
+    if let Some(actor) = actor {
+        let required = writes
+            .iter()
+            .chain(updates.iter())
+            .chain(deletes.iter())
+            .map(|t| (t.grant_relation.clone(), t.object.clone()))
+            .chain(
+                enable_managed
+                    .iter()
+                    .chain(disable_managed.iter())
+                    .map(|object| {
+                        (
+                            NamespaceRelation::CanSetManagedAccess.to_string(),
+                            object.clone(),
+                        )
+                    }),
+            )
+            .collect::<BTreeSet<_>>();
+        require_all(authorizer, actor, required).await?;
+    }
+
+    if options.apply {
+        let managed_tuples = |objects: Vec<String>| {
+            objects
+                .into_iter()
+                .flat_map(|object| {
+                    ["user:*", "role:*"].map(|user| ResolvedTuple {
+                        object: object.clone(),
+                        relation: NamespaceRelation::ManagedAccess.to_string(),
+                        user: user.to_string(),
+                        grant_relation: String::new(),
+                        expires_at: None,
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        writes.extend(managed_tuples(enable_managed));
+        deletes.extend(managed_tuples(disable_managed));
+        apply_changes(authorizer, writes, updates, deletes).await?;
+        report.applied = true;
+    }
+
+    Ok(report)
+}
+
+/// Tuple of an assignment in the target, together with the relation required to grant it.
+#[derive(Debug, Clone)]
+struct ResolvedTuple {
+    object: String,
+    relation: String,
+    user: String,
+    grant_relation: String,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+/// Resolves names of the document to ids of the target project.
+struct Resolver<C: Catalog> {
+    project_id: ProjectIdent,
+    roles: HashMap<String, RoleId>,
+    warehouses: HashMap<String, Option<WarehouseIdent>>,
+    catalog_state: C::State,
+}
+
+impl<C: Catalog> Resolver<C> {
+    /// Returns `None` if the entity or principal does not exist in the target.
+    async fn resolve(&mut self, assignment: &ExportedAssignment) -> Result<Option<ResolvedTuple>> {
+        let grant_relation = match &assignment.entity {
+            EntityRef::Project => grant_relation::<ProjectAssignment>(&assignment.relation),
+            EntityRef::Role { .. } => grant_relation::<RoleAssignment>(&assignment.relation),
+            EntityRef::Warehouse { .. } => {
+                grant_relation::<WarehouseAssignment>(&assignment.relation)
+            }
+            EntityRef::Namespace { .. } => {
+                grant_relation::<NamespaceAssignment>(&assignment.relation)
+            }
+            EntityRef::Table { .. } => grant_relation::<TableAssignment>(&assignment.relation),
+            EntityRef::View { .. } => grant_relation::<ViewAssignment>(&assignment.relation),
+        }
+        .ok_or_else(|| {
+            ErrorModel::bad_request(
+                format!(
+                    "`{}` cannot be assigned on {:?}",
+                    assignment.relation, assignment.entity
+                ),
+                "InvalidAssignment",
+                None,
+            )
+        })?;
+
+        let user = match &assignment.principal {
+            PrincipalRef::User(user_id) => UserId::try_from(user_id.clone())?.to_openfga(),
+            PrincipalRef::Role(name) => match self.roles.get(name) {
+                Some(role_id) => (*role_id).into_assignees().to_openfga(),
+                None => return Ok(None),
+            },
+        };
+        let Some(object) = self.resolve_entity(&assignment.entity).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(ResolvedTuple {
+            object,
+            relation: assignment.relation.clone(),
+            user,
+            grant_relation,
+            expires_at: assignment.expires_at,
+        }))
+    }
+
+    /// Get the `OpenFGA` object of an entity, or `None` if it does not exist.
+    async fn resolve_entity(&mut self, entity: &EntityRef) -> Result<Option<String>> {
+        let warehouse_id = match entity {
+            EntityRef::Project => return Ok(Some(self.project_id.to_openfga())),
+            EntityRef::Role { role } => return Ok(self.roles.get(role).map(|r| r.to_openfga())),
+            EntityRef::Warehouse { warehouse }
+            | EntityRef::Namespace { warehouse, .. }
+            | EntityRef::Table { warehouse, .. }
+            | EntityRef::View { warehouse, .. } => {
+                let Some(warehouse_id) = self.resolve_warehouse(warehouse).await? else {
+                    return Ok(None);
+                };
+                warehouse_id
+            }
+        };
+
+        let mut t = C::Transaction::begin_read(self.catalog_state.clone()).await?;
+        let object = match entity {
+            EntityRef::Project | EntityRef::Role { .. } => unreachable!("handled above"),
+            EntityRef::Warehouse { .. } => Some(warehouse_id.to_openfga()),
+            EntityRef::Namespace { namespace, .. } => {
+                C::namespace_to_id(warehouse_id, &namespace_ident(namespace)?, t.transaction())
+                    .await?
+                    .map(|id| id.to_openfga())
+            }
+            EntityRef::Table {
+                namespace, name, ..
+            } => C::table_to_id(
+                warehouse_id,
+                &TableIdent::new(namespace_ident(namespace)?, name.clone()),
+                ListFlags::default(),
+                t.transaction(),
+            )
+            .await?
+            .map(|id| id.to_openfga()),
+            EntityRef::View {
+                namespace, name, ..
+            } => C::view_to_id(
+                warehouse_id,
+                &TableIdent::new(namespace_ident(namespace)?, name.clone()),
+                t.transaction(),
+            )
+            .await?
+            .map(|id| id.to_openfga()),
+        };
+        t.commit().await?;
+
+        Ok(object)
+    }
+
+    async fn resolve_warehouse(&mut self, name: &str) -> Result<Option<WarehouseIdent>> {
+        if let Some(warehouse_id) = self.warehouses.get(name) {
+            return Ok(*warehouse_id);
+        }
+        let warehouse_id =
+            C::get_warehouse_by_name(name, self.project_id, self.catalog_state.clone()).await?;
+        self.warehouses.insert(name.to_string(), warehouse_id);
+        Ok(warehouse_id)
+    }
+}
+
+/// Get the relation required to grant `relation` on entities assigned via `RA`.
+/// Returns `None` if `relation` is not assignable.
+fn grant_relation<RA: Assignment>(relation: &str) -> Option<String> {
+    RA::Relation::iter()
+        .find(|r| r.to_openfga().to_string() == relation)
+        .map(|r| r.grant_relation().to_string())
+}
+
+fn namespace_ident(parts: &[String]) -> Result<NamespaceIdent> {
+    NamespaceIdent::from_vec(parts.to_vec()).map_err(|e| {
+        ErrorModel::bad_request(
+            format!("Invalid namespace `{}`", parts.join(".")),
+            "InvalidNamespaceIdentifier",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+/// Map an `OpenFGA` user to a principal of the document.
+fn principal_ref(user: &str, role_names: &HashMap<RoleId, String>) -> Option<PrincipalRef> {
+    let (user_type, id) = user.split_once(':')?;
+    match user_type {
+        "user" => Some(PrincipalRef::User(id.to_string())),
+        "role" => {
+            let role_id = id.strip_suffix("#assignee")?.parse::<uuid::Uuid>().ok()?;
+            role_names
+                .get(&RoleId::new(role_id))
+                .cloned()
+                .map(PrincipalRef::Role)
+        }
+        _ => None,
+    }
+}
+
+/// Read the assignments of an object as `(relation, user)`.
+async fn read_assignments(
+    authorizer: &OpenFGAAuthorizer,
+    entity: &EntityRef,
+    object: &str,
+) -> Result<Vec<(String, String)>> {
+    match entity {
+        EntityRef::Project => read_typed_assignments::<ProjectAssignment>(authorizer, object).await,
+        EntityRef::Role { .. } => {
+            read_typed_assignments::<RoleAssignment>(authorizer, object).await
+        }
+        EntityRef::Warehouse { .. } => {
+            read_typed_assignments::<WarehouseAssignment>(authorizer, object).await
+        }
+        EntityRef::Namespace { .. } => {
+            read_typed_assignments::<NamespaceAssignment>(authorizer, object).await
+        }
+        EntityRef::Table { .. } => {
+            read_typed_assignments::<TableAssignment>(authorizer, object).await
+        }
+        EntityRef::View { .. } => {
+            read_typed_assignments::<ViewAssignment>(authorizer, object).await
+        }
+    }
+}
+
+async fn read_typed_assignments<RA: Assignment>(
+    authorizer: &OpenFGAAuthorizer,
+    object: &str,
+) -> Result<Vec<(String, String)>> {
+    Ok(get_relations::<RA>(authorizer.clone(), None, object)
+        .await?
+        .into_iter()
+        .map(|ra| (ra.relation().to_openfga().to_string(), ra.openfga_user()))
+        .collect())
+}
+
+/// Require that `actor` has all `(relation, object)` pairs.
+async fn require_all(
+    authorizer: &OpenFGAAuthorizer,
+    actor: &Actor,
+    required: BTreeSet<(String, String)>,
+) -> Result<()> {
+    let user = actor.to_openfga();
+    let required = required.into_iter().collect::<Vec<_>>();
+    let allowed = authorizer
+        .batch_check(
+            required
+                .iter()
+                .map(|(relation, object)| CheckRequestTupleKey {
+                    user: user.clone(),
+                    relation: relation.clone(),
+                    object: object.clone(),
+                })
+                .collect(),
+        )
+        .await?;
+
+    match required
+        .into_iter()
+        .zip(allowed)
+        .find(|(_, allowed)| !allowed)
+    {
+        Some(((relation, object), _)) => Err(OpenFGAError::Unauthorized {
+            user,
+            relation,
+            object,
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Apply the changes of an import. Expirations are recorded in the grant ledger.
+///
+/// Writes and updates are applied before deletes, so that an interrupted import never
+/// removes access without granting the access of the document. Changes are computed
+/// from the current assignments, so importing the document again completes the import.
+async fn apply_changes(
+    authorizer: &OpenFGAAuthorizer,
+    writes: Vec<ResolvedTuple>,
+    updates: Vec<ResolvedTuple>,
+    deletes: Vec<ResolvedTuple>,
+) -> Result<()> {
+    let chunk_size = usize::try_from(MAX_TUPLES_PER_WRITE).unwrap_or(100);
+    for (expires_at, writes) in group_by_expiration(writes) {
+        for writes in writes.chunks(chunk_size) {
+            authorizer
+                .write_with_expiration(tuple_keys(writes), vec![], expires_at)
+                .await?;
+        }
+    }
+    for (expires_at, updates) in group_by_expiration(updates) {
+        for updates in updates.chunks(chunk_size) {
+            authorizer
+                .set_grant_expirations(tuple_keys(updates), expires_at)
+                .await?;
+        }
+    }
+    for deletes in deletes.chunks(chunk_size) {
+        let deletes = deletes
+            .iter()
+            .map(|t| TupleKeyWithoutCondition {
+                user: t.user.clone(),
+                relation: t.relation.clone(),
+                object: t.object.clone(),
+            })
+            .collect();
+        authorizer
+            .write_with_expiration(vec![], deletes, None)
+            .await?;
+    }
+    Ok(())
+}
+
+fn group_by_expiration(
+    tuples: Vec<ResolvedTuple>,
+) -> BTreeMap<Option<DateTime<Utc>>, Vec<ResolvedTuple>> {
+    let mut groups = BTreeMap::<_, Vec<_>>::new();
+    for tuple in tuples {
+        groups.entry(tuple.expires_at).or_default().push(tuple);
+    }
+    groups
+}
+
+fn tuple_keys(tuples: &[ResolvedTuple]) -> Vec<TupleKey> {
+    tuples
+        .iter()
+        .map(|t| TupleKey {
+            user: t.user.clone(),
+            relation: t.relation.clone(),
+            object: t.object.clone(),
+            condition: None,
+        })
+        .collect()
+}
+
+/// List all roles of a project as `(name, id)`.
+async fn list_roles<C: Catalog>(
+    project_id: ProjectIdent,
+    catalog_state: C::State,
+) -> Result<Vec<(String, RoleId)>> {
+    let mut roles = vec![];
+    let mut page_token = PageToken::NotSpecified;
+    loop {
+        let page = C::list_roles(
+            Some(project_id),
+            None,
+            None,
+            PaginationQuery {
+                page_token,
+                page_size: Some(LIST_PAGE_SIZE),
+            },
+            catalog_state.clone(),
+        )
+        .await?;
+        roles.extend(page.roles.into_iter().map(|r| (r.name, r.id)));
+        match page.next_page_token {
+            Some(token) if !token.is_empty() => page_token = PageToken::Present(token),
+            _ => break,
+        }
+    }
+    Ok(roles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::postgres::namespace::tests::initialize_namespace;
+    use crate::implementations::postgres::tabular::table::tests::initialize_table;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+    use crate::implementations::postgres::{CatalogState, PostgresCatalog};
+    use crate::service::authz::implementations::openfga::new_postgres_authorizer;
+
+    #[test]
+    fn test_principal_ref() {
+        let role_id = RoleId::new(uuid::Uuid::now_v7());
+        let role_names = HashMap::from([(role_id, "engineers".to_string())]);
+
+        assert_eq!(
+            principal_ref("user:oidc~alice", &role_names),
+            Some(PrincipalRef::User("oidc~alice".to_string()))
+        );
+        assert_eq!(
+            principal_ref(&format!("role:{role_id}#assignee"), &role_names),
+            Some(PrincipalRef::Role("engineers".to_string()))
+        );
+        assert_eq!(
+            principal_ref(
+                &format!("role:{}#assignee", uuid::Uuid::now_v7()),
+                &role_names
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_grant_relation() {
+        assert_eq!(
+            grant_relation::<TableAssignment>("select"),
+            Some("can_grant_select".to_string())
+        );
+        assert_eq!(grant_relation::<TableAssignment>("project_admin"), None);
+    }
+
+    #[test]
+    fn test_document_roundtrip() {
+        let document = AuthzDocument {
+            version: AUTHZ_DOCUMENT_VERSION,
+            warehouse: Some("staging".to_string()),
+            assignments: vec![ExportedAssignment {
+                entity: EntityRef::Table {
+                    warehouse: "staging".to_string(),
+                    namespace: vec!["sales".to_string()],
+                    name: "orders".to_string(),
+                },
+                relation: "select".to_string(),
+                principal: PrincipalRef::Role("analysts".to_string()),
+                expires_at: Some("2025-01-31T18:00:00Z".parse().unwrap()),
+            }],
+            managed_access: vec![],
+        };
+        let json = serde_json::to_value(&document).unwrap();
+        assert_eq!(json["assignments"][0]["entity"]["type"], "table");
+        assert_eq!(json["assignments"][0]["principal"]["role"], "analysts");
+        assert_eq!(json["assignments"][0]["expires-at"], "2025-01-31T18:00:00Z");
+        assert_eq!(
+            serde_json::from_value::<AuthzDocument>(json).unwrap(),
+            document
+        );
+        assert_eq!(
+            document.assignments[0]
+                .entity
+                .clone()
+                .with_warehouse("production"),
+            EntityRef::Table {
+                warehouse: "production".to_string(),
+                namespace: vec!["sales".to_string()],
+                name: "orders".to_string(),
+            }
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_export_requires_read_assignments_on_every_entity(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let authorizer = new_postgres_authorizer(pool).unwrap();
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = NamespaceIdent::from_vec(vec!["sales".to_string()]).unwrap();
+        let (namespace_id, _) =
+            initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        let table =
+            initialize_table(warehouse_id, state.clone(), false, Some(namespace), None).await;
+
+        let tuple = |user: &str, relation: &str, object: String| TupleKey {
+            user: user.to_string(),
+            relation: relation.to_string(),
+            object,
+            condition: None,
+        };
+        authorizer
+            .write(
+                Some(vec![
+                    tuple(
+                        &warehouse_id.to_openfga(),
+                        "parent",
+                        namespace_id.to_openfga(),
+                    ),
+                    tuple(
+                        &namespace_id.to_openfga(),
+                        "parent",
+                        table.table_id.to_openfga(),
+                    ),
+                    tuple("user:oidc~owner", "ownership", warehouse_id.to_openfga()),
+                    // Can grant `select` on the warehouse, but `pass_grants` is not inherited
+                    tuple("user:oidc~alice", "select", warehouse_id.to_openfga()),
+                    tuple("user:oidc~alice", "pass_grants", warehouse_id.to_openfga()),
+                ]),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let scope = AuthzScope::Warehouse(warehouse_id);
+        let owner = Actor::Principal(UserId::oidc("owner").unwrap());
+        let alice = Actor::Principal(UserId::oidc("alice").unwrap());
+
+        let document =
+            export_assignments::<PostgresCatalog>(&authorizer, scope, Some(&owner), state.clone())
+                .await
+                .unwrap();
+        assert_eq!(document.assignments.len(), 3);
+        assert_eq!(
+            export_assignments::<PostgresCatalog>(&authorizer, scope, None, state.clone())
+                .await
+                .unwrap(),
+            document
+        );
+
+        let err =
+            export_assignments::<PostgresCatalog>(&authorizer, scope, Some(&alice), state.clone())
+                .await
+                .unwrap_err();
+        assert_eq!(err.error.r#type, "Unauthorized");
+        assert!(err.error.message.contains(&namespace_id.to_openfga()));
+    }
+}
@@ -4,8 +4,9 @@ use crate::{
         authn::Actor,
         authz::{
             Authorizer, CatalogNamespaceAction, CatalogProjectAction, CatalogServerAction,
-            CatalogTableAction, CatalogViewAction, CatalogWarehouseAction, ErrorModel,
-            ListProjectsResponse, Result,
+            CatalogTableAction, CatalogViewAction, CatalogWarehouseAction, ColumnPolicy,
+            ErrorModel, ListProjectsResponse, PolicyPrincipal, RefProtectionRule, Result,
+            RowFilter,
         },
         NamespaceIdentUuid, TableIdentUuid,
     },
@@ -15,32 +16,41 @@ use async_stream::__private::AsyncStream;
 use async_stream::stream;
 use async_trait::async_trait;
 use axum::Router;
-use futures::{pin_mut, StreamExt};
+use futures::{pin_mut, StreamExt, TryStreamExt};
 use openfga_rs::open_fga_service_client::OpenFgaServiceClient;
 use openfga_rs::tonic::{Response, Status};
 use openfga_rs::{
     tonic::{self},
-    CheckRequest, CheckRequestTupleKey, CheckResponse, ConsistencyPreference, ListObjectsRequest,
-    ListObjectsResponse, ReadRequest, ReadRequestTupleKey, ReadResponse, Tuple, TupleKey,
-    TupleKeyWithoutCondition, WriteRequest, WriteRequestDeletes, WriteRequestWrites, WriteResponse,
+    userset_tree, CheckRequest, CheckRequestTupleKey, CheckResponse, ConsistencyPreference,
+    ExpandRequest, ExpandRequestTupleKey, ExpandResponse, ListObjectsRequest, ListObjectsResponse,
+    ReadRequest, ReadRequestTupleKey, ReadResponse, Tuple, TupleKey, TupleKeyWithoutCondition,
+    WriteRequest, WriteRequestDeletes, WriteRequestWrites, WriteResponse,
 };
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
-use std::{collections::HashSet, fmt};
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt,
+};
 
 pub(super) mod api;
 mod client;
 mod entities;
 mod error;
+mod expiration;
+mod explain;
+mod export;
 mod health;
 mod migration;
 mod models;
+mod postgres;
 mod relations;
 
 mod service_ext;
 
 use crate::api::ApiContext;
 use crate::service::authn::UserId;
+use crate::service::authz::implementations::decision_cache::DecisionCache;
 use crate::service::authz::implementations::openfga::client::ClientConnection;
 use crate::service::authz::implementations::openfga::relations::OpenFgaRelation;
 use crate::service::authz::implementations::FgaType;
@@ -54,18 +64,28 @@ pub use client::{
 };
 use entities::{OpenFgaEntity, ParseOpenFgaEntity as _};
 pub use error::{OpenFGAError, OpenFGAResult};
+pub use export::{
+    export_assignments, import_assignments, AuthzDocument, AuthzScope, EntityRef,
+    ExportedAssignment, ImportOptions, ImportReport, PrincipalRef, AUTHZ_DOCUMENT_VERSION,
+};
 use iceberg_ext::catalog::rest::IcebergErrorResponse;
 pub(crate) use migration::migrate;
 pub(crate) use models::{ModelVersion, OpenFgaType, RoleAssignee};
+pub use postgres::{new_postgres_authorizer, PostgresTupleStore};
 use relations::{
-    NamespaceRelation, ProjectRelation, RoleRelation, ServerRelation, TableRelation, ViewRelation,
-    WarehouseRelation,
+    NamespaceRelation, ProjectRelation, ReducedRelation as _, RoleRelation, ServerRelation,
+    TableRelation, ViewRelation, WarehouseRelation,
 };
 pub(crate) use service_ext::ClientHelper;
 use service_ext::MAX_TUPLES_PER_WRITE;
 use tokio::sync::RwLock;
 use utoipa::OpenApi;
 
+/// Maximum number of concurrent `OpenFGA` checks of a single batch
+const MAX_CONCURRENT_CHECKS: usize = 50;
+/// Minimum number of checks in a batch to query `ListObjects` first
+const MIN_CHECKS_FOR_LIST_OBJECTS: usize = 20;
+
 lazy_static::lazy_static! {
     static ref AUTH_CONFIG: crate::config::OpenFGAConfig = {
         CONFIG.openfga.clone().expect("OpenFGAConfig not found")
@@ -80,6 +100,10 @@ pub struct OpenFGAAuthorizer {
     pub(crate) client: Arc<dyn Client + Send + Sync + 'static>,
     pub(crate) store_id: String,
     pub(crate) authorization_model_id: String,
+    /// Cached decisions, cleared on every write
+    pub(crate) cache: Option<Arc<DecisionCache>>,
+    /// Expirations of time-bound grants. Without a ledger, grants cannot expire.
+    pub(crate) grant_ledger: Option<expiration::GrantLedger>,
     pub(crate) health: Arc<RwLock<Vec<Health>>>,
 }
 
@@ -88,6 +112,8 @@ impl Debug for OpenFGAAuthorizer {
         f.debug_struct("OpenFGAAuthorizer")
             .field("store_id", &self.store_id)
             .field("authorization_model_id", &self.authorization_model_id)
+            .field("cache", &self.cache.is_some())
+            .field("grant_ledger", &self.grant_ledger.is_some())
             .field("health", &self.health)
             .field("client", &"...")
             .finish()
@@ -288,7 +314,7 @@ impl Authorizer for OpenFGAAuthorizer {
         let check_actor_fut = self.check_actor(actor);
         let check_fut = self.check(CheckRequestTupleKey {
             user: actor.to_openfga(),
-            relation: action.to_string(),
+            relation: action.to_openfga().to_string(),
             object: format!("warehouse:{warehouse_id}"),
         });
 
@@ -309,7 +335,7 @@ impl Authorizer for OpenFGAAuthorizer {
         let check_actor_fut = self.check_actor(actor);
         let check_fut = self.check(CheckRequestTupleKey {
             user: actor.to_openfga(),
-            relation: action.to_string(),
+            relation: action.to_openfga().to_string(),
             object: format!("namespace:{namespace_id}"),
         });
 
@@ -330,7 +356,7 @@ impl Authorizer for OpenFGAAuthorizer {
         let check_actor_fut = self.check_actor(actor);
         let check_fut = self.check(CheckRequestTupleKey {
             user: actor.to_openfga(),
-            relation: action.to_string(),
+            relation: action.to_openfga().to_string(),
             object: format!("table:{table_id}"),
         });
 
@@ -360,6 +386,132 @@ impl Authorizer for OpenFGAAuthorizer {
         check.map_err(Into::into)
     }
 
+    async fn are_allowed_namespace_actions(
+        &self,
+        metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+        namespace_ids: &[NamespaceIdentUuid],
+        action: &CatalogNamespaceAction,
+    ) -> Result<Vec<bool>> {
+        self.are_allowed_actions(
+            metadata,
+            action.to_openfga(),
+            namespace_ids.iter().map(OpenFgaEntity::to_openfga),
+        )
+        .await
+    }
+
+    async fn are_allowed_table_actions(
+        &self,
+        metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+        table_ids: &[TableIdentUuid],
+        action: &CatalogTableAction,
+    ) -> Result<Vec<bool>> {
+        self.are_allowed_actions(
+            metadata,
+            action.to_openfga(),
+            table_ids.iter().map(OpenFgaEntity::to_openfga),
+        )
+        .await
+    }
+
+    async fn are_allowed_view_actions(
+        &self,
+        metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+        view_ids: &[ViewIdentUuid],
+        action: &CatalogViewAction,
+    ) -> Result<Vec<bool>> {
+        self.are_allowed_actions(
+            metadata,
+            action,
+            view_ids.iter().map(OpenFgaEntity::to_openfga),
+        )
+        .await
+    }
+
+    async fn restricting_column_policies(
+        &self,
+        metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+        _table_id: TableIdentUuid,
+        policies: Vec<ColumnPolicy>,
+    ) -> Result<Vec<ColumnPolicy>> {
+        let actor = metadata.actor();
+        let exempt_roles = policies
+            .iter()
+            .flat_map(|policy| policy.exempt.iter())
+            .filter_map(|principal| match principal {
+                PolicyPrincipal::Role(role_id) => Some(*role_id),
+                PolicyPrincipal::User(_) => None,
+            });
+        let assigned_roles = self.assigned_roles(actor, exempt_roles).await?;
+
+        Ok(policies
+            .into_iter()
+            .filter(|policy| {
+                !policy.exempts_actor_directly(actor)
+                    && !policy.exempt.iter().any(|principal| match principal {
+                        PolicyPrincipal::Role(role_id) => assigned_roles.contains(role_id),
+                        PolicyPrincipal::User(_) => false,
+                    })
+            })
+            .collect())
+    }
+
+    async fn applicable_row_filters(
+        &self,
+        metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+        _table_id: TableIdentUuid,
+        filters: Vec<RowFilter>,
+    ) -> Result<Vec<RowFilter>> {
+        let assigned_roles = self
+            .assigned_roles(
+                metadata.actor(),
+                filters.iter().map(|filter| filter.role_id),
+            )
+            .await?;
+
+        Ok(filters
+            .into_iter()
+            .filter(|filter| {
+                filter.applies_to_actor_directly(metadata.actor())
+                    || assigned_roles.contains(&filter.role_id)
+            })
+            .collect())
+    }
+
+    async fn restricting_ref_protection_rules(
+        &self,
+        metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+        _table_id: TableIdentUuid,
+        rules: Vec<RefProtectionRule>,
+    ) -> Result<Vec<RefProtectionRule>> {
+        let actor = metadata.actor();
+        let assigned_roles = self
+            .assigned_roles(
+                actor,
+                rules
+                    .iter()
+                    .flat_map(|rule| rule.allowed_committers.iter().copied()),
+            )
+            .await?;
+
+        Ok(rules
+            .into_iter()
+            .filter(|rule| {
+                !rule.allows_actor_directly(actor)
+                    && !rule
+                        .allowed_committers
+                        .iter()
+                        .any(|role_id| assigned_roles.contains(role_id))
+            })
+            .collect())
+    }
+
     async fn delete_user(&self, _metadata: &RequestMetadata, user_id: UserId) -> Result<()> {
         self.delete_all_relations(&user_id).await
     }
@@ -505,16 +657,7 @@ impl Authorizer for OpenFGAAuthorizer {
         self.require_no_relations(&namespace_id, ConsistencyPreference::MinimizeLatency)
             .await?;
 
-        let (parent_id, parent_child_relation) = match parent {
-            NamespaceParent::Warehouse(warehouse_id) => (
-                warehouse_id.to_openfga(),
-                WarehouseRelation::Namespace.to_string(),
-            ),
-            NamespaceParent::Namespace(parent_namespace_id) => (
-                parent_namespace_id.to_openfga(),
-                NamespaceRelation::Child.to_string(),
-            ),
-        };
+        let (parent_id, parent_child_relation) = namespace_parent_to_openfga(parent);
         let this_id = namespace_id.to_openfga();
 
         self.write(
@@ -552,6 +695,118 @@ impl Authorizer for OpenFGAAuthorizer {
         self.delete_all_relations(&namespace_id).await
     }
 
+    async fn move_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        namespace_id: NamespaceIdentUuid,
+        old_parent: NamespaceParent,
+        new_parent: NamespaceParent,
+    ) -> Result<()> {
+        let (old_parent_id, old_parent_child_relation) = namespace_parent_to_openfga(old_parent);
+        let (new_parent_id, new_parent_child_relation) = namespace_parent_to_openfga(new_parent);
+        let this_id = namespace_id.to_openfga();
+
+        self.write(
+            Some(vec![
+                TupleKey {
+                    user: new_parent_id.clone(),
+                    relation: NamespaceRelation::Parent.to_string(),
+                    object: this_id.clone(),
+                    condition: None,
+                },
+                TupleKey {
+                    user: this_id.clone(),
+                    relation: new_parent_child_relation,
+                    object: new_parent_id,
+                    condition: None,
+                },
+            ]),
+            Some(vec![
+                TupleKeyWithoutCondition {
+                    user: old_parent_id.clone(),
+                    relation: NamespaceRelation::Parent.to_string(),
+                    object: this_id.clone(),
+                },
+                TupleKeyWithoutCondition {
+                    user: this_id,
+                    relation: old_parent_child_relation,
+                    object: old_parent_id,
+                },
+            ]),
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn undrop_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        namespace_id: NamespaceIdentUuid,
+        parent: NamespaceParent,
+    ) -> Result<()> {
+        let (parent_id, parent_child_relation) = namespace_parent_to_openfga(parent);
+        let this_id = namespace_id.to_openfga();
+
+        let current_parents = self
+            .read_all(ReadRequestTupleKey {
+                user: String::new(),
+                relation: NamespaceRelation::Parent.to_string(),
+                object: this_id.clone(),
+            })
+            .await?
+            .into_iter()
+            .filter_map(|t| t.key)
+            .map(|t| t.user)
+            .collect::<Vec<_>>();
+        if current_parents == [parent_id.clone()] {
+            return Ok(());
+        }
+
+        let deletes = current_parents
+            .into_iter()
+            .flat_map(|old_parent_id| {
+                let old_parent_child_relation =
+                    if old_parent_id.starts_with(&format!("{}:", FgaType::Warehouse)) {
+                        WarehouseRelation::Namespace.to_string()
+                    } else {
+                        NamespaceRelation::Child.to_string()
+                    };
+                [
+                    TupleKeyWithoutCondition {
+                        user: old_parent_id.clone(),
+                        relation: NamespaceRelation::Parent.to_string(),
+                        object: this_id.clone(),
+                    },
+                    TupleKeyWithoutCondition {
+                        user: this_id.clone(),
+                        relation: old_parent_child_relation,
+                        object: old_parent_id,
+                    },
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        self.write(
+            Some(vec![
+                TupleKey {
+                    user: parent_id.clone(),
+                    relation: NamespaceRelation::Parent.to_string(),
+                    object: this_id.clone(),
+                    condition: None,
+                },
+                TupleKey {
+                    user: this_id,
+                    relation: parent_child_relation,
+                    object: parent_id,
+                    condition: None,
+                },
+            ]),
+            (!deletes.is_empty()).then_some(deletes),
+        )
+        .await
+        .map_err(Into::into)
+    }
+
     async fn create_table(
         &self,
         metadata: &RequestMetadata,
@@ -644,6 +899,63 @@ impl Authorizer for OpenFGAAuthorizer {
 }
 
 impl OpenFGAAuthorizer {
+    /// Roles out of `roles` the actor is an assignee of, directly or through other roles.
+    async fn assigned_roles(
+        &self,
+        actor: &Actor,
+        roles: impl Iterator<Item = RoleId>,
+    ) -> Result<HashSet<RoleId>> {
+        let roles = roles.collect::<BTreeSet<_>>();
+        if roles.is_empty() {
+            return Ok(HashSet::new());
+        }
+        self.check_actor(actor).await?;
+
+        let user = actor.to_openfga();
+        let assigned = self
+            .batch_check(
+                roles
+                    .iter()
+                    .map(|role_id| CheckRequestTupleKey {
+                        user: user.clone(),
+                        relation: RoleRelation::Assignee.to_string(),
+                        object: role_id.to_openfga(),
+                    })
+                    .collect(),
+            )
+            .await?;
+        Ok(roles
+            .into_iter()
+            .zip(assigned)
+            .filter_map(|(role_id, assigned)| assigned.then_some(role_id))
+            .collect())
+    }
+
+    /// Check the same action for multiple objects.
+    /// Returns one boolean per object, in the order of `objects`.
+    async fn are_allowed_actions(
+        &self,
+        metadata: &RequestMetadata,
+        action: impl ToString,
+        objects: impl Iterator<Item = String>,
+    ) -> Result<Vec<bool>> {
+        let actor = metadata.actor();
+        let user = actor.to_openfga();
+        let relation = action.to_string();
+        let tuple_keys = objects
+            .map(|object| CheckRequestTupleKey {
+                user: user.clone(),
+                relation: relation.clone(),
+                object,
+            })
+            .collect();
+
+        let (check_actor, checks) =
+            futures::join!(self.check_actor(actor), self.batch_check(tuple_keys));
+        check_actor?;
+        checks.map_err(Into::into)
+    }
+
     async fn list_projects_internal(&self, actor: &Actor) -> Result<ListProjectsResponse> {
         let check_actor_fut = self.check_actor(actor);
         let list_all_fut = self.check(CheckRequestTupleKey {
@@ -714,8 +1026,13 @@ impl OpenFGAAuthorizer {
             .map_err(|e| OpenFGAError::WriteFailed {
                 write_request,
                 source: e,
-            })
-            .map(|_| ())
+            })?;
+
+        // Permissions are inherited, so any write may change any decision.
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+        Ok(())
     }
 
     /// A convenience wrapper around read that handles error conversion
@@ -748,8 +1065,41 @@ impl OpenFGAAuthorizer {
         self.client.read_all_pages(&self.store_id, tuple_key).await
     }
 
-    /// A convenience wrapper around check
+    /// A convenience wrapper around expand.
+    /// Returns the root node of the userset tree of `relation` on `object`.
+    async fn expand(
+        &self,
+        object: &str,
+        relation: &str,
+    ) -> OpenFGAResult<Option<userset_tree::Node>> {
+        let expand_request = ExpandRequest {
+            store_id: self.store_id.clone(),
+            authorization_model_id: self.authorization_model_id.clone(),
+            tuple_key: Some(ExpandRequestTupleKey {
+                relation: relation.to_string(),
+                object: object.to_string(),
+            }),
+            consistency: ConsistencyPreference::MinimizeLatency.into(),
+            ..Default::default()
+        };
+
+        self.client
+            .expand(expand_request.clone())
+            .await
+            .map_err(|source| OpenFGAError::ExpandFailed {
+                expand_request: Box::new(expand_request),
+                source,
+            })
+            .map(|response| response.into_inner().tree.and_then(|tree| tree.root))
+    }
+
+    /// A convenience wrapper around check. Decisions are cached.
     async fn check(&self, tuple_key: CheckRequestTupleKey) -> OpenFGAResult<bool> {
+        let cache_key = decision_cache_key(&tuple_key);
+        if let Some(allowed) = self.cache.as_ref().and_then(|c| c.get(&cache_key)) {
+            return Ok(allowed);
+        }
+
         let check_request = CheckRequest {
             tuple_key: Some(tuple_key),
             store_id: self.store_id.clone(),
@@ -768,6 +1118,48 @@ impl OpenFGAAuthorizer {
                 source,
             })
             .map(|response| response.get_ref().allowed)
+            .inspect(|allowed| {
+                if let Some(cache) = &self.cache {
+                    cache.put(cache_key, *allowed);
+                }
+            })
+    }
+
+    /// Check multiple tuples at once. Decisions are cached.
+    /// Returns one boolean per tuple, in the order of `tuple_keys`.
+    async fn batch_check(&self, tuple_keys: Vec<CheckRequestTupleKey>) -> OpenFGAResult<Vec<bool>> {
+        let cache_keys = tuple_keys
+            .iter()
+            .map(decision_cache_key)
+            .collect::<Vec<_>>();
+        let mut decisions = cache_keys
+            .iter()
+            .map(|key| self.cache.as_ref().and_then(|c| c.get(key)))
+            .collect::<Vec<_>>();
+
+        let (missing_idx, missing): (Vec<usize>, Vec<CheckRequestTupleKey>) = tuple_keys
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| decisions[*i].is_none())
+            .unzip();
+
+        if !missing.is_empty() {
+            let num_checks = missing.len();
+            let fetched = self
+                .client
+                .batch_check(&self.store_id, &self.authorization_model_id, missing)
+                .await
+                .map_err(|source| OpenFGAError::BatchCheckFailed { num_checks, source })?;
+
+            for (i, allowed) in missing_idx.into_iter().zip(fetched) {
+                if let Some(cache) = &self.cache {
+                    cache.put(cache_keys[i].clone(), allowed);
+                }
+                decisions[i] = Some(allowed);
+            }
+        }
+
+        Ok(decisions.into_iter().map(|d| d.unwrap_or(false)).collect())
     }
 
     async fn require_action(
@@ -1107,6 +1499,73 @@ pub trait Client {
         &self,
         request: CheckRequest,
     ) -> std::result::Result<Response<CheckResponse>, tonic::Status>;
+
+    /// Expand the users of a relation one level, see `OpenFGA`'s `Expand` API.
+    async fn expand(
+        &self,
+        request: ExpandRequest,
+    ) -> std::result::Result<Response<ExpandResponse>, tonic::Status>;
+
+    /// Check multiple tuples against the given store and model.
+    /// Returns one boolean per tuple, in the order of `tuple_keys`.
+    async fn batch_check(
+        &self,
+        store_id: &str,
+        authorization_model_id: &str,
+        tuple_keys: Vec<CheckRequestTupleKey>,
+    ) -> std::result::Result<Vec<bool>, tonic::Status>;
+}
+
+fn decision_cache_key(tuple_key: &CheckRequestTupleKey) -> String {
+    format!(
+        "{}#{}@{}",
+        tuple_key.object, tuple_key.relation, tuple_key.user
+    )
+}
+
+/// Build a `ListObjects` request that covers all checks, if they are numerous
+/// enough and only differ in the object id.
+fn list_objects_for_checks(
+    store_id: &str,
+    authorization_model_id: &str,
+    tuple_keys: &[CheckRequestTupleKey],
+) -> Option<ListObjectsRequest> {
+    if tuple_keys.len() < MIN_CHECKS_FOR_LIST_OBJECTS {
+        return None;
+    }
+    let first = tuple_keys.first()?;
+    let (object_type, _) = first.object.split_once(':')?;
+    let all_similar = tuple_keys.iter().all(|k| {
+        k.user == first.user
+            && k.relation == first.relation
+            && k.object.split_once(':').map(|(t, _)| t) == Some(object_type)
+    });
+
+    all_similar.then(|| ListObjectsRequest {
+        store_id: store_id.to_string(),
+        authorization_model_id: authorization_model_id.to_string(),
+        r#type: object_type.to_string(),
+        relation: first.relation.clone(),
+        user: first.user.clone(),
+        contextual_tuples: None,
+        context: None,
+        consistency: ConsistencyPreference::MinimizeLatency.into(),
+    })
+}
+
+/// Returns the parent object and the relation that links the parent to a
+/// child namespace.
+fn namespace_parent_to_openfga(parent: NamespaceParent) -> (String, String) {
+    match parent {
+        NamespaceParent::Warehouse(warehouse_id) => (
+            warehouse_id.to_openfga(),
+            WarehouseRelation::Namespace.to_string(),
+        ),
+        NamespaceParent::Namespace(parent_namespace_id) => (
+            parent_namespace_id.to_openfga(),
+            NamespaceRelation::Child.to_string(),
+        ),
+    }
 }
 
 fn suffixes_for_user(user: &FgaType) -> Vec<String> {
@@ -1151,6 +1610,58 @@ impl Client for OpenFgaServiceClient<ClientConnection> {
     ) -> std::result::Result<Response<CheckResponse>, Status> {
         Self::check(&mut self.clone(), request).await
     }
+
+    async fn expand(
+        &self,
+        request: ExpandRequest,
+    ) -> std::result::Result<Response<ExpandResponse>, Status> {
+        Self::expand(&mut self.clone(), request).await
+    }
+
+    /// `OpenFGA` truncates `ListObjects` results after a deadline or a maximum number of results.
+    /// Listed objects are thus only used to allow checks, all others are checked individually.
+    async fn batch_check(
+        &self,
+        store_id: &str,
+        authorization_model_id: &str,
+        tuple_keys: Vec<CheckRequestTupleKey>,
+    ) -> std::result::Result<Vec<bool>, Status> {
+        let listed = match list_objects_for_checks(store_id, authorization_model_id, &tuple_keys) {
+            Some(request) => Self::list_objects(&mut self.clone(), request)
+                .await?
+                .into_inner()
+                .objects
+                .into_iter()
+                .collect::<HashSet<_>>(),
+            None => HashSet::new(),
+        };
+
+        futures::stream::iter(tuple_keys)
+            .map(|tuple_key| {
+                let mut client = self.clone();
+                let allowed_by_list = listed.contains(&tuple_key.object);
+                async move {
+                    if allowed_by_list {
+                        return Ok(true);
+                    }
+                    let request = CheckRequest {
+                        tuple_key: Some(tuple_key),
+                        store_id: store_id.to_string(),
+                        authorization_model_id: authorization_model_id.to_string(),
+                        contextual_tuples: None,
+                        trace: false,
+                        context: None,
+                        consistency: ConsistencyPreference::MinimizeLatency.into(),
+                    };
+                    Self::check(&mut client, request)
+                        .await
+                        .map(|response| response.into_inner().allowed)
+                }
+            })
+            .buffered(MAX_CONCURRENT_CHECKS)
+            .try_collect()
+            .await
+    }
 }
 #[cfg(test)]
 #[allow(dead_code)]
@@ -1193,6 +1704,15 @@ pub(crate) mod tests {
                     resolution: String::new(),
                 }))
             });
+            let hidden_clone = hidden.clone();
+            mock.expect_batch_check()
+                .returning(move |_, _, tuple_keys| {
+                    let hidden = hidden_clone.read().unwrap();
+                    Ok(tuple_keys
+                        .iter()
+                        .map(|k| !hidden.contains(&k.object))
+                        .collect())
+                });
             mock.expect_read().returning(|_| {
                 Ok(openfga_rs::tonic::Response::new(ReadResponse {
                     tuples: vec![],
@@ -1219,6 +1739,8 @@ pub(crate) mod tests {
                 client: self.mock.clone(),
                 store_id: "test_store".to_string(),
                 authorization_model_id: "test_model".to_string(),
+                cache: None,
+                grant_ledger: None,
                 health: Arc::default(),
             }
         }
@@ -1282,6 +1804,80 @@ pub(crate) mod tests {
             );
         }
 
+        #[tokio::test]
+        async fn test_move_namespace() {
+            let authorizer = new_authorizer_in_empty_store().await;
+            let warehouse_id = WarehouseIdent::from(uuid::Uuid::now_v7());
+            let parent_id = NamespaceIdentUuid::from(uuid::Uuid::now_v7());
+            let namespace_id = NamespaceIdentUuid::from(uuid::Uuid::now_v7());
+
+            authorizer
+                .write(
+                    Some(vec![
+                        TupleKey {
+                            user: warehouse_id.to_openfga(),
+                            relation: NamespaceRelation::Parent.to_string(),
+                            object: namespace_id.to_openfga(),
+                            condition: None,
+                        },
+                        TupleKey {
+                            user: namespace_id.to_openfga(),
+                            relation: WarehouseRelation::Namespace.to_string(),
+                            object: warehouse_id.to_openfga(),
+                            condition: None,
+                        },
+                    ]),
+                    None,
+                )
+                .await
+                .unwrap();
+
+            authorizer
+                .move_namespace(
+                    &RequestMetadata::new_unauthenticated(),
+                    namespace_id,
+                    NamespaceParent::Warehouse(warehouse_id),
+                    NamespaceParent::Namespace(parent_id),
+                )
+                .await
+                .unwrap();
+
+            let read_users = |relation: String, object: String| {
+                let authorizer = authorizer.clone();
+                async move {
+                    authorizer
+                        .read_all(ReadRequestTupleKey {
+                            user: String::new(),
+                            relation,
+                            object,
+                        })
+                        .await
+                        .unwrap()
+                        .into_iter()
+                        .filter_map(|t| t.key.map(|k| k.user))
+                        .collect::<Vec<_>>()
+                }
+            };
+            assert_eq!(
+                read_users(
+                    NamespaceRelation::Parent.to_string(),
+                    namespace_id.to_openfga()
+                )
+                .await,
+                vec![parent_id.to_openfga()]
+            );
+            assert_eq!(
+                read_users(NamespaceRelation::Child.to_string(), parent_id.to_openfga()).await,
+                vec![namespace_id.to_openfga()]
+            );
+            assert!(read_users(
+                WarehouseRelation::Namespace.to_string(),
+                warehouse_id.to_openfga()
+            )
+            .await
+            .is_empty());
+        }
+
         #[tokio::test]
         async fn test_require_no_relations_own_relations() {
             let authorizer = new_authorizer_in_empty_store().await;
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::service::{authz::implementations::FgaType, RoleId};
-use openfga_rs::{Condition, TypeDefinition};
+use openfga_rs::{Condition, TypeDefinition, Userset};
 use std::sync::LazyLock;
 
 const V1_MODEL: &str = include_str!("../../../../../../../authz/openfga/v1/schema.json");
@@ -14,6 +14,17 @@ static MODEL: LazyLock<CollaborationModels> = LazyLock::new(|| CollaborationMode
 
 const ACTIVE_MODEL: ModelVersion = ModelVersion::V2;
 
+/// Relations of the active model by type and relation name
+pub(super) static ACTIVE_MODEL_RELATIONS: LazyLock<HashMap<String, HashMap<String, Userset>>> =
+    LazyLock::new(|| {
+        ModelVersion::active()
+            .get_model()
+            .type_definitions
+            .into_iter()
+            .map(|t| (t.r#type, t.relations))
+            .collect()
+    });
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(transparent)]
 pub(crate) struct RoleAssignee(RoleId);
@@ -0,0 +1,1072 @@
+//! Built-in tuple store that evaluates the Lakekeeper authorization model in Postgres.
+//!
+//! [`PostgresTupleStore`] implements the same [`Client`] interface as the `OpenFGA` gRPC client.
+//! Tuples are stored in the `authz_tuple` table. All tuples a check can depend on are
+//! loaded with a single recursive query, the check is then resolved in memory by walking
+//! the active authorization model. This allows to use the full permission model,
+//! including the management API, without running `OpenFGA`.
+
+use super::expiration::GrantLedger;
+use super::models::ACTIVE_MODEL_RELATIONS;
+use super::{Client, ModelVersion, OpenFGAAuthorizer, OpenFGAResult};
+use crate::implementations::postgres::authz::{
+    get_object_relation_users, get_reachable_relation_graph, get_relation_graph, read_tuples,
+    write_tuples, AuthzTuple, AuthzTupleFilter, RelationEdges, RelationGraphRow,
+};
+use crate::service::authz::implementations::decision_cache::DecisionCache;
+use crate::service::{ErrorModel, IcebergErrorResponse};
+use crate::CONFIG;
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use openfga_rs::tonic::{Response, Status};
+use openfga_rs::{
+    userset, userset_tree, CheckRequest, CheckRequestTupleKey, CheckResponse, ExpandRequest,
+    ExpandResponse, ListObjectsRequest, ListObjectsResponse, ReadRequest, ReadRequestTupleKey,
+    ReadResponse, Tuple, TupleKey, Userset, UsersetTree, WriteRequest, WriteResponse,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use tokio::sync::RwLock;
+
+/// Store id reported for the built-in store. There is only a single store.
+const POSTGRES_STORE_ID: &str = "postgres";
+/// Default page size of `OpenFGA` read requests
+const DEFAULT_PAGE_SIZE: i32 = 50;
+/// Maximum page size of `OpenFGA` read requests
+const MAX_PAGE_SIZE: i32 = 100;
+/// Maximum depth of nested relations, same as `OpenFGA`'s default resolution depth.
+const MAX_RESOLUTION_DEPTH: usize = 25;
+
+/// Dependencies between the relations of the active model.
+/// Intersections and differences are followed like unions, so that all tuples
+/// any part of a rewrite depends on are loaded.
+static ACTIVE_MODEL_EDGES: LazyLock<RelationEdges> = LazyLock::new(|| {
+    let mut edges = RelationEdges::default();
+    for (object_type, relations) in ACTIVE_MODEL_RELATIONS.iter() {
+        for (relation, rewrite) in relations {
+            collect_edges(object_type, relation, rewrite, &mut edges);
+        }
+    }
+    edges
+});
+
+/// Create an [`OpenFGAAuthorizer`] that stores its tuples in Postgres.
+///
+/// # Errors
+/// - The configuration of the grant expiration queue is invalid
+pub fn new_postgres_authorizer(pool: sqlx::PgPool) -> Result<OpenFGAAuthorizer, ErrorModel> {
+    Ok(OpenFGAAuthorizer {
+        client: Arc::new(PostgresTupleStore::new(pool.clone())),
+        store_id: POSTGRES_STORE_ID.to_string(),
+        authorization_model_id: ModelVersion::active().to_string(),
+        cache: DecisionCache::from_config(
+            CONFIG.authz_decision_cache_ttl_seconds,
+            CONFIG.authz_decision_cache_capacity,
+        ),
+        grant_ledger: Some(GrantLedger::new(pool)?),
+        health: Arc::new(RwLock::new(vec![])),
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresTupleStore {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresTupleStore {
+    #[must_use]
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl Client for PostgresTupleStore {
+    async fn write(&self, request: WriteRequest) -> Result<Response<WriteResponse>, Status> {
+        let writes = request
+            .writes
+            .map(|w| w.tuple_keys)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|k| parse_tuple(&k.user, &k.relation, &k.object))
+            .collect::<Result<Vec<_>, _>>()?;
+        let deletes = request
+            .deletes
+            .map(|d| d.tuple_keys)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|k| parse_tuple(&k.user, &k.relation, &k.object))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut transaction = self.pool.begin().await.map_err(|e| {
+            Status::internal(format!("Failed to start authorization transaction: {e}"))
+        })?;
+        write_tuples(&writes, &deletes, &mut transaction)
+            .await
+            .map_err(to_status)?;
+        transaction.commit().await.map_err(|e| {
+            Status::internal(format!("Failed to commit authorization transaction: {e}"))
+        })?;
+
+        Ok(Response::new(WriteResponse {}))
+    }
+
+    async fn list_objects(
+        &self,
+        request: ListObjectsRequest,
+    ) -> Result<Response<ListObjectsResponse>, Status> {
+        // Only objects reachable from the user can be related to it,
+        // so that not every object of the type has to be checked.
+        let (user_type, user_id, user_relation) = split_user(&request.user)?;
+        let mut subjects = vec![(
+            user_type.to_string(),
+            user_id.to_string(),
+            user_relation.to_string(),
+        )];
+        if user_relation.is_empty() {
+            subjects.push((user_type.to_string(), "*".to_string(), String::new()));
+        }
+        let graph = RelationGraph::from(
+            get_reachable_relation_graph(
+                &subjects,
+                &request.r#type,
+                &request.relation,
+                &ACTIVE_MODEL_EDGES,
+                &self.pool,
+            )
+            .await
+            .map_err(to_status)?,
+        );
+
+        let mut resolver = CheckResolver::new(&graph, &request.user);
+        let mut objects = Vec::new();
+        for object in graph.objects(&request.r#type, &request.relation) {
+            if resolver.check(&object, &request.relation, 0)? {
+                objects.push(object);
+            }
+        }
+
+        Ok(Response::new(ListObjectsResponse { objects }))
+    }
+
+    async fn read(&self, request: ReadRequest) -> Result<Response<ReadResponse>, Status> {
+        let filter = request
+            .tuple_key
+            .as_ref()
+            .map(parse_filter)
+            .transpose()?
+            .unwrap_or_default();
+        let page_size = request
+            .page_size
+            .filter(|s| *s > 0)
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .min(MAX_PAGE_SIZE);
+        let after_tuple_id = if request.continuation_token.is_empty() {
+            None
+        } else {
+            Some(
+                request
+                    .continuation_token
+                    .parse::<i64>()
+                    .map_err(|_e| Status::invalid_argument("Invalid continuation token"))?,
+            )
+        };
+
+        let rows = read_tuples(&filter, after_tuple_id, page_size.into(), &self.pool)
+            .await
+            .map_err(to_status)?;
+
+        let continuation_token = if rows.len() < usize::try_from(page_size).unwrap_or(0) {
+            String::new()
+        } else {
+            rows.last()
+                .map(|row| row.tuple_id.to_string())
+                .unwrap_or_default()
+        };
+
+        Ok(Response::new(ReadResponse {
+            tuples: rows
+                .into_iter()
+                .map(|row| Tuple {
+                    key: Some(to_tuple_key(row.tuple)),
+                    timestamp: None,
+                })
+                .collect(),
+            continuation_token,
+        }))
+    }
+
+    async fn read_all_pages(
+        &self,
+        _store_id: &str,
+        tuple: ReadRequestTupleKey,
+    ) -> OpenFGAResult<Vec<Tuple>> {
+        let mut tuples = vec![];
+        let mut continuation_token = String::new();
+        loop {
+            let read_request = ReadRequest {
+                store_id: POSTGRES_STORE_ID.to_string(),
+                tuple_key: Some(tuple.clone()),
+                page_size: Some(MAX_PAGE_SIZE),
+                continuation_token: continuation_token.clone(),
+                consistency: 0,
+            };
+            let response = self
+                .read(read_request.clone())
+                .await
+                .map_err(|source| super::OpenFGAError::ReadFailed {
+                    read_request: Box::new(read_request),
+                    source,
+                })?
+                .into_inner();
+            tuples.extend(response.tuples);
+            if response.continuation_token.is_empty() {
+                return Ok(tuples);
+            }
+            continuation_token = response.continuation_token;
+        }
+    }
+
+    async fn check(&self, request: CheckRequest) -> Result<Response<CheckResponse>, Status> {
+        let tuple_key = request
+            .tuple_key
+            .ok_or_else(|| Status::invalid_argument("Check request requires a tuple key"))?;
+        let graph = RelationGraph::load(&self.pool, std::slice::from_ref(&tuple_key)).await?;
+        let allowed = CheckResolver::new(&graph, &tuple_key.user).check(
+            &tuple_key.object,
+            &tuple_key.relation,
+            0,
+        )?;
+
+        Ok(Response::new(CheckResponse {
+            allowed,
+            resolution: String::new(),
+        }))
+    }
+
+    async fn expand(&self, request: ExpandRequest) -> Result<Response<ExpandResponse>, Status> {
+        let tuple_key = request
+            .tuple_key
+            .ok_or_else(|| Status::invalid_argument("Expand request requires a tuple key"))?;
+        let (object_type, _) = split_object(&tuple_key.object)?;
+        let rewrite = ACTIVE_MODEL_RELATIONS
+            .get(object_type)
+            .and_then(|relations| relations.get(&tuple_key.relation))
+            .ok_or_else(|| {
+                Status::invalid_argument(format!(
+                    "Relation '{}' is not defined for type '{object_type}'",
+                    tuple_key.relation
+                ))
+            })?;
+        let root =
+            expand_userset(&self.pool, &tuple_key.object, &tuple_key.relation, rewrite).await?;
+
+        Ok(Response::new(ExpandResponse {
+            tree: Some(UsersetTree { root: Some(root) }),
+        }))
+    }
+
+    async fn batch_check(
+        &self,
+        _store_id: &str,
+        _authorization_model_id: &str,
+        tuple_keys: Vec<CheckRequestTupleKey>,
+    ) -> Result<Vec<bool>, Status> {
+        let graph = RelationGraph::load(&self.pool, &tuple_keys).await?;
+        // One resolver per user, so that parents shared by the objects are only resolved once.
+        let mut resolvers = HashMap::new();
+        let mut results = Vec::with_capacity(tuple_keys.len());
+        for tuple_key in &tuple_keys {
+            let resolver = resolvers
+                .entry(tuple_key.user.as_str())
+                .or_insert_with(|| CheckResolver::new(&graph, &tuple_key.user));
+            results.push(resolver.check(&tuple_key.object, &tuple_key.relation, 0)?);
+        }
+        Ok(results)
+    }
+}
+
+fn collect_edges(object_type: &str, relation: &str, rewrite: &Userset, edges: &mut RelationEdges) {
+    match &rewrite.userset {
+        Some(userset::Userset::ComputedUserset(computed)) => {
+            edges.push_computed(object_type, relation, &computed.relation);
+        }
+        Some(userset::Userset::TupleToUserset(ttu)) => {
+            if let (Some(tupleset), Some(computed)) = (&ttu.tupleset, &ttu.computed_userset) {
+                edges.push_computed(object_type, relation, &tupleset.relation);
+                edges.push_tupleset(
+                    object_type,
+                    relation,
+                    &tupleset.relation,
+                    &computed.relation,
+                );
+            }
+        }
+        Some(userset::Userset::Union(usersets) | userset::Userset::Intersection(usersets)) => {
+            for child in &usersets.child {
+                collect_edges(object_type, relation, child, edges);
+            }
+        }
+        Some(userset::Userset::Difference(difference)) => {
+            for child in [&difference.base, &difference.subtract]
+                .into_iter()
+                .flatten()
+            {
+                collect_edges(object_type, relation, child, edges);
+            }
+        }
+        Some(userset::Userset::This(_)) | None => {}
+    }
+}
+
+/// Users directly related to objects, by object and relation.
+/// Contains an entry for every relation the loaded checks can depend on.
+#[derive(Debug, Default)]
+struct RelationGraph {
+    users: HashMap<(String, String), Vec<(String, String, String)>>,
+}
+
+impl RelationGraph {
+    /// Load all tuples the checks of `tuple_keys` depend on.
+    async fn load(
+        pool: &sqlx::PgPool,
+        tuple_keys: &[CheckRequestTupleKey],
+    ) -> Result<Self, Status> {
+        let relations = tuple_keys
+            .iter()
+            .map(|key| {
+                let (object_type, object_id) = split_object(&key.object)?;
+                Ok((
+                    object_type.to_string(),
+                    object_id.to_string(),
+                    key.relation.clone(),
+                ))
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+        Ok(Self::from(
+            get_relation_graph(&relations, &ACTIVE_MODEL_EDGES, pool)
+                .await
+                .map_err(to_status)?,
+        ))
+    }
+
+    fn users(&self, object: &str, relation: &str) -> &[(String, String, String)] {
+        self.users
+            .get(&(object.to_string(), relation.to_string()))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// All loaded objects of `object_type` with `relation`, sorted.
+    fn objects(&self, object_type: &str, relation: &str) -> Vec<String> {
+        let mut objects = self
+            .users
+            .keys()
+            .filter(|(object, r)| {
+                r == relation && split_object(object).is_ok_and(|(typ, _)| typ == object_type)
+            })
+            .map(|(object, _)| object.clone())
+            .collect::<Vec<_>>();
+        objects.sort();
+        objects
+    }
+}
+
+impl From<Vec<RelationGraphRow>> for RelationGraph {
+    fn from(rows: Vec<RelationGraphRow>) -> Self {
+        let mut graph = Self::default();
+        for row in rows {
+            let users = graph
+                .users
+                .entry((
+                    format!("{}:{}", row.object_type, row.object_id),
+                    row.relation,
+                ))
+                .or_default();
+            users.extend(row.user);
+        }
+        graph
+    }
+}
+
+/// Resolves checks for a single user. Results are memoized, so that
+/// a resolver can be reused for checks on multiple objects.
+struct CheckResolver<'a> {
+    graph: &'a RelationGraph,
+    user: &'a str,
+    memo: HashMap<(String, String), bool>,
+    /// Checks that are currently resolved, with their position on the resolution stack.
+    in_progress: HashMap<(String, String), usize>,
+    /// Lowest stack position of an in-progress check that the current result
+    /// was assumed `false` for. Such results are not final and must not be memoized.
+    cycle_dependency: Option<usize>,
+}
+
+impl<'a> CheckResolver<'a> {
+    fn new(graph: &'a RelationGraph, user: &'a str) -> Self {
+        Self {
+            graph,
+            user,
+            memo: HashMap::new(),
+            in_progress: HashMap::new(),
+            cycle_dependency: None,
+        }
+    }
+
+    /// Check if the user has `relation` on `object`.
+    fn check(&mut self, object: &str, relation: &str, depth: usize) -> Result<bool, Status> {
+        if depth > MAX_RESOLUTION_DEPTH {
+            return Err(Status::invalid_argument(
+                "Authorization check exceeded the maximum resolution depth",
+            ));
+        }
+
+        let key = (object.to_string(), relation.to_string());
+        if let Some(allowed) = self.memo.get(&key) {
+            return Ok(*allowed);
+        }
+        // Cyclic relations, i.e. roles assigned to each other, don't grant anything.
+        if let Some(position) = self.in_progress.get(&key) {
+            self.cycle_dependency = Some(
+                self.cycle_dependency
+                    .map_or(*position, |dependency| dependency.min(*position)),
+            );
+            return Ok(false);
+        }
+        let position = self.in_progress.len();
+        self.in_progress.insert(key.clone(), position);
+        let outer_dependency = self.cycle_dependency.take();
+
+        let (object_type, _) = split_object(object)?;
+        let allowed = match ACTIVE_MODEL_RELATIONS
+            .get(object_type)
+            .and_then(|relations| relations.get(relation))
+        {
+            Some(rewrite) => self.evaluate(object, relation, rewrite, depth)?,
+            None => false,
+        };
+
+        self.in_progress.remove(&key);
+        // A cycle back to this check itself is resolved now. Results that depend on
+        // checks further up the stack may change once those are resolved.
+        let dependency = self
+            .cycle_dependency
+            .filter(|dependency| *dependency < position);
+        if dependency.is_none() {
+            self.memo.insert(key, allowed);
+        }
+        self.cycle_dependency = match (outer_dependency, dependency) {
+            (Some(outer), Some(inner)) => Some(outer.min(inner)),
+            (outer, inner) => outer.or(inner),
+        };
+        Ok(allowed)
+    }
+
+    fn evaluate(
+        &mut self,
+        object: &str,
+        relation: &str,
+        rewrite: &Userset,
+        depth: usize,
+    ) -> Result<bool, Status> {
+        match &rewrite.userset {
+            Some(userset::Userset::This(_)) => self.check_direct(object, relation, depth),
+            Some(userset::Userset::ComputedUserset(computed)) => {
+                self.check(object, &computed.relation, depth + 1)
+            }
+            Some(userset::Userset::TupleToUserset(ttu)) => {
+                let (Some(tupleset), Some(computed)) = (&ttu.tupleset, &ttu.computed_userset)
+                else {
+                    return Ok(false);
+                };
+                let graph = self.graph;
+                for (parent_type, parent_id, _) in graph.users(object, &tupleset.relation) {
+                    let parent = format!("{parent_type}:{parent_id}");
+                    if self.check(&parent, &computed.relation, depth + 1)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Some(userset::Userset::Union(usersets)) => {
+                for child in &usersets.child {
+                    if self.evaluate(object, relation, child, depth)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Some(userset::Userset::Intersection(usersets)) => {
+                for child in &usersets.child {
+                    if !self.evaluate(object, relation, child, depth)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(!usersets.child.is_empty())
+            }
+            Some(userset::Userset::Difference(difference)) => {
+                let (Some(base), Some(subtract)) = (&difference.base, &difference.subtract) else {
+                    return Ok(false);
+                };
+                Ok(self.evaluate(object, relation, base, depth)?
+                    && !self.evaluate(object, relation, subtract, depth)?)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Check tuples that directly relate users to the object, including
+    /// wildcards (`user:*`) and usersets (`role:<id>#assignee`).
+    fn check_direct(&mut self, object: &str, relation: &str, depth: usize) -> Result<bool, Status> {
+        let graph = self.graph;
+        let user_type = self.user.split_once(':').map(|(t, _)| t);
+        let user_is_userset = self.user.contains('#');
+
+        let mut usersets = vec![];
+        for (tuple_user_type, tuple_user_id, tuple_user_relation) in graph.users(object, relation) {
+            if tuple_user_relation.is_empty() {
+                let tuple_user = format!("{tuple_user_type}:{tuple_user_id}");
+                if tuple_user == self.user
+                    || (tuple_user_id == "*"
+                        && !user_is_userset
+                        && user_type == Some(tuple_user_type.as_str()))
+                {
+                    return Ok(true);
+                }
+            } else {
+                let userset = format!("{tuple_user_type}:{tuple_user_id}#{tuple_user_relation}");
+                if userset == self.user {
+                    return Ok(true);
+                }
+                usersets.push((
+                    format!("{tuple_user_type}:{tuple_user_id}"),
+                    tuple_user_relation,
+                ));
+            }
+        }
+
+        for (userset_object, userset_relation) in usersets {
+            if self.check(&userset_object, userset_relation, depth + 1)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Expand `relation` on `object` one level, like `OpenFGA`'s `Expand`:
+/// computed and inherited relations are returned as references, not resolved.
+fn expand_userset<'a>(
+    pool: &'a sqlx::PgPool,
+    object: &'a str,
+    relation: &'a str,
+    rewrite: &'a Userset,
+) -> BoxFuture<'a, Result<userset_tree::Node, Status>> {
+    Box::pin(async move {
+        let value = match &rewrite.userset {
+            Some(userset::Userset::This(_)) => {
+                let (object_type, object_id) = split_object(object)?;
+                let users = get_object_relation_users(object_type, object_id, relation, pool)
+                    .await
+                    .map_err(to_status)?
+                    .into_iter()
+                    .map(|(user_type, user_id, user_relation)| {
+                        if user_relation.is_empty() {
+                            format!("{user_type}:{user_id}")
+                        } else {
+                            format!("{user_type}:{user_id}#{user_relation}")
+                        }
+                    })
+                    .collect();
+                Some(userset_tree::node::Value::Leaf(userset_tree::Leaf {
+                    value: Some(userset_tree::leaf::Value::Users(userset_tree::Users {
+                        users,
+                    })),
+                }))
+            }
+            Some(userset::Userset::ComputedUserset(computed)) => {
+                Some(userset_tree::node::Value::Leaf(userset_tree::Leaf {
+                    value: Some(userset_tree::leaf::Value::Computed(
+                        userset_tree::Computed {
+                            userset: format!("{object}#{}", computed.relation),
+                        },
+                    )),
+                }))
+            }
+            Some(userset::Userset::TupleToUserset(ttu)) => {
+                let (Some(tupleset), Some(computed)) = (&ttu.tupleset, &ttu.computed_userset)
+                else {
+                    return Err(Status::internal("Incomplete tuple to userset rewrite"));
+                };
+                let (object_type, object_id) = split_object(object)?;
+                let parents =
+                    get_object_relation_users(object_type, object_id, &tupleset.relation, pool)
+                        .await
+                        .map_err(to_status)?;
+                Some(userset_tree::node::Value::Leaf(userset_tree::Leaf {
+                    value: Some(userset_tree::leaf::Value::TupleToUserset(
+                        userset_tree::TupleToUserset {
+                            tupleset: format!("{object}#{}", tupleset.relation),
+                            computed: parents
+                                .into_iter()
+                                .map(|(parent_type, parent_id, _)| userset_tree::Computed {
+                                    userset: format!(
+                                        "{parent_type}:{parent_id}#{}",
+                                        computed.relation
+                                    ),
+                                })
+                                .collect(),
+                        },
+                    )),
+                }))
+            }
+            Some(userset::Userset::Union(usersets)) => {
+                let mut nodes = Vec::with_capacity(usersets.child.len());
+                for child in &usersets.child {
+                    nodes.push(expand_userset(pool, object, relation, child).await?);
+                }
+                Some(userset_tree::node::Value::Union(userset_tree::Nodes {
+                    nodes,
+                }))
+            }
+            Some(userset::Userset::Intersection(usersets)) => {
+                let mut nodes = Vec::with_capacity(usersets.child.len());
+                for child in &usersets.child {
+                    nodes.push(expand_userset(pool, object, relation, child).await?);
+                }
+                Some(userset_tree::node::Value::Intersection(
+                    userset_tree::Nodes { nodes },
+                ))
+            }
+            Some(userset::Userset::Difference(difference)) => {
+                let (Some(base), Some(subtract)) = (&difference.base, &difference.subtract) else {
+                    return Err(Status::internal("Incomplete difference rewrite"));
+                };
+                let base = expand_userset(pool, object, relation, base).await?;
+                let subtract = expand_userset(pool, object, relation, subtract).await?;
+                Some(userset_tree::node::Value::Difference(
+                    userset_tree::Difference {
+                        base: Some(base.into()),
+                        subtract: Some(subtract.into()),
+                    }
+                    .into(),
+                ))
+            }
+            None => None,
+        };
+
+        Ok(userset_tree::Node {
+            name: format!("{object}#{relation}"),
+            value,
+        })
+    })
+}
+
+fn to_status(e: IcebergErrorResponse) -> Status {
+    if e.error.code == http::StatusCode::CONFLICT.as_u16() {
+        Status::invalid_argument(e.error.message)
+    } else {
+        Status::internal(e.error.message)
+    }
+}
+
+fn split_object(object: &str) -> Result<(&str, &str), Status> {
+    object
+        .split_once(':')
+        .ok_or_else(|| Status::invalid_argument(format!("Invalid object `{object}`")))
+}
+
+/// Split a user into `(type, id, relation)`, i.e. `role:<id>#assignee`.
+fn split_user(user: &str) -> Result<(&str, &str, &str), Status> {
+    let (user, relation) = user.split_once('#').unwrap_or((user, ""));
+    let (user_type, user_id) = user
+        .split_once(':')
+        .ok_or_else(|| Status::invalid_argument(format!("Invalid user `{user}`")))?;
+    Ok((user_type, user_id, relation))
+}
+
+fn parse_tuple(user: &str, relation: &str, object: &str) -> Result<AuthzTuple, Status> {
+    let (object_type, object_id) = split_object(object)?;
+    let (user_type, user_id, user_relation) = split_user(user)?;
+    if object_id.is_empty() || user_id.is_empty() || relation.is_empty() {
+        return Err(Status::invalid_argument(format!(
+            "Incomplete tuple `{user}` `{relation}` `{object}`"
+        )));
+    }
+    Ok(AuthzTuple {
+        object_type: object_type.to_string(),
+        object_id: object_id.to_string(),
+        relation: relation.to_string(),
+        user_type: user_type.to_string(),
+        user_id: user_id.to_string(),
+        user_relation: user_relation.to_string(),
+    })
+}
+
+/// `OpenFGA` read semantics: an object of the form `type:` matches all objects of the type,
+/// empty user or relation match any value.
+fn parse_filter(key: &ReadRequestTupleKey) -> Result<AuthzTupleFilter, Status> {
+    let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+    let mut filter = AuthzTupleFilter {
+        relation: non_empty(&key.relation),
+        ..Default::default()
+    };
+    if !key.object.is_empty() {
+        let (object_type, object_id) = split_object(&key.object)?;
+        filter.object_type = Some(object_type.to_string());
+        filter.object_id = non_empty(object_id);
+    }
+    if !key.user.is_empty() {
+        let (user_type, user_id, user_relation) = split_user(&key.user)?;
+        filter.user_type = Some(user_type.to_string());
+        filter.user_id = Some(user_id.to_string());
+        filter.user_relation = Some(user_relation.to_string());
+    }
+    Ok(filter)
+}
+
+fn to_tuple_key(tuple: AuthzTuple) -> TupleKey {
+    let user_suffix = if tuple.user_relation.is_empty() {
+        String::new()
+    } else {
+        format!("#{}", tuple.user_relation)
+    };
+    TupleKey {
+        user: format!("{}:{}{user_suffix}", tuple.user_type, tuple.user_id),
+        relation: tuple.relation,
+        object: format!("{}:{}", tuple.object_type, tuple.object_id),
+        condition: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openfga_rs::WriteRequestWrites;
+
+    async fn write(store: &PostgresTupleStore, tuples: &[(&str, &str, &str)]) {
+        store
+            .write(WriteRequest {
+                store_id: POSTGRES_STORE_ID.to_string(),
+                writes: Some(WriteRequestWrites {
+                    tuple_keys: tuples
+                        .iter()
+                        .map(|(user, relation, object)| TupleKey {
+                            user: (*user).to_string(),
+                            relation: (*relation).to_string(),
+                            object: (*object).to_string(),
+                            condition: None,
+                        })
+                        .collect(),
+                }),
+                deletes: None,
+                authorization_model_id: String::new(),
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn check(store: &PostgresTupleStore, user: &str, relation: &str, object: &str) -> bool {
+        store
+            .check(CheckRequest {
+                store_id: POSTGRES_STORE_ID.to_string(),
+                tuple_key: Some(CheckRequestTupleKey {
+                    user: user.to_string(),
+                    relation: relation.to_string(),
+                    object: object.to_string(),
+                }),
+                contextual_tuples: None,
+                authorization_model_id: String::new(),
+                trace: false,
+                context: None,
+                consistency: 0,
+            })
+            .await
+            .unwrap()
+            .into_inner()
+            .allowed
+    }
+
+    #[test]
+    fn test_parse_tuple() {
+        let tuple = parse_tuple("role:r1#assignee", "select", "table:t1").unwrap();
+        assert_eq!(tuple.user_type, "role");
+        assert_eq!(tuple.user_id, "r1");
+        assert_eq!(tuple.user_relation, "assignee");
+        assert_eq!(to_tuple_key(tuple).user, "role:r1#assignee");
+
+        assert!(parse_tuple("user:u1", "select", "table:").is_err());
+        assert!(parse_tuple("u1", "select", "table:t1").is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_inheritance_down_the_hierarchy(pool: sqlx::PgPool) {
+        let store = PostgresTupleStore::new(pool);
+        write(
+            &store,
+            &[
+                ("warehouse:w1", "parent", "namespace:n1"),
+                ("namespace:n1", "namespace", "warehouse:w1"),
+                ("namespace:n1", "parent", "namespace:n2"),
+                ("namespace:n2", "child", "namespace:n1"),
+                ("namespace:n2", "parent", "table:t1"),
+                ("table:t1", "child", "namespace:n2"),
+                ("user:oidc~peter", "select", "warehouse:w1"),
+                ("role:r1#assignee", "modify", "namespace:n2"),
+                ("user:oidc~anna", "assignee", "role:r1"),
+            ],
+        )
+        .await;
+
+        assert!(check(&store, "user:oidc~peter", "can_read_data", "table:t1").await);
+        assert!(!check(&store, "user:oidc~peter", "can_write_data", "table:t1").await);
+        assert!(check(&store, "user:oidc~anna", "can_write_data", "table:t1").await);
+        assert!(check(&store, "role:r1#assignee", "can_drop", "table:t1").await);
+        assert!(
+            !check(
+                &store,
+                "user:oidc~anna",
+                "can_create_namespace",
+                "warehouse:w1"
+            )
+            .await
+        );
+        // Bottom-up inheritance of navigational privileges
+        assert!(check(&store, "user:oidc~anna", "can_get_metadata", "namespace:n1").await);
+        assert!(
+            !check(
+                &store,
+                "user:oidc~other",
+                "can_get_metadata",
+                "namespace:n1"
+            )
+            .await
+        );
+
+        let tuple_keys = [
+            ("user:oidc~anna", "can_write_data", "table:t1"),
+            ("user:oidc~peter", "can_write_data", "table:t1"),
+            ("user:oidc~anna", "can_get_metadata", "namespace:n1"),
+            ("user:oidc~peter", "can_read_data", "table:t1"),
+        ]
+        .into_iter()
+        .map(|(user, relation, object)| CheckRequestTupleKey {
+            user: user.to_string(),
+            relation: relation.to_string(),
+            object: object.to_string(),
+        })
+        .collect();
+        let allowed = store
+            .batch_check(POSTGRES_STORE_ID, "", tuple_keys)
+            .await
+            .unwrap();
+        assert_eq!(allowed, vec![true, false, true, true]);
+    }
+
+    #[sqlx::test]
+    async fn test_cyclic_roles_terminate(pool: sqlx::PgPool) {
+        let store = PostgresTupleStore::new(pool);
+        write(
+            &store,
+            &[
+                ("role:r1#assignee", "assignee", "role:r2"),
+                ("role:r2#assignee", "assignee", "role:r1"),
+                ("role:r1#assignee", "select", "table:t1"),
+            ],
+        )
+        .await;
+
+        assert!(!check(&store, "user:oidc~peter", "can_read_data", "table:t1").await);
+    }
+
+    #[sqlx::test]
+    async fn test_cycles_are_not_memoized(pool: sqlx::PgPool) {
+        let store = PostgresTupleStore::new(pool);
+        write(
+            &store,
+            &[
+                ("role:r2#assignee", "assignee", "role:r1"),
+                ("role:r3#assignee", "assignee", "role:r1"),
+                ("role:r1#assignee", "assignee", "role:r2"),
+                ("user:oidc~anna", "assignee", "role:r3"),
+                ("role:r1#assignee", "select", "table:t1"),
+                ("role:r2#assignee", "select", "table:t2"),
+            ],
+        )
+        .await;
+
+        // Resolving r1 visits r2 while r1 is in progress. r2 must not be memoized as `false`.
+        let tuple_keys = [("can_read_data", "table:t1"), ("can_read_data", "table:t2")]
+            .into_iter()
+            .map(|(relation, object)| CheckRequestTupleKey {
+                user: "user:oidc~anna".to_string(),
+                relation: relation.to_string(),
+                object: object.to_string(),
+            })
+            .collect();
+        let allowed = store
+            .batch_check(POSTGRES_STORE_ID, "", tuple_keys)
+            .await
+            .unwrap();
+        assert_eq!(allowed, vec![true, true]);
+    }
+
+    #[sqlx::test]
+    async fn test_list_objects(pool: sqlx::PgPool) {
+        let store = PostgresTupleStore::new(pool);
+        write(
+            &store,
+            &[
+                ("warehouse:w1", "parent", "namespace:n1"),
+                ("namespace:n1", "namespace", "warehouse:w1"),
+                ("namespace:n1", "parent", "table:t1"),
+                ("table:t1", "child", "namespace:n1"),
+                ("namespace:n1", "parent", "table:t2"),
+                ("table:t2", "child", "namespace:n1"),
+                ("warehouse:w2", "parent", "namespace:n2"),
+                ("namespace:n2", "namespace", "warehouse:w2"),
+                ("namespace:n2", "parent", "table:t3"),
+                ("table:t3", "child", "namespace:n2"),
+                ("role:r1#assignee", "select", "namespace:n1"),
+                ("user:oidc~anna", "assignee", "role:r1"),
+                ("user:*", "select", "table:t3"),
+            ],
+        )
+        .await;
+
+        let list = |user: &str| {
+            let request = ListObjectsRequest {
+                store_id: POSTGRES_STORE_ID.to_string(),
+                authorization_model_id: String::new(),
+                r#type: "table".to_string(),
+                relation: "can_read_data".to_string(),
+                user: user.to_string(),
+                contextual_tuples: None,
+                context: None,
+                consistency: 0,
+            };
+            let store = store.clone();
+            async move {
+                let mut objects = store
+                    .list_objects(request)
+                    .await
+                    .unwrap()
+                    .into_inner()
+                    .objects;
+                objects.sort();
+                objects
+            }
+        };
+
+        assert_eq!(
+            list("user:oidc~anna").await,
+            vec!["table:t1", "table:t2", "table:t3"]
+        );
+        assert_eq!(list("user:oidc~peter").await, vec!["table:t3"]);
+        assert_eq!(list("role:r1#assignee").await, vec!["table:t1", "table:t2"]);
+    }
+
+    #[sqlx::test]
+    async fn test_intersection_and_difference(pool: sqlx::PgPool) {
+        let store = PostgresTupleStore::new(pool);
+        write(
+            &store,
+            &[
+                ("warehouse:w1", "parent", "namespace:n1"),
+                ("namespace:n1", "namespace", "warehouse:w1"),
+                ("namespace:n1", "parent", "namespace:n2"),
+                ("namespace:n2", "child", "namespace:n1"),
+                ("user:oidc~peter", "ownership", "namespace:n2"),
+                ("user:oidc~anna", "select", "namespace:n2"),
+            ],
+        )
+        .await;
+
+        let list = |user: &str, relation: &str| {
+            let request = ListObjectsRequest {
+                store_id: POSTGRES_STORE_ID.to_string(),
+                authorization_model_id: String::new(),
+                r#type: "namespace".to_string(),
+                relation: relation.to_string(),
+                user: user.to_string(),
+                contextual_tuples: None,
+                context: None,
+                consistency: 0,
+            };
+            let store = store.clone();
+            async move {
+                store
+                    .list_objects(request)
+                    .await
+                    .unwrap()
+                    .into_inner()
+                    .objects
+            }
+        };
+
+        // `can_grant_select` requires `select and pass_grants`
+        assert!(!check(&store, "user:oidc~anna", "can_grant_select", "namespace:n2").await);
+        write(&store, &[("user:oidc~anna", "pass_grants", "namespace:n2")]).await;
+        assert!(check(&store, "user:oidc~anna", "can_grant_select", "namespace:n2").await);
+        assert_eq!(
+            list("user:oidc~anna", "can_grant_select").await,
+            vec!["namespace:n2"]
+        );
+
+        // Managed access on a parent removes `manage_grants` from owners
+        assert!(check(&store, "user:oidc~peter", "manage_grants", "namespace:n2").await);
+        write(&store, &[("user:*", "managed_access", "namespace:n1")]).await;
+        assert!(!check(&store, "user:oidc~peter", "manage_grants", "namespace:n2").await);
+        assert!(list("user:oidc~peter", "manage_grants").await.is_empty());
+        assert_eq!(
+            list("user:oidc~peter", "can_delete").await,
+            vec!["namespace:n2"]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_read_pagination(pool: sqlx::PgPool) {
+        let store = PostgresTupleStore::new(pool);
+        let tuples = (0..5)
+            .map(|i| ("user:oidc~peter".to_string(), format!("table:t{i}")))
+            .collect::<Vec<_>>();
+        write(
+            &store,
+            &tuples
+                .iter()
+                .map(|(user, object)| (user.as_str(), "select", object.as_str()))
+                .collect::<Vec<_>>(),
+        )
+        .await;
+
+        let all = store
+            .read_all_pages(
+                POSTGRES_STORE_ID,
+                ReadRequestTupleKey {
+                    user: "user:oidc~peter".to_string(),
+                    relation: String::new(),
+                    object: "table:".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 5);
+
+        let first_page = store
+            .read(ReadRequest {
+                store_id: POSTGRES_STORE_ID.to_string(),
+                tuple_key: Some(ReadRequestTupleKey {
+                    user: String::new(),
+                    relation: "select".to_string(),
+                    object: "table:".to_string(),
+                }),
+                page_size: Some(3),
+                continuation_token: String::new(),
+                consistency: 0,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(first_page.tuples.len(), 3);
+        assert!(!first_page.continuation_token.is_empty());
+    }
+}
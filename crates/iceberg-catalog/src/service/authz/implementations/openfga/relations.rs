@@ -836,6 +836,15 @@ impl ReducedRelation for CatalogWarehouseAction {
             CatalogWarehouseAction::CanListDeletedTabulars => {
                 WarehouseRelation::CanListDeletedTabulars
             }
+            // Ref protection rules decide who may commit to refs
+            CatalogWarehouseAction::CanReadRefProtectionRules => WarehouseRelation::CanGetMetadata,
+            CatalogWarehouseAction::CanManageRefProtectionRules => {
+                WarehouseRelation::CanGrantManageGrants
+            }
+            // Backups expose the storage profile and the location of every tabular
+            CatalogWarehouseAction::CanBackup => WarehouseRelation::CanUpdateStorage,
+            // Table defaults and overrides are warehouse-wide configuration
+            CatalogWarehouseAction::CanUpdateProperties => WarehouseRelation::CanUpdateStorage,
         }
     }
 }
@@ -1052,6 +1061,8 @@ impl ReducedRelation for CatalogNamespaceAction {
             CatalogNamespaceAction::CanListTables => NamespaceRelation::CanListTables,
             CatalogNamespaceAction::CanListViews => NamespaceRelation::CanListViews,
             CatalogNamespaceAction::CanListNamespaces => NamespaceRelation::CanListNamespaces,
+            // Undropping restores what has been deleted before.
+            CatalogNamespaceAction::CanUndrop => NamespaceRelation::CanDelete,
         }
     }
 }
@@ -1248,6 +1259,11 @@ impl ReducedRelation for CatalogTableAction {
             CatalogTableAction::CanRename => TableRelation::CanRename,
             CatalogTableAction::CanIncludeInList => TableRelation::CanIncludeInList,
             CatalogTableAction::CanUndrop => TableRelation::CanUndrop,
+            // Column policies and row filters are part of the access control of a table
+            CatalogTableAction::CanReadColumnPolicies => TableRelation::CanReadAssignments,
+            CatalogTableAction::CanManageColumnPolicies => TableRelation::CanGrantManageGrants,
+            CatalogTableAction::CanReadRowFilters => TableRelation::CanReadAssignments,
+            CatalogTableAction::CanManageRowFilters => TableRelation::CanGrantManageGrants,
         }
     }
 }
@@ -1430,6 +1446,46 @@ impl ReducedRelation for CatalogViewAction {
 mod test {
     use super::*;
 
+    /// Relations defined for `typ` in the current authorization model.
+    fn model_relations(typ: &str) -> Vec<String> {
+        let model = include_str!("../../../../../../../authz/openfga/v2/schema.fga");
+        model
+            .split("\ntype ")
+            .find_map(|block| block.strip_prefix(typ).filter(|b| b.starts_with('\n')))
+            .expect("Type not found in model")
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("define "))
+            .filter_map(|line| line.split(':').next())
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Assert that every variant of `A` maps to a relation of `typ` in the model.
+    fn assert_actions_exist_in_model<A>(typ: &str)
+    where
+        A: ReducedRelation + IntoEnumIterator + std::fmt::Display,
+    {
+        let relations = model_relations(typ);
+        for action in A::iter() {
+            let relation = action.to_openfga().to_string();
+            assert!(
+                relations.contains(&relation),
+                "{action} maps to undefined {typ} relation {relation}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_catalog_actions_exist_in_model() {
+        assert_actions_exist_in_model::<CatalogServerAction>("server");
+        assert_actions_exist_in_model::<CatalogProjectAction>("project");
+        assert_actions_exist_in_model::<CatalogRoleAction>("role");
+        assert_actions_exist_in_model::<CatalogWarehouseAction>("warehouse");
+        assert_actions_exist_in_model::<CatalogNamespaceAction>("namespace");
+        assert_actions_exist_in_model::<CatalogTableAction>("table");
+        assert_actions_exist_in_model::<CatalogViewAction>("view");
+    }
+
     #[test]
     fn test_assignment_serialization() {
         let user_id = UserId::oidc("my_user").unwrap();
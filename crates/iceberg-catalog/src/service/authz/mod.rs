@@ -9,12 +9,18 @@ use axum::Router;
 use std::collections::HashSet;
 use strum::EnumIter;
 
+pub mod column_policy;
 pub mod implementations;
+pub mod ref_protection;
+pub mod row_filter;
 
 use crate::api::ApiContext;
 use crate::service::authn::UserId;
+pub use column_policy::{ColumnPolicy, ColumnPolicyEffect, ColumnRestrictions, PolicyPrincipal};
 use iceberg_ext::catalog::rest::ErrorModel;
 pub use implementations::allow_all::AllowAllAuthorizer;
+pub use ref_protection::{RefProtectionRule, RefProtectionScope};
+pub use row_filter::{RowFilter, RowFilterExpression};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter)]
 #[strum(serialize_all = "snake_case")]
@@ -81,6 +87,10 @@ pub enum CatalogWarehouseAction {
     CanRename,
     CanListDeletedTabulars,
     CanModifySoftDeletion,
+    CanReadRefProtectionRules,
+    CanManageRefProtectionRules,
+    CanBackup,
+    CanUpdateProperties,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter)]
@@ -95,6 +105,7 @@ pub enum CatalogNamespaceAction {
     CanListTables,
     CanListViews,
     CanListNamespaces,
+    CanUndrop,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter)]
@@ -108,6 +119,10 @@ pub enum CatalogTableAction {
     CanRename,
     CanIncludeInList,
     CanUndrop,
+    CanReadColumnPolicies,
+    CanManageColumnPolicies,
+    CanReadRowFilters,
+    CanManageRowFilters,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter)]
@@ -243,6 +258,121 @@ where
         action: &CatalogViewAction,
     ) -> Result<bool>;
 
+    /// Check the same action for multiple namespaces at once.
+    /// Returns one boolean per namespace, in the order of `namespace_ids`.
+    /// Return Err for internal errors.
+    ///
+    /// The default implementation checks each namespace individually.
+    /// Implementations should override this if their backend supports batch checks.
+    async fn are_allowed_namespace_actions(
+        &self,
+        metadata: &RequestMetadata,
+        warehouse_id: WarehouseIdent,
+        namespace_ids: &[NamespaceIdentUuid],
+        action: &CatalogNamespaceAction,
+    ) -> Result<Vec<bool>> {
+        futures::future::try_join_all(namespace_ids.iter().map(|namespace_id| {
+            self.is_allowed_namespace_action(metadata, warehouse_id, *namespace_id, action)
+        }))
+        .await
+    }
+
+    /// Check the same action for multiple tables at once.
+    /// Returns one boolean per table, in the order of `table_ids`.
+    /// Return Err for internal errors.
+    ///
+    /// The default implementation checks each table individually.
+    /// Implementations should override this if their backend supports batch checks.
+    async fn are_allowed_table_actions(
+        &self,
+        metadata: &RequestMetadata,
+        warehouse_id: WarehouseIdent,
+        table_ids: &[TableIdentUuid],
+        action: &CatalogTableAction,
+    ) -> Result<Vec<bool>> {
+        futures::future::try_join_all(table_ids.iter().map(|table_id| {
+            self.is_allowed_table_action(metadata, warehouse_id, *table_id, action)
+        }))
+        .await
+    }
+
+    /// Check the same action for multiple views at once.
+    /// Returns one boolean per view, in the order of `view_ids`.
+    /// Return Err for internal errors.
+    ///
+    /// The default implementation checks each view individually.
+    /// Implementations should override this if their backend supports batch checks.
+    async fn are_allowed_view_actions(
+        &self,
+        metadata: &RequestMetadata,
+        warehouse_id: WarehouseIdent,
+        view_ids: &[ViewIdentUuid],
+        action: &CatalogViewAction,
+    ) -> Result<Vec<bool>> {
+        futures::future::try_join_all(
+            view_ids.iter().map(|view_id| {
+                self.is_allowed_view_action(metadata, warehouse_id, *view_id, action)
+            }),
+        )
+        .await
+    }
+
+    /// Return the column policies of a table that restrict the actor of the request,
+    /// i.e. all policies the actor is not exempt from.
+    /// Return Err for internal errors.
+    ///
+    /// The default implementation only exempts principals that are listed in a policy directly.
+    /// Implementations should override this if users can be assigned to roles.
+    async fn restricting_column_policies(
+        &self,
+        metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+        _table_id: TableIdentUuid,
+        policies: Vec<ColumnPolicy>,
+    ) -> Result<Vec<ColumnPolicy>> {
+        Ok(policies
+            .into_iter()
+            .filter(|policy| !policy.exempts_actor_directly(metadata.actor()))
+            .collect())
+    }
+
+    /// Return the row filters of a table that apply to the actor of the request.
+    /// Return Err for internal errors.
+    ///
+    /// The default implementation only applies filters of the role the actor has assumed.
+    /// Implementations should override this if users can be assigned to roles.
+    async fn applicable_row_filters(
+        &self,
+        metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+        _table_id: TableIdentUuid,
+        filters: Vec<RowFilter>,
+    ) -> Result<Vec<RowFilter>> {
+        Ok(filters
+            .into_iter()
+            .filter(|filter| filter.applies_to_actor_directly(metadata.actor()))
+            .collect())
+    }
+
+    /// Return the ref protection rules that do not allow the actor of the request
+    /// to create, move or remove matching refs.
+    /// Return Err for internal errors.
+    ///
+    /// The default implementation only allows the roles the actor has assumed.
+    /// Implementations should override this if users can be assigned to roles.
+    async fn restricting_ref_protection_rules(
+        &self,
+        metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+        _table_id: TableIdentUuid,
+        rules: Vec<RefProtectionRule>,
+    ) -> Result<Vec<RefProtectionRule>> {
+        Ok(rules
+            .into_iter()
+            .filter(|rule| !rule.allows_actor_directly(metadata.actor()))
+            .collect())
+    }
+
     /// Hook that is called when a user is deleted.
     async fn delete_user(&self, metadata: &RequestMetadata, user_id: UserId) -> Result<()>;
 
@@ -309,6 +439,26 @@ where
         namespace_id: NamespaceIdentUuid,
     ) -> Result<()>;
 
+    /// Hook that is called when a namespace is moved to a new parent.
+    /// This is used to replace the parent relations of the namespace.
+    async fn move_namespace(
+        &self,
+        metadata: &RequestMetadata,
+        namespace_id: NamespaceIdentUuid,
+        old_parent: NamespaceParent,
+        new_parent: NamespaceParent,
+    ) -> Result<()>;
+
+    /// Hook that is called when a soft-deleted namespace is restored.
+    /// Relations of the namespace are kept during soft deletion, but its parent
+    /// might have been re-created in the meantime.
+    async fn undrop_namespace(
+        &self,
+        metadata: &RequestMetadata,
+        namespace_id: NamespaceIdentUuid,
+        parent: NamespaceParent,
+    ) -> Result<()>;
+
     /// Hook that is called when a new table is created.
     /// This is used to set up the initial permissions for the table.
     async fn create_table(
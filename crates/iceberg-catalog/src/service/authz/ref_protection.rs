@@ -0,0 +1,435 @@
+//! Protection rules for branches and tags of tables.
+//!
+//! Rules are defined for a warehouse, a namespace including its children or a single
+//! table and match refs by name. They are enforced whenever changes to a table are
+//! committed. Which rules restrict the committer of a change is decided by the [`Authorizer`].
+
+use super::Authorizer;
+use crate::api::iceberg::v1::Result;
+use crate::request_metadata::RequestMetadata;
+use crate::service::{Actor, RoleId, TableIdentUuid};
+use crate::WarehouseIdent;
+use iceberg::spec::TableMetadata;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use utoipa::ToSchema;
+
+/// Objects a ref protection rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum RefProtectionScope {
+    /// All tables of the warehouse.
+    Warehouse,
+    /// All tables of the namespace and its children.
+    #[serde(rename_all = "kebab-case")]
+    Namespace { namespace_id: uuid::Uuid },
+    /// A single table.
+    #[serde(rename_all = "kebab-case")]
+    Table { table_id: uuid::Uuid },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RefProtectionRule {
+    /// Id of the rule.
+    pub rule_id: uuid::Uuid,
+    /// Objects the rule applies to.
+    pub scope: RefProtectionScope,
+    /// Names of the protected branches and tags.
+    /// `*` matches any sequence of characters, for example `release-*`.
+    pub ref_pattern: String,
+    /// Roles whose assignees may create, move or remove matching refs.
+    /// If empty, all principals that may commit to the table are allowed.
+    #[schema(value_type = Vec<uuid::Uuid>)]
+    pub allowed_committers: Vec<RoleId>,
+    /// Matching refs must not be removed.
+    pub prevent_deletion: bool,
+    /// Matching refs may only be moved to descendants of their current snapshot.
+    pub fast_forward_only: bool,
+}
+
+impl RefProtectionRule {
+    /// Returns true if the name of the ref matches the pattern of the rule.
+    #[must_use]
+    pub fn matches(&self, ref_name: &str) -> bool {
+        glob_matches(&self.ref_pattern, ref_name)
+    }
+
+    /// Returns true if the rule does not restrict committers or the actor has
+    /// assumed one of the allowed roles.
+    /// Assignments of users to roles are not considered.
+    #[must_use]
+    pub fn allows_actor_directly(&self, actor: &Actor) -> bool {
+        if self.allowed_committers.is_empty() {
+            return true;
+        }
+        match actor {
+            Actor::Role { assumed_role, .. } => self.allowed_committers.contains(assumed_role),
+            Actor::Principal(_) | Actor::Anonymous => false,
+        }
+    }
+}
+
+/// Match `name` against `pattern`, where `*` matches any sequence of characters.
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    // `split` always yields at least one element
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = parts.collect::<Vec<_>>();
+    let Some(last) = parts.pop() else {
+        // No wildcard in pattern
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Names of the refs that are created, moved or removed by a commit.
+///
+/// Refs are compared on the metadata before and after the commit instead of looking at
+/// `SetSnapshotRef` and `RemoveSnapshotRef` updates only, so that refs changed as a
+/// side effect of other updates, such as `AddSnapshot` or `RemoveSnapshots`, are
+/// covered as well.
+#[must_use]
+pub fn modified_refs<'a>(
+    previous_metadata: &'a TableMetadata,
+    new_metadata: &'a TableMetadata,
+) -> BTreeSet<&'a str> {
+    previous_metadata
+        .refs()
+        .keys()
+        .chain(new_metadata.refs().keys())
+        .map(String::as_str)
+        .filter(|ref_name| {
+            previous_metadata.refs().get(*ref_name) != new_metadata.refs().get(*ref_name)
+        })
+        .collect()
+}
+
+/// Returns true if `ancestor_id` is `snapshot_id` or one of its ancestors in `metadata`.
+fn is_ancestor(metadata: &TableMetadata, ancestor_id: i64, snapshot_id: i64) -> bool {
+    let mut current = Some(snapshot_id);
+    while let Some(id) = current {
+        if id == ancestor_id {
+            return true;
+        }
+        current = metadata
+            .snapshot_by_id(id)
+            .and_then(|snapshot| snapshot.parent_snapshot_id());
+    }
+    false
+}
+
+/// Check the changes of a commit to refs against the protection rules of the table.
+/// `restricting` contains the ids of the rules that do not allow the committer to modify
+/// matching refs.
+///
+/// # Errors
+/// Returns a forbidden error for the first violated rule.
+pub fn check_ref_protection(
+    previous_metadata: &TableMetadata,
+    new_metadata: &TableMetadata,
+    rules: &[RefProtectionRule],
+    restricting: &HashSet<uuid::Uuid>,
+) -> Result<(), ErrorModel> {
+    for ref_name in modified_refs(previous_metadata, new_metadata) {
+        let previous_ref = previous_metadata.refs().get(ref_name);
+        let new_ref = new_metadata.refs().get(ref_name);
+
+        for rule in rules.iter().filter(|rule| rule.matches(ref_name)) {
+            let violation = if restricting.contains(&rule.rule_id) {
+                Some(("not allowed to modify", "RefProtected"))
+            } else {
+                match (previous_ref, new_ref) {
+                    (Some(_), None) if rule.prevent_deletion => {
+                        Some(("must not be removed", "RefProtected"))
+                    }
+                    (Some(previous_ref), Some(new_ref))
+                        if rule.fast_forward_only
+                            && !is_ancestor(
+                                new_metadata,
+                                previous_ref.snapshot_id,
+                                new_ref.snapshot_id,
+                            ) =>
+                    {
+                        Some(("may only be fast-forwarded", "RefNotFastForward"))
+                    }
+                    _ => None,
+                }
+            };
+
+            if let Some((reason, r#type)) = violation {
+                return Err(ErrorModel::forbidden(
+                    format!(
+                        "Ref `{ref_name}` of table {} is protected and {reason}",
+                        new_metadata.uuid()
+                    ),
+                    r#type,
+                    None,
+                )
+                .append_detail(format!("Protection rule: {}", rule.rule_id)));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Enforce the protection rules of a table on the changes of a commit.
+/// Only rules matching a modified ref are evaluated.
+///
+/// # Errors
+/// - The authorizer fails to evaluate the allowed committers
+/// - A rule is violated
+pub(crate) async fn require_ref_protection<A: Authorizer>(
+    authorizer: &A,
+    metadata: &RequestMetadata,
+    warehouse_id: WarehouseIdent,
+    table_id: TableIdentUuid,
+    rules: Vec<RefProtectionRule>,
+    previous_metadata: &TableMetadata,
+    new_metadata: &TableMetadata,
+) -> Result<()> {
+    let modified = modified_refs(previous_metadata, new_metadata);
+    let rules = rules
+        .into_iter()
+        .filter(|rule| modified.iter().any(|ref_name| rule.matches(ref_name)))
+        .collect::<Vec<_>>();
+    if rules.is_empty() {
+        return Ok(());
+    }
+
+    let restricting = authorizer
+        .restricting_ref_protection_rules(metadata, warehouse_id, table_id, rules.clone())
+        .await?
+        .into_iter()
+        .map(|rule| rule.rule_id)
+        .collect();
+    check_ref_protection(previous_metadata, new_metadata, &rules, &restricting)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::authn::UserId;
+    use iceberg::spec::{
+        FormatVersion, NestedField, Operation, PrimitiveType, Schema, Snapshot, SnapshotReference,
+        SnapshotRetention, Summary, TableMetadataBuilder, Type, UnboundPartitionSpec, MAIN_BRANCH,
+    };
+    use iceberg::TableUpdate;
+    use std::collections::HashMap;
+
+    fn rule(
+        ref_pattern: &str,
+        allowed_committers: Vec<RoleId>,
+        prevent_deletion: bool,
+        fast_forward_only: bool,
+    ) -> RefProtectionRule {
+        RefProtectionRule {
+            rule_id: uuid::Uuid::now_v7(),
+            scope: RefProtectionScope::Warehouse,
+            ref_pattern: ref_pattern.to_string(),
+            allowed_committers,
+            prevent_deletion,
+            fast_forward_only,
+        }
+    }
+
+    fn snapshot(snapshot_id: i64, parent_snapshot_id: Option<i64>) -> Snapshot {
+        Snapshot::builder()
+            .with_snapshot_id(snapshot_id)
+            .with_parent_snapshot_id(parent_snapshot_id)
+            .with_sequence_number(snapshot_id)
+            .with_timestamp_ms(chrono::Utc::now().timestamp_millis())
+            .with_manifest_list(format!(
+                "s3://bucket/table/metadata/snap-{snapshot_id}.avro"
+            ))
+            .with_summary(Summary {
+                operation: Operation::Append,
+                other: HashMap::new(),
+            })
+            .build()
+    }
+
+    fn branch(snapshot_id: i64) -> SnapshotReference {
+        SnapshotReference {
+            snapshot_id,
+            retention: SnapshotRetention::Branch {
+                min_snapshots_to_keep: None,
+                max_snapshot_age_ms: None,
+                max_ref_age_ms: None,
+            },
+        }
+    }
+
+    /// Table with snapshots 1 <- 2 and 1 <- 3 and `main` pointing to 2.
+    fn table_metadata() -> TableMetadata {
+        let schema = Schema::builder()
+            .with_fields(vec![NestedField::required(
+                1,
+                "id",
+                Type::Primitive(PrimitiveType::Int),
+            )
+            .into()])
+            .build()
+            .unwrap();
+        TableMetadataBuilder::new(
+            schema,
+            UnboundPartitionSpec::builder().build(),
+            iceberg::spec::SortOrder::unsorted_order(),
+            "s3://bucket/table".to_string(),
+            FormatVersion::V2,
+            HashMap::new(),
+        )
+        .unwrap()
+        .add_snapshot(snapshot(1, None))
+        .unwrap()
+        .add_snapshot(snapshot(2, Some(1)))
+        .unwrap()
+        .add_snapshot(snapshot(3, Some(1)))
+        .unwrap()
+        .set_ref(MAIN_BRANCH, branch(2))
+        .unwrap()
+        .build()
+        .unwrap()
+        .metadata
+    }
+
+    fn apply(metadata: &TableMetadata, updates: &[TableUpdate]) -> TableMetadata {
+        updates
+            .iter()
+            .cloned()
+            .fold(metadata.clone().into_builder(None), |builder, update| {
+                TableUpdate::apply(update, builder).unwrap()
+            })
+            .build()
+            .unwrap()
+            .metadata
+    }
+
+    fn set_main(snapshot_id: i64) -> TableUpdate {
+        TableUpdate::SetSnapshotRef {
+            ref_name: MAIN_BRANCH.to_string(),
+            reference: branch(snapshot_id),
+        }
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("main", "main"));
+        assert!(!glob_matches("main", "main2"));
+        assert!(glob_matches("*", "anything"));
+        assert!(glob_matches("release-*", "release-1.0"));
+        assert!(!glob_matches("release-*", "dev-release-1.0"));
+        assert!(glob_matches("*-prod", "eu-prod"));
+        assert!(glob_matches("a*b*c", "aXbYc"));
+        assert!(!glob_matches("a*b*c", "aXcYb"));
+        assert!(!glob_matches("ab*ba", "aba"));
+    }
+
+    #[test]
+    fn test_allows_actor_directly() {
+        let role = RoleId::default();
+        let user = UserId::oidc("alice").unwrap();
+        let restricted = rule("main", vec![role], false, false);
+        assert!(restricted.allows_actor_directly(&Actor::Role {
+            principal: user.clone(),
+            assumed_role: role,
+        }));
+        assert!(!restricted.allows_actor_directly(&Actor::Principal(user.clone())));
+        assert!(rule("main", vec![], false, false).allows_actor_directly(&Actor::Principal(user)));
+    }
+
+    #[test]
+    fn test_restricted_committer_is_rejected() {
+        let previous = table_metadata();
+        let updates = [set_main(3)];
+        let new = apply(&previous, &updates);
+        let rules = [rule("main", vec![RoleId::default()], false, false)];
+
+        check_ref_protection(&previous, &new, &rules, &HashSet::new()).unwrap();
+        let restricting = HashSet::from([rules[0].rule_id]);
+        let err = check_ref_protection(&previous, &new, &rules, &restricting).unwrap_err();
+        assert_eq!(err.r#type, "RefProtected");
+    }
+
+    #[test]
+    fn test_prevent_deletion() {
+        let previous = table_metadata();
+        let updates = [TableUpdate::RemoveSnapshotRef {
+            ref_name: MAIN_BRANCH.to_string(),
+        }];
+        let new = apply(&previous, &updates);
+
+        let rules = [rule("ma*", vec![], true, false)];
+        let err = check_ref_protection(&previous, &new, &rules, &HashSet::new()).unwrap_err();
+        assert_eq!(err.r#type, "RefProtected");
+
+        let rules = [rule("dev", vec![], true, false)];
+        check_ref_protection(&previous, &new, &rules, &HashSet::new()).unwrap();
+    }
+
+    #[test]
+    fn test_fast_forward_only() {
+        let previous = table_metadata();
+        let rules = [rule("main", vec![], false, true)];
+
+        // 2 -> 4 is a fast-forward
+        let updates = [
+            TableUpdate::AddSnapshot {
+                snapshot: snapshot(4, Some(2)),
+            },
+            set_main(4),
+        ];
+        let new = apply(&previous, &updates);
+        check_ref_protection(&previous, &new, &rules, &HashSet::new()).unwrap();
+
+        // 2 -> 3 is not
+        let updates = [set_main(3)];
+        let new = apply(&previous, &updates);
+        let err = check_ref_protection(&previous, &new, &rules, &HashSet::new()).unwrap_err();
+        assert_eq!(err.r#type, "RefNotFastForward");
+
+        // Adding a snapshot without moving a ref does not modify refs
+        let updates = [TableUpdate::AddSnapshot {
+            snapshot: snapshot(4, Some(3)),
+        }];
+        let new = apply(&previous, &updates);
+        assert!(modified_refs(&previous, &new).is_empty());
+        check_ref_protection(&previous, &new, &rules, &HashSet::new()).unwrap();
+
+        // Creating a new ref is always allowed
+        let updates = [TableUpdate::SetSnapshotRef {
+            ref_name: "main-2".to_string(),
+            reference: branch(3),
+        }];
+        let new = apply(&previous, &updates);
+        let rules = [rule("main*", vec![], false, true)];
+        check_ref_protection(&previous, &new, &rules, &HashSet::new()).unwrap();
+    }
+
+    #[test]
+    fn test_scope_serde() {
+        let namespace_id = uuid::Uuid::now_v7();
+        let scope: RefProtectionScope = serde_json::from_value(serde_json::json!({
+            "type": "namespace",
+            "namespace-id": namespace_id,
+        }))
+        .unwrap();
+        assert_eq!(scope, RefProtectionScope::Namespace { namespace_id });
+        assert_eq!(
+            serde_json::to_value(RefProtectionScope::Warehouse).unwrap(),
+            serde_json::json!({"type": "warehouse"})
+        );
+    }
+}
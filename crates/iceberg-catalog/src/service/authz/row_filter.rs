@@ -0,0 +1,475 @@
+//! Row-level filter policies.
+//!
+//! A row filter restricts the rows of a table that assignees of a role may read. Filters
+//! are stored by the catalog per table and role, and are validated against the table schema
+//! whenever they are set or the schema of the table changes. Which filters apply to the
+//! actor of a request is decided by the [`Authorizer`].
+
+use super::Authorizer;
+use crate::api::iceberg::v1::Result;
+use crate::request_metadata::RequestMetadata;
+use crate::service::contract_verification::{
+    updated_current_schema, ContractVerification, ContractVerificationOutcome,
+};
+use crate::service::{Actor, Catalog, RoleId, TableIdentUuid, TabularIdentUuid};
+use crate::WarehouseIdent;
+use async_trait::async_trait;
+use iceberg::spec::{PrimitiveType, Schema, TableMetadata, Type, ViewMetadata};
+use iceberg::{TableIdent, TableUpdate};
+use iceberg_ext::catalog::rest::{ErrorModel, ViewUpdate};
+use iceberg_ext::configs::table::{custom, TableProperties};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+use utoipa::ToSchema;
+
+/// Table config key containing the row filter of a principal as Iceberg expression JSON
+pub const ROW_FILTER_KEY: &str = "lakekeeper.row-filter";
+
+/// Filter expression in the JSON representation of Iceberg expressions.
+/// Terms reference columns by their name in the current schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum RowFilterExpression {
+    True,
+    False,
+    And {
+        left: Box<RowFilterExpression>,
+        right: Box<RowFilterExpression>,
+    },
+    Or {
+        left: Box<RowFilterExpression>,
+        right: Box<RowFilterExpression>,
+    },
+    Not {
+        child: Box<RowFilterExpression>,
+    },
+    IsNull {
+        term: String,
+    },
+    NotNull {
+        term: String,
+    },
+    IsNan {
+        term: String,
+    },
+    NotNan {
+        term: String,
+    },
+    Lt {
+        term: String,
+        value: serde_json::Value,
+    },
+    LtEq {
+        term: String,
+        value: serde_json::Value,
+    },
+    Gt {
+        term: String,
+        value: serde_json::Value,
+    },
+    GtEq {
+        term: String,
+        value: serde_json::Value,
+    },
+    Eq {
+        term: String,
+        value: serde_json::Value,
+    },
+    NotEq {
+        term: String,
+        value: serde_json::Value,
+    },
+    StartsWith {
+        term: String,
+        value: serde_json::Value,
+    },
+    NotStartsWith {
+        term: String,
+        value: serde_json::Value,
+    },
+    In {
+        term: String,
+        values: Vec<serde_json::Value>,
+    },
+    NotIn {
+        term: String,
+        values: Vec<serde_json::Value>,
+    },
+}
+
+impl RowFilterExpression {
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self::Or {
+            left: Box::new(self),
+            right: Box::new(other),
+        }
+    }
+
+    /// Check that all terms reference primitive columns of `schema` and that
+    /// literals match the type of the column.
+    ///
+    /// # Errors
+    /// Returns a description of the first problem found.
+    pub fn validate(&self, schema: &Schema) -> std::result::Result<(), String> {
+        match self {
+            Self::True | Self::False => Ok(()),
+            Self::And { left, right } | Self::Or { left, right } => {
+                left.validate(schema)?;
+                right.validate(schema)
+            }
+            Self::Not { child } => child.validate(schema),
+            Self::IsNull { term } | Self::NotNull { term } => {
+                primitive_type(schema, term).map(|_| ())
+            }
+            Self::IsNan { term } | Self::NotNan { term } => match primitive_type(schema, term)? {
+                PrimitiveType::Float | PrimitiveType::Double => Ok(()),
+                t => Err(format!(
+                    "NaN checks require a float column, `{term}` is {t}"
+                )),
+            },
+            Self::StartsWith { term, value } | Self::NotStartsWith { term, value } => {
+                match primitive_type(schema, term)? {
+                    PrimitiveType::String if value.is_string() => Ok(()),
+                    PrimitiveType::String => Err(format!(
+                        "Value `{value}` for column `{term}` is not a string"
+                    )),
+                    t => Err(format!(
+                        "Prefix checks require a string column, `{term}` is {t}"
+                    )),
+                }
+            }
+            Self::Lt { term, value }
+            | Self::LtEq { term, value }
+            | Self::Gt { term, value }
+            | Self::GtEq { term, value }
+            | Self::Eq { term, value }
+            | Self::NotEq { term, value } => {
+                validate_literal(term, &primitive_type(schema, term)?, value)
+            }
+            Self::In { term, values } | Self::NotIn { term, values } => {
+                let field_type = primitive_type(schema, term)?;
+                values
+                    .iter()
+                    .try_for_each(|value| validate_literal(term, &field_type, value))
+            }
+        }
+    }
+}
+
+fn primitive_type(schema: &Schema, term: &str) -> std::result::Result<PrimitiveType, String> {
+    let field = schema
+        .field_by_name(term)
+        .ok_or_else(|| format!("Column `{term}` does not exist"))?;
+    match field.field_type.as_ref() {
+        Type::Primitive(primitive) => Ok(primitive.clone()),
+        _ => Err(format!("Column `{term}` is not a primitive column")),
+    }
+}
+
+fn validate_literal(
+    term: &str,
+    field_type: &PrimitiveType,
+    value: &serde_json::Value,
+) -> std::result::Result<(), String> {
+    let valid = match field_type {
+        PrimitiveType::Boolean => value.is_boolean(),
+        PrimitiveType::Int | PrimitiveType::Long => value.is_i64() || value.is_u64(),
+        PrimitiveType::Float | PrimitiveType::Double => value.is_number(),
+        PrimitiveType::Decimal { .. } => value.is_number() || value.is_string(),
+        _ => value.is_string(),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Value `{value}` is not valid for column `{term}` of type {field_type}"
+        ))
+    }
+}
+
+/// Filter restricting the rows assignees of a role may read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RowFilter {
+    /// Role the filter applies to.
+    #[schema(value_type = uuid::Uuid)]
+    pub role_id: RoleId,
+    /// Iceberg expression JSON, for example `{"type": "eq", "term": "region", "value": "EU"}`.
+    #[schema(value_type = Object)]
+    pub filter: RowFilterExpression,
+}
+
+impl RowFilter {
+    /// Returns true if the actor has assumed the role of the filter.
+    /// Assignments of users to roles are not considered.
+    #[must_use]
+    pub fn applies_to_actor_directly(&self, actor: &Actor) -> bool {
+        matches!(actor, Actor::Role { assumed_role, .. } if *assumed_role == self.role_id)
+    }
+}
+
+/// Validate row filters against the schema of a table.
+///
+/// # Errors
+/// Returns a bad request error for the first invalid filter.
+pub fn validate_row_filters(filters: &[RowFilter], schema: &Schema) -> Result<(), ErrorModel> {
+    filters.iter().try_for_each(|filter| {
+        filter.filter.validate(schema).map_err(|reason| {
+            ErrorModel::bad_request(
+                format!(
+                    "Row filter of role {} is not valid for schema {}: {reason}",
+                    filter.role_id,
+                    schema.schema_id()
+                ),
+                "InvalidRowFilter",
+                None,
+            )
+        })
+    })
+}
+
+/// Table config that tells query engines which rows to filter.
+#[must_use]
+pub fn row_filter_table_config(filter: &RowFilterExpression) -> TableProperties {
+    let mut config = TableProperties::default();
+    config.insert(&custom::CustomConfig {
+        key: ROW_FILTER_KEY.to_string(),
+        value: serde_json::to_string(filter).unwrap_or_default(),
+    });
+    config
+}
+
+/// Get the effective row filter of the actor of the request on a table.
+/// If multiple filters apply, rows matching any of them are visible.
+/// Returns `None` if no filter applies.
+///
+/// # Errors
+/// - Loading the filters from the catalog fails
+/// - The authorizer fails to determine the applicable filters
+pub(crate) async fn get_row_filter<C: Catalog, A: Authorizer>(
+    authorizer: &A,
+    metadata: &RequestMetadata,
+    warehouse_id: WarehouseIdent,
+    table_id: TableIdentUuid,
+    catalog_state: C::State,
+) -> Result<Option<RowFilterExpression>> {
+    let filters = C::get_row_filters(table_id, catalog_state).await?;
+    if filters.is_empty() {
+        return Ok(None);
+    }
+    let applicable = authorizer
+        .applicable_row_filters(metadata, warehouse_id, table_id, filters)
+        .await?;
+    Ok(applicable
+        .into_iter()
+        .map(|filter| filter.filter)
+        .reduce(RowFilterExpression::or))
+}
+
+/// Blocks schema changes that would invalidate row filters of a table.
+pub struct RowFilterVerifier<C: Catalog> {
+    catalog_state: C::State,
+}
+
+impl<C: Catalog> RowFilterVerifier<C> {
+    #[must_use]
+    pub fn new(catalog_state: C::State) -> Self {
+        Self { catalog_state }
+    }
+}
+
+impl<C: Catalog> Debug for RowFilterVerifier<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RowFilterVerifier").finish()
+    }
+}
+
+#[async_trait]
+impl<C: Catalog> ContractVerification for RowFilterVerifier<C> {
+    fn name(&self) -> &'static str {
+        "RowFilterVerifier"
+    }
+
+    async fn check_table_updates(
+        &self,
+        table_updates: &[TableUpdate],
+        current_metadata: &TableMetadata,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        let Some(schema) = updated_current_schema(table_updates, current_metadata) else {
+            return Ok(ContractVerificationOutcome::Clear {});
+        };
+        let filters = C::get_row_filters(
+            TableIdentUuid::from(current_metadata.uuid()),
+            self.catalog_state.clone(),
+        )
+        .await
+        .map_err(|e| e.error)?;
+
+        Ok(match validate_row_filters(&filters, &schema) {
+            Ok(()) => ContractVerificationOutcome::Clear {},
+            Err(error_model) => ContractVerificationOutcome::Violation {
+                error_model: error_model
+                    .append_detail("Update or remove the row filters before changing the schema"),
+            },
+        })
+    }
+
+    async fn check_view_updates(
+        &self,
+        _view_updates: &[ViewUpdate],
+        _current_metadata: &ViewMetadata,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        Ok(ContractVerificationOutcome::Clear {})
+    }
+
+    async fn check_drop(
+        &self,
+        _table_ident_uuid: TabularIdentUuid,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        Ok(ContractVerificationOutcome::Clear {})
+    }
+
+    async fn check_rename(
+        &self,
+        _source: TabularIdentUuid,
+        _destination: &TableIdent,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        Ok(ContractVerificationOutcome::Clear {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::tables::create_table_request_into_table_metadata;
+    use iceberg::spec::NestedField;
+    use iceberg_ext::catalog::rest::CreateTableRequest;
+
+    fn schema(schema_id: i32) -> Schema {
+        Schema::builder()
+            .with_schema_id(schema_id)
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)).into(),
+                NestedField::optional(2, "region", Type::Primitive(PrimitiveType::String)).into(),
+                NestedField::optional(3, "score", Type::Primitive(PrimitiveType::Double)).into(),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    fn parse(value: serde_json::Value) -> RowFilterExpression {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_expression_serde() {
+        let value = serde_json::json!({
+            "type": "and",
+            "left": {"type": "eq", "term": "region", "value": "EU"},
+            "right": {"type": "not", "child": {"type": "in", "term": "id", "values": [1, 2]}}
+        });
+        let expression = parse(value.clone());
+        assert_eq!(
+            expression,
+            RowFilterExpression::And {
+                left: Box::new(RowFilterExpression::Eq {
+                    term: "region".to_string(),
+                    value: "EU".into(),
+                }),
+                right: Box::new(RowFilterExpression::Not {
+                    child: Box::new(RowFilterExpression::In {
+                        term: "id".to_string(),
+                        values: vec![1.into(), 2.into()],
+                    }),
+                }),
+            }
+        );
+        assert_eq!(serde_json::to_value(&expression).unwrap(), value);
+        assert_eq!(
+            parse(serde_json::json!({"type": "lt-eq", "term": "id", "value": 5})),
+            RowFilterExpression::LtEq {
+                term: "id".to_string(),
+                value: 5.into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_validate_expression() {
+        let schema = schema(0);
+        for valid in [
+            serde_json::json!({"type": "eq", "term": "region", "value": "EU"}),
+            serde_json::json!({"type": "gt", "term": "score", "value": 0.5}),
+            serde_json::json!({"type": "not-in", "term": "id", "values": [1, 2]}),
+            serde_json::json!({"type": "is-nan", "term": "score"}),
+            serde_json::json!({"type": "starts-with", "term": "region", "value": "E"}),
+        ] {
+            assert_eq!(parse(valid).validate(&schema), Ok(()));
+        }
+
+        for invalid in [
+            serde_json::json!({"type": "eq", "term": "country", "value": "DE"}),
+            serde_json::json!({"type": "eq", "term": "id", "value": "one"}),
+            serde_json::json!({"type": "in", "term": "id", "values": [1, "two"]}),
+            serde_json::json!({"type": "is-nan", "term": "region"}),
+            serde_json::json!({"type": "starts-with", "term": "id", "value": "1"}),
+            serde_json::json!({
+                "type": "or",
+                "left": {"type": "true"},
+                "right": {"type": "is-null", "term": "missing"}
+            }),
+        ] {
+            assert!(parse(invalid).validate(&schema).is_err());
+        }
+    }
+
+    #[test]
+    fn test_updated_current_schema() {
+        let metadata = create_table_request_into_table_metadata(
+            TableIdentUuid::from(uuid::Uuid::now_v7()),
+            CreateTableRequest {
+                name: "my_table".to_string(),
+                location: Some("s3://my_bucket/my_table".to_string()),
+                schema: schema(0),
+                partition_spec: None,
+                write_order: None,
+                stage_create: None,
+                properties: None,
+            },
+        )
+        .unwrap();
+        let update =
+            |value: serde_json::Value| -> TableUpdate { serde_json::from_value(value).unwrap() };
+        let add_schema = update(serde_json::json!({
+            "action": "add-schema",
+            "schema": {
+                "type": "struct",
+                "schema-id": 1,
+                "fields": [{"id": 1, "name": "id", "required": true, "type": "long"}]
+            }
+        }));
+
+        assert!(updated_current_schema(&[], &metadata).is_none());
+        assert!(updated_current_schema(std::slice::from_ref(&add_schema), &metadata).is_none());
+
+        let schema = updated_current_schema(
+            &[
+                add_schema,
+                update(serde_json::json!({"action": "set-current-schema", "schema-id": -1})),
+            ],
+            &metadata,
+        )
+        .unwrap();
+        assert!(schema.field_by_name("region").is_none());
+        let filter = RowFilter {
+            role_id: RoleId::default(),
+            filter: parse(serde_json::json!({"type": "eq", "term": "region", "value": "EU"})),
+        };
+        assert!(validate_row_filters(&[filter.clone()], metadata.current_schema()).is_ok());
+        assert_eq!(
+            validate_row_filters(&[filter], &schema).unwrap_err().r#type,
+            "InvalidRowFilter"
+        );
+    }
+}
@@ -1,7 +1,8 @@
-use super::authz::TableUuid;
+use super::authz::{ColumnPolicy, RefProtectionRule, RowFilter, TableUuid};
 use super::{
-    storage::StorageProfile, NamespaceIdentUuid, ProjectIdent, RoleId, TableIdentUuid,
-    ViewIdentUuid, WarehouseIdent, WarehouseStatus,
+    storage::{StorageProfile, TabularLocationTemplate},
+    NamespaceIdentUuid, ProjectIdent, RoleId, TableIdentUuid, ViewIdentUuid, WarehouseIdent,
+    WarehouseStatus,
 };
 pub use crate::api::iceberg::v1::{
     CreateNamespaceRequest, CreateNamespaceResponse, ListNamespacesQuery, NamespaceIdent, Result,
@@ -16,6 +17,7 @@ use crate::api::management::v1::user::{
     ListUsersResponse, SearchUserResponse, User, UserLastUpdatedWith, UserType,
 };
 use crate::api::management::v1::warehouse::TabularDeleteProfile;
+use crate::service::table_property_policy::TablePropertyPolicy;
 use crate::service::tabular_idents::{TabularIdentOwned, TabularIdentUuid};
 use iceberg::spec::{TableMetadata, ViewMetadata};
 use iceberg_ext::catalog::rest::{CatalogConfig, ErrorModel};
@@ -119,6 +121,13 @@ pub struct GetWarehouseResponse {
     pub status: WarehouseStatus,
     /// Tabular delete profile used for the warehouse.
     pub tabular_delete_profile: TabularDeleteProfile,
+    /// Properties of the warehouse, i.e. `table-default.*` and `table-override.*`.
+    pub properties: HashMap<String, String>,
+    /// Policy for the properties of tables and views in the warehouse.
+    pub table_property_policy: TablePropertyPolicy,
+    /// Template for the default location of tables and views.
+    /// If `None`, they are placed at `{namespace-location}/{uuid}`.
+    pub location_template: Option<TabularLocationTemplate>,
 }
 
 #[derive(Debug, Clone)]
@@ -145,6 +154,14 @@ pub struct TableCreation<'c> {
     pub(crate) table_metadata: TableMetadata,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableAndNamespaceProperties {
+    /// Properties of the table.
+    pub table_properties: HashMap<String, String>,
+    /// Properties of the namespace the table is in.
+    pub namespace_properties: HashMap<String, String>,
+}
+
 #[derive(Debug, Clone)]
 pub enum CreateOrUpdateUserResponse {
     Created(User),
@@ -269,12 +286,74 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<Option<NamespaceIdentUuid>>;
 
+    /// Get the properties of `namespace` and all of its ancestors.
+    /// The result is ordered from the top-level namespace down to `namespace` itself.
+    /// Namespaces that do not exist are skipped.
+    async fn get_namespace_hierarchy_properties<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace: &NamespaceIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<HashMap<String, String>>>;
+
     async fn drop_namespace<'a>(
         warehouse_id: WarehouseIdent,
         namespace_id: NamespaceIdentUuid,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Get a namespace, all of its children and the tables and views they contain,
+    /// including staged and soft-deleted ones.
+    /// Fails with a not-found error if the namespace does not exist.
+    async fn get_namespace_subtree<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<NamespaceSubtree>;
+
+    /// Rename a namespace, moving it and all of its children to `destination`.
+    /// Ids of namespaces, tables and views as well as their locations are kept.
+    /// Fails with a conflict error if `destination` or one of the new child
+    /// namespaces already exists.
+    async fn rename_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        destination: &NamespaceIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Soft-delete a namespace, all of its children and the tables and views they contain
+    /// that are not deleted yet. All entities share the same deletion timestamp.
+    async fn mark_namespace_as_deleted<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Undrop a soft-deleted namespace.
+    ///
+    /// Restores the namespace together with the children, tables and views that
+    /// were deleted with it. Fails if the parent of the namespace does not exist or if
+    /// a namespace with the same name exists.
+    async fn undrop_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<UndroppedNamespace>;
+
+    async fn list_deleted_namespaces<'a>(
+        warehouse_id: WarehouseIdent,
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<PaginatedMapping<NamespaceIdentUuid, (NamespaceIdent, DeletionDetails)>>;
+
+    /// Finally drop a soft-deleted namespace including all tables and views it contains.
+    /// Children of the namespace are not affected.
+    async fn drop_deleted_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<DroppedTabular>>;
+
     /// Update the properties of a namespace.
     ///
     /// The properties are the final key-value properties that should
@@ -341,6 +420,22 @@ where
         catalog_state: Self::State,
     ) -> Result<Option<GetTableMetadataResponse>>;
 
+    /// Get the properties of a table and of its namespace.
+    /// Also returns properties of soft-deleted tables.
+    /// Return Ok(None) if the table does not exist.
+    async fn get_table_and_namespace_properties(
+        table_id: TableIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<Option<TableAndNamespaceProperties>>;
+
+    /// Get the namespace of a table or view, irrespective of the warehouse it belongs to.
+    /// Also returns namespaces of soft-deleted tabulars.
+    /// Return Ok(None) if the tabular does not exist.
+    async fn get_tabular_namespace(
+        tabular_id: TabularIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<Option<GetNamespaceResponse>>;
+
     /// Get table metadata by location.
     /// Return Ok(None) if the table does not exist.
     async fn get_table_metadata_by_s3_location(
@@ -392,6 +487,61 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Get the column policies of a table, ordered by field id.
+    async fn get_column_policies(
+        table_id: TableIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<Vec<ColumnPolicy>>;
+
+    /// Replace all column policies of a table.
+    async fn set_column_policies<'a>(
+        table_id: TableIdentUuid,
+        policies: &[ColumnPolicy],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Get the row filters of a table, ordered by role id.
+    async fn get_row_filters(
+        table_id: TableIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<Vec<RowFilter>>;
+
+    /// Replace all row filters of a table.
+    async fn set_row_filters<'a>(
+        table_id: TableIdentUuid,
+        filters: &[RowFilter],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// List all ref protection rules of a warehouse.
+    async fn list_ref_protection_rules(
+        warehouse_id: WarehouseIdent,
+        catalog_state: Self::State,
+    ) -> Result<Vec<RefProtectionRule>>;
+
+    /// Get the ref protection rules that apply to a table, including rules
+    /// of its warehouse and of its namespace or any parent namespace.
+    async fn get_table_ref_protection_rules<'a>(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<RefProtectionRule>>;
+
+    /// Create a ref protection rule.
+    /// Return not found if the namespace or table of the scope does not exist in the warehouse.
+    async fn create_ref_protection_rule<'a>(
+        warehouse_id: WarehouseIdent,
+        rule: &RefProtectionRule,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Delete a ref protection rule.
+    async fn delete_ref_protection_rule<'a>(
+        warehouse_id: WarehouseIdent,
+        rule_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
     // ---------------- Role Management API ----------------
     async fn create_role<'a>(
         role_id: RoleId,
@@ -548,6 +698,27 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Replace all properties of a warehouse.
+    async fn set_warehouse_properties<'a>(
+        warehouse_id: WarehouseIdent,
+        properties: &HashMap<String, String>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Replace the table property policy of a warehouse.
+    async fn set_warehouse_table_property_policy<'a>(
+        warehouse_id: WarehouseIdent,
+        policy: &TablePropertyPolicy,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the location template of a warehouse. `None` restores the default layout.
+    async fn set_warehouse_location_template<'a>(
+        warehouse_id: WarehouseIdent,
+        location_template: Option<&TabularLocationTemplate>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
     /// Rename a project.
     async fn rename_project<'a>(
         project_id: ProjectIdent,
@@ -629,6 +800,47 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> Result<()>;
 
+    /// Link a view to the storage table of a materialized view and replace the
+    /// recorded snapshots of its source tables.
+    async fn set_materialized_view<'a>(
+        view_id: ViewIdentUuid,
+        storage_table_id: TableIdentUuid,
+        sources: &[MaterializedViewSource],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Storage table and refresh state of a view.
+    /// Return Ok(None) if the view is not a materialized view.
+    async fn get_materialized_view<'a>(
+        view_id: ViewIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<MaterializedView>>;
+
+    /// Active materialized view that stores its data in the given table, if any.
+    async fn get_materialized_view_of_storage_table<'a>(
+        table_id: TableIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<ViewIdentUuid>>;
+
+    /// Replace the tables and views referenced by the SQL representations of a view.
+    async fn set_view_dependencies<'a>(
+        view_id: ViewIdentUuid,
+        dependencies: &[TabularIdentUuid],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Active tables and views referenced by a view.
+    async fn list_view_dependencies<'a>(
+        view_id: ViewIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<LineageTabular>>;
+
+    /// Active views that reference the given table or view.
+    async fn list_dependent_views<'a>(
+        tabular_id: TabularIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<LineageTabular>>;
+
     async fn list_tabulars(
         warehouse_id: WarehouseIdent,
         namespace_id: Option<NamespaceIdentUuid>, // Filter by namespace
@@ -701,6 +913,90 @@ pub struct ViewMetadataWithLocation {
     pub metadata: ViewMetadata,
 }
 
+/// Snapshot of a source table that a materialized view was refreshed from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaterializedViewSource {
+    pub table_id: TableIdentUuid,
+    /// Identifier of the source table at the time of the refresh.
+    pub table: TableIdent,
+    /// `None` if the source table had no snapshot.
+    pub snapshot_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaterializedViewSourceState {
+    pub source: MaterializedViewSource,
+    /// `false` if the source table has been dropped since the refresh.
+    pub exists: bool,
+    /// Current snapshot of the main branch of the source table.
+    pub current_snapshot_id: Option<i64>,
+}
+
+impl MaterializedViewSourceState {
+    #[must_use]
+    pub fn is_fresh(&self) -> bool {
+        self.exists && self.current_snapshot_id == self.source.snapshot_id
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaterializedView {
+    pub storage_table_id: TableIdentUuid,
+    pub sources: Vec<MaterializedViewSourceState>,
+}
+
+impl MaterializedView {
+    /// Sources that changed or were dropped since the last refresh.
+    pub fn stale_sources(&self) -> impl Iterator<Item = &MaterializedViewSource> {
+        self.sources
+            .iter()
+            .filter(|state| !state.is_fresh())
+            .map(|state| &state.source)
+    }
+}
+
+/// A table or view on one side of a view dependency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineageTabular {
+    pub tabular_id: TabularIdentUuid,
+    pub ident: TableIdent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamespaceSubtree {
+    /// The namespace and its children. Children are ordered before their parents.
+    pub namespaces: Vec<(NamespaceIdentUuid, NamespaceIdent)>,
+    pub tabulars: Vec<NamespaceSubtreeTabular>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamespaceSubtreeTabular {
+    pub tabular_id: TabularIdentUuid,
+    pub namespace_id: NamespaceIdentUuid,
+    pub tabular_ident: TableIdent,
+    /// Expiration task of soft-deleted tabulars
+    pub expiration_task_id: Option<TaskId>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndroppedNamespace {
+    pub namespace: NamespaceIdent,
+    /// Parent of the namespace, `None` for top-level namespaces.
+    pub parent_id: Option<NamespaceIdentUuid>,
+    /// Expiration tasks of the restored namespaces
+    pub namespace_expiration_tasks: Vec<TaskId>,
+    /// Expiration tasks of the restored tables and views
+    pub tabular_expiration_tasks: Vec<TaskId>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DroppedTabular {
+    pub tabular_id: TabularIdentUuid,
+    pub location: String,
+    /// Expiration task of the tabular if it was soft-deleted on its own
+    pub expiration_task_id: Option<TaskId>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct DeletionDetails {
     pub expiration_task_id: uuid::Uuid,
@@ -1,7 +1,7 @@
 #![allow(clippy::module_name_repetitions)]
 use crate::service::TabularIdentUuid;
 use async_trait::async_trait;
-use iceberg::spec::{TableMetadata, ViewMetadata};
+use iceberg::spec::{Schema, TableMetadata, ViewMetadata};
 use iceberg::{TableIdent, TableUpdate};
 use iceberg_ext::catalog::rest::{ErrorModel, ViewUpdate};
 use std::fmt::Debug;
@@ -183,6 +183,33 @@ impl ContractVerificationOutcome {
     }
 }
 
+/// Schema that is current after applying `table_updates`,
+/// or `None` if the current schema does not change.
+pub(crate) fn updated_current_schema(
+    table_updates: &[TableUpdate],
+    current_metadata: &TableMetadata,
+) -> Option<Schema> {
+    let mut added_schema: Option<&Schema> = None;
+    let mut current_schema = None;
+    for update in table_updates {
+        match update {
+            TableUpdate::AddSchema { schema, .. } => added_schema = Some(schema),
+            TableUpdate::SetCurrentSchema { schema_id } => {
+                current_schema = match added_schema {
+                    Some(schema) if *schema_id == -1 || schema.schema_id() == *schema_id => {
+                        Some(schema.clone())
+                    }
+                    _ => current_metadata
+                        .schema_by_id(*schema_id)
+                        .map(|schema| schema.as_ref().clone()),
+                };
+            }
+            _ => {}
+        }
+    }
+    current_schema
+}
+
 #[derive(Debug, Clone)]
 pub struct ContractVerifiers {
     checkers: Vec<Arc<dyn ContractVerification + Sync + Send>>,
@@ -0,0 +1,377 @@
+//! Contract verification by external HTTP services.
+//!
+//! Each webhook configured in `contract_webhooks` receives proposed changes to tables
+//! and views as a `POST` request and decides whether they violate a data contract.
+
+use crate::config::{ContractWebhookConfig, WebhookFailurePolicy};
+use crate::service::contract_verification::{ContractVerification, ContractVerificationOutcome};
+use crate::service::{Catalog, GetNamespaceResponse, TabularIdentUuid};
+use crate::{WarehouseIdent, CONFIG};
+use async_trait::async_trait;
+use iceberg::spec::{TableMetadata, ViewMetadata};
+use iceberg::{NamespaceIdent, TableIdent, TableUpdate};
+use iceberg_ext::catalog::rest::{ErrorModel, ViewUpdate};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Tabular the change applies to.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct WebhookTabular {
+    id: uuid::Uuid,
+    r#type: &'static str,
+    /// `None` if the tabular could not be found in the catalog.
+    warehouse_id: Option<uuid::Uuid>,
+    namespace: Option<NamespaceIdent>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "operation", rename_all = "kebab-case")]
+enum WebhookOperation<'a> {
+    #[serde(rename_all = "kebab-case")]
+    TableUpdate {
+        updates: &'a [TableUpdate],
+        current_metadata: &'a TableMetadata,
+    },
+    #[serde(rename_all = "kebab-case")]
+    ViewUpdate {
+        updates: &'a [ViewUpdate],
+        current_metadata: &'a ViewMetadata,
+    },
+    Drop {},
+    Rename {
+        destination: &'a TableIdent,
+    },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct WebhookRequest<'a> {
+    webhook: &'a str,
+    tabular: WebhookTabular,
+    #[serde(flatten)]
+    operation: WebhookOperation<'a>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "outcome", rename_all = "kebab-case")]
+enum WebhookResponse {
+    Clear {},
+    Violation {
+        message: String,
+        #[serde(default)]
+        details: Vec<String>,
+    },
+}
+
+/// Asks an external HTTP service whether changes to tables and views are allowed.
+pub struct WebhookContractVerifier<C: Catalog> {
+    name: String,
+    config: ContractWebhookConfig,
+    client: reqwest::Client,
+    catalog_state: C::State,
+}
+
+impl<C: Catalog> WebhookContractVerifier<C> {
+    /// Create a verifier for the webhook `name`.
+    ///
+    /// # Errors
+    /// Fails if the HTTP client cannot be built.
+    pub fn new(
+        name: String,
+        config: ContractWebhookConfig,
+        catalog_state: C::State,
+    ) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()?;
+        Ok(Self {
+            name,
+            config,
+            client,
+            catalog_state,
+        })
+    }
+
+    /// Create a verifier for each webhook in `contract_webhooks`, ordered by name.
+    ///
+    /// # Errors
+    /// Fails if an HTTP client cannot be built.
+    pub fn from_config(
+        catalog_state: &C::State,
+    ) -> anyhow::Result<Vec<Arc<dyn ContractVerification + Sync + Send>>> {
+        let mut webhooks = CONFIG.contract_webhooks.iter().collect::<Vec<_>>();
+        webhooks.sort_by_key(|(name, _)| *name);
+        webhooks
+            .into_iter()
+            .map(|(name, config)| {
+                Ok(Arc::new(Self::new(
+                    name.clone(),
+                    config.clone(),
+                    catalog_state.clone(),
+                )?)
+                    as Arc<dyn ContractVerification + Sync + Send>)
+            })
+            .collect()
+    }
+
+    async fn verify(
+        &self,
+        tabular_id: TabularIdentUuid,
+        operation: WebhookOperation<'_>,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        let namespace = C::get_tabular_namespace(tabular_id, self.catalog_state.clone())
+            .await
+            .map_err(|e| e.error)?;
+        if let Some(namespace) = &namespace {
+            if !matches_filters(&self.config, namespace.warehouse_id, &namespace.namespace) {
+                return Ok(ContractVerificationOutcome::Clear {});
+            }
+        }
+
+        let request = WebhookRequest {
+            webhook: &self.name,
+            tabular: WebhookTabular {
+                id: *tabular_id,
+                r#type: match tabular_id {
+                    TabularIdentUuid::Table(_) => "table",
+                    TabularIdentUuid::View(_) => "view",
+                },
+                warehouse_id: namespace.as_ref().map(|n| *n.warehouse_id),
+                namespace: namespace.map(|GetNamespaceResponse { namespace, .. }| namespace),
+            },
+            operation,
+        };
+
+        match self.send(&request).await {
+            Ok(WebhookResponse::Clear {}) => Ok(ContractVerificationOutcome::Clear {}),
+            Ok(WebhookResponse::Violation { message, details }) => {
+                Ok(ContractVerificationOutcome::Violation {
+                    error_model: ErrorModel::conflict(message, "ContractViolation", None)
+                        .append_details(details),
+                })
+            }
+            Err(error_model) => match self.config.failure_policy {
+                WebhookFailurePolicy::FailOpen => {
+                    tracing::warn!(
+                        "Contract webhook '{}' failed, allowing change of {tabular_id}: {error_model:?}",
+                        self.name
+                    );
+                    Ok(ContractVerificationOutcome::Clear {})
+                }
+                WebhookFailurePolicy::FailClosed => {
+                    Ok(ContractVerificationOutcome::Violation { error_model })
+                }
+            },
+        }
+    }
+
+    async fn send(&self, request: &WebhookRequest<'_>) -> Result<WebhookResponse, ErrorModel> {
+        let error = |e: reqwest::Error| {
+            ErrorModel::failed_dependency(
+                format!("Contract webhook '{}' failed", self.name),
+                "ContractWebhookError",
+                Some(Box::new(e)),
+            )
+        };
+
+        let mut http_request = self.client.post(self.config.url.clone()).json(request);
+        if let Some(token) = &self.config.bearer_token {
+            http_request = http_request.bearer_auth(token);
+        }
+
+        http_request
+            .send()
+            .await
+            .map_err(error)?
+            .error_for_status()
+            .map_err(error)?
+            .json::<WebhookResponse>()
+            .await
+            .map_err(error)
+    }
+}
+
+/// Whether a change in `namespace` of `warehouse_id` is sent to the webhook.
+fn matches_filters(
+    config: &ContractWebhookConfig,
+    warehouse_id: WarehouseIdent,
+    namespace: &NamespaceIdent,
+) -> bool {
+    let warehouse_matches =
+        config.warehouses.is_empty() || config.warehouses.contains(&*warehouse_id);
+    let namespace_matches = config.namespaces.is_empty()
+        || config.namespaces.iter().any(|filter| {
+            let filter = filter.split('.').collect::<Vec<_>>();
+            namespace.len() >= filter.len()
+                && namespace.iter().zip(filter).all(|(level, f)| level == f)
+        });
+    warehouse_matches && namespace_matches
+}
+
+impl<C: Catalog> Debug for WebhookContractVerifier<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookContractVerifier")
+            .field("name", &self.name)
+            .field("url", &self.config.url)
+            .field("failure_policy", &self.config.failure_policy)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl<C: Catalog> ContractVerification for WebhookContractVerifier<C> {
+    fn name(&self) -> &'static str {
+        "WebhookContractVerifier"
+    }
+
+    async fn check_table_updates(
+        &self,
+        table_updates: &[TableUpdate],
+        current_metadata: &TableMetadata,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        self.verify(
+            TabularIdentUuid::Table(current_metadata.uuid()),
+            WebhookOperation::TableUpdate {
+                updates: table_updates,
+                current_metadata,
+            },
+        )
+        .await
+    }
+
+    async fn check_view_updates(
+        &self,
+        view_updates: &[ViewUpdate],
+        current_metadata: &ViewMetadata,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        self.verify(
+            TabularIdentUuid::View(current_metadata.view_uuid),
+            WebhookOperation::ViewUpdate {
+                updates: view_updates,
+                current_metadata,
+            },
+        )
+        .await
+    }
+
+    async fn check_drop(
+        &self,
+        table_ident_uuid: TabularIdentUuid,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        self.verify(table_ident_uuid, WebhookOperation::Drop {})
+            .await
+    }
+
+    async fn check_rename(
+        &self,
+        source: TabularIdentUuid,
+        destination: &TableIdent,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        self.verify(source, WebhookOperation::Rename { destination })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(warehouses: Vec<uuid::Uuid>, namespaces: Vec<&str>) -> ContractWebhookConfig {
+        ContractWebhookConfig {
+            url: "http://localhost:8080/verify".parse().unwrap(),
+            bearer_token: None,
+            timeout_ms: 1000,
+            failure_policy: WebhookFailurePolicy::FailClosed,
+            warehouses,
+            namespaces: namespaces.into_iter().map(str::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_matches_filters() {
+        let warehouse_id = uuid::Uuid::now_v7();
+        let namespace = NamespaceIdent::from_strs(["sales", "eu", "orders"]).unwrap();
+
+        assert!(matches_filters(
+            &config(vec![], vec![]),
+            warehouse_id.into(),
+            &namespace
+        ));
+        assert!(matches_filters(
+            &config(vec![warehouse_id], vec!["sales.eu"]),
+            warehouse_id.into(),
+            &namespace
+        ));
+        assert!(!matches_filters(
+            &config(vec![uuid::Uuid::now_v7()], vec![]),
+            warehouse_id.into(),
+            &namespace
+        ));
+        assert!(!matches_filters(
+            &config(vec![], vec!["sales.us", "finance"]),
+            warehouse_id.into(),
+            &namespace
+        ));
+        assert!(!matches_filters(
+            &config(vec![], vec!["sales.eu.orders.archive"]),
+            warehouse_id.into(),
+            &namespace
+        ));
+    }
+
+    #[test]
+    fn test_request_serialization() {
+        let id = uuid::Uuid::now_v7();
+        let destination = TableIdent::from_strs(["sales", "orders_v2"]).unwrap();
+        let request = WebhookRequest {
+            webhook: "contracts",
+            tabular: WebhookTabular {
+                id,
+                r#type: "table",
+                warehouse_id: None,
+                namespace: Some(NamespaceIdent::new("sales".to_string())),
+            },
+            operation: WebhookOperation::Rename {
+                destination: &destination,
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "webhook": "contracts",
+                "tabular": {
+                    "id": id,
+                    "type": "table",
+                    "warehouse-id": null,
+                    "namespace": ["sales"],
+                },
+                "operation": "rename",
+                "destination": {
+                    "namespace": ["sales"],
+                    "name": "orders_v2",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_response_deserialization() {
+        let clear: WebhookResponse = serde_json::from_str(r#"{"outcome": "clear"}"#).unwrap();
+        assert_eq!(clear, WebhookResponse::Clear {});
+
+        let violation: WebhookResponse = serde_json::from_str(
+            r#"{"outcome": "violation", "message": "Column `id` is part of contract `orders`"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            violation,
+            WebhookResponse::Violation {
+                message: "Column `id` is part of contract `orders`".to_string(),
+                details: vec![],
+            }
+        );
+    }
+}
@@ -33,7 +33,7 @@ impl CloudEventsPublisher {
         id: Uuid,
         typ: &str,
         data: serde_json::Value,
-        metadata: EventMetadata,
+        metadata: impl Into<PayloadMetadata>,
     ) -> anyhow::Result<()> {
         self.tx
             .send_timeout(
@@ -41,7 +41,7 @@ impl CloudEventsPublisher {
                     id,
                     typ: typ.to_string(),
                     data,
-                    metadata,
+                    metadata: metadata.into(),
                 }),
                 self.timeout,
             )
@@ -66,12 +66,42 @@ pub struct EventMetadata {
     pub trace_id: Uuid,
 }
 
+/// Metadata of events about a namespace.
+#[derive(Debug, Clone)]
+pub struct NamespaceEventMetadata {
+    pub namespace_id: Uuid,
+    pub warehouse_id: Uuid,
+    pub namespace: String,
+    pub prefix: String,
+    pub num_events: usize,
+    pub sequence_number: usize,
+    pub trace_id: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub enum PayloadMetadata {
+    Tabular(EventMetadata),
+    Namespace(NamespaceEventMetadata),
+}
+
+impl From<EventMetadata> for PayloadMetadata {
+    fn from(metadata: EventMetadata) -> Self {
+        Self::Tabular(metadata)
+    }
+}
+
+impl From<NamespaceEventMetadata> for PayloadMetadata {
+    fn from(metadata: NamespaceEventMetadata) -> Self {
+        Self::Namespace(metadata)
+    }
+}
+
 #[derive(Debug)]
 pub struct Payload {
     pub id: Uuid,
     pub typ: String,
     pub data: serde_json::Value,
-    pub metadata: EventMetadata,
+    pub metadata: PayloadMetadata,
 }
 
 #[derive(Debug)]
@@ -111,34 +141,53 @@ impl CloudEventsPublisherBackgroundTask {
                 .ty(typ)
                 .data("application/json", data);
 
-            let EventMetadata {
-                tabular_id,
-                warehouse_id,
-                name,
-                namespace,
-                prefix,
-                num_events,
-                sequence_number,
-                trace_id,
-            } = metadata;
-            // TODO: this could be more elegant with a proc macro to give us IntoIter for EventMetadata
-            let event = event_builder
-                .extension("tabular-type", tabular_id.typ_str())
-                .extension("tabular-id", tabular_id.to_string())
-                .extension("warehouse-id", warehouse_id.to_string())
-                .extension("name", name.to_string())
-                .extension("namespace", namespace.to_string())
-                .extension("prefix", prefix.to_string())
-                // TODO: decide what to do with these numbers, likely they are never anywhere close to
-                // saturating the respective int types, so probably a non-issue. Still we are converting
-                // the numbers to_string here to avoid usize -> i64 which is what EventBuilderV10
-                // uses to represent integers. The CloudEvents spec states i32 would be the correct int
-                // type.
-                .extension("num-events", num_events.to_string())
-                .extension("sequence-number", sequence_number.to_string())
-                // Implement distributed tracing: https://github.com/lakekeeper/lakekeeper/issues/63
-                .extension("trace-id", trace_id.to_string())
-                .build()?;
+            let event_builder = match metadata {
+                PayloadMetadata::Tabular(EventMetadata {
+                    tabular_id,
+                    warehouse_id,
+                    name,
+                    namespace,
+                    prefix,
+                    num_events,
+                    sequence_number,
+                    trace_id,
+                }) => {
+                    // TODO: this could be more elegant with a proc macro to give us IntoIter for EventMetadata
+                    event_builder
+                        .extension("tabular-type", tabular_id.typ_str())
+                        .extension("tabular-id", tabular_id.to_string())
+                        .extension("warehouse-id", warehouse_id.to_string())
+                        .extension("name", name.to_string())
+                        .extension("namespace", namespace.to_string())
+                        .extension("prefix", prefix.to_string())
+                        // TODO: decide what to do with these numbers, likely they are never anywhere close to
+                        // saturating the respective int types, so probably a non-issue. Still we are converting
+                        // the numbers to_string here to avoid usize -> i64 which is what EventBuilderV10
+                        // uses to represent integers. The CloudEvents spec states i32 would be the correct int
+                        // type.
+                        .extension("num-events", num_events.to_string())
+                        .extension("sequence-number", sequence_number.to_string())
+                        // Implement distributed tracing: https://github.com/lakekeeper/lakekeeper/issues/63
+                        .extension("trace-id", trace_id.to_string())
+                }
+                PayloadMetadata::Namespace(NamespaceEventMetadata {
+                    namespace_id,
+                    warehouse_id,
+                    namespace,
+                    prefix,
+                    num_events,
+                    sequence_number,
+                    trace_id,
+                }) => event_builder
+                    .extension("namespace-id", namespace_id.to_string())
+                    .extension("warehouse-id", warehouse_id.to_string())
+                    .extension("namespace", namespace)
+                    .extension("prefix", prefix)
+                    .extension("num-events", num_events.to_string())
+                    .extension("sequence-number", sequence_number.to_string())
+                    .extension("trace-id", trace_id.to_string()),
+            };
+            let event = event_builder.build()?;
 
             for sink in &self.sinks {
                 if let Err(e) = sink.publish(event.clone()).await {
@@ -2,10 +2,13 @@ pub mod authn;
 pub mod authz;
 mod catalog;
 pub mod contract_verification;
+pub mod contract_webhook;
 pub mod event_publisher;
 pub mod health;
+pub mod schema_compatibility;
 pub mod secrets;
 pub mod storage;
+pub mod table_property_policy;
 mod tabular_idents;
 pub mod task_queue;
 
@@ -13,10 +16,12 @@ pub use authn::{Actor, AuthDetails};
 pub use catalog::{
     Catalog, CommitTableResponse, CreateNamespaceRequest, CreateNamespaceResponse,
     CreateOrUpdateUserResponse, CreateTableRequest, CreateTableResponse, DeletionDetails,
-    DropFlags, GetNamespaceResponse, GetProjectResponse, GetStorageConfigResponse,
-    GetTableMetadataResponse, GetWarehouseResponse, ListFlags, ListNamespacesQuery,
-    ListNamespacesResponse, LoadTableResponse, NamespaceIdent, Result, StartupValidationData,
-    TableCommit, TableCreation, TableIdent, Transaction, UpdateNamespacePropertiesRequest,
+    DropFlags, DroppedTabular, GetNamespaceResponse, GetProjectResponse, GetStorageConfigResponse,
+    GetTableMetadataResponse, GetWarehouseResponse, LineageTabular, ListFlags, ListNamespacesQuery,
+    ListNamespacesResponse, LoadTableResponse, MaterializedView, MaterializedViewSource,
+    MaterializedViewSourceState, NamespaceIdent, NamespaceSubtree, NamespaceSubtreeTabular, Result,
+    StartupValidationData, TableAndNamespaceProperties, TableCommit, TableCreation, TableIdent,
+    Transaction, UndroppedNamespace, UpdateNamespacePropertiesRequest,
     UpdateNamespacePropertiesResponse, ViewMetadataWithLocation,
 };
 use std::ops::Deref;
@@ -34,7 +39,7 @@ use http::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-pub use authn::{IdpVerifier, K8sVerifier, UserId};
+pub use authn::{CertificateVerifier, IdpVerifier, IntrospectionVerifier, K8sVerifier, UserId};
 pub use secrets::{SecretIdent, SecretStore};
 
 // ---------------- State ----------------
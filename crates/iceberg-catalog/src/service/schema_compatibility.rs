@@ -0,0 +1,675 @@
+//! Built-in contract verification that keeps table schemas compatible.
+//!
+//! The compatibility mode of a table is configured with the property
+//! `lakekeeper.schema-compatibility` on the table or, as a fallback, on its namespace.
+//! Tables with `lakekeeper.contract-protected=true` on the table or its namespace can
+//! neither be dropped nor renamed.
+
+use crate::service::contract_verification::{
+    updated_current_schema, ContractVerification, ContractVerificationOutcome,
+};
+use crate::service::{Catalog, TableAndNamespaceProperties, TableIdentUuid, TabularIdentUuid};
+use async_trait::async_trait;
+use iceberg::spec::{
+    NestedField, NestedFieldRef, PrimitiveType, Schema, TableMetadata, Type, ViewMetadata,
+};
+use iceberg::{TableIdent, TableUpdate};
+use iceberg_ext::catalog::rest::{ErrorModel, ViewUpdate};
+use itertools::Itertools;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{Debug, Formatter};
+use std::str::FromStr;
+
+/// Table or namespace property that sets the schema compatibility mode.
+pub const SCHEMA_COMPATIBILITY_KEY: &str = "lakekeeper.schema-compatibility";
+/// Table or namespace property that protects tables from being dropped or renamed.
+pub const CONTRACT_PROTECTED_KEY: &str = "lakekeeper.contract-protected";
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::EnumString, Default,
+)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum SchemaCompatibility {
+    /// Schema changes are not checked.
+    #[default]
+    None,
+    /// Readers using the new schema can read data written with the previous schema.
+    Backward,
+    /// Readers using the previous schema can read data written with the new schema.
+    Forward,
+    /// Both backward and forward compatible.
+    Full,
+}
+
+impl SchemaCompatibility {
+    fn is_backward(self) -> bool {
+        matches!(self, Self::Backward | Self::Full)
+    }
+
+    fn is_forward(self) -> bool {
+        matches!(self, Self::Forward | Self::Full)
+    }
+}
+
+/// Returns true if values of type `from` can be read as type `to`.
+fn is_valid_promotion(from: &PrimitiveType, to: &PrimitiveType) -> bool {
+    match (from, to) {
+        (PrimitiveType::Int, PrimitiveType::Long)
+        | (PrimitiveType::Float, PrimitiveType::Double) => true,
+        (
+            PrimitiveType::Decimal {
+                precision: from_precision,
+                scale: from_scale,
+            },
+            PrimitiveType::Decimal {
+                precision: to_precision,
+                scale: to_scale,
+            },
+        ) => from_scale == to_scale && to_precision >= from_precision,
+        _ => from == to,
+    }
+}
+
+/// All fields of a schema, including nested fields, by field id.
+fn fields_by_id(schema: &Schema) -> HashMap<i32, &NestedField> {
+    fn collect<'a>(fields: &'a [NestedFieldRef], out: &mut HashMap<i32, &'a NestedField>) {
+        for field in fields {
+            out.insert(field.id, field.as_ref());
+            match field.field_type.as_ref() {
+                Type::Primitive(_) => {}
+                Type::Struct(struct_type) => collect(struct_type.fields(), out),
+                Type::List(list_type) => {
+                    collect(std::slice::from_ref(&list_type.element_field), out)
+                }
+                Type::Map(map_type) => {
+                    collect(std::slice::from_ref(&map_type.key_field), out);
+                    collect(std::slice::from_ref(&map_type.value_field), out);
+                }
+            }
+        }
+    }
+
+    let mut fields = HashMap::new();
+    collect(schema.as_struct().fields(), &mut fields);
+    fields
+}
+
+/// Reasons why `new_schema` is not compatible with `previous_schema` in the given mode.
+/// Returns an empty list if the schemas are compatible.
+#[must_use]
+pub fn schema_incompatibilities(
+    previous_schema: &Schema,
+    new_schema: &Schema,
+    mode: SchemaCompatibility,
+) -> Vec<String> {
+    if mode == SchemaCompatibility::None {
+        return vec![];
+    }
+    let mut reasons = vec![];
+
+    let previous_identifiers = previous_schema
+        .identifier_field_ids()
+        .collect::<BTreeSet<_>>();
+    let new_identifiers = new_schema.identifier_field_ids().collect::<BTreeSet<_>>();
+    if previous_identifiers != new_identifiers {
+        reasons.push(format!(
+            "Identifier fields changed from [{}] to [{}]",
+            previous_identifiers.iter().join(", "),
+            new_identifiers.iter().join(", ")
+        ));
+    }
+
+    let previous_fields = fields_by_id(previous_schema);
+    let new_fields = fields_by_id(new_schema);
+
+    for (id, previous) in previous_fields.iter().sorted_by_key(|(id, _)| **id) {
+        let Some(new) = new_fields.get(id) else {
+            // Consumers rely on required fields in every mode
+            if previous.required {
+                reasons.push(format!(
+                    "Required field `{}` (id {id}) was dropped",
+                    previous.name
+                ));
+            }
+            continue;
+        };
+
+        if mode.is_backward() && !previous.required && new.required {
+            reasons.push(format!("Field `{}` (id {id}) was made required", new.name));
+        }
+        if mode.is_forward() && previous.required && !new.required {
+            reasons.push(format!("Field `{}` (id {id}) was made optional", new.name));
+        }
+
+        match (previous.field_type.as_ref(), new.field_type.as_ref()) {
+            (Type::Primitive(previous_type), Type::Primitive(new_type))
+                if previous_type != new_type =>
+            {
+                if mode.is_backward() && !is_valid_promotion(previous_type, new_type) {
+                    reasons.push(format!(
+                        "Type of field `{}` (id {id}) changed from {previous_type} to {new_type}, which is not a valid promotion",
+                        new.name
+                    ));
+                } else if mode.is_forward() {
+                    reasons.push(format!(
+                        "Type of field `{}` (id {id}) changed from {previous_type} to {new_type}",
+                        new.name
+                    ));
+                }
+            }
+            (Type::Primitive(_), Type::Primitive(_))
+            | (Type::Struct(_), Type::Struct(_))
+            | (Type::List(_), Type::List(_))
+            | (Type::Map(_), Type::Map(_)) => {}
+            (previous_type, new_type) => reasons.push(format!(
+                "Type of field `{}` (id {id}) changed from {previous_type} to {new_type}",
+                new.name
+            )),
+        }
+    }
+
+    if mode.is_backward() {
+        for (id, new) in new_fields.iter().sorted_by_key(|(id, _)| **id) {
+            if new.required && !previous_fields.contains_key(id) {
+                reasons.push(format!("Required field `{}` (id {id}) was added", new.name));
+            }
+        }
+    }
+
+    reasons
+}
+
+/// Parse the value of [`SCHEMA_COMPATIBILITY_KEY`]. A missing value is [`SchemaCompatibility::None`].
+///
+/// # Errors
+/// Returns a bad request error if the value is not a valid mode.
+pub fn parse_schema_compatibility(value: Option<&str>) -> Result<SchemaCompatibility, ErrorModel> {
+    value.map_or(Ok(SchemaCompatibility::None), |value| {
+        SchemaCompatibility::from_str(value).map_err(|_| {
+            ErrorModel::bad_request(
+                format!("Invalid value `{value}` for `{SCHEMA_COMPATIBILITY_KEY}`. Expected one of none, backward, forward, full"),
+                "InvalidSchemaCompatibility",
+                None,
+            )
+        })
+    })
+}
+
+/// Check a change of [`SCHEMA_COMPATIBILITY_KEY`] of `subject`, a table or namespace.
+/// The mode may be raised but neither lowered nor removed, as that would allow
+/// breaking changes that the previous mode guarantees against.
+/// An invalid previous value may be replaced by any valid value.
+///
+/// # Errors
+/// - The new value is not a valid mode
+/// - The new mode drops a compatibility direction of the previous mode
+pub fn check_schema_compatibility_change(
+    previous: Option<&str>,
+    new: Option<&str>,
+    subject: &str,
+) -> Result<(), ErrorModel> {
+    let new_mode = parse_schema_compatibility(new)?;
+    let Ok(previous_mode) = parse_schema_compatibility(previous) else {
+        return Ok(());
+    };
+
+    if (previous_mode.is_backward() && !new_mode.is_backward())
+        || (previous_mode.is_forward() && !new_mode.is_forward())
+    {
+        return Err(ErrorModel::bad_request(
+            format!("Schema compatibility of {subject} cannot be lowered from `{previous_mode}` to `{new_mode}`"),
+            "SchemaCompatibilityDowngrade",
+            None,
+        ));
+    }
+    Ok(())
+}
+
+/// Value of `key` after applying the property changes of `table_updates` to `properties`.
+fn updated_property<'a>(
+    table_updates: &'a [TableUpdate],
+    properties: &'a HashMap<String, String>,
+    key: &str,
+) -> Option<&'a str> {
+    let mut value = properties.get(key);
+    for update in table_updates {
+        match update {
+            TableUpdate::SetProperties { updates } => {
+                if let Some(new) = updates.get(key) {
+                    value = Some(new);
+                }
+            }
+            TableUpdate::RemoveProperties { removals } => {
+                if removals.iter().any(|removal| removal == key) {
+                    value = None;
+                }
+            }
+            _ => {}
+        }
+    }
+    value.map(String::as_str)
+}
+
+/// Blocks incompatible schema changes and drops or renames of protected tables.
+pub struct SchemaCompatibilityVerifier<C: Catalog> {
+    catalog_state: C::State,
+}
+
+impl<C: Catalog> SchemaCompatibilityVerifier<C> {
+    #[must_use]
+    pub fn new(catalog_state: C::State) -> Self {
+        Self { catalog_state }
+    }
+
+    async fn properties(
+        &self,
+        table_id: TableIdentUuid,
+    ) -> Result<TableAndNamespaceProperties, ErrorModel> {
+        Ok(
+            C::get_table_and_namespace_properties(table_id, self.catalog_state.clone())
+                .await
+                .map_err(|e| e.error)?
+                .unwrap_or_default(),
+        )
+    }
+
+    async fn check_protected(
+        &self,
+        tabular_id: TabularIdentUuid,
+        operation: &str,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        let TabularIdentUuid::Table(table_id) = tabular_id else {
+            return Ok(ContractVerificationOutcome::Clear {});
+        };
+        let properties = self.properties(TableIdentUuid::from(table_id)).await?;
+        let protected = property(
+            &properties.table_properties,
+            &properties.namespace_properties,
+            CONTRACT_PROTECTED_KEY,
+        )
+        .is_some_and(|value| value.eq_ignore_ascii_case("true"));
+
+        Ok(if protected {
+            ContractVerificationOutcome::Violation {
+                error_model: ErrorModel::conflict(
+                    format!("Table {table_id} is contract-protected and cannot be {operation}"),
+                    "ContractProtected",
+                    None,
+                )
+                .append_detail(format!(
+                    "Remove the property `{CONTRACT_PROTECTED_KEY}` from the table or its namespace first"
+                )),
+            }
+        } else {
+            ContractVerificationOutcome::Clear {}
+        })
+    }
+}
+
+/// Property of a table, falling back to the property of its namespace.
+fn property<'a>(
+    table_properties: &'a HashMap<String, String>,
+    namespace_properties: &'a HashMap<String, String>,
+    key: &str,
+) -> Option<&'a str> {
+    table_properties
+        .get(key)
+        .or_else(|| namespace_properties.get(key))
+        .map(String::as_str)
+}
+
+impl<C: Catalog> Debug for SchemaCompatibilityVerifier<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SchemaCompatibilityVerifier").finish()
+    }
+}
+
+#[async_trait]
+impl<C: Catalog> ContractVerification for SchemaCompatibilityVerifier<C> {
+    fn name(&self) -> &'static str {
+        "SchemaCompatibilityVerifier"
+    }
+
+    async fn check_table_updates(
+        &self,
+        table_updates: &[TableUpdate],
+        current_metadata: &TableMetadata,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        let new_schema = updated_current_schema(table_updates, current_metadata);
+        let table_mode = current_metadata
+            .properties()
+            .get(SCHEMA_COMPATIBILITY_KEY)
+            .map(String::as_str);
+        let new_table_mode = updated_property(
+            table_updates,
+            current_metadata.properties(),
+            SCHEMA_COMPATIBILITY_KEY,
+        );
+        if new_schema.is_none() && table_mode == new_table_mode {
+            return Ok(ContractVerificationOutcome::Clear {});
+        }
+
+        let namespace_properties = if table_mode.is_some() && new_table_mode.is_some() {
+            HashMap::new()
+        } else {
+            self.properties(TableIdentUuid::from(current_metadata.uuid()))
+                .await?
+                .namespace_properties
+        };
+        let namespace_mode = namespace_properties
+            .get(SCHEMA_COMPATIBILITY_KEY)
+            .map(String::as_str);
+        let previous_mode = table_mode.or(namespace_mode);
+
+        if table_mode != new_table_mode {
+            if let Err(error_model) = check_schema_compatibility_change(
+                previous_mode,
+                new_table_mode.or(namespace_mode),
+                &format!("table {}", current_metadata.uuid()),
+            ) {
+                return Ok(ContractVerificationOutcome::Violation { error_model });
+            }
+        }
+
+        // Schema changes are checked against the mode before the commit, which is
+        // at least as strict as the new mode.
+        let Some(new_schema) = new_schema else {
+            return Ok(ContractVerificationOutcome::Clear {});
+        };
+        let mode = match parse_schema_compatibility(previous_mode) {
+            Ok(mode) => mode,
+            Err(error_model) => return Ok(ContractVerificationOutcome::Violation { error_model }),
+        };
+
+        let reasons =
+            schema_incompatibilities(current_metadata.current_schema(), &new_schema, mode);
+        Ok(if reasons.is_empty() {
+            ContractVerificationOutcome::Clear {}
+        } else {
+            ContractVerificationOutcome::Violation {
+                error_model: ErrorModel::bad_request(
+                    format!(
+                        "Schema change of table {} is not {mode} compatible",
+                        current_metadata.uuid()
+                    ),
+                    "IncompatibleSchemaChange",
+                    None,
+                )
+                .append_details(reasons),
+            }
+        })
+    }
+
+    async fn check_view_updates(
+        &self,
+        _view_updates: &[ViewUpdate],
+        _current_metadata: &ViewMetadata,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        Ok(ContractVerificationOutcome::Clear {})
+    }
+
+    async fn check_drop(
+        &self,
+        table_ident_uuid: TabularIdentUuid,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        self.check_protected(table_ident_uuid, "dropped").await
+    }
+
+    async fn check_rename(
+        &self,
+        source: TabularIdentUuid,
+        _destination: &TableIdent,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        self.check_protected(source, "renamed").await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iceberg::spec::{ListType, StructType};
+
+    fn schema(fields: Vec<NestedField>, identifier_field_ids: Vec<i32>) -> Schema {
+        Schema::builder()
+            .with_fields(fields.into_iter().map(Into::into))
+            .with_identifier_field_ids(identifier_field_ids)
+            .build()
+            .unwrap()
+    }
+
+    fn base_fields() -> Vec<NestedField> {
+        vec![
+            NestedField::required(1, "id", Type::Primitive(PrimitiveType::Int)),
+            NestedField::optional(2, "name", Type::Primitive(PrimitiveType::String)),
+            NestedField::optional(
+                3,
+                "tags",
+                Type::List(ListType::new(
+                    NestedField::list_element(4, Type::Primitive(PrimitiveType::String), true)
+                        .into(),
+                )),
+            ),
+            NestedField::required(
+                5,
+                "price",
+                Type::Primitive(PrimitiveType::Decimal {
+                    precision: 10,
+                    scale: 2,
+                }),
+            ),
+        ]
+    }
+
+    fn with_field(id: i32, field: NestedField) -> Vec<NestedField> {
+        base_fields()
+            .into_iter()
+            .map(|f| if f.id == id { field.clone() } else { f })
+            .collect()
+    }
+
+    fn check(new_fields: Vec<NestedField>, mode: SchemaCompatibility) -> Vec<String> {
+        schema_incompatibilities(
+            &schema(base_fields(), vec![1]),
+            &schema(new_fields, vec![1]),
+            mode,
+        )
+    }
+
+    #[test]
+    fn test_mode_from_str() {
+        assert_eq!(
+            SchemaCompatibility::from_str("backward").unwrap(),
+            SchemaCompatibility::Backward
+        );
+        assert_eq!(
+            SchemaCompatibility::from_str("FULL").unwrap(),
+            SchemaCompatibility::Full
+        );
+        assert!(SchemaCompatibility::from_str("sideways").is_err());
+        assert_eq!(SchemaCompatibility::Forward.to_string(), "forward");
+    }
+
+    #[test]
+    fn test_adding_optional_field_is_fully_compatible() {
+        let mut fields = base_fields();
+        fields.push(NestedField::optional(
+            6,
+            "comment",
+            Type::Primitive(PrimitiveType::String),
+        ));
+        assert!(check(fields, SchemaCompatibility::Full).is_empty());
+    }
+
+    #[test]
+    fn test_adding_required_field() {
+        let mut fields = base_fields();
+        fields.push(NestedField::required(
+            6,
+            "comment",
+            Type::Primitive(PrimitiveType::String),
+        ));
+        assert!(check(fields.clone(), SchemaCompatibility::Forward).is_empty());
+        assert_eq!(check(fields, SchemaCompatibility::Backward).len(), 1);
+    }
+
+    #[test]
+    fn test_dropping_required_field() {
+        let fields = base_fields()
+            .into_iter()
+            .filter(|f| f.id != 5)
+            .collect_vec();
+        assert_eq!(
+            check(fields.clone(), SchemaCompatibility::Backward).len(),
+            1
+        );
+        assert_eq!(check(fields.clone(), SchemaCompatibility::Forward).len(), 1);
+        assert!(check(fields, SchemaCompatibility::None).is_empty());
+
+        // Dropping optional fields is always compatible
+        let fields = base_fields()
+            .into_iter()
+            .filter(|f| f.id != 2)
+            .collect_vec();
+        assert!(check(fields, SchemaCompatibility::Full).is_empty());
+    }
+
+    #[test]
+    fn test_type_changes() {
+        let widened = with_field(
+            1,
+            NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)),
+        );
+        assert!(check(widened.clone(), SchemaCompatibility::Backward).is_empty());
+        assert_eq!(check(widened, SchemaCompatibility::Forward).len(), 1);
+
+        let narrowed = with_field(
+            5,
+            NestedField::required(
+                5,
+                "price",
+                Type::Primitive(PrimitiveType::Decimal {
+                    precision: 8,
+                    scale: 2,
+                }),
+            ),
+        );
+        assert_eq!(check(narrowed, SchemaCompatibility::Backward).len(), 1);
+
+        let changed_kind = with_field(
+            2,
+            NestedField::optional(
+                2,
+                "name",
+                Type::Struct(StructType::new(vec![NestedField::optional(
+                    6,
+                    "first",
+                    Type::Primitive(PrimitiveType::String),
+                )
+                .into()])),
+            ),
+        );
+        assert!(!check(changed_kind, SchemaCompatibility::Backward).is_empty());
+    }
+
+    #[test]
+    fn test_nested_and_nullability_changes() {
+        let required_element = with_field(
+            3,
+            NestedField::optional(
+                3,
+                "tags",
+                Type::List(ListType::new(
+                    NestedField::list_element(4, Type::Primitive(PrimitiveType::String), false)
+                        .into(),
+                )),
+            ),
+        );
+        assert_eq!(
+            check(required_element.clone(), SchemaCompatibility::Backward).len(),
+            1
+        );
+        assert!(check(required_element, SchemaCompatibility::Forward).is_empty());
+
+        let optional_id = with_field(
+            1,
+            NestedField::optional(1, "id", Type::Primitive(PrimitiveType::Int)),
+        );
+        assert_eq!(check(optional_id, SchemaCompatibility::Forward).len(), 1);
+    }
+
+    #[test]
+    fn test_identifier_fields_changed() {
+        let reasons = schema_incompatibilities(
+            &schema(base_fields(), vec![1]),
+            &schema(base_fields(), vec![1, 5]),
+            SchemaCompatibility::Backward,
+        );
+        assert_eq!(
+            reasons,
+            vec!["Identifier fields changed from [1] to [1, 5]"]
+        );
+        assert!(schema_incompatibilities(
+            &schema(base_fields(), vec![1]),
+            &schema(base_fields(), vec![5]),
+            SchemaCompatibility::None,
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_schema_compatibility_change() {
+        // Raising the mode is allowed
+        assert!(check_schema_compatibility_change(None, Some("backward"), "t").is_ok());
+        assert!(check_schema_compatibility_change(Some("backward"), Some("full"), "t").is_ok());
+        assert!(check_schema_compatibility_change(Some("forward"), Some("FORWARD"), "t").is_ok());
+        // Fixing an invalid value is allowed
+        assert!(check_schema_compatibility_change(Some("sideways"), None, "t").is_ok());
+
+        for (previous, new) in [
+            (Some("backward"), None),
+            (Some("backward"), Some("none")),
+            (Some("full"), Some("forward")),
+            (Some("forward"), Some("backward")),
+        ] {
+            let err = check_schema_compatibility_change(previous, new, "t").unwrap_err();
+            assert_eq!(
+                err.r#type, "SchemaCompatibilityDowngrade",
+                "{previous:?} -> {new:?}"
+            );
+        }
+
+        let err = check_schema_compatibility_change(None, Some("sideways"), "t").unwrap_err();
+        assert_eq!(err.r#type, "InvalidSchemaCompatibility");
+    }
+
+    #[test]
+    fn test_updated_property() {
+        let properties =
+            HashMap::from([(SCHEMA_COMPATIBILITY_KEY.to_string(), "full".to_string())]);
+        let removed = vec![TableUpdate::RemoveProperties {
+            removals: vec![SCHEMA_COMPATIBILITY_KEY.to_string()],
+        }];
+        assert_eq!(
+            updated_property(&removed, &properties, SCHEMA_COMPATIBILITY_KEY),
+            None
+        );
+
+        let readded = vec![
+            removed[0].clone(),
+            TableUpdate::SetProperties {
+                updates: HashMap::from([(
+                    SCHEMA_COMPATIBILITY_KEY.to_string(),
+                    "backward".to_string(),
+                )]),
+            },
+        ];
+        assert_eq!(
+            updated_property(&readded, &properties, SCHEMA_COMPATIBILITY_KEY),
+            Some("backward")
+        );
+        assert_eq!(
+            updated_property(&[], &properties, SCHEMA_COMPATIBILITY_KEY),
+            Some("full")
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! Templates for the default location of tables and views.
+//!
+//! A template starts with `{namespace-location}` or `{base-location}` followed by
+//! path segments which may contain the following placeholders:
+//!
+//! * `{namespace}`: one segment per level of the namespace. Must be a segment on its own.
+//! * `{namespace-id}`: id of the namespace.
+//! * `{name}`: name of the table or view.
+//! * `{uuid}`: id of the table or view. Must be part of the last segment.
+//! * `{hash}`: 8 hex characters derived from the id of the table or view.
+
+use crate::service::{NamespaceIdentUuid, TabularIdentUuid};
+use iceberg::NamespaceIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
+use iceberg_ext::configs::Location;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+const NAMESPACE_LOCATION: &str = "{namespace-location}";
+const BASE_LOCATION: &str = "{base-location}";
+const NAMESPACE: &str = "{namespace}";
+const NAMESPACE_ID: &str = "{namespace-id}";
+const NAME: &str = "{name}";
+const UUID: &str = "{uuid}";
+const HASH: &str = "{hash}";
+
+/// Template for the default location of tables and views of a warehouse.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TabularLocationTemplate(String);
+
+/// Values for the placeholders of a [`TabularLocationTemplate`].
+#[derive(Debug, Clone, Copy)]
+pub struct TabularLocationContext<'a> {
+    /// Base location of the storage profile of the warehouse.
+    pub base_location: &'a Location,
+    /// Location of the namespace the tabular is created in.
+    pub namespace_location: &'a Location,
+    pub namespace: &'a NamespaceIdent,
+    pub namespace_id: NamespaceIdentUuid,
+    pub name: &'a str,
+    pub tabular_id: TabularIdentUuid,
+}
+
+impl Default for TabularLocationTemplate {
+    fn default() -> Self {
+        Self(format!("{NAMESPACE_LOCATION}/{UUID}"))
+    }
+}
+
+impl Display for TabularLocationTemplate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<TabularLocationTemplate> for String {
+    fn from(template: TabularLocationTemplate) -> Self {
+        template.0
+    }
+}
+
+impl TryFrom<String> for TabularLocationTemplate {
+    type Error = ErrorModel;
+
+    fn try_from(template: String) -> Result<Self, Self::Error> {
+        Self::try_new(template)
+    }
+}
+
+fn invalid_template(template: &str, reason: &str) -> ErrorModel {
+    ErrorModel::bad_request(
+        format!("Invalid location template '{template}': {reason}"),
+        "InvalidLocationTemplate",
+        None,
+    )
+}
+
+impl TabularLocationTemplate {
+    /// Parse and validate a template.
+    ///
+    /// # Errors
+    /// Fails if the template does not start with `{namespace-location}` or `{base-location}`,
+    /// contains unknown placeholders or empty segments, or if its last segment
+    /// does not contain `{uuid}`. The id keeps generated locations unique.
+    pub fn try_new(template: String) -> Result<Self, ErrorModel> {
+        let Some(rest) = template
+            .strip_prefix(NAMESPACE_LOCATION)
+            .or_else(|| template.strip_prefix(BASE_LOCATION))
+        else {
+            return Err(invalid_template(
+                &template,
+                "must start with '{namespace-location}' or '{base-location}'",
+            ));
+        };
+        let Some(rest) = rest.strip_prefix('/') else {
+            return Err(invalid_template(
+                &template,
+                "the root placeholder must be followed by '/' and at least one segment",
+            ));
+        };
+
+        let segments = rest.split('/').collect::<Vec<_>>();
+        for segment in &segments {
+            if segment.is_empty() {
+                return Err(invalid_template(&template, "segments must not be empty"));
+            }
+            if segment.contains(NAMESPACE) && *segment != NAMESPACE {
+                return Err(invalid_template(
+                    &template,
+                    "'{namespace}' must be a segment on its own",
+                ));
+            }
+            let stripped = [NAMESPACE, NAMESPACE_ID, NAME, UUID, HASH]
+                .iter()
+                .fold((*segment).to_string(), |s, p| s.replace(p, ""));
+            if stripped.contains('{') || stripped.contains('}') {
+                return Err(invalid_template(
+                    &template,
+                    &format!("segment '{segment}' contains an unknown placeholder"),
+                ));
+            }
+        }
+        if !segments.last().is_some_and(|s| s.contains(UUID)) {
+            return Err(invalid_template(
+                &template,
+                "the last segment must contain '{uuid}'",
+            ));
+        }
+
+        Ok(Self(template))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Render the location of a table or view.
+    #[must_use]
+    pub fn render(&self, context: &TabularLocationContext<'_>) -> Location {
+        let (mut location, rest) = if let Some(rest) = self.0.strip_prefix(NAMESPACE_LOCATION) {
+            (context.namespace_location.clone(), rest)
+        } else {
+            let rest = self.0.strip_prefix(BASE_LOCATION).unwrap_or(&self.0);
+            (context.base_location.clone(), rest)
+        };
+        location.without_trailing_slash();
+
+        let hash = format!(
+            "{:08x}",
+            fxhash::hash64(context.tabular_id.as_bytes()) >> 32
+        );
+        for segment in rest.trim_start_matches('/').split('/') {
+            if segment == NAMESPACE {
+                location.extend(context.namespace.iter());
+                continue;
+            }
+            // The name is substituted last, so that it is never interpreted as a placeholder.
+            let segment = segment
+                .replace(NAMESPACE_ID, &context.namespace_id.to_string())
+                .replace(UUID, &context.tabular_id.to_string())
+                .replace(HASH, &hash)
+                .replace(NAME, context.name);
+            location.push(&segment);
+        }
+        location
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    fn render(template: &str, tabular_id: uuid::Uuid) -> String {
+        let base_location = Location::from_str("s3://bucket/warehouse/").unwrap();
+        let namespace_location =
+            Location::from_str("s3://bucket/warehouse/0194b0c2-6a55-7a63-a8e4-5b5e6d7a8f01")
+                .unwrap();
+        let namespace =
+            NamespaceIdent::from_vec(vec!["sales".to_string(), "eu west".to_string()]).unwrap();
+        TabularLocationTemplate::try_new(template.to_string())
+            .unwrap()
+            .render(&TabularLocationContext {
+                base_location: &base_location,
+                namespace_location: &namespace_location,
+                namespace: &namespace,
+                namespace_id: uuid::Uuid::nil().into(),
+                name: "orders",
+                tabular_id: TabularIdentUuid::Table(tabular_id),
+            })
+            .to_string()
+    }
+
+    #[test]
+    fn test_default_template_matches_default_location() {
+        let id = uuid::Uuid::now_v7();
+        assert_eq!(
+            render(TabularLocationTemplate::default().as_str(), id),
+            format!("s3://bucket/warehouse/0194b0c2-6a55-7a63-a8e4-5b5e6d7a8f01/{id}")
+        );
+    }
+
+    #[test]
+    fn test_human_readable_template() {
+        let id = uuid::Uuid::now_v7();
+        assert_eq!(
+            render("{base-location}/{namespace}/{name}-{uuid}", id),
+            format!("s3://bucket/warehouse/sales/eu%20west/orders-{id}")
+        );
+    }
+
+    #[test]
+    fn test_hash_prefix() {
+        let id = uuid::Uuid::now_v7();
+        let location = render("{base-location}/{hash}/{uuid}", id);
+        let hash = location
+            .strip_prefix("s3://bucket/warehouse/")
+            .unwrap()
+            .split('/')
+            .next()
+            .unwrap()
+            .to_string();
+        assert_eq!(hash.len(), 8);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        // Hashes are stable
+        assert_eq!(location, render("{base-location}/{hash}/{uuid}", id));
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        for template in [
+            "s3://bucket/{uuid}",
+            "{base-location}",
+            "{base-location}/{name}",
+            "{base-location}/{uuid}/{name}",
+            "{base-location}//{uuid}",
+            "{base-location}/ns-{namespace}/{uuid}",
+            "{base-location}/{table}/{uuid}",
+        ] {
+            assert!(
+                TabularLocationTemplate::try_new(template.to_string()).is_err(),
+                "{template} should be invalid"
+            );
+        }
+    }
+}
@@ -3,6 +3,7 @@
 mod az;
 mod error;
 mod gcs;
+mod location_template;
 mod s3;
 
 use super::{secrets::SecretInStorage, NamespaceIdentUuid, TableIdentUuid};
@@ -19,6 +20,7 @@ pub use gcs::{GcsCredential, GcsProfile, GcsServiceKey};
 use iceberg::io::FileIO;
 use iceberg_ext::configs::table::TableProperties;
 use iceberg_ext::configs::Location;
+pub use location_template::{TabularLocationContext, TabularLocationTemplate};
 pub use s3::{S3Credential, S3Flavor, S3Location, S3Profile};
 
 use crate::retry::retry_fn;
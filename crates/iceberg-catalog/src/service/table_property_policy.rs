@@ -0,0 +1,302 @@
+//! Warehouse-level policies for the properties of tables and views.
+//!
+//! A policy is a list of rules that is checked whenever a table or view is created,
+//! whenever a view is registered and whenever a table or view commit sets or removes
+//! properties. For commits, only rules for the modified properties are checked, so
+//! that tables created before a rule was added can still be written to.
+
+use iceberg::spec::{FormatVersion, TableMetadata, PROPERTY_FORMAT_VERSION};
+use iceberg::TableUpdate;
+use iceberg_ext::catalog::rest::{ErrorModel, ViewUpdate};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TablePropertyPolicy {
+    /// Rules that properties of tables and views must satisfy.
+    #[serde(default)]
+    pub rules: Vec<TablePropertyRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum TablePropertyRule {
+    /// The property must be set. If `value` is specified, it must have exactly this value.
+    #[serde(rename_all = "kebab-case")]
+    Required { key: String, value: Option<String> },
+    /// The property must not be set. If `value` is specified, only this value is forbidden.
+    #[serde(rename_all = "kebab-case")]
+    Forbidden { key: String, value: Option<String> },
+    /// If the property is set, its value must be one of `values`.
+    #[serde(rename_all = "kebab-case")]
+    AllowedValues { key: String, values: Vec<String> },
+}
+
+impl TablePropertyRule {
+    #[must_use]
+    pub fn key(&self) -> &str {
+        match self {
+            TablePropertyRule::Required { key, .. }
+            | TablePropertyRule::Forbidden { key, .. }
+            | TablePropertyRule::AllowedValues { key, .. } => key,
+        }
+    }
+
+    fn is_satisfied_by(&self, properties: &HashMap<String, String>) -> bool {
+        let actual = properties.get(self.key());
+        match self {
+            TablePropertyRule::Required { value, .. } => match (actual, value) {
+                (None, _) => false,
+                (Some(_), None) => true,
+                (Some(actual), Some(value)) => actual == value,
+            },
+            TablePropertyRule::Forbidden { value, .. } => match (actual, value) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some(actual), Some(value)) => actual != value,
+            },
+            TablePropertyRule::AllowedValues { values, .. } => {
+                actual.map_or(true, |actual| values.contains(actual))
+            }
+        }
+    }
+}
+
+impl Display for TablePropertyRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TablePropertyRule::Required { key, value: None } => {
+                write!(f, "Property '{key}' is required")
+            }
+            TablePropertyRule::Required {
+                key,
+                value: Some(value),
+            } => write!(f, "Property '{key}' must be '{value}'"),
+            TablePropertyRule::Forbidden { key, value: None } => {
+                write!(f, "Property '{key}' is forbidden")
+            }
+            TablePropertyRule::Forbidden {
+                key,
+                value: Some(value),
+            } => write!(f, "Property '{key}' must not be '{value}'"),
+            TablePropertyRule::AllowedValues { key, values } => write!(
+                f,
+                "Property '{key}' must be one of [{}]",
+                values.iter().join(", ")
+            ),
+        }
+    }
+}
+
+impl TablePropertyPolicy {
+    /// Check that the policy itself is well-formed.
+    ///
+    /// # Errors
+    /// Fails if a rule has an empty key or an empty list of allowed values.
+    pub fn validate(&self) -> Result<(), ErrorModel> {
+        for rule in &self.rules {
+            if rule.key().is_empty() {
+                return Err(ErrorModel::bad_request(
+                    "Keys of table property rules must not be empty",
+                    "InvalidTablePropertyPolicy",
+                    None,
+                ));
+            }
+            if matches!(rule, TablePropertyRule::AllowedValues { values, .. } if values.is_empty())
+            {
+                return Err(ErrorModel::bad_request(
+                    format!("Rule for property '{}' allows no values", rule.key()),
+                    "InvalidTablePropertyPolicy",
+                    None,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rules that are violated by `properties`.
+    /// If `keys` is specified, only rules for these keys are checked.
+    #[must_use]
+    pub fn violations(
+        &self,
+        properties: &HashMap<String, String>,
+        keys: Option<&HashSet<String>>,
+    ) -> Vec<&TablePropertyRule> {
+        self.rules
+            .iter()
+            .filter(|rule| keys.map_or(true, |keys| keys.contains(rule.key())))
+            .filter(|rule| !rule.is_satisfied_by(properties))
+            .collect()
+    }
+
+    /// Keys of the rules that apply to new views. `format-version` only exists for tables.
+    #[must_use]
+    pub fn view_keys(&self) -> HashSet<String> {
+        self.rules
+            .iter()
+            .map(TablePropertyRule::key)
+            .filter(|key| *key != PROPERTY_FORMAT_VERSION)
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Fail with an error listing all violated rules.
+    ///
+    /// # Errors
+    /// Fails if `properties` violate at least one rule for `keys`.
+    pub fn require_compliance(
+        &self,
+        properties: &HashMap<String, String>,
+        keys: Option<&HashSet<String>>,
+    ) -> Result<(), ErrorModel> {
+        let violations = self.violations(properties, keys);
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        Err(ErrorModel::bad_request(
+            format!(
+                "Properties violate the table property policy of the warehouse: {}",
+                violations.iter().join("; ")
+            ),
+            "TablePropertyPolicyViolation",
+            None,
+        )
+        .append_details(
+            violations
+                .iter()
+                .map(|rule| serde_json::to_string(rule).unwrap_or_else(|_| rule.to_string()))
+                .collect(),
+        ))
+    }
+}
+
+/// Properties of a table as seen by the policy. Includes `format-version`, which
+/// is not part of the properties stored in the metadata.
+#[must_use]
+pub fn table_policy_properties(metadata: &TableMetadata) -> HashMap<String, String> {
+    let mut properties = metadata.properties().clone();
+    let format_version = match metadata.format_version() {
+        FormatVersion::V1 => "1",
+        FormatVersion::V2 => "2",
+    };
+    properties.insert(
+        PROPERTY_FORMAT_VERSION.to_string(),
+        format_version.to_string(),
+    );
+    properties
+}
+
+/// Keys of the table properties that are set or removed by `updates`.
+#[must_use]
+pub fn modified_table_properties(updates: &[TableUpdate]) -> HashSet<String> {
+    updates
+        .iter()
+        .flat_map(|update| match update {
+            TableUpdate::SetProperties { updates } => updates.keys().cloned().collect(),
+            TableUpdate::RemoveProperties { removals } => removals.clone(),
+            TableUpdate::UpgradeFormatVersion { .. } => vec![PROPERTY_FORMAT_VERSION.to_string()],
+            _ => vec![],
+        })
+        .collect()
+}
+
+/// Keys of the view properties that are set or removed by `updates`.
+#[must_use]
+pub fn modified_view_properties(updates: &[ViewUpdate]) -> HashSet<String> {
+    updates
+        .iter()
+        .flat_map(|update| match update {
+            ViewUpdate::SetProperties { updates } => updates.keys().cloned().collect(),
+            ViewUpdate::RemoveProperties { removals } => removals.clone(),
+            _ => vec![],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn policy() -> TablePropertyPolicy {
+        serde_json::from_value(serde_json::json!({
+            "rules": [
+                {"type": "required", "key": "format-version", "value": "2"},
+                {"type": "required", "key": "owner"},
+                {"type": "forbidden", "key": "write.object-storage.enabled", "value": "false"},
+                {"type": "allowed-values", "key": "write.parquet.compression-codec", "values": ["zstd", "snappy"]},
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn properties(props: &[(&str, &str)]) -> HashMap<String, String> {
+        props
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_compliant_properties() {
+        let policy = policy();
+        policy.validate().unwrap();
+        let props = properties(&[
+            ("format-version", "2"),
+            ("owner", "me"),
+            ("write.object-storage.enabled", "true"),
+            ("write.parquet.compression-codec", "zstd"),
+        ]);
+        assert!(policy.violations(&props, None).is_empty());
+    }
+
+    #[test]
+    fn test_violations() {
+        let policy = policy();
+        let props = properties(&[
+            ("format-version", "1"),
+            ("write.object-storage.enabled", "false"),
+            ("write.parquet.compression-codec", "gzip"),
+        ]);
+        let violations = policy.violations(&props, None);
+        assert_eq!(violations.len(), 4);
+
+        let err = policy.require_compliance(&props, None).unwrap_err();
+        assert_eq!(err.r#type, "TablePropertyPolicyViolation");
+        assert!(err.message.contains("Property 'owner' is required"));
+        assert_eq!(err.stack.len(), 4);
+    }
+
+    #[test]
+    fn test_only_modified_keys_are_checked() {
+        let policy = policy();
+        let props = properties(&[("write.parquet.compression-codec", "gzip")]);
+        let keys = HashSet::from(["write.parquet.compression-codec".to_string()]);
+        let violations = policy.violations(&props, Some(&keys));
+        assert_eq!(
+            violations,
+            vec![&TablePropertyRule::AllowedValues {
+                key: "write.parquet.compression-codec".to_string(),
+                values: vec!["zstd".to_string(), "snappy".to_string()],
+            }]
+        );
+
+        let keys = HashSet::from(["unrelated".to_string()]);
+        assert!(policy.violations(&props, Some(&keys)).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_policy() {
+        let policy = TablePropertyPolicy {
+            rules: vec![TablePropertyRule::AllowedValues {
+                key: "key".to_string(),
+                values: vec![],
+            }],
+        };
+        assert!(policy.validate().is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::implementations::postgres::authz::Grant;
+use crate::service::authz::implementations::openfga::OpenFGAAuthorizer;
+use crate::service::task_queue::{Task, TaskQueue};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::Instrument;
+
+pub type GrantExpirationQueue = Arc<
+    dyn TaskQueue<Task = GrantExpirationTask, Input = GrantExpirationInput> + Send + Sync + 'static,
+>;
+
+pub async fn grant_expiration_task(fetcher: GrantExpirationQueue, authorizer: OpenFGAAuthorizer) {
+    loop {
+        tokio::time::sleep(fetcher.config().poll_interval).await;
+
+        let expiration = match fetcher.pick_new_task().await {
+            Ok(expiration) => expiration,
+            Err(err) => {
+                tracing::error!("Failed to fetch grant expiration: {:?}", err);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(expiration) = expiration else {
+            continue;
+        };
+
+        let span = tracing::debug_span!(
+            "grant_expiration",
+            queue_name = %expiration.task.queue_name,
+            object = %expiration.object,
+            relation = %expiration.relation,
+            principal = %expiration.principal,
+            task = ?expiration.task,
+        );
+
+        let grant = Grant {
+            object: expiration.object.clone(),
+            relation: expiration.relation.clone(),
+            principal: expiration.principal.clone(),
+        };
+        match authorizer
+            .revoke_expired_grant(&grant)
+            .instrument(span.or_current())
+            .await
+        {
+            Ok(revoked) => {
+                fetcher.retrying_record_success(&expiration.task).await;
+                if revoked {
+                    tracing::info!("Revoked expired grant");
+                } else {
+                    tracing::debug!("Grant was renewed or removed, nothing to revoke");
+                }
+            }
+            Err(e) => {
+                tracing::error!("Failed to revoke expired grant: {:?}", e);
+                fetcher
+                    .retrying_record_failure(&expiration.task, &format!("{e:?}"))
+                    .await;
+            }
+        };
+    }
+}
+
+#[derive(Debug)]
+pub struct GrantExpirationTask {
+    pub object: String,
+    pub relation: String,
+    pub principal: String,
+    pub task: Task,
+}
+
+#[derive(Debug, Clone)]
+pub struct GrantExpirationInput {
+    /// Object of the grant, i.e. `warehouse:<id>`
+    pub object: String,
+    pub relation: String,
+    /// `OpenFGA` user of the grant, i.e. `user:<id>` or `role:<id>#assignee`
+    pub principal: String,
+    pub expire_at: chrono::DateTime<chrono::Utc>,
+}
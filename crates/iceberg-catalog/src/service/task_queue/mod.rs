@@ -1,3 +1,4 @@
+use crate::service::task_queue::namespace_expiration_queue::NamespaceExpirationInput;
 use crate::service::task_queue::tabular_expiration_queue::TabularExpirationInput;
 use crate::service::task_queue::tabular_purge_queue::TabularPurgeInput;
 use crate::service::{Catalog, SecretStore};
@@ -14,6 +15,8 @@ use uuid::Uuid;
 use super::authz::Authorizer;
 use super::WarehouseIdent;
 
+pub mod grant_expiration_queue;
+pub mod namespace_expiration_queue;
 pub mod tabular_expiration_queue;
 pub mod tabular_purge_queue;
 
@@ -21,6 +24,7 @@ pub mod tabular_purge_queue;
 pub struct TaskQueues {
     tabular_expiration: tabular_expiration_queue::ExpirationQueue,
     tabular_purge: tabular_purge_queue::TabularPurgeQueue,
+    namespace_expiration: namespace_expiration_queue::NamespaceExpirationQueue,
 }
 
 impl TaskQueues {
@@ -28,10 +32,12 @@ impl TaskQueues {
     pub fn new(
         expiration: tabular_expiration_queue::ExpirationQueue,
         purge: tabular_purge_queue::TabularPurgeQueue,
+        namespace_expiration: namespace_expiration_queue::NamespaceExpirationQueue,
     ) -> Self {
         Self {
             tabular_expiration: expiration,
             tabular_purge: purge,
+            namespace_expiration,
         }
     }
 
@@ -59,6 +65,22 @@ impl TaskQueues {
         self.tabular_purge.enqueue(task).await
     }
 
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn queue_namespace_expiration(
+        &self,
+        task: NamespaceExpirationInput,
+    ) -> crate::api::Result<()> {
+        self.namespace_expiration.enqueue(task).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn cancel_namespace_expiration(
+        &self,
+        filter: TaskFilter,
+    ) -> crate::api::Result<()> {
+        self.namespace_expiration.cancel_pending_tasks(filter).await
+    }
+
     pub async fn spawn_queues<C, S, A>(
         &self,
         catalog_state: C::State,
@@ -78,6 +100,16 @@ impl TaskQueues {
                 authorizer.clone(),
             ));
 
+        let namespace_expiration_queue_handler = tokio::task::spawn(
+            namespace_expiration_queue::namespace_expiration_task::<C, A>(
+                self.namespace_expiration.clone(),
+                self.tabular_expiration.clone(),
+                self.tabular_purge.clone(),
+                catalog_state.clone(),
+                authorizer.clone(),
+            ),
+        );
+
         let purge_queue_handler = tokio::task::spawn(tabular_purge_queue::purge_task::<C, S>(
             self.tabular_purge.clone(),
             catalog_state.clone(),
@@ -89,6 +121,10 @@ impl TaskQueues {
                 tracing::error!("Tabular expiration queue handler exited unexpectedly");
                 Err(anyhow::anyhow!("Tabular expiration queue handler exited unexpectedly"))
             },
+            _ = namespace_expiration_queue_handler => {
+                tracing::error!("Namespace expiration queue handler exited unexpectedly");
+                Err(anyhow::anyhow!("Namespace expiration queue handler exited unexpectedly"))
+            },
             _ = purge_queue_handler => {
                 tracing::error!("Tabular purge queue handler exited unexpectedly");
                 Err(anyhow::anyhow!("Tabular purge queue handler exited unexpectedly"))
@@ -298,6 +334,13 @@ mod test {
         );
         let purge_queue = Arc::new(
             crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(
+                rw.clone(),
+                config.clone(),
+            )
+            .unwrap(),
+        );
+        let namespace_expiration_queue = Arc::new(
+            crate::implementations::postgres::task_queues::NamespaceExpirationQueue::from_config(
                 rw.clone(),
                 config,
             )
@@ -306,8 +349,11 @@ mod test {
 
         let catalog_state = CatalogState::from_pools(pool.clone(), pool.clone());
 
-        let queues =
-            crate::service::task_queue::TaskQueues::new(expiration_queue.clone(), purge_queue);
+        let queues = crate::service::task_queue::TaskQueues::new(
+            expiration_queue.clone(),
+            purge_queue,
+            namespace_expiration_queue,
+        );
         let secrets =
             crate::implementations::postgres::SecretsState::from_pools(pool.clone(), pool);
         let cloned = queues.clone();
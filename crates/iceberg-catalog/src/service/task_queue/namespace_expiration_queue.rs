@@ -0,0 +1,168 @@
+use crate::api::management::v1::{DeleteKind, TabularType};
+use crate::api::Result;
+use crate::request_metadata::RequestMetadata;
+use crate::service::task_queue::tabular_expiration_queue::ExpirationQueue;
+use crate::service::task_queue::tabular_purge_queue::{TabularPurgeInput, TabularPurgeQueue};
+use crate::service::task_queue::{Task, TaskFilter, TaskQueue};
+use crate::service::{Catalog, NamespaceIdentUuid, TabularIdentUuid, Transaction};
+use crate::WarehouseIdent;
+use std::sync::Arc;
+
+use crate::service::authz::Authorizer;
+use std::time::Duration;
+use tracing::Instrument;
+use uuid::Uuid;
+
+pub type NamespaceExpirationQueue = Arc<
+    dyn TaskQueue<Task = NamespaceExpirationTask, Input = NamespaceExpirationInput>
+        + Send
+        + Sync
+        + 'static,
+>;
+
+pub async fn namespace_expiration_task<C: Catalog, A: Authorizer>(
+    fetcher: NamespaceExpirationQueue,
+    tabular_expiration: ExpirationQueue,
+    cleaner: TabularPurgeQueue,
+    catalog_state: C::State,
+    authorizer: A,
+) {
+    loop {
+        tokio::time::sleep(fetcher.config().poll_interval).await;
+
+        let expiration = match fetcher.pick_new_task().await {
+            Ok(expiration) => expiration,
+            Err(err) => {
+                tracing::error!("Failed to fetch namespace deletion: {:?}", err);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(expiration) = expiration else {
+            continue;
+        };
+
+        let span = tracing::debug_span!(
+            "namespace_expiration",
+            queue_name = %expiration.task.queue_name,
+            namespace_id = %expiration.namespace_id,
+            warehouse_id = %expiration.warehouse_ident,
+            deletion_kind = ?expiration.deletion_kind,
+            task = ?expiration.task,
+        );
+
+        match handle_namespace::<C, A>(
+            catalog_state.clone(),
+            authorizer.clone(),
+            &tabular_expiration,
+            &cleaner,
+            &expiration,
+        )
+        .instrument(span.or_current())
+        .await
+        {
+            Ok(()) => {
+                fetcher.retrying_record_success(&expiration.task).await;
+                tracing::info!("Successfully handled namespace expiration");
+            }
+            Err(e) => {
+                tracing::error!("Failed to handle namespace expiration: {:?}", e);
+                fetcher
+                    .retrying_record_failure(&expiration.task, &format!("{e:?}"))
+                    .await;
+            }
+        };
+    }
+}
+
+async fn handle_namespace<C, A>(
+    catalog_state: C::State,
+    authorizer: A,
+    tabular_expiration: &ExpirationQueue,
+    delete_queue: &TabularPurgeQueue,
+    expiration: &NamespaceExpirationTask,
+) -> Result<()>
+where
+    C: Catalog,
+    A: Authorizer,
+{
+    let mut trx = C::Transaction::begin_write(catalog_state)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to start transaction: {:?}", e);
+            e
+        })?;
+
+    let namespace_id = NamespaceIdentUuid::from(expiration.namespace_id);
+    let tabulars =
+        C::drop_deleted_namespace(expiration.warehouse_ident, namespace_id, trx.transaction())
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to drop namespace: {:?}", e);
+                e
+            })?;
+
+    for tabular in &tabulars {
+        match tabular.tabular_id {
+            TabularIdentUuid::Table(id) => authorizer.delete_table(id.into()).await?,
+            TabularIdentUuid::View(id) => authorizer.delete_view(id.into()).await?,
+        }
+    }
+    authorizer
+        .delete_namespace(&RequestMetadata::new_unauthenticated(), namespace_id)
+        .await?;
+
+    if matches!(expiration.deletion_kind, DeleteKind::Purge) {
+        for tabular in &tabulars {
+            delete_queue
+                .enqueue(TabularPurgeInput {
+                    tabular_id: *tabular.tabular_id,
+                    warehouse_ident: expiration.warehouse_ident,
+                    tabular_type: match tabular.tabular_id {
+                        TabularIdentUuid::Table(_) => TabularType::Table,
+                        TabularIdentUuid::View(_) => TabularType::View,
+                    },
+                    parent_id: Some(expiration.task.task_id),
+                    tabular_location: tabular.location.clone(),
+                })
+                .await?;
+        }
+    }
+
+    // Here we commit after the queuing of the deletion since we're in a fault-tolerant workflow
+    // which will restart if the commit fails.
+    trx.commit().await.map_err(|e| {
+        tracing::error!("Failed to commit transaction: {:?}", e);
+        e
+    })?;
+
+    // The tables and views of the namespace are gone, their own expirations have nothing left to do.
+    let tabular_tasks = tabulars
+        .into_iter()
+        .filter_map(|t| t.expiration_task_id)
+        .collect::<Vec<_>>();
+    if !tabular_tasks.is_empty() {
+        tabular_expiration
+            .cancel_pending_tasks(TaskFilter::TaskIds(tabular_tasks))
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct NamespaceExpirationTask {
+    pub deletion_kind: DeleteKind,
+    pub namespace_id: Uuid,
+    pub warehouse_ident: WarehouseIdent,
+    pub task: Task,
+}
+
+#[derive(Debug, Clone)]
+pub struct NamespaceExpirationInput {
+    pub namespace_id: Uuid,
+    pub warehouse_ident: WarehouseIdent,
+    pub purge: bool,
+    pub expire_at: chrono::DateTime<chrono::Utc>,
+}
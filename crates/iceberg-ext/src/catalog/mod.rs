@@ -31,7 +31,11 @@ pub mod rest {
     };
 
     mod view;
-    pub use view::{CommitViewRequest, CreateViewRequest, LoadViewResult};
+    pub use view::{
+        CommitMaterializedViewRequest, CommitViewRequest, CreateMaterializedViewRequest,
+        CreateViewRequest, LoadMaterializedViewResult, LoadViewResult, MaterializedViewFreshness,
+        RegisterViewRequest, SourceTableSnapshot,
+    };
 
     mod namespace;
     pub use namespace::{
@@ -1,6 +1,6 @@
 #[cfg(feature = "axum")]
 use crate::catalog::rest::impl_into_response;
-use crate::catalog::rest::ViewUpdate;
+use crate::catalog::rest::{CommitTableRequest, CreateTableRequest, ViewUpdate};
 use crate::catalog::{TableIdent, ViewRequirement};
 use crate::spec::{Schema, ViewMetadata, ViewVersion};
 
@@ -18,6 +18,14 @@ pub struct CreateViewRequest {
     pub properties: std::collections::HashMap<String, String>,
 }
 
+/// Register an existing view using the location of its current metadata file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RegisterViewRequest {
+    pub name: String,
+    pub metadata_location: String,
+}
+
 /// Result used when a view is successfully loaded.
 /// The view metadata JSON is returned in the `metadata` field.
 /// The corresponding file location of view metadata is returned in the `metadata-location` field.
@@ -43,5 +51,60 @@ pub struct CommitViewRequest {
     pub updates: Vec<ViewUpdate>,
 }
 
+/// Snapshot of a source table that the storage table of a materialized view was refreshed from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SourceTableSnapshot {
+    pub table: TableIdent,
+    /// `None` if the source table had no snapshot at the time of the refresh.
+    pub snapshot_id: Option<i64>,
+}
+
+/// Create a materialized view together with the table that stores its data.
+/// The storage table is created in the namespace of the view.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateMaterializedViewRequest {
+    pub view: CreateViewRequest,
+    pub storage_table: CreateTableRequest,
+    #[serde(default)]
+    pub refresh_state: Vec<SourceTableSnapshot>,
+}
+
+/// Commit updates to a materialized view and its storage table, for example after a refresh.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitMaterializedViewRequest {
+    pub view: CommitViewRequest,
+    pub storage_table: CommitTableRequest,
+    /// Replaces the recorded refresh state. The previous state is kept if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_state: Option<Vec<SourceTableSnapshot>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MaterializedViewFreshness {
+    /// All source tables are at the snapshot the storage table was refreshed from.
+    Fresh,
+    /// At least one source table changed or was dropped since the last refresh.
+    Stale,
+}
+
+/// Result used when a materialized view is successfully loaded.
+/// Contains all fields of [`LoadViewResult`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LoadMaterializedViewResult {
+    #[serde(flatten)]
+    pub view: LoadViewResult,
+    pub storage_table: TableIdent,
+    pub freshness: MaterializedViewFreshness,
+    /// Recorded snapshots of the source tables that changed since the last refresh.
+    pub stale_sources: Vec<SourceTableSnapshot>,
+}
+
 #[cfg(feature = "axum")]
 impl_into_response!(LoadViewResult);
+#[cfg(feature = "axum")]
+impl_into_response!(LoadMaterializedViewResult);